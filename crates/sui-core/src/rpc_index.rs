@@ -7,6 +7,7 @@ use crate::checkpoints::CheckpointStore;
 use crate::par_index_live_object_set::LiveObjectIndexer;
 use crate::par_index_live_object_set::ParMakeLiveObjectIndexer;
 use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::TypeTag;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use serde::Deserialize;
@@ -23,23 +24,28 @@ use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::SuiAddress;
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::visitor as DFV;
+use sui_types::effects::TransactionEvents;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::layout_resolver::LayoutResolver;
 use sui_types::messages_checkpoint::CheckpointContents;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Object;
 use sui_types::object::Owner;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::BackingPackageStore;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
+use sui_types::storage::EventPosition;
+use sui_types::storage::EventSelector;
 use tracing::{debug, info};
-use typed_store::rocks::{DBMap, MetricConf};
+use typed_store::rocks::{default_db_options, DBMap, DBMapTableConfigMap, DBOptions, MetricConf};
+use typed_store::rocksdb::{CompactionDecision, MergeOperands};
 use typed_store::traits::Map;
 use typed_store::traits::{TableSummary, TypedStoreDebug};
 use typed_store::DBMapUtils;
 use typed_store::TypedStoreError;
 
-const CURRENT_DB_VERSION: u64 = 0;
+const CURRENT_DB_VERSION: u64 = 1;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct MetadataInfo {
@@ -102,6 +108,28 @@ impl CoinIndexInfo {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BalanceKey {
+    pub owner: SuiAddress,
+    pub coin_type: StructTag,
+}
+
+/// The balance of a single coin type for a single address.
+///
+/// Updates to the `balance` table are written as deltas, which are summed by
+/// `balance_delta_merge_operator`, so a stored value is the sum of all the deltas written for its
+/// key.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+pub struct BalanceIndexInfo {
+    pub balance_delta: i128,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct EventIndexKey {
+    pub selector: EventSelector,
+    pub position: EventPosition,
+}
+
 /// RocksDB tables for the RpcIndexStore
 ///
 /// Anytime a new table is added, or and existing one has it's schema changed, make sure to also
@@ -143,6 +171,23 @@ struct IndexStoreTables {
     /// Allows looking up information related to published Coins, like the ObjectID of its
     /// coorisponding CoinMetadata.
     coin: DBMap<CoinIndexKey, CoinIndexInfo>,
+
+    /// An index of the balances of address-owned coins.
+    ///
+    /// Allows looking up the total balance of each coin type owned by an address, and iterating
+    /// over all of the coin types an address owns. Entries whose balance has dropped to zero are
+    /// removed by `balance_compaction_filter`.
+    balance: DBMap<BalanceKey, BalanceIndexInfo>,
+
+    /// An index of Events, by their sender, the module that emitted them, and their type.
+    ///
+    /// Only contains entries for events emitted by transactions which have yet to be pruned from
+    /// the main database.
+    event: DBMap<EventIndexKey, TransactionDigest>,
+
+    /// The keys written to the `event` index for each transaction, so that they can be removed
+    /// when the transaction is pruned.
+    event_keys: DBMap<TransactionDigest, Vec<EventIndexKey>>,
     // NOTE: Authors and Reviewers before adding any new tables ensure that they are either:
     // - bounded in size by the live object set
     // - are prune-able and have corresponding logic in the `prune` function
@@ -154,7 +199,9 @@ impl IndexStoreTables {
             path.into(),
             MetricConf::new("rpc-index"),
             None,
-            None,
+            Some(DBMapTableConfigMap::new(
+                [("balance".to_owned(), balance_table_options())].into(),
+            )),
         )
     }
 
@@ -212,9 +259,41 @@ impl IndexStoreTables {
                     checkpoint: checkpoint.sequence_number,
                 };
 
-                self.transactions
-                    .multi_insert(contents.iter().map(|digests| (digests.transaction, info)))
-                    .map_err(StorageError::from)
+                let mut batch = self.transactions.batch();
+                batch.insert_batch(
+                    &self.transactions,
+                    contents.iter().map(|digests| (digests.transaction, info)),
+                )?;
+
+                let effects = authority_store
+                    .multi_get_effects(contents.iter().map(|digests| &digests.effects))
+                    .map_err(StorageError::custom)?;
+
+                for (transaction_index, (digests, effects)) in
+                    contents.iter().zip(effects).enumerate()
+                {
+                    let effects = effects.ok_or_else(|| {
+                        StorageError::missing(format!("missing effects {}", digests.effects))
+                    })?;
+
+                    let Some(events_digest) = effects.events_digest() else {
+                        continue;
+                    };
+
+                    let events = authority_store.get_events(events_digest)?.ok_or_else(|| {
+                        StorageError::missing(format!("missing events {events_digest}"))
+                    })?;
+
+                    self.index_transaction_events(
+                        &mut batch,
+                        seq,
+                        transaction_index,
+                        digests.transaction,
+                        &events,
+                    )?;
+                }
+
+                batch.write().map_err(StorageError::from)
             })?;
 
             info!(
@@ -258,11 +337,19 @@ impl IndexStoreTables {
     ) -> Result<(), TypedStoreError> {
         let mut batch = self.transactions.batch();
 
-        let transactions_to_prune = checkpoint_contents_to_prune
+        let transactions_to_prune: Vec<_> = checkpoint_contents_to_prune
             .iter()
-            .flat_map(|contents| contents.iter().map(|digests| digests.transaction));
+            .flat_map(|contents| contents.iter().map(|digests| digests.transaction))
+            .collect();
 
-        batch.delete_batch(&self.transactions, transactions_to_prune)?;
+        let event_keys_to_prune = self.event_keys.multi_get(&transactions_to_prune)?;
+        batch.delete_batch(
+            &self.event,
+            event_keys_to_prune.into_iter().flatten().flatten(),
+        )?;
+
+        batch.delete_batch(&self.event_keys, &transactions_to_prune)?;
+        batch.delete_batch(&self.transactions, &transactions_to_prune)?;
 
         batch.write()
     }
@@ -295,11 +382,46 @@ impl IndexStoreTables {
             )?;
         }
 
+        // event indexes
+        for (transaction_index, tx) in checkpoint.transactions.iter().enumerate() {
+            if let Some(events) = &tx.events {
+                self.index_transaction_events(
+                    &mut batch,
+                    checkpoint.checkpoint_summary.sequence_number,
+                    transaction_index,
+                    *tx.transaction.digest(),
+                    events,
+                )?;
+            }
+        }
+
         // object indexes
         {
             let mut coin_index = HashMap::new();
+            let mut balance_changes: HashMap<BalanceKey, i128> = HashMap::new();
 
             for tx in &checkpoint.transactions {
+                // balance indexing
+                //
+                // balances are tracked as the sum of the values of an address's coins, so every
+                // coin that is removed or modified contributes its previous value negatively, and
+                // every coin that is created or modified contributes its new value positively.
+                for removed_object in tx.removed_objects_pre_version() {
+                    if let Some((key, value)) = try_create_balance_info(removed_object) {
+                        *balance_changes.entry(key).or_default() -= value as i128;
+                    }
+                }
+
+                for (object, old_object) in tx.changed_objects() {
+                    if let Some((key, value)) = old_object.and_then(try_create_balance_info) {
+                        *balance_changes.entry(key).or_default() -= value as i128;
+                    }
+
+                    if let Some((key, value)) = try_create_balance_info(object) {
+                        *balance_changes.entry(key).or_default() += value as i128;
+                    }
+                }
+
                 // determine changes from removed objects
                 for removed_object in tx.removed_objects_pre_version() {
                     match removed_object.owner() {
@@ -389,6 +511,14 @@ impl IndexStoreTables {
             }
 
             batch.insert_batch(&self.coin, coin_index)?;
+
+            batch.merge_batch(
+                &self.balance,
+                balance_changes
+                    .into_iter()
+                    .filter(|(_, balance_delta)| *balance_delta != 0)
+                    .map(|(key, balance_delta)| (key, BalanceIndexInfo { balance_delta })),
+            )?;
         }
 
         batch.write()?;
@@ -400,6 +530,45 @@ impl IndexStoreTables {
         Ok(())
     }
 
+    /// Add the entries for the `events` emitted by the transaction at `transaction_index` in
+    /// `checkpoint` to the event indexes.
+    fn index_transaction_events(
+        &self,
+        batch: &mut typed_store::rocks::DBBatch,
+        checkpoint: CheckpointSequenceNumber,
+        transaction_index: usize,
+        digest: TransactionDigest,
+        events: &TransactionEvents,
+    ) -> Result<(), StorageError> {
+        if events.data.is_empty() {
+            return Ok(());
+        }
+
+        let mut keys = Vec::with_capacity(events.data.len() * 3);
+        for (event_index, event) in events.data.iter().enumerate() {
+            let position = EventPosition {
+                checkpoint,
+                transaction_index: transaction_index as u32,
+                event_index: event_index as u32,
+            };
+
+            for selector in [
+                EventSelector::Sender(event.sender),
+                EventSelector::Module {
+                    package: event.package_id,
+                    module: event.transaction_module.clone(),
+                },
+                EventSelector::Type(event.type_.clone()),
+            ] {
+                keys.push(EventIndexKey { selector, position });
+            }
+        }
+
+        batch.insert_batch(&self.event, keys.iter().map(|key| (key, digest)))?;
+        batch.insert_batch(&self.event_keys, [(digest, keys)])?;
+        Ok(())
+    }
+
     fn get_transaction_info(
         &self,
         digest: &TransactionDigest,
@@ -453,6 +622,57 @@ impl IndexStoreTables {
         };
         self.coin.get(&key)
     }
+
+    fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<Option<BalanceIndexInfo>, TypedStoreError> {
+        let key = BalanceKey {
+            owner: owner.to_owned(),
+            coin_type: coin_type.to_owned(),
+        };
+        self.balance.get(&key)
+    }
+
+    fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<StructTag>,
+    ) -> Result<
+        impl Iterator<Item = Result<(BalanceKey, BalanceIndexInfo), TypedStoreError>> + '_,
+        TypedStoreError,
+    > {
+        // `owner` is the leading field of `BalanceKey`, so its encoding prefixes every key of
+        // `owner`'s balances.
+        let iter = self.balance.safe_iter_with_prefix(&owner)?;
+
+        match cursor {
+            Some(coin_type) => iter.skip_to(&BalanceKey { owner, coin_type }),
+            None => Ok(iter),
+        }
+    }
+
+    fn event_iter(
+        &self,
+        selector: EventSelector,
+        cursor: Option<EventPosition>,
+    ) -> Result<impl Iterator<Item = (EventIndexKey, TransactionDigest)> + '_, TypedStoreError>
+    {
+        let lower_bound = EventIndexKey {
+            selector: selector.clone(),
+            position: cursor.unwrap_or(EventPosition {
+                checkpoint: 0,
+                transaction_index: 0,
+                event_index: 0,
+            }),
+        };
+
+        Ok(self
+            .event
+            .iter_with_bounds(Some(lower_bound), None)
+            .take_while(move |(key, _)| key.selector == selector))
+    }
 }
 
 pub struct RpcIndexStore {
@@ -558,6 +778,98 @@ impl RpcIndexStore {
     ) -> Result<Option<CoinIndexInfo>, TypedStoreError> {
         self.tables.get_coin_info(coin_type)
     }
+
+    pub fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<Option<BalanceIndexInfo>, TypedStoreError> {
+        self.tables.get_balance(owner, coin_type)
+    }
+
+    pub fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<StructTag>,
+    ) -> Result<
+        impl Iterator<Item = Result<(BalanceKey, BalanceIndexInfo), TypedStoreError>> + '_,
+        TypedStoreError,
+    > {
+        self.tables.balance_iter(owner, cursor)
+    }
+
+    pub fn event_iter(
+        &self,
+        selector: EventSelector,
+        cursor: Option<EventPosition>,
+    ) -> Result<impl Iterator<Item = (EventIndexKey, TransactionDigest)> + '_, TypedStoreError>
+    {
+        self.tables.event_iter(selector, cursor)
+    }
+}
+
+fn balance_table_options() -> DBOptions {
+    let mut options = default_db_options();
+    options
+        .options
+        .set_merge_operator_associative("balance_delta", balance_delta_merge_operator);
+    options
+        .options
+        .set_compaction_filter("balance_zero", balance_compaction_filter);
+    options
+}
+
+/// Drop entries of the `balance` table whose deltas have summed to zero, i.e. coin types that an
+/// address no longer owns.
+fn balance_compaction_filter(_level: u32, _key: &[u8], value: &[u8]) -> CompactionDecision {
+    match bcs::from_bytes::<BalanceIndexInfo>(value) {
+        Ok(info) if info.balance_delta == 0 => CompactionDecision::Remove,
+        _ => CompactionDecision::Keep,
+    }
+}
+
+/// Sum the balance deltas that have been merged into a key of the `balance` table.
+fn balance_delta_merge_operator(
+    _key: &[u8],
+    existing: Option<&[u8]>,
+    operands: &MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut balance_delta = match existing {
+        Some(bytes) => {
+            bcs::from_bytes::<BalanceIndexInfo>(bytes)
+                .ok()?
+                .balance_delta
+        }
+        None => 0,
+    };
+
+    for operand in operands.iter() {
+        balance_delta += bcs::from_bytes::<BalanceIndexInfo>(operand)
+            .ok()?
+            .balance_delta;
+    }
+
+    bcs::to_bytes(&BalanceIndexInfo { balance_delta }).ok()
+}
+
+/// The key and value to index in the `balance` table for `object`, if it is a coin owned by an
+/// address.
+fn try_create_balance_info(object: &Object) -> Option<(BalanceKey, u64)> {
+    let Owner::AddressOwner(owner) = object.owner() else {
+        return None;
+    };
+
+    let Some(TypeTag::Struct(coin_type)) = object.coin_type_maybe() else {
+        return None;
+    };
+
+    Some((
+        BalanceKey {
+            owner: *owner,
+            coin_type: *coin_type,
+        },
+        object.get_coin_value_unsafe(),
+    ))
 }
 
 fn try_create_dynamic_field_info(
@@ -695,6 +1007,19 @@ impl<'a> LiveObjectIndexer for RpcLiveObjectIndexer<'a> {
             Owner::ConsensusV2 { .. } => todo!(),
         }
 
+        // Balance Index
+        if let Some((key, value)) = try_create_balance_info(&object) {
+            self.batch.merge_batch(
+                &self.tables.balance,
+                [(
+                    key,
+                    BalanceIndexInfo {
+                        balance_delta: value as i128,
+                    },
+                )],
+            )?;
+        }
+
         // Look for CoinMetadata<T> and TreasuryCap<T> objects
         if let Some((key, value)) = try_create_coin_index_info(&object) {
             use std::collections::hash_map::Entry;
@@ -22,9 +22,12 @@ use sui_types::object::Object;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::error::Result;
 use sui_types::storage::AccountOwnedObjectInfo;
+use sui_types::storage::BalanceInfo;
 use sui_types::storage::CoinInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
+use sui_types::storage::EventPosition;
+use sui_types::storage::EventSelector;
 use sui_types::storage::ObjectStore;
 use sui_types::storage::RpcIndexes;
 use sui_types::storage::RpcStateReader;
@@ -37,7 +40,10 @@ use crate::authority::AuthorityState;
 use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::ExecutionCacheTraitPointers;
+use crate::rpc_index::BalanceIndexInfo;
+use crate::rpc_index::BalanceKey;
 use crate::rpc_index::CoinIndexInfo;
+use crate::rpc_index::EventIndexKey;
 use crate::rpc_index::OwnerIndexInfo;
 use crate::rpc_index::OwnerIndexKey;
 use crate::rpc_index::RpcIndexStore;
@@ -528,4 +534,58 @@ impl RpcIndexes for RpcIndexStore {
             )
             .pipe(Ok)
     }
+
+    fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &StructTag,
+    ) -> sui_types::storage::error::Result<Option<BalanceInfo>> {
+        self.get_balance(owner, coin_type)?
+            .map(balance_info)
+            .transpose()
+    }
+
+    fn balance_iter(
+        &self,
+        owner: &SuiAddress,
+        cursor: Option<StructTag>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::error::Result<(StructTag, BalanceInfo)>> + '_>,
+    > {
+        // Zero balances are only removed when the index is compacted, so filter out the coin
+        // types that an address no longer has a balance of.
+        let iter = self
+            .balance_iter(*owner, cursor)?
+            .map(|result| {
+                let (BalanceKey { coin_type, .. }, info) = result?;
+                Ok((coin_type, balance_info(info)?))
+            })
+            .filter(|result| !matches!(result, Ok((_, info)) if info.balance == 0));
+
+        Ok(Box::new(iter) as _)
+    }
+
+    fn event_iter(
+        &self,
+        selector: EventSelector,
+        cursor: Option<EventPosition>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = (EventPosition, TransactionDigest)> + '_>,
+    > {
+        let iter = self
+            .event_iter(selector, cursor)?
+            .map(|(EventIndexKey { position, .. }, digest)| (position, digest));
+
+        Ok(Box::new(iter) as _)
+    }
+}
+
+fn balance_info(
+    BalanceIndexInfo { balance_delta }: BalanceIndexInfo,
+) -> sui_types::storage::error::Result<BalanceInfo> {
+    let balance = balance_delta.try_into().map_err(|_| {
+        StorageError::custom(format!("invalid balance in rpc index: {balance_delta}"))
+    })?;
+
+    Ok(BalanceInfo { balance })
 }
@@ -0,0 +1,116 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc_api::client::sdk::Client;
+use sui_rpc_api::proto::node::node_service_client::NodeServiceClient;
+use sui_rpc_api::proto::node::GetBalanceRequest;
+use sui_rpc_api::proto::node::GetBalanceResponse;
+use sui_rpc_api::proto::node::ListBalancesRequest;
+use sui_rpc_api::proto::node::ListBalancesResponse;
+use sui_rpc_api::rest::accounts::ListBalancesQueryParameters;
+use sui_rpc_api::types::Balance;
+use sui_sdk_types::Address;
+use sui_sdk_types::StructTag;
+use sui_types::gas_coin::GAS;
+use sui_types::sui_sdk_types_conversions::struct_tag_core_to_sdk;
+use test_cluster::TestClusterBuilder;
+
+use crate::transfer_coin;
+
+#[sim_test]
+async fn get_balance() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let _transaction_digest = transfer_coin(&test_cluster.wallet).await;
+
+    let address: Address = test_cluster.get_address_0().into();
+    let sui: StructTag = struct_tag_core_to_sdk(GAS::type_()).unwrap();
+
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let Balance { coin_type, balance } = client
+        .get_balance(address, &sui)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(coin_type, sui);
+    assert!(balance > 0);
+
+    let GetBalanceResponse {
+        balance: grpc_balance,
+    } = grpc_client
+        .get_balance(GetBalanceRequest::new(address, sui.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+    let grpc_balance: Balance = grpc_balance.as_ref().unwrap().try_into().unwrap();
+    assert_eq!(grpc_balance.coin_type, sui);
+    assert_eq!(grpc_balance.balance, balance);
+
+    // An address has a balance of zero for coin types that it doesn't own any coins of.
+    let unknown: StructTag =
+        struct_tag_core_to_sdk("0x2::unknown::UNKNOWN".parse().unwrap()).unwrap();
+    let Balance { balance, .. } = client
+        .get_balance(address, &unknown)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(balance, 0);
+}
+
+#[sim_test]
+async fn list_balances() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let _transaction_digest = transfer_coin(&test_cluster.wallet).await;
+
+    let address: Address = test_cluster.get_address_0().into();
+    let sui: StructTag = struct_tag_core_to_sdk(GAS::type_()).unwrap();
+
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let balances = client
+        .list_balances(
+            address,
+            &ListBalancesQueryParameters {
+                limit: None,
+                start: None,
+            },
+        )
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Accounts in the test cluster only own SUI.
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].coin_type, sui);
+
+    let expected = client
+        .get_balance(address, &sui)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(balances[0], expected);
+
+    let ListBalancesResponse {
+        balances,
+        next_page_token,
+    } = grpc_client
+        .list_balances(ListBalancesRequest::new(address).with_page_size(1))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(balances.len(), 1);
+    assert!(next_page_token.is_none());
+
+    let balance: Balance = (&balances[0]).try_into().unwrap();
+    assert_eq!(balance, expected);
+}
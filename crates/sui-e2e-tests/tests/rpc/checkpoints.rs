@@ -1,6 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use rand::rngs::OsRng;
+use std::time::Duration;
+use sui_config::node::RunWithRange;
 use sui_macros::sim_test;
 use sui_rpc_api::client::sdk::Client;
 use sui_rpc_api::client::Client as CoreClient;
@@ -8,7 +11,7 @@ use sui_rpc_api::proto::node::node_service_client::NodeServiceClient;
 use sui_rpc_api::proto::node::{
     FullCheckpointObject, FullCheckpointTransaction, GetCheckpointOptions, GetCheckpointRequest,
     GetCheckpointResponse, GetFullCheckpointOptions, GetFullCheckpointRequest,
-    GetFullCheckpointResponse,
+    GetFullCheckpointResponse, SubscribeCheckpointsRequest, SubscribeCheckpointsResponse,
 };
 use test_cluster::TestClusterBuilder;
 
@@ -417,3 +420,179 @@ async fn get_full_checkpoint() {
         .await
         .unwrap_err();
 }
+
+#[sim_test]
+async fn subscribe_checkpoints() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let transaction_digest = transfer_coin(&test_cluster.wallet).await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    // A Checkpoint that has already been executed, which the subscription catches up from
+    let checkpoint = grpc_client
+        .get_transaction(sui_rpc_api::proto::node::GetTransactionRequest::new(
+            transaction_digest,
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .checkpoint
+        .unwrap();
+
+    let mut stream = grpc_client
+        .subscribe_checkpoints(
+            SubscribeCheckpointsRequest::from_sequence_number(checkpoint)
+                .with_options(GetFullCheckpointOptions::none()),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Checkpoints are streamed in order, starting from the requested checkpoint, and continue
+    // past the latest checkpoint as new checkpoints are executed.
+    let latest = grpc_client
+        .get_checkpoint(GetCheckpointRequest::latest())
+        .await
+        .unwrap()
+        .into_inner()
+        .sequence_number
+        .unwrap();
+
+    let mut found_transaction = false;
+    for expected in checkpoint..=latest + 2 {
+        let SubscribeCheckpointsResponse { checkpoint } = stream.message().await.unwrap().unwrap();
+        let GetFullCheckpointResponse {
+            sequence_number,
+            digest,
+            summary,
+            transactions,
+            ..
+        } = checkpoint.unwrap();
+
+        assert_eq!(sequence_number, Some(expected));
+        assert!(digest.is_some());
+        assert!(summary.is_none());
+
+        if transactions
+            .iter()
+            .any(|t| t.digest == Some(transaction_digest.into()))
+        {
+            found_transaction = true;
+        }
+    }
+
+    // Ensure we found the transaction we used for picking the checkpoint to subscribe from
+    assert!(found_transaction);
+
+    // By default, the subscription starts after the latest executed checkpoint
+    let mut stream = grpc_client
+        .subscribe_checkpoints(
+            SubscribeCheckpointsRequest::latest().with_options(GetFullCheckpointOptions::all()),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+
+    let SubscribeCheckpointsResponse { checkpoint } = stream.message().await.unwrap().unwrap();
+    let checkpoint = checkpoint.unwrap();
+    assert!(checkpoint.sequence_number.unwrap() > latest);
+    assert!(checkpoint.summary.is_some());
+    assert!(checkpoint.signature.is_some());
+}
+
+#[sim_test]
+async fn subscribe_checkpoints_limit() {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+
+    // A fullnode that only allows one checkpoint subscription at a time
+    let mut config = test_cluster
+        .fullnode_config_builder()
+        .build(&mut OsRng, test_cluster.swarm.config());
+    config
+        .rpc
+        .get_or_insert_with(Default::default)
+        .max_checkpoint_subscriptions = Some(1);
+    let fullnode = test_cluster.start_fullnode_from_config(config).await;
+
+    let mut grpc_client = NodeServiceClient::connect(fullnode.rpc_url.clone())
+        .await
+        .unwrap();
+
+    let mut stream = grpc_client
+        .subscribe_checkpoints(SubscribeCheckpointsRequest::latest())
+        .await
+        .unwrap()
+        .into_inner();
+    stream.message().await.unwrap().unwrap();
+
+    let status = grpc_client
+        .subscribe_checkpoints(SubscribeCheckpointsRequest::latest())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        status.message(),
+        "Too many checkpoint subscriptions. Limit: 1"
+    );
+
+    // Once the open subscription is closed, its place can be taken by a new one. The server
+    // notices that the subscription was closed asynchronously, so retry until it has.
+    drop(stream);
+    let mut stream = loop {
+        match grpc_client
+            .subscribe_checkpoints(SubscribeCheckpointsRequest::latest())
+            .await
+        {
+            Ok(response) => break response.into_inner(),
+            Err(status) => {
+                assert_eq!(
+                    status.message(),
+                    "Too many checkpoint subscriptions. Limit: 1"
+                );
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    };
+    stream.message().await.unwrap().unwrap();
+}
+
+#[sim_test]
+async fn subscribe_checkpoints_ends_on_shutdown() {
+    // A fullnode that shuts down once it has executed checkpoint 5
+    let stop_after_checkpoint = 5;
+    let test_cluster = TestClusterBuilder::new()
+        .with_fullnode_run_with_range(Some(RunWithRange::Checkpoint(stop_after_checkpoint)))
+        .build()
+        .await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let mut stream = grpc_client
+        .subscribe_checkpoints(
+            SubscribeCheckpointsRequest::from_sequence_number(0)
+                .with_options(GetFullCheckpointOptions::none()),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+
+    // No checkpoints are executed after the fullnode shuts down, so rather than waiting for them,
+    // the subscription ends.
+    let received = tokio::time::timeout(Duration::from_secs(120), async {
+        let mut received = vec![];
+        while let Some(SubscribeCheckpointsResponse { checkpoint }) =
+            stream.message().await.unwrap()
+        {
+            received.push(checkpoint.unwrap().sequence_number.unwrap());
+        }
+        received
+    })
+    .await
+    .expect("subscription did not end when the fullnode shut down");
+
+    assert!(received.iter().all(|seq| *seq <= stop_after_checkpoint));
+}
@@ -0,0 +1,112 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc_api::client::sdk::Client;
+use sui_rpc_api::proto::node::node_service_client::NodeServiceClient;
+use sui_rpc_api::proto::node::ListEventsRequest;
+use sui_rpc_api::proto::node::ListEventsResponse;
+use sui_rpc_api::rest::events::ListEventsQueryParameters;
+use sui_rpc_api::types::EventFilter;
+use sui_rpc_api::types::IndexedEvent;
+use test_cluster::TestClusterBuilder;
+
+use crate::stake_with_validator;
+
+#[sim_test]
+async fn list_events() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    // Staking emits an event from the system package.
+    let transaction_digest = stake_with_validator(&test_cluster).await;
+
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let events = client
+        .list_events(&ListEventsQueryParameters {
+            transaction: Some(transaction_digest),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(!events.is_empty());
+    for (i, event) in events.iter().enumerate() {
+        assert_eq!(event.transaction_digest, transaction_digest);
+        assert_eq!(event.event_index, i as u32);
+    }
+
+    let staking_event = events[0].clone();
+
+    // The same event can be found by its sender, and by its type.
+    let by_sender = client
+        .list_events(&ListEventsQueryParameters {
+            sender: Some(staking_event.event.sender),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(by_sender.contains(&staking_event));
+
+    let by_type = client
+        .list_events(&ListEventsQueryParameters {
+            event_type: Some(staking_event.event.type_.clone()),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(by_type.contains(&staking_event));
+
+    let by_module = client
+        .list_events(&ListEventsQueryParameters {
+            package: Some(staking_event.event.package_id),
+            module: Some(staking_event.event.module.clone()),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(by_module.contains(&staking_event));
+
+    // A filter must be provided
+    client
+        .list_events(&ListEventsQueryParameters::default())
+        .await
+        .unwrap_err();
+
+    // Page through the events of the transaction over gRPC, one at a time.
+    let mut grpc_events = Vec::new();
+    let mut page_token = None;
+    loop {
+        let mut request =
+            ListEventsRequest::new(EventFilter::Transaction(transaction_digest)).with_page_size(1);
+        if let Some(page_token) = page_token {
+            request = request.with_page_token(page_token);
+        }
+
+        let ListEventsResponse {
+            events,
+            next_page_token,
+        } = grpc_client.list_events(request).await.unwrap().into_inner();
+
+        assert_eq!(events.len(), 1);
+        grpc_events.extend(
+            events
+                .iter()
+                .map(|event| IndexedEvent::try_from(event).unwrap()),
+        );
+
+        page_token = next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(grpc_events, events);
+}
@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod balances;
 mod checkpoints;
 mod committee;
+mod events;
 mod execute;
 mod node_info;
 mod objects;
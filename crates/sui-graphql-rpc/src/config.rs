@@ -8,9 +8,14 @@ use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use move_core_types::ident_str;
 use move_core_types::identifier::IdentStr;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeSet, fmt::Display, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    time::Duration,
+};
 use sui_default_config::DefaultConfig;
 use sui_json_rpc::name_service::NameServiceConfig;
+use sui_protocol_config::Chain;
 use sui_types::base_types::{ObjectID, SuiAddress};
 
 pub(crate) const RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD: Duration = Duration::from_millis(30_000);
@@ -79,6 +84,10 @@ pub struct ServiceConfig {
     pub background_tasks: BackgroundTasksConfig,
     pub zklogin: ZkLoginConfig,
     pub move_registry: MoveRegistryConfig,
+    pub persisted_queries: PersistedQueriesConfig,
+    pub cost_budget: CostBudgetConfig,
+    pub response_cache: ResponseCacheConfig,
+    pub subscriptions: SubscriptionsConfig,
 }
 
 #[DefaultConfig]
@@ -136,6 +145,53 @@ pub struct BackgroundTasksConfig {
     pub watermark_update_ms: u64,
 }
 
+/// Configuration for automatic persisted queries, which allow clients to send the hash of a query
+/// they have sent before, in place of its text.
+#[DefaultConfig]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct PersistedQueriesConfig {
+    /// Number of queries (by hash) to remember. Must be greater than zero. Once full, the least
+    /// recently used queries are forgotten first.
+    pub cache_size: usize,
+}
+
+/// Budgets limiting the total cost of the queries each client can run within a window of time. The
+/// cost of a query is the sum of its input and output node estimates, as computed when checking
+/// its limits.
+#[DefaultConfig]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CostBudgetConfig {
+    /// The length of the window (in milliseconds) that a budget applies to. A client's budget is
+    /// replenished once its window has elapsed.
+    pub window_ms: u64,
+    /// The budget for each client that is identified by its IP address (clients that have not
+    /// provided a known API key). If this is not set, these clients are not budgeted.
+    pub ip_budget: Option<u64>,
+    /// The budgets for clients that identify themselves by API key, keyed by API key.
+    pub api_key_budgets: BTreeMap<String, u64>,
+}
+
+/// Configuration for the cache of responses to queries that only read immutable data
+/// (checkpoints, transaction blocks and packages at or below the checkpoint watermark).
+#[DefaultConfig]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ResponseCacheConfig {
+    /// Maximum total size (in bytes) of the cached queries and their responses. Once full, the
+    /// least recently used responses are evicted first.
+    pub max_size_bytes: usize,
+}
+
+/// Limits on the subscriptions that clients can have open at the same time. Each subscription
+/// counts towards both limits from the moment it is started until its stream ends or is dropped.
+#[DefaultConfig]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SubscriptionsConfig {
+    /// Maximum number of subscriptions open across all connections to the service.
+    pub max_subscriptions: u32,
+    /// Maximum number of subscriptions open on a single connection.
+    pub max_subscriptions_per_connection: u32,
+}
+
 #[DefaultConfig]
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct MoveRegistryConfig {
@@ -207,6 +263,8 @@ pub struct Experiments {
 #[derive(Clone, Debug)]
 pub struct InternalFeatureConfig {
     pub(crate) query_limits_checker: bool,
+    pub(crate) persisted_queries: bool,
+    pub(crate) response_cache: bool,
     pub(crate) directive_checker: bool,
     pub(crate) feature_gate: bool,
     pub(crate) logger: bool,
@@ -463,6 +521,15 @@ impl MoveRegistryConfig {
             registry_id,
         }
     }
+
+    /// The Move Registry that names are resolved against on `chain`, if the registry is deployed
+    /// there. It is only deployed on mainnet, so the default configuration is the mainnet one.
+    pub fn for_chain(chain: Chain) -> Option<Self> {
+        match chain {
+            Chain::Mainnet => Some(Self::default()),
+            Chain::Testnet | Chain::Unknown => None,
+        }
+    }
 }
 
 impl Default for Ide {
@@ -532,6 +599,8 @@ impl Default for InternalFeatureConfig {
     fn default() -> Self {
         Self {
             query_limits_checker: true,
+            persisted_queries: true,
+            response_cache: false,
             directive_checker: true,
             feature_gate: true,
             logger: true,
@@ -544,6 +613,39 @@ impl Default for InternalFeatureConfig {
     }
 }
 
+impl Default for PersistedQueriesConfig {
+    fn default() -> Self {
+        Self { cache_size: 1_000 }
+    }
+}
+
+impl Default for CostBudgetConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 60_000,
+            ip_budget: None,
+            api_key_budgets: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_size_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+impl Default for SubscriptionsConfig {
+    fn default() -> Self {
+        Self {
+            max_subscriptions: 1_000,
+            max_subscriptions_per_connection: 10,
+        }
+    }
+}
+
 impl Default for BackgroundTasksConfig {
     fn default() -> Self {
         Self {
@@ -729,6 +831,79 @@ mod tests {
         assert_eq!(actual, expect);
     }
 
+    #[test]
+    fn test_read_cost_budget_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [persisted-queries]
+                cache-size = 42
+
+                [cost-budget]
+                window-ms = 1000
+                ip-budget = 5000
+
+                [cost-budget.api-key-budgets]
+                alice = 100000
+                bob = 200000
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            persisted_queries: PersistedQueriesConfig { cache_size: 42 },
+            cost_budget: CostBudgetConfig {
+                window_ms: 1000,
+                ip_budget: Some(5000),
+                api_key_budgets: BTreeMap::from([
+                    ("alice".to_string(), 100_000),
+                    ("bob".to_string(), 200_000),
+                ]),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_read_response_cache_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [response-cache]
+                max-size-bytes = 1048576
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            response_cache: ResponseCacheConfig {
+                max_size_bytes: 1024 * 1024,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_read_subscriptions_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [subscriptions]
+                max-subscriptions = 42
+                max-subscriptions-per-connection = 3
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            subscriptions: SubscriptionsConfig {
+                max_subscriptions: 42,
+                max_subscriptions_per_connection: 3,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
     #[test]
     fn test_read_partial_in_service_config() {
         let actual = ServiceConfig::read(
@@ -0,0 +1,278 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sui_graphql_rpc_headers::API_KEY_HEADER;
+
+use crate::config::CostBudgetConfig;
+
+/// The API key that the request was made with, as it comes from the request header. Only keys that
+/// the service has been configured with are given their own budget.
+#[derive(Clone, Debug)]
+pub(crate) struct ApiKey(pub String);
+
+/// Tracks how much of its budget each client has spent in its current window. Clients are
+/// identified by their API key if it is one the service knows about, or by their IP address
+/// otherwise.
+pub(crate) struct CostBudgets {
+    config: CostBudgetConfig,
+    state: Mutex<State>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Client {
+    ApiKey(String),
+    Ip(IpAddr),
+}
+
+struct State {
+    windows: HashMap<Client, Window>,
+    /// The last time windows that had elapsed were cleared out.
+    last_swept: Instant,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Window {
+    start: Instant,
+    spent: u64,
+}
+
+/// How much of its budget a client has spent, as reported in a response's extensions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BudgetUsage {
+    /// The cost of this request.
+    pub cost: u64,
+    /// The cost of all the requests in the current window, including this request if it was
+    /// accepted.
+    pub spent: u64,
+    /// The budget for the current window.
+    pub budget: u64,
+    /// Time (in milliseconds) until the current window elapses, and the budget is replenished.
+    pub reset_ms: u64,
+}
+
+impl ApiKey {
+    pub(crate) fn name() -> &'static axum::http::HeaderName {
+        &API_KEY_HEADER
+    }
+}
+
+impl CostBudgets {
+    pub(crate) fn new(config: CostBudgetConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State {
+                windows: HashMap::new(),
+                last_swept: Instant::now(),
+            }),
+        }
+    }
+
+    /// Charge `cost` to the budget of the client identified by `api_key` or `addr`.
+    ///
+    /// Returns the client's usage after the charge, or `None` if the client is not budgeted. If
+    /// the charge would take the client over budget, it is not applied, and the client's usage
+    /// before the charge is returned as an error.
+    pub(crate) fn charge(
+        &self,
+        api_key: Option<&ApiKey>,
+        addr: &SocketAddr,
+        cost: u64,
+    ) -> Result<Option<BudgetUsage>, BudgetUsage> {
+        self.charge_at(api_key, addr, cost, Instant::now())
+    }
+
+    fn charge_at(
+        &self,
+        api_key: Option<&ApiKey>,
+        addr: &SocketAddr,
+        cost: u64,
+        now: Instant,
+    ) -> Result<Option<BudgetUsage>, BudgetUsage> {
+        let (client, budget) = match api_key {
+            Some(ApiKey(key)) if self.config.api_key_budgets.contains_key(key) => (
+                Client::ApiKey(key.clone()),
+                self.config.api_key_budgets[key],
+            ),
+            _ => match self.config.ip_budget {
+                Some(budget) => (Client::Ip(addr.ip()), budget),
+                None => return Ok(None),
+            },
+        };
+
+        let length = Duration::from_millis(self.config.window_ms);
+        let mut state = self.state.lock().unwrap();
+
+        // Forget about clients whose windows have elapsed, so that clients who have stopped
+        // sending requests don't accumulate.
+        if now.duration_since(state.last_swept) >= length {
+            state
+                .windows
+                .retain(|_, window| now.duration_since(window.start) < length);
+            state.last_swept = now;
+        }
+
+        let window = state
+            .windows
+            .entry(client)
+            .and_modify(|window| {
+                if now.duration_since(window.start) >= length {
+                    *window = Window {
+                        start: now,
+                        spent: 0,
+                    };
+                }
+            })
+            .or_insert(Window {
+                start: now,
+                spent: 0,
+            });
+
+        let mut usage = BudgetUsage {
+            cost,
+            spent: window.spent,
+            budget,
+            reset_ms: length
+                .saturating_sub(now.duration_since(window.start))
+                .as_millis() as u64,
+        };
+
+        if window.spent.saturating_add(cost) > budget {
+            return Err(usage);
+        }
+
+        window.spent += cost;
+        usage.spent = window.spent;
+        Ok(Some(usage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn budgets(ip_budget: Option<u64>) -> CostBudgets {
+        CostBudgets::new(CostBudgetConfig {
+            window_ms: 1000,
+            ip_budget,
+            api_key_budgets: BTreeMap::from([("key".to_string(), 100)]),
+        })
+    }
+
+    fn addr(ip: &str) -> SocketAddr {
+        format!("{ip}:8000").parse().unwrap()
+    }
+
+    #[test]
+    fn test_unbudgeted_clients() {
+        let budgets = budgets(None);
+        let now = Instant::now();
+
+        // Without an IP budget, clients that don't provide a known API key are not budgeted.
+        assert_eq!(
+            budgets.charge_at(None, &addr("1.2.3.4"), 1000, now),
+            Ok(None)
+        );
+
+        let unknown = ApiKey("unknown".to_string());
+        assert_eq!(
+            budgets.charge_at(Some(&unknown), &addr("1.2.3.4"), 1000, now),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_charge_within_budget() {
+        let budgets = budgets(Some(50));
+        let now = Instant::now();
+        let key = ApiKey("key".to_string());
+
+        let usage = budgets
+            .charge_at(Some(&key), &addr("1.2.3.4"), 60, now)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            usage,
+            BudgetUsage {
+                cost: 60,
+                spent: 60,
+                budget: 100,
+                reset_ms: 1000,
+            }
+        );
+
+        // The API key's budget is separate from the IP address's budget.
+        let usage = budgets
+            .charge_at(None, &addr("1.2.3.4"), 50, now)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(usage.spent, 50);
+        assert_eq!(usage.budget, 50);
+    }
+
+    #[test]
+    fn test_charge_over_budget() {
+        let budgets = budgets(Some(50));
+        let now = Instant::now();
+
+        budgets.charge_at(None, &addr("1.2.3.4"), 40, now).unwrap();
+
+        // Going over budget is rejected, and does not count towards the amount spent.
+        let later = now + Duration::from_millis(400);
+        let usage = budgets
+            .charge_at(None, &addr("1.2.3.4"), 20, later)
+            .unwrap_err();
+
+        assert_eq!(
+            usage,
+            BudgetUsage {
+                cost: 20,
+                spent: 40,
+                budget: 50,
+                reset_ms: 600,
+            }
+        );
+
+        // Requests that fit in the remaining budget are accepted.
+        let usage = budgets
+            .charge_at(None, &addr("1.2.3.4"), 10, later)
+            .unwrap()
+            .unwrap();
+        assert_eq!(usage.spent, 50);
+
+        // Other IP addresses have their own budgets.
+        budgets
+            .charge_at(None, &addr("5.6.7.8"), 20, later)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_budget_replenished() {
+        let budgets = budgets(Some(50));
+        let now = Instant::now();
+
+        budgets.charge_at(None, &addr("1.2.3.4"), 50, now).unwrap();
+        budgets
+            .charge_at(None, &addr("1.2.3.4"), 1, now)
+            .unwrap_err();
+
+        let later = now + Duration::from_millis(1000);
+        let usage = budgets
+            .charge_at(None, &addr("1.2.3.4"), 50, later)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(usage.spent, 50);
+        assert_eq!(usage.reset_ms, 1000);
+    }
+}
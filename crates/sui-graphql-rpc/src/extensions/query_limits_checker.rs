@@ -3,6 +3,7 @@
 
 use crate::config::{Limits, ServiceConfig};
 use crate::error::{code, graphql_error, graphql_error_at_pos};
+use crate::extensions::cost_budget::{ApiKey, BudgetUsage, CostBudgets};
 use crate::metrics::Metrics;
 use async_graphql::extensions::NextParseQuery;
 use async_graphql::extensions::NextRequest;
@@ -43,6 +44,7 @@ pub(crate) struct QueryLimitsChecker;
 #[derive(Debug, Default)]
 struct QueryLimitsCheckerExt {
     usage: Mutex<Option<Usage>>,
+    budget: Mutex<Option<BudgetUsage>>,
 }
 
 /// Only display usage information if this header was in the request.
@@ -648,6 +650,30 @@ impl<'a> Reporter<'a> {
         )
     }
 
+    /// Error returned if running the query would take the client over its cost budget.
+    fn over_budget_error(&self, usage: &BudgetUsage) -> ServerError {
+        let BudgetUsage {
+            cost,
+            spent,
+            budget,
+            reset_ms,
+        } = usage;
+
+        let mut error = self.graphql_error(
+            code::BUDGET_EXCEEDED,
+            format!(
+                "Query cost {cost} exceeds remaining budget. {spent} of {budget} has already been \
+                 spent, and the budget will be replenished in {reset_ms}ms."
+            ),
+        );
+
+        if let Some(extensions) = &mut error.extensions {
+            extensions.set("budget", value!(usage));
+        }
+
+        error
+    }
+
     /// Error returned if the payload size exceeds the limit.
     fn payload_size_error(&self, message: &str) -> ServerError {
         self.graphql_error(
@@ -696,6 +722,11 @@ impl<'a> Reporter<'a> {
 }
 
 impl Usage {
+    /// The cost of the query, charged against the client's budget.
+    fn cost(&self) -> u64 {
+        self.input_nodes as u64 + self.output_nodes as u64
+    }
+
     fn report(&self, metrics: &Metrics) {
         metrics
             .request_metrics
@@ -720,6 +751,7 @@ impl ExtensionFactory for QueryLimitsChecker {
     fn create(&self) -> Arc<dyn Extension> {
         Arc::new(QueryLimitsCheckerExt {
             usage: Mutex::new(None),
+            budget: Mutex::new(None),
         })
     }
 }
@@ -727,13 +759,19 @@ impl ExtensionFactory for QueryLimitsChecker {
 #[async_trait]
 impl Extension for QueryLimitsCheckerExt {
     async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
-        let resp = next.run(ctx).await;
+        let mut resp = next.run(ctx).await;
+
         let usage = self.usage.lock().unwrap().take();
         if let Some(usage) = usage {
-            resp.extension("usage", value!(usage))
-        } else {
-            resp
+            resp = resp.extension("usage", value!(usage));
         }
+
+        let budget = self.budget.lock().unwrap().take();
+        if let Some(budget) = budget {
+            resp = resp.extension("budget", value!(budget));
+        }
+
+        resp
     }
 
     /// Validates the query against the limits set in the service config
@@ -746,9 +784,13 @@ impl Extension for QueryLimitsCheckerExt {
         next: NextParseQuery<'_>,
     ) -> ServerResult<ExecutableDocument> {
         let metrics: &Metrics = ctx.data_unchecked();
-        let payload_size: &PayloadSize = ctx.data_unchecked();
         let reporter = Reporter::new(ctx);
 
+        // Persisted queries are sent by hash, so their text is not part of the request's payload.
+        // Account for the query as if it had been sent in full.
+        let PayloadSize(payload_size) = ctx.data_unchecked();
+        let payload_size = PayloadSize((*payload_size).max(query.len() as u64));
+
         let instant = Instant::now();
 
         // Make sure the request meets a basic size limit before trying to parse it.
@@ -777,19 +819,34 @@ impl Extension for QueryLimitsCheckerExt {
         }
 
         let mut traversal =
-            LimitsTraversal::new(*payload_size, &reporter, &doc.fragments, variables);
+            LimitsTraversal::new(payload_size, &reporter, &doc.fragments, variables);
 
         let res = traversal.check_document(&doc);
         let usage = traversal.finish(query.len() as u32);
         metrics.query_validation_latency(instant.elapsed());
         usage.report(metrics);
-
-        res.map(|()| {
-            if ctx.data_opt::<ShowUsage>().is_some() {
-                *self.usage.lock().unwrap() = Some(usage);
+        res?;
+
+        // Only queries that are within limits are charged against the client's budget.
+        if let Some(budgets) = ctx.data_opt::<CostBudgets>() {
+            let cost = usage.cost();
+            metrics.request_metrics.query_cost.observe(cost as f64);
+
+            match budgets.charge(ctx.data_opt::<ApiKey>(), reporter.session_id, cost) {
+                Ok(budget) => *self.budget.lock().unwrap() = budget,
+                Err(budget) => {
+                    metrics.request_metrics.num_over_budget.inc();
+                    let error = reporter.over_budget_error(&budget);
+                    *self.budget.lock().unwrap() = Some(budget);
+                    return Err(error);
+                }
             }
+        }
 
-            doc
-        })
+        if ctx.data_opt::<ShowUsage>().is_some() {
+            *self.usage.lock().unwrap() = Some(usage);
+        }
+
+        Ok(doc)
     }
 }
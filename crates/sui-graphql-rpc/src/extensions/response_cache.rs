@@ -0,0 +1,433 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextRequest,
+    NextResolve, ResolveInfo,
+};
+use async_graphql::parser::types::{
+    DocumentOperations, ExecutableDocument, Field, OperationType, Selection,
+};
+use async_graphql::{value, Response, ServerResult, Variables};
+use async_graphql_value::ConstValue;
+use async_trait::async_trait;
+use lru::LruCache;
+use serde::Serialize;
+
+use crate::config::ResponseCacheConfig;
+use crate::metrics::Metrics;
+
+/// Extension factory for the response cache. Caches the responses to queries that only read data
+/// that cannot change once it has been indexed: Checkpoints, transaction blocks and packages, at
+/// or below the checkpoint watermark. The cache is shared between all requests.
+pub(crate) struct ResponseCache {
+    cache: Arc<Mutex<Cache>>,
+}
+
+/// Responses, keyed by the normalized query and variables that produced them. Bounded by the
+/// total size of its keys and (printed) responses, evicting the least recently used responses
+/// first.
+struct Cache {
+    entries: LruCache<String, Arc<ConstValue>>,
+    size: usize,
+    max_size: usize,
+}
+
+struct ResponseCacheExt {
+    cache: Arc<Mutex<Cache>>,
+    /// The key for this request's response, if the request is eligible to be cached.
+    key: Mutex<Option<String>>,
+    /// Set if the request read data that can change, so its response should not be cached.
+    mutable: AtomicBool,
+    status: Mutex<Option<CacheStatus>>,
+}
+
+/// Whether a request was served from the cache, as reported in a response's extensions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum CacheStatus {
+    /// The response was served from the cache.
+    Hit,
+    /// The request was eligible to be cached, but its response was not in the cache.
+    Miss,
+    /// The request was not eligible to be cached.
+    Bypass,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: ResponseCacheConfig) -> Self {
+        Self {
+            cache: Arc::new(Mutex::new(Cache::new(config.max_size_bytes))),
+        }
+    }
+}
+
+impl Cache {
+    fn new(max_size: usize) -> Self {
+        Self {
+            entries: LruCache::unbounded(),
+            size: 0,
+            max_size,
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<Arc<ConstValue>> {
+        self.entries.get(key).cloned()
+    }
+
+    /// Add `data` to the cache under `key`, evicting the least recently used entries to make
+    /// space for it. Responses that are too large to ever fit in the cache are not added.
+    fn insert(&mut self, key: String, data: ConstValue) {
+        let size = entry_size(&key, &data);
+        if size > self.max_size {
+            return;
+        }
+
+        if let Some(prev) = self.entries.pop(&key) {
+            self.size -= entry_size(&key, &prev);
+        }
+
+        while self.size + size > self.max_size {
+            let Some((evicted_key, evicted)) = self.entries.pop_lru() else {
+                break;
+            };
+
+            self.size -= entry_size(&evicted_key, &evicted);
+        }
+
+        self.size += size;
+        self.entries.put(key, Arc::new(data));
+    }
+}
+
+impl CacheStatus {
+    fn name(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "hit",
+            CacheStatus::Miss => "miss",
+            CacheStatus::Bypass => "bypass",
+        }
+    }
+}
+
+impl ExtensionFactory for ResponseCache {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ResponseCacheExt {
+            cache: self.cache.clone(),
+            key: Mutex::new(None),
+            mutable: AtomicBool::new(false),
+            status: Mutex::new(None),
+        })
+    }
+}
+
+#[async_trait]
+impl Extension for ResponseCacheExt {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let resp = next.run(ctx).await;
+
+        let status = self.status.lock().unwrap().take();
+        if let Some(status) = status {
+            let metrics: &Metrics = ctx.data_unchecked();
+            metrics
+                .request_metrics
+                .num_response_cache_requests
+                .with_label_values(&[status.name()])
+                .inc();
+
+            resp.extension("cache", value!(status))
+        } else {
+            resp
+        }
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let doc = next.run(ctx, query, variables).await?;
+
+        *self.key.lock().unwrap() = if is_immutable_query(&doc, variables) {
+            // Variables are included in the key in full, in case the normalized document elides
+            // any of their values.
+            let doc = ctx.stringify_execute_doc(&doc, variables);
+            let variables = serde_json::to_string(variables).unwrap_or_default();
+            Some(format!("{doc}\n{variables}"))
+        } else {
+            None
+        };
+
+        Ok(doc)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let key = self.key.lock().unwrap().clone();
+        let Some(key) = key else {
+            *self.status.lock().unwrap() = Some(CacheStatus::Bypass);
+            return next.run(ctx, operation_name).await;
+        };
+
+        let key = format!("{}\n{key}", operation_name.unwrap_or_default());
+
+        let cached = self.cache.lock().unwrap().get(&key);
+        if let Some(data) = cached {
+            *self.status.lock().unwrap() = Some(CacheStatus::Hit);
+            return Response::new(data.as_ref().clone());
+        }
+
+        *self.status.lock().unwrap() = Some(CacheStatus::Miss);
+
+        let resp = next.run(ctx, operation_name).await;
+        if resp.errors.is_empty() && !self.mutable.load(Ordering::Relaxed) {
+            let mut cache = self.cache.lock().unwrap();
+            cache.insert(key, resp.data.clone());
+
+            let metrics: &Metrics = ctx.data_unchecked();
+            metrics
+                .request_metrics
+                .response_cache_size
+                .set(cache.size as i64);
+        }
+
+        resp
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<ConstValue>> {
+        // Only requests that are eligible to be cached need to watch for reads of data that
+        // could change.
+        if self.key.lock().unwrap().is_none() {
+            return next.run(ctx, info).await;
+        }
+
+        if is_mutable_field(info.parent_type, info.name) {
+            self.mutable.store(true, Ordering::Relaxed);
+        }
+
+        let is_top_level = info.path_node.parent.is_none();
+        let resolved = next.run(ctx, info).await;
+
+        // An entity that was not found may still be indexed later.
+        if is_top_level && matches!(resolved, Ok(None) | Ok(Some(ConstValue::Null))) {
+            self.mutable.store(true, Ordering::Relaxed);
+        }
+
+        resolved
+    }
+}
+
+/// Size of a cache entry, for the purposes of bounding the cache's size.
+fn entry_size(key: &str, data: &ConstValue) -> usize {
+    key.len() + data.to_string().len()
+}
+
+/// A query is eligible to be cached if it is a single query operation, whose top-level fields all
+/// fetch an entity that cannot change once it has been indexed. Its response is only cached if
+/// all those entities are found, and none of their nested fields read data that can change (see
+/// `is_mutable_field`).
+fn is_immutable_query(doc: &ExecutableDocument, variables: &Variables) -> bool {
+    let DocumentOperations::Single(op) = &doc.operations else {
+        return false;
+    };
+
+    if op.node.ty != OperationType::Query {
+        return false;
+    }
+
+    op.node
+        .selection_set
+        .node
+        .items
+        .iter()
+        .all(|selection| match &selection.node {
+            Selection::Field(field) => is_immutable_root(&field.node, variables),
+            Selection::FragmentSpread(_) | Selection::InlineFragment(_) => false,
+        })
+}
+
+/// Whether the top-level `field` fetches an entity that cannot change once it has been indexed.
+/// Fields that fetch the latest version of an entity do not qualify.
+fn is_immutable_root(field: &Field, variables: &Variables) -> bool {
+    let argument = |name: &str| -> Option<ConstValue> {
+        let value = field.get_argument(name)?.node.clone();
+        value
+            .into_const_with(|var| variables.get(&var).cloned().ok_or(()))
+            .ok()
+            .filter(|value| *value != ConstValue::Null)
+    };
+
+    match field.name.node.as_str() {
+        "__typename" | "transactionBlock" => true,
+
+        // Without an ID, this fetches the latest checkpoint.
+        "checkpoint" => match argument("id") {
+            Some(ConstValue::Object(id)) => id.values().any(|v| *v != ConstValue::Null),
+            _ => false,
+        },
+
+        // Without a version, this fetches the latest version of the package.
+        "package" => argument("version").is_some(),
+
+        _ => false,
+    }
+}
+
+/// Whether the field `name` on `type_` reads data that can change after the entity it belongs to
+/// has been indexed, typically because it reflects the state of the network as of the latest
+/// checkpoint.
+fn is_mutable_field(type_: &str, name: &str) -> bool {
+    // Types representing state that is always read as of the latest checkpoint, and the only
+    // field of each that is safe to include in a cached response (the one identifying it).
+    static MUTABLE_TYPES: &[(&str, &str)] = &[
+        ("Address", "address"),
+        ("Epoch", "epochId"),
+        ("Owner", "address"),
+    ];
+
+    // Fields of otherwise immutable types that read the latest state of the network.
+    static MUTABLE_FIELDS: &[&str] = &[
+        "balance",
+        "balances",
+        "coins",
+        "defaultSuinsName",
+        "display",
+        "dynamicField",
+        "dynamicFields",
+        "dynamicObjectField",
+        "estimatedReward",
+        "latestPackage",
+        "objects",
+        "packageVersions",
+        "receivedTransactionBlocks",
+        "stakeStatus",
+        "stakedSuis",
+        "suinsRegistrations",
+        "supply",
+    ];
+
+    if name == "__typename" {
+        return false;
+    }
+
+    if let Some((_, id)) = MUTABLE_TYPES.iter().find(|(t, _)| *t == type_) {
+        return name != *id;
+    }
+
+    MUTABLE_FIELDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::parser::parse_query;
+
+    use super::*;
+
+    fn immutable(query: &str, variables: serde_json::Value) -> bool {
+        let doc = parse_query(query).unwrap();
+        let variables = Variables::from_json(variables);
+        is_immutable_query(&doc, &variables)
+    }
+
+    fn data(s: &str) -> ConstValue {
+        ConstValue::from_json(serde_json::json!({ "s": s })).unwrap()
+    }
+
+    #[test]
+    fn test_immutable_queries() {
+        let null = serde_json::Value::Null;
+
+        assert!(immutable(
+            "{ checkpoint(id: { sequenceNumber: 42 }) { digest } }",
+            null.clone()
+        ));
+
+        assert!(immutable(
+            r#"query ($d: String!) { transactionBlock(digest: $d) { digest } __typename }"#,
+            serde_json::json!({ "d": "abc" })
+        ));
+
+        assert!(immutable(
+            r#"query ($v: UInt53) { package(address: "0x2", version: $v) { version } }"#,
+            serde_json::json!({ "v": 1 })
+        ));
+    }
+
+    #[test]
+    fn test_mutable_queries() {
+        let null = serde_json::Value::Null;
+
+        // Latest checkpoint and package.
+        assert!(!immutable("{ checkpoint { digest } }", null.clone()));
+        assert!(!immutable(
+            "{ checkpoint(id: {}) { digest } }",
+            null.clone()
+        ));
+        assert!(!immutable(
+            r#"query ($v: UInt53) { package(address: "0x2", version: $v) { version } }"#,
+            null.clone()
+        ));
+
+        // Unsupported roots, mutations and fragments.
+        assert!(!immutable(
+            r#"{ transactionBlock(digest: "abc") { digest } epoch { epochId } }"#,
+            null.clone()
+        ));
+        assert!(!immutable(
+            r#"mutation { executeTransactionBlock(txBytes: "", signatures: []) { errors } }"#,
+            null.clone()
+        ));
+        assert!(!immutable(
+            r#"{ ... on Query { transactionBlock(digest: "abc") { digest } } }"#,
+            null.clone()
+        ));
+    }
+
+    #[test]
+    fn test_mutable_fields() {
+        assert!(!is_mutable_field("TransactionBlock", "sender"));
+        assert!(!is_mutable_field("Address", "address"));
+        assert!(!is_mutable_field("Epoch", "__typename"));
+        assert!(is_mutable_field("Address", "transactionBlocks"));
+        assert!(is_mutable_field("Epoch", "endTimestamp"));
+        assert!(is_mutable_field("MovePackage", "latestPackage"));
+        assert!(is_mutable_field("Object", "dynamicFields"));
+    }
+
+    #[test]
+    fn test_cache_size_bound() {
+        let entry = entry_size("a", &data("xxxxxxxx"));
+        let mut cache = Cache::new(entry * 2);
+
+        cache.insert("a".to_string(), data("xxxxxxxx"));
+        cache.insert("b".to_string(), data("yyyyyyyy"));
+        assert_eq!(cache.size, entry * 2);
+
+        // Reading `a` makes `b` the least recently used entry, so it is evicted first.
+        assert!(cache.get("a").is_some());
+        cache.insert("c".to_string(), data("zzzzzzzz"));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+        assert_eq!(cache.size, entry * 2);
+
+        // Responses that don't fit in the cache are not added.
+        cache.insert("d".to_string(), data(&"w".repeat(entry * 2)));
+        assert!(cache.get("d").is_none());
+        assert_eq!(cache.size, entry * 2);
+    }
+}
@@ -15,5 +15,13 @@ mod metrics;
 mod mutation;
 pub(crate) mod raw_query;
 pub mod server;
+mod subscription;
 pub mod test_infra;
 mod types;
+
+/// Move Registry (MVR) on-chain types, exposed so that clients outside of the GraphQL service can
+/// resolve names the same way.
+pub mod move_registry {
+    pub use crate::types::move_registry::error::MoveRegistryError;
+    pub use crate::types::move_registry::on_chain::{AppInfo, AppRecord, Name, VersionedName};
+}
@@ -7,8 +7,8 @@ use async_graphql::{PathSegment, ServerError};
 use prometheus::{
     register_gauge_with_registry, register_histogram_vec_with_registry,
     register_histogram_with_registry, register_int_counter_vec_with_registry,
-    register_int_counter_with_registry, Gauge, Histogram, HistogramVec, IntCounter, IntCounterVec,
-    Registry,
+    register_int_counter_with_registry, register_int_gauge_with_registry, Gauge, Histogram,
+    HistogramVec, IntCounter, IntCounterVec, IntGauge, Registry,
 };
 
 use crate::error::code;
@@ -28,6 +28,10 @@ const OUTPUT_NODES_BUCKETS: &[f64] = &[
     100., 200., 400., 800., 1200., 1600., 2400., 3200., 4800., 6400., 9600., 12800., 25600.,
     51200., 102400.,
 ];
+const QUERY_COST_BUCKETS: &[f64] = &[
+    100., 200., 400., 800., 1200., 1600., 2400., 3200., 4800., 6400., 9600., 12800., 25600.,
+    51200., 102400., 204800.,
+];
 const QUERY_DEPTH_BUCKETS: &[f64] = &[
     1., 2., 4., 8., 12., 16., 24., 32., 48., 64., 96., 128., 256., 512., 1024.,
 ];
@@ -64,6 +68,8 @@ pub(crate) struct RequestMetrics {
     pub input_nodes: Histogram,
     /// The number of nodes in the result
     pub output_nodes: Histogram,
+    /// The cost of queries that were charged against a client's budget
+    pub query_cost: Histogram,
     /// The query depth
     pub query_depth: Histogram,
     /// The size (in bytes) of the payload
@@ -78,6 +84,12 @@ pub(crate) struct RequestMetrics {
     pub num_queries: IntCounter,
     /// Number of queries by top level path
     pub num_queries_top_level: IntCounterVec,
+    /// Number of queries rejected because they would exceed the client's cost budget
+    pub num_over_budget: IntCounter,
+    /// Number of requests by whether they were served from the response cache
+    pub num_response_cache_requests: IntCounterVec,
+    /// Total size (in bytes) of the responses in the response cache
+    pub response_cache_size: IntGauge,
     /// Total inflight requests
     pub inflight_requests: Gauge,
 }
@@ -205,6 +217,13 @@ impl RequestMetrics {
                 registry,
             )
             .unwrap(),
+            query_cost: register_histogram_with_registry!(
+                "query_cost",
+                "Cost of the query, charged against the client's budget",
+                QUERY_COST_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
             query_depth: register_histogram_with_registry!(
                 "query_depth",
                 "Depth of the query",
@@ -253,6 +272,25 @@ impl RequestMetrics {
                 registry
             )
             .unwrap(),
+            num_over_budget: register_int_counter_with_registry!(
+                "num_over_budget_requests",
+                "Number of queries rejected for exceeding the client's cost budget",
+                registry
+            )
+            .unwrap(),
+            num_response_cache_requests: register_int_counter_vec_with_registry!(
+                "num_response_cache_requests",
+                "Number of requests by response cache status (hit, miss or bypass)",
+                &["status"],
+                registry
+            )
+            .unwrap(),
+            response_cache_size: register_int_gauge_with_registry!(
+                "response_cache_size",
+                "Total size (in bytes) of the queries and responses in the response cache",
+                registry
+            )
+            .unwrap(),
             inflight_requests: register_gauge_with_registry!(
                 "inflight_requests",
                 "Number of queries that are being resolved at a moment in time",
@@ -14,6 +14,7 @@ use crate::data::{DataLoader, Db};
 use crate::extensions::directive_checker::DirectiveChecker;
 use crate::metrics::Metrics;
 use crate::mutation::Mutation;
+use crate::subscription::{ConnectionSubscriptions, ServiceSubscriptions, Subscription};
 use crate::types::datatype::IMoveDatatype;
 use crate::types::move_object::IMoveObject;
 use crate::types::object::IObject;
@@ -23,22 +24,27 @@ use crate::{
     context_data::db_data_provider::PgManager,
     error::Error,
     extensions::{
+        cost_budget::{ApiKey, CostBudgets},
         feature_gate::FeatureGate,
         logger::Logger,
         query_limits_checker::{PayloadSize, QueryLimitsChecker, ShowUsage},
+        response_cache::ResponseCache,
         timeout::Timeout,
     },
     server::version::set_version_middleware,
     types::query::{Query, SuiGraphQLSchema},
 };
+use async_graphql::extensions::apollo_persisted_queries::{
+    ApolloPersistedQueries, LruCacheStorage,
+};
 use async_graphql::extensions::ApolloTracing;
 use async_graphql::extensions::Tracing;
-use async_graphql::EmptySubscription;
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
 use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
 use axum::body::Body;
 use axum::extract::FromRef;
-use axum::extract::{ConnectInfo, Query as AxumQuery, State};
+use axum::extract::{ConnectInfo, Query as AxumQuery, State, WebSocketUpgrade};
 use axum::http::{HeaderMap, StatusCode};
 use axum::middleware::{self};
 use axum::response::IntoResponse;
@@ -56,12 +62,12 @@ use std::net::TcpStream;
 use std::sync::Arc;
 use std::time::Duration;
 use std::{any::Any, net::SocketAddr, time::Instant};
-use sui_graphql_rpc_headers::LIMITS_HEADER;
+use sui_graphql_rpc_headers::{API_KEY_HEADER, LIMITS_HEADER};
 use sui_indexer::db::check_db_migration_consistency;
 use sui_package_resolver::{PackageStoreWithLruCache, Resolver};
 use sui_sdk::SuiClientBuilder;
 use tokio::join;
-use tokio::sync::OnceCell;
+use tokio::sync::{watch, OnceCell};
 use tokio_util::sync::CancellationToken;
 use tower::{Layer, Service};
 use tower_http::cors::{AllowOrigin, CorsLayer};
@@ -145,7 +151,7 @@ impl Server {
 
 pub(crate) struct ServerBuilder {
     state: AppState,
-    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, Subscription>,
     router: Option<Router>,
     db_reader: Option<Db>,
     resolver: Option<PackageResolver>,
@@ -184,6 +190,12 @@ impl FromRef<AppState> for ConnectionConfig {
     }
 }
 
+impl FromRef<AppState> for ServiceConfig {
+    fn from_ref(app_state: &AppState) -> ServiceConfig {
+        app_state.service.clone()
+    }
+}
+
 impl FromRef<AppState> for Metrics {
     fn from_ref(app_state: &AppState) -> Metrics {
         app_state.metrics.clone()
@@ -219,7 +231,7 @@ impl ServerBuilder {
     }
 
     #[cfg(test)]
-    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
+    fn build_schema(self) -> Schema<Query, Mutation, Subscription> {
         self.schema.finish()
     }
 
@@ -229,7 +241,7 @@ impl ServerBuilder {
         self,
     ) -> (
         String,
-        Schema<Query, Mutation, EmptySubscription>,
+        Schema<Query, Mutation, Subscription>,
         Db,
         PackageResolver,
         Router,
@@ -256,6 +268,8 @@ impl ServerBuilder {
             let router: Router = Router::new()
                 .route("/", post(graphql_handler))
                 .route("/graphql", post(graphql_handler))
+                .route("/subscriptions", get(subscription_handler))
+                .route("/graphql/subscriptions", get(subscription_handler))
                 .route("/health", get(health_check))
                 .route("/graphql/health", get(health_check))
                 .with_state(self.state.clone())
@@ -308,7 +322,11 @@ impl ServerBuilder {
             .allow_methods([Method::POST])
             // Allow requests from any origin
             .allow_origin(acl)
-            .allow_headers([hyper::header::CONTENT_TYPE, LIMITS_HEADER.clone()]);
+            .allow_headers([
+                hyper::header::CONTENT_TYPE,
+                LIMITS_HEADER.clone(),
+                API_KEY_HEADER.clone(),
+            ]);
         Ok(cors)
     }
 
@@ -344,6 +362,9 @@ impl ServerBuilder {
             ))
             .layer(axum::extract::Extension(schema))
             .layer(axum::extract::Extension(watermark_task.lock()))
+            .layer(axum::extract::Extension(
+                watermark_task.watermark_receiver(),
+            ))
             .layer(axum::extract::Extension(watermark_task.chain_id_lock()))
             .layer(Self::cors()?);
 
@@ -472,7 +493,15 @@ impl ServerBuilder {
             .context_data(MoveRegistryDataLoader::new(
                 move_registry_config,
                 metrics.clone(),
-            ));
+            ))
+            .context_data(CostBudgets::new(config.service.cost_budget.clone()))
+            .context_data(ServiceSubscriptions::new(&config.service.subscriptions));
+
+        if config.internal_features.persisted_queries {
+            builder = builder.extension(ApolloPersistedQueries::new(LruCacheStorage::new(
+                config.service.persisted_queries.cache_size,
+            )));
+        }
 
         if config.internal_features.feature_gate {
             builder = builder.extension(FeatureGate);
@@ -494,6 +523,10 @@ impl ServerBuilder {
             builder = builder.extension(Timeout);
         }
 
+        if config.internal_features.response_cache {
+            builder = builder.extension(ResponseCache::new(config.service.response_cache));
+        }
+
         if config.internal_features.tracing {
             builder = builder.extension(Tracing);
         }
@@ -509,8 +542,8 @@ impl ServerBuilder {
     }
 }
 
-fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    async_graphql::Schema::build(Query, Mutation, EmptySubscription)
+fn schema_builder() -> SchemaBuilder<Query, Mutation, Subscription> {
+    async_graphql::Schema::build(Query, Mutation, Subscription)
         .register_output_type::<IMoveObject>()
         .register_output_type::<IObject>()
         .register_output_type::<IOwner>()
@@ -523,7 +556,7 @@ pub fn export_schema() -> String {
 }
 
 /// Entry point for graphql requests. Each request is stamped with a unique ID, a `ShowUsage` flag
-/// if set in the request headers, and the watermark as set by the background task.
+/// and `ApiKey` if set in the request headers, and the watermark as set by the background task.
 async fn graphql_handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     TypedHeader(ContentLength(content_length)): TypedHeader<ContentLength>,
@@ -541,6 +574,10 @@ async fn graphql_handler(
         req.data.insert(ShowUsage)
     }
 
+    if let Some(key) = headers.get(ApiKey::name()).and_then(|v| v.to_str().ok()) {
+        req.data.insert(ApiKey(key.to_owned()))
+    }
+
     // Capture the IP address of the client
     // Note: if a load balancer is used it must be configured to forward the client IP address
     req.data.insert(addr);
@@ -558,6 +595,53 @@ async fn graphql_handler(
     (extensions, result.into())
 }
 
+/// Entry point for graphql subscriptions, served over a WebSocket (using either the `graphql-ws`
+/// or `graphql-transport-ws` protocol). Unlike a request, a connection can outlive many
+/// watermarks, so it is given a receiver for watermark updates, and a limit on the subscriptions
+/// it can have open at once, in addition to the data that `graphql_handler` stamps each request
+/// with.
+async fn subscription_handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(service): State<ServiceConfig>,
+    schema: Extension<SuiGraphQLSchema>,
+    Extension(watermark_lock): Extension<WatermarkLock>,
+    Extension(watermarks): Extension<watch::Receiver<Watermark>>,
+    Extension(chain_identifier_lock): Extension<ChainIdentifierLock>,
+    headers: HeaderMap,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> impl IntoResponse {
+    // Messages on the socket are bounded by the query payload limit, so every subscription is
+    // accounted for as if it were the largest payload allowed.
+    let max_query_payload_size = service.limits.max_query_payload_size;
+
+    let mut data = async_graphql::Data::default();
+    data.insert(PayloadSize(max_query_payload_size as u64));
+    data.insert(Uuid::new_v4());
+    if headers.contains_key(ShowUsage::name()) {
+        data.insert(ShowUsage)
+    }
+
+    if let Some(key) = headers.get(ApiKey::name()).and_then(|v| v.to_str().ok()) {
+        data.insert(ApiKey(key.to_owned()))
+    }
+
+    data.insert(addr);
+    data.insert(Watermark::new(watermark_lock).await);
+    data.insert(watermarks);
+    data.insert(ConnectionSubscriptions::new(&service.subscriptions));
+    data.insert(chain_identifier_lock.read().await);
+
+    upgrade
+        .max_message_size(max_query_payload_size as usize)
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema.0, protocol)
+                .with_data(data)
+                .serve()
+        })
+}
+
 #[derive(Clone)]
 struct MetricsMakeCallbackHandler {
     metrics: Metrics,
@@ -684,7 +768,7 @@ pub mod tests {
     use crate::test_infra::cluster::{prep_executor_cluster, start_cluster};
     use crate::types::chain_identifier::ChainIdentifier;
     use crate::{
-        config::{ConnectionConfig, Limits, ServiceConfig, Version},
+        config::{ConnectionConfig, Limits, ServiceConfig, SubscriptionsConfig, Version},
         context_data::db_data_provider::PgManager,
         extensions::{query_limits_checker::QueryLimitsChecker, timeout::Timeout},
     };
@@ -692,6 +776,7 @@ pub mod tests {
         extensions::{Extension, ExtensionContext, NextExecute},
         Request, Response, Variables,
     };
+    use futures::StreamExt;
     use serde_json::json;
     use std::sync::Arc;
     use std::time::Duration;
@@ -751,11 +836,12 @@ pub mod tests {
             .context_data(db)
             .context_data(loader)
             .context_data(pg_conn_pool)
-            .context_data(service_config)
+            .context_data(service_config.clone())
             .context_data(query_id())
             .context_data(ip_address())
             .context_data(watermark)
             .context_data(ChainIdentifier::from(get_mainnet_chain_identifier()))
+            .context_data(ServiceSubscriptions::new(&service_config.subscriptions))
             .context_data(metrics)
     }
 
@@ -2186,4 +2272,164 @@ pub mod tests {
         assert!(resp.is_ok());
         assert!(resp.errors.is_empty());
     }
+
+    /// A watermark at checkpoint `hi_cp` of the cluster set up by `prep_executor_cluster`.
+    fn watermark(hi_cp: u64) -> Watermark {
+        Watermark {
+            hi_cp,
+            hi_cp_timestamp_ms: 0,
+            epoch: 1,
+            lo_cp: 0,
+            lo_tx: 0,
+        }
+    }
+
+    /// The digests of the transactions in checkpoints after `after_checkpoint`, as of checkpoint
+    /// `hi_cp`.
+    async fn transaction_digests(
+        schema: &SuiGraphQLSchema,
+        after_checkpoint: u64,
+        hi_cp: u64,
+    ) -> Vec<serde_json::Value> {
+        let query = format!(
+            "{{ transactionBlocks(filter: {{ afterCheckpoint: {after_checkpoint} }}) {{ nodes {{ digest }} }} }}"
+        );
+        let resp = schema
+            .execute(Request::new(query).data(watermark(hi_cp)))
+            .await;
+        assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+        let data = resp.data.into_json().unwrap();
+        data["transactionBlocks"]["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| node["digest"].clone())
+            .collect()
+    }
+
+    /// Start a `transactions` subscription on the connection that `subscriptions` counts the
+    /// subscriptions of, and poll it until it is either waiting for the next watermark, or has
+    /// finished with its only response, which is returned.
+    async fn open_subscription(
+        schema: &SuiGraphQLSchema,
+        watermarks: watch::Receiver<Watermark>,
+        subscriptions: &ConnectionSubscriptions,
+    ) -> (
+        impl futures::Stream<Item = Response> + Unpin,
+        Option<Response>,
+    ) {
+        let request = Request::new("subscription { transactions { digest } }")
+            .data(watermarks)
+            .data(subscriptions.clone());
+        let mut stream = schema.execute_stream(request);
+        let resp = tokio::time::timeout(Duration::from_millis(500), stream.next())
+            .await
+            .ok()
+            .flatten();
+        (stream, resp)
+    }
+
+    #[tokio::test]
+    async fn test_subscription_starts_from_first_watermark() {
+        let cluster = prep_executor_cluster().await;
+        let db_url = cluster.graphql_connection_config.db_url.clone();
+        let config = ServiceConfig::default();
+        let schema = prep_schema(db_url, Some(config.clone()))
+            .await
+            .build_schema();
+
+        // The cluster has transactions in checkpoints 4 (the end of the first epoch) and 6 (a
+        // clock update), so a subscription that started from the placeholder watermark would
+        // also see the transaction in checkpoint 4.
+        let expect = transaction_digests(&schema, 5, 6).await;
+        assert_eq!(expect.len(), 1);
+        assert_eq!(transaction_digests(&schema, 0, 6).await.len(), 2);
+
+        // Open the subscription before the first watermark has been published.
+        let (sender, receiver) = watch::channel(Watermark::default());
+        let request = Request::new("subscription { transactions { digest } }")
+            .data(receiver)
+            .data(ConnectionSubscriptions::new(&config.subscriptions));
+        let stream = schema.execute_stream(request);
+        let subscription = tokio::spawn(stream.collect::<Vec<_>>());
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        sender.send(watermark(5)).unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        sender.send(watermark(6)).unwrap();
+
+        // The subscription ends once it has caught up with the last watermark.
+        drop(sender);
+        let resps = tokio::time::timeout(Duration::from_secs(10), subscription)
+            .await
+            .expect("Subscription should end when the watermark task stops")
+            .unwrap();
+
+        let actual: Vec<_> = resps
+            .into_iter()
+            .map(|resp| {
+                assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+                resp.data.into_json().unwrap()["transactions"]["digest"].clone()
+            })
+            .collect();
+        assert_eq!(actual, expect);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_limits() {
+        let cluster = prep_executor_cluster().await;
+        let db_url = cluster.graphql_connection_config.db_url.clone();
+        let config = ServiceConfig {
+            subscriptions: SubscriptionsConfig {
+                max_subscriptions: 2,
+                max_subscriptions_per_connection: 1,
+            },
+            ..Default::default()
+        };
+        let schema = prep_schema(db_url, Some(config.clone()))
+            .await
+            .build_schema();
+
+        let (sender, receiver) = watch::channel(Watermark::default());
+        sender.send(watermark(6)).unwrap();
+
+        let conn1 = ConnectionSubscriptions::new(&config.subscriptions);
+        let conn2 = ConnectionSubscriptions::new(&config.subscriptions);
+        let conn3 = ConnectionSubscriptions::new(&config.subscriptions);
+
+        let (sub1, resp) = open_subscription(&schema, receiver.clone(), &conn1).await;
+        assert!(resp.is_none(), "{resp:?}");
+
+        let (_, resp) = open_subscription(&schema, receiver.clone(), &conn1).await;
+        let errs: Vec<_> = resp
+            .unwrap()
+            .errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            errs,
+            vec!["Too many subscriptions on this connection. Limit: 1".to_string()]
+        );
+
+        let (_sub2, resp) = open_subscription(&schema, receiver.clone(), &conn2).await;
+        assert!(resp.is_none(), "{resp:?}");
+
+        let (_, resp) = open_subscription(&schema, receiver.clone(), &conn3).await;
+        let errs: Vec<_> = resp
+            .unwrap()
+            .errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            errs,
+            vec!["Too many subscriptions on the service. Limit: 2".to_string()]
+        );
+
+        // Closing a subscription frees up its place on its connection and on the service.
+        drop(sub1);
+        let (_sub3, resp) = open_subscription(&schema, receiver.clone(), &conn1).await;
+        assert!(resp.is_none(), "{resp:?}");
+    }
 }
@@ -32,6 +32,8 @@ pub(crate) struct WatermarkTask {
     cancel: CancellationToken,
     sender: watch::Sender<u64>,
     receiver: watch::Receiver<u64>,
+    watermark_sender: watch::Sender<Watermark>,
+    watermark_receiver: watch::Receiver<Watermark>,
 }
 
 #[derive(Clone, Default)]
@@ -63,6 +65,7 @@ impl WatermarkTask {
         cancel: CancellationToken,
     ) -> Self {
         let (sender, receiver) = watch::channel(0);
+        let (watermark_sender, watermark_receiver) = watch::channel(Watermark::default());
 
         Self {
             watermark: Default::default(),
@@ -73,6 +76,8 @@ impl WatermarkTask {
             cancel,
             sender,
             receiver,
+            watermark_sender,
+            watermark_receiver,
         }
     }
 
@@ -82,6 +87,10 @@ impl WatermarkTask {
         // so that it can be used in all requests.
         self.get_and_cache_chain_identifier(&mut interval).await;
 
+        // Subscribers wait for the first watermark to be published, even if its checkpoint
+        // matches the placeholder's.
+        let mut published = false;
+
         loop {
             tokio::select! {
                 _ = self.cancel.cancelled() => {
@@ -113,6 +122,15 @@ impl WatermarkTask {
                     if epoch > prev_epoch {
                         self.sender.send(epoch).unwrap();
                     }
+
+                    // Notify watermark subscribers whenever a new checkpoint has been indexed.
+                    let watermark = Watermark { lo_cp, lo_tx, hi_cp, hi_cp_timestamp_ms, epoch };
+                    self.watermark_sender.send_if_modified(|w| {
+                        let modified = !published || w.hi_cp != hi_cp;
+                        *w = watermark;
+                        modified
+                    });
+                    published = true;
                 }
             }
         }
@@ -131,6 +149,11 @@ impl WatermarkTask {
         self.receiver.clone()
     }
 
+    /// Receiver for subscribing to changes in the checkpoint upper bound of the watermark.
+    pub(crate) fn watermark_receiver(&self) -> watch::Receiver<Watermark> {
+        self.watermark_receiver.clone()
+    }
+
     // Fetch the chain identifier (once) from the database and cache it.
     async fn get_and_cache_chain_identifier(&self, interval: &mut Interval) {
         loop {
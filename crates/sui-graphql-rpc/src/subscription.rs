@@ -0,0 +1,241 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_graphql::{connection::CursorType, *};
+use async_stream::try_stream;
+use futures::Stream;
+use tokio::sync::{watch, OwnedSemaphorePermit, Semaphore};
+
+use crate::{
+    config::{ServiceConfig, SubscriptionsConfig},
+    data::Db,
+    error::Error,
+    server::watermark_task::Watermark,
+    types::{
+        cursor::Page,
+        event::{self, Event, EventFilter, EventKey},
+        transaction_block::{self, TransactionBlock, TransactionBlockFilter},
+        uint53::UInt53,
+    },
+};
+
+pub(crate) struct Subscription;
+
+/// The subscriptions that can still be opened on the service, shared by all its connections.
+#[derive(Clone)]
+pub(crate) struct ServiceSubscriptions(Arc<Semaphore>);
+
+/// The subscriptions that can still be opened on a single connection.
+#[derive(Clone)]
+pub(crate) struct ConnectionSubscriptions(Arc<Semaphore>);
+
+/// Subscriptions stream data from the Sui network to the client as it is indexed.
+///
+/// Subscriptions start from the checkpoint that was the latest indexed checkpoint when the
+/// subscription was created (or from the first checkpoint the service learns about, if it has not
+/// read the latest checkpoint from its database yet), and deliver new results each time the RPC's
+/// checkpoint watermark advances, in the order they occurred on chain.
+#[Subscription]
+impl Subscription {
+    /// Stream the events emitted by transactions in newly indexed checkpoints, optionally
+    /// filtered.
+    ///
+    /// We currently do not support filtering by emitting module and event type at the same time
+    /// so if both are provided in one filter, the subscription will error.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>>> {
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let config: ServiceConfig = ctx.data_unchecked::<ServiceConfig>().clone();
+        let mut watermarks = ctx.data::<watch::Receiver<Watermark>>()?.clone();
+        let filter = filter.unwrap_or_default();
+        let permits = reserve(ctx, &config.subscriptions)?;
+
+        // Pagination only accepts cursors that point at an event that matches the filter, so anchor
+        // the subscription on the latest such event at the current watermark. If there isn't one,
+        // then every matching event is new, and the subscription can start from the beginning.
+        let Watermark { hi_cp, .. } = first_watermark(&mut watermarks).await?;
+        let page = Page::from_params(&config, None, None, Some(1), None)?;
+        let mut after: Option<event::Cursor> = Event::paginate(&db, page, filter.clone(), hi_cp)
+            .await
+            .extend()?
+            .edges
+            .last()
+            .map(|edge| event::Cursor::decode_cursor(&edge.cursor))
+            .transpose()?;
+
+        Ok(try_stream! {
+            let _permits = permits;
+            while watermarks.changed().await.is_ok() {
+                let Watermark { hi_cp, .. } = *watermarks.borrow_and_update();
+
+                loop {
+                    // The cursor from the last page was issued at an earlier watermark, which it
+                    // would otherwise hold the next page to.
+                    let cursor = after.as_ref().map(|cursor| {
+                        event::Cursor::new(EventKey {
+                            checkpoint_viewed_at: hi_cp,
+                            ..(**cursor).clone()
+                        })
+                    });
+
+                    let first = Some(config.limits.max_page_size as u64);
+                    let page = Page::from_params(&config, first, cursor, None, None)?;
+                    let conn = Event::paginate(&db, page, filter.clone(), hi_cp)
+                        .await
+                        .extend()?;
+
+                    for edge in conn.edges {
+                        after = Some(event::Cursor::decode_cursor(&edge.cursor)?);
+                        yield edge.node;
+                    }
+
+                    if !conn.has_next_page {
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Stream the transactions in newly indexed checkpoints, optionally filtered.
+    ///
+    /// Filters that would require a `scanLimit` when querying `Query.transactionBlocks` are
+    /// scanned with the maximum scan limit.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<impl Stream<Item = Result<TransactionBlock>>> {
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let config: ServiceConfig = ctx.data_unchecked::<ServiceConfig>().clone();
+        let mut watermarks = ctx.data::<watch::Receiver<Watermark>>()?.clone();
+        let filter = filter.unwrap_or_default();
+
+        let scan_limit = filter
+            .requires_scan_limit()
+            .then_some(config.limits.max_scan_limit as u64);
+
+        let permits = reserve(ctx, &config.subscriptions)?;
+
+        let Watermark { hi_cp, .. } = first_watermark(&mut watermarks).await?;
+        let mut prev_cp = hi_cp;
+
+        Ok(try_stream! {
+            let _permits = permits;
+            while watermarks.changed().await.is_ok() {
+                let Watermark { lo_cp, hi_cp, .. } = *watermarks.borrow_and_update();
+
+                // Only visit the checkpoints that have been indexed since the last watermark.
+                let Some(filter) = filter.clone().intersect(TransactionBlockFilter {
+                    after_checkpoint: Some(UInt53::from(prev_cp)),
+                    ..Default::default()
+                }) else {
+                    break;
+                };
+
+                prev_cp = hi_cp;
+
+                let mut after: Option<transaction_block::Cursor> = None;
+                loop {
+                    let first = Some(config.limits.max_page_size as u64);
+                    let page = Page::from_params(&config, first, after.take(), None, None)?;
+                    let conn = TransactionBlock::paginate_with_bounds(
+                        &db,
+                        &config.limits,
+                        page,
+                        filter.clone(),
+                        lo_cp,
+                        hi_cp,
+                        scan_limit,
+                    )
+                    .await
+                    .extend()?;
+
+                    // A scan-limited page may be empty but still have an end cursor to continue
+                    // scanning from.
+                    let end_cursor = conn
+                        .end_cursor
+                        .clone()
+                        .or_else(|| conn.edges.last().map(|edge| edge.cursor.clone()));
+
+                    for edge in conn.edges {
+                        yield edge.node;
+                    }
+
+                    if !conn.has_next_page {
+                        break;
+                    }
+
+                    let Some(end_cursor) = end_cursor else {
+                        break;
+                    };
+
+                    after = Some(transaction_block::Cursor::decode_cursor(&end_cursor)?);
+                }
+            }
+        })
+    }
+}
+
+impl ServiceSubscriptions {
+    pub(crate) fn new(config: &SubscriptionsConfig) -> Self {
+        Self(Arc::new(Semaphore::new(config.max_subscriptions as usize)))
+    }
+}
+
+impl ConnectionSubscriptions {
+    pub(crate) fn new(config: &SubscriptionsConfig) -> Self {
+        Self(Arc::new(Semaphore::new(
+            config.max_subscriptions_per_connection as usize,
+        )))
+    }
+}
+
+/// Reserve a place for a new subscription among the subscriptions of its connection and of the
+/// service. The subscription counts towards both limits until the returned permits are dropped.
+fn reserve(ctx: &Context<'_>, config: &SubscriptionsConfig) -> Result<[OwnedSemaphorePermit; 2]> {
+    let connection = ctx
+        .data::<ConnectionSubscriptions>()?
+        .0
+        .clone()
+        .try_acquire_owned()
+        .map_err(|_| {
+            Error::Client(format!(
+                "Too many subscriptions on this connection. Limit: {}",
+                config.max_subscriptions_per_connection,
+            ))
+            .extend()
+        })?;
+
+    let service = ctx
+        .data::<ServiceSubscriptions>()?
+        .0
+        .clone()
+        .try_acquire_owned()
+        .map_err(|_| {
+            Error::Client(format!(
+                "Too many subscriptions on the service. Limit: {}",
+                config.max_subscriptions,
+            ))
+            .extend()
+        })?;
+
+    Ok([connection, service])
+}
+
+/// The latest watermark. The watermark channel starts out holding a placeholder until the
+/// watermark task has read the first watermark from the database, so if it has not done that yet,
+/// wait for it.
+async fn first_watermark(watermarks: &mut watch::Receiver<Watermark>) -> Result<Watermark> {
+    let stopped = |_| Error::Internal("Watermark task has stopped".to_string()).extend();
+    if !watermarks.has_changed().map_err(stopped)? {
+        watermarks.changed().await.map_err(stopped)?;
+    }
+
+    Ok(*watermarks.borrow_and_update())
+}
@@ -28,7 +28,7 @@ use sui_types::{
 mod cursor;
 mod filter;
 mod lookups;
-pub(crate) use cursor::Cursor;
+pub(crate) use cursor::{Cursor, EventKey};
 pub(crate) use filter::EventFilter;
 
 /// A Sui node emits one of the following events:
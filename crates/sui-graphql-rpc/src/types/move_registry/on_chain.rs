@@ -60,38 +60,38 @@ pub(crate) static VERSIONED_NAME_REG: Lazy<Regex> =
 /// Attention: The format of this struct should not change unless the on-chain format changes,
 /// as we define it to deserialize on-chain data.
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
-pub(crate) struct AppRecord {
-    pub(crate) app_cap_id: ID,
-    pub(crate) ns_nft_id: ID,
-    pub(crate) app_info: Option<AppInfo>,
-    pub(crate) networks: VecMap<String, AppInfo>,
-    pub(crate) metadata: VecMap<String, String>,
-    pub(crate) storage: ObjectID,
+pub struct AppRecord {
+    pub app_cap_id: ID,
+    pub ns_nft_id: ID,
+    pub app_info: Option<AppInfo>,
+    pub networks: VecMap<String, AppInfo>,
+    pub metadata: VecMap<String, String>,
+    pub storage: ObjectID,
 }
 
 /// Attention: The format of this struct should not change unless the on-chain format changes,
 /// as we define it to deserialize on-chain data.
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
-pub(crate) struct AppInfo {
-    pub(crate) package_info_id: Option<ID>,
-    pub(crate) package_address: Option<SuiAddress>,
-    pub(crate) upgrade_cap_id: Option<ID>,
+pub struct AppInfo {
+    pub package_info_id: Option<ID>,
+    pub package_address: Option<SuiAddress>,
+    pub upgrade_cap_id: Option<ID>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
-pub(crate) struct VersionedName {
+pub struct VersionedName {
     /// A version name defaults at None, which means we need the latest version.
-    pub(crate) version: Option<u64>,
+    pub version: Option<u64>,
     /// The on-chain `Name` object that represents the move registry name.
-    pub(crate) name: Name,
+    pub name: Name,
 }
 
 /// Attention: The format of this struct should not change unless the on-chain format changes,
 /// as we define it to deserialize on-chain data.
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq)]
-pub(crate) struct Name {
-    pub(crate) org: Domain,
-    pub(crate) app: Vec<String>,
+pub struct Name {
+    pub org: Domain,
+    pub app: Vec<String>,
 }
 
 impl Name {
@@ -120,10 +120,7 @@ impl Name {
     }
 
     /// Generate the ObjectID for a given `Name`
-    pub(crate) fn to_dynamic_field_id(
-        &self,
-        config: &MoveRegistryConfig,
-    ) -> Result<ObjectID, bcs::Error> {
+    pub fn to_dynamic_field_id(&self, config: &MoveRegistryConfig) -> Result<ObjectID, bcs::Error> {
         let domain_type_tag = Self::type_(config.package_address);
 
         sui_types::dynamic_field::derive_dynamic_field_id(
@@ -13,7 +13,7 @@ use super::{
     transaction_block_kind::TransactionBlockKind,
 };
 use crate::{
-    config::ServiceConfig,
+    config::{Limits, ServiceConfig},
     connection::ScanConnection,
     data::{self, DataLoader, Db, DbConnection, QueryExecutor},
     error::Error,
@@ -352,12 +352,34 @@ impl TransactionBlock {
         checkpoint_viewed_at: u64,
         scan_limit: Option<u64>,
     ) -> Result<ScanConnection<String, TransactionBlock>, Error> {
-        let limits = &ctx.data_unchecked::<ServiceConfig>().limits;
-        let db: &Db = ctx.data_unchecked();
         // If we've entered this function, we already fetched `checkpoint_viewed_at` from the
         // `Watermark`, and so we must be able to retrieve `lo_cp` as well.
         let Watermark { lo_cp, .. } = *ctx.data_unchecked();
 
+        Self::paginate_with_bounds(
+            ctx.data_unchecked(),
+            &ctx.data_unchecked::<ServiceConfig>().limits,
+            page,
+            filter,
+            lo_cp,
+            checkpoint_viewed_at,
+            scan_limit,
+        )
+        .await
+    }
+
+    /// Like `paginate`, but the database, limits and the lowest unpruned checkpoint (`lo_cp`) are
+    /// passed in explicitly rather than read from the request's context, so that it can be used
+    /// by long-lived subscriptions that observe more than one watermark.
+    pub(crate) async fn paginate_with_bounds(
+        db: &Db,
+        limits: &Limits,
+        page: Page<Cursor>,
+        filter: TransactionBlockFilter,
+        lo_cp: u64,
+        checkpoint_viewed_at: u64,
+        scan_limit: Option<u64>,
+    ) -> Result<ScanConnection<String, TransactionBlock>, Error> {
         // If the caller has provided some arbitrary combination of `function`, `kind`,
         // `recvAddress`, `inputObject`, or `changedObject`, we require setting a `scanLimit`.
         if let Some(scan_limit) = scan_limit {
@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. SUI balance of an address, by default and when asked for explicitly
+// 2. Balance of another coin type, and of a coin type the address has no coins of
+// 3. All balances of an address, grouped by coin type
+// 4. Balances reflect coins being spent and received
+// 5. Coin metadata, for a coin type with and without metadata
+// 6. Bad coin types
+
+//# publish --sender A
+module test::fake {
+  use sui::coin;
+
+  public struct FAKE has drop {}
+
+  fun init(witness: FAKE, ctx: &mut TxContext){
+    let (mut treasury_cap, metadata) = coin::create_currency(
+      witness,
+      2,
+      b"FAKE",
+      b"Fake",
+      b"A fake coin",
+      option::none(),
+      ctx,
+    );
+
+    let c1 = coin::mint(&mut treasury_cap, 1, ctx);
+    let c2 = coin::mint(&mut treasury_cap, 2, ctx);
+
+    transfer::public_freeze_object(metadata);
+    transfer::public_transfer(treasury_cap, ctx.sender());
+    transfer::public_transfer(c1, ctx.sender());
+    transfer::public_transfer(c2, ctx.sender());
+  }
+}
+
+//# programmable --sender A --inputs 100 200 @B
+//> 0: SplitCoins(Gas, [Input(0), Input(1)]);
+//> 1: TransferObjects([NestedResult(0,0), NestedResult(0,1)], Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{B}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}", "@{test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{B}", "@{test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllBalances",
+  "params": ["@{A}"]
+}
+
+//# programmable --sender B --inputs object(2,0) @A
+//> 0: TransferObjects([Input(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllBalances",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllBalances",
+  "params": ["@{B}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoinMetadata",
+  "params": ["@{test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoinMetadata",
+  "params": ["@{test}::fake::NOT_A_COIN"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{B}", "not a type"]
+}
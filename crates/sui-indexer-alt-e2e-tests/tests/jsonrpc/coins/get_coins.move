@@ -0,0 +1,96 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. SUI coins owned by an address, by default and when asked for explicitly
+// 2. Paginating through coins with a cursor
+// 3. Coins of another type, and all coins regardless of type
+// 4. Coins that have been spent are no longer returned
+// 5. Unknown cursors and bad coin types
+
+//# publish --sender A
+module test::fake {
+  use sui::coin;
+
+  public struct FAKE has drop {}
+
+  fun init(witness: FAKE, ctx: &mut TxContext){
+    let (mut treasury_cap, metadata) = coin::create_currency(
+      witness,
+      2,
+      b"FAKE",
+      b"Fake",
+      b"A fake coin",
+      option::none(),
+      ctx,
+    );
+
+    let c1 = coin::mint(&mut treasury_cap, 1, ctx);
+    let c2 = coin::mint(&mut treasury_cap, 2, ctx);
+
+    transfer::public_freeze_object(metadata);
+    transfer::public_transfer(treasury_cap, ctx.sender());
+    transfer::public_transfer(c1, ctx.sender());
+    transfer::public_transfer(c2, ctx.sender());
+  }
+}
+
+//# programmable --sender A --inputs 100 200 300 @B
+//> 0: SplitCoins(Gas, [Input(0), Input(1), Input(2)]);
+//> 1: TransferObjects([NestedResult(0,0), NestedResult(0,1), NestedResult(0,2)], Input(3))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{B}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{B}", "0x2::sui::SUI", null, 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{B}", "0x2::sui::SUI", "@{obj_2_1}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}", "@{test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}"]
+}
+
+//# programmable --sender B --inputs @A
+//> 0: TransferObjects([Gas], Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{B}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{B}", null, "0x0000000000000000000000000000000000000000000000000000000000000042"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{B}", "not a type"]
+}
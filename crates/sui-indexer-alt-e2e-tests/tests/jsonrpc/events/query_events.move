@@ -0,0 +1,117 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. All events, in ascending and descending order
+// 2. Paginating through the events of a single transaction with a cursor
+// 3. Filtering by sender
+// 4. Filtering by the module that emitted the event
+// 5. Filtering by the module that defines the event's type, and by its type
+// 6. Filtering by transaction
+// 7. Unsupported filters
+// 8. Events from transactions that have not been included in a checkpoint yet are not returned
+
+//# publish
+module test::emitter {
+  public struct Ping has copy, drop, store {
+    x: u64,
+  }
+
+  public struct Pong has copy, drop, store {
+    x: u64,
+  }
+
+  public fun ping(x: u64) {
+    sui::event::emit(Ping { x })
+  }
+
+  public fun ping_pong(x: u64) {
+    sui::event::emit(Ping { x });
+    sui::event::emit(Pong { x });
+    sui::event::emit(Ping { x: x + 1 });
+  }
+}
+
+//# programmable --sender A --inputs 1
+//> test::emitter::ping(Input(0))
+
+//# programmable --sender B --inputs 10
+//> test::emitter::ping_pong(Input(0))
+
+//# create-checkpoint
+
+//# programmable --sender A --inputs 100
+//> test::emitter::ping(Input(0))
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, null, null, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, { "txDigest": "@{digest_3}", "eventSeq": "0" }, 1]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, { "txDigest": "@{digest_3}", "eventSeq": "2" }, 2, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{B}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveModule": { "package": "@{test}", "module": "emitter" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventModule": { "package": "@{test}", "module": "emitter" } }, null, 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{test}::emitter::Pong" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Transaction": "@{digest_2}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Transaction": "@{digest_5}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Any": [{ "Sender": "@{A}" }, { "Sender": "@{B}" }] }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "TimeRange": { "startTime": "0", "endTime": "1000" } }]
+}
@@ -0,0 +1,97 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --addresses test=0x0 --simulator
+
+// 1. Dynamic fields and dynamic object fields of an object
+// 2. Paginating through dynamic fields with a cursor
+// 3. Fields that have been removed are no longer returned
+// 4. An object without dynamic fields, and one that does not exist
+
+//# publish
+module test::fields {
+  use sui::dynamic_field as df;
+  use sui::dynamic_object_field as dof;
+
+  public struct Parent has key, store {
+    id: UID,
+  }
+
+  public struct Child has key, store {
+    id: UID,
+    x: u64,
+  }
+
+  public fun new(ctx: &mut TxContext): Parent {
+    Parent { id: object::new(ctx) }
+  }
+
+  public fun add_field(p: &mut Parent, name: u64, value: u64) {
+    df::add(&mut p.id, name, value);
+  }
+
+  public fun add_object_field(p: &mut Parent, name: u64, x: u64, ctx: &mut TxContext) {
+    dof::add(&mut p.id, name, Child { id: object::new(ctx), x });
+  }
+
+  public fun remove_field(p: &mut Parent, name: u64) {
+    let _: u64 = df::remove(&mut p.id, name);
+  }
+}
+
+//# programmable --sender A --inputs @A
+//> 0: test::fields::new();
+//> 1: test::fields::new();
+//> 2: TransferObjects([Result(0), Result(1)], Input(0))
+
+//# programmable --sender A --inputs object(2,0) 1 2 3 4 5 6
+//> 0: test::fields::add_field(Input(0), Input(1), Input(4));
+//> 1: test::fields::add_field(Input(0), Input(2), Input(5));
+//> 2: test::fields::add_field(Input(0), Input(3), Input(6))
+
+//# programmable --sender A --inputs object(2,0) 4 5 7 8
+//> 0: test::fields::add_object_field(Input(0), Input(1), Input(3));
+//> 1: test::fields::add_object_field(Input(0), Input(2), Input(4))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_2_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_2_0}", null, 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_2_0}", "@{obj_3_0}", 2]
+}
+
+//# programmable --sender A --inputs object(2,0) 2
+//> test::fields::remove_field(Input(0), Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_2_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_2_1}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["0x0000000000000000000000000000000000000000000000000000000000001234"]
+}
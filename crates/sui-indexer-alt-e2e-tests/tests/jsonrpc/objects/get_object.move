@@ -0,0 +1,132 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. Fetching an object, by default and with all of its data
+// 2. Fetching an object that does not exist, and one that has been deleted
+// 3. Fetching multiple objects at once, including missing and deleted ones
+// 4. Fetching past versions of an object: a past version, the latest version, a version that
+//    never existed, and a version that is too high
+// 5. Changes that have not been included in a checkpoint yet are not visible
+
+//# publish
+module test::objects {
+  public struct O has key, store {
+    id: UID,
+    x: u64,
+  }
+
+  public fun new(x: u64, ctx: &mut TxContext): O {
+    O { id: object::new(ctx), x }
+  }
+
+  public fun inc(o: &mut O) { o.x = o.x + 1 }
+
+  public fun destroy(o: O) {
+    let O { id, x: _ } = o;
+    id.delete();
+  }
+}
+
+//# programmable --sender A --inputs 42 @A
+//> 0: test::objects::new(Input(0));
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 43 @A
+//> 0: test::objects::new(Input(0));
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs object(2,0)
+//> test::objects::inc(Input(0))
+
+//# programmable --sender A --inputs object(3,0)
+//> test::objects::destroy(Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getObject",
+  "params": ["@{obj_2_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getObject",
+  "params": [
+    "@{obj_2_0}",
+    {
+      "showType": true,
+      "showOwner": true,
+      "showPreviousTransaction": true,
+      "showContent": true,
+      "showBcs": true,
+      "showStorageRebate": true
+    }
+  ]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getObject",
+  "params": ["0x0000000000000000000000000000000000000000000000000000000000001234"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getObject",
+  "params": ["@{obj_3_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetObjects",
+  "params": [
+    [
+      "@{obj_2_0}",
+      "@{obj_3_0}",
+      "0x0000000000000000000000000000000000000000000000000000000000001234"
+    ],
+    { "showType": true, "showOwner": true }
+  ]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_tryGetPastObject",
+  "params": ["@{obj_2_0}", 2, { "showContent": true }]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_tryGetPastObject",
+  "params": ["@{obj_2_0}", 4, { "showContent": true }]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_tryGetPastObject",
+  "params": ["@{obj_2_0}", 3]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_tryGetPastObject",
+  "params": ["@{obj_2_0}", 100]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_tryGetPastObject",
+  "params": ["0x0000000000000000000000000000000000000000000000000000000000001234", 1]
+}
+
+//# programmable --sender A --inputs object(2,0)
+//> test::objects::inc(Input(0))
+
+//# run-jsonrpc
+{
+  "method": "sui_getObject",
+  "params": ["@{obj_2_0}", { "showContent": true }]
+}
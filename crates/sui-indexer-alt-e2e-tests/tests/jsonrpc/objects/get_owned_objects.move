@@ -0,0 +1,129 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. All objects owned by an address
+// 2. Paginating through owned objects with a cursor
+// 3. Filtering by package, module and type, with and without type parameters
+// 4. Objects that have been transferred away or deleted are no longer returned
+// 5. Unsupported filters
+
+//# publish
+module test::objects {
+  public struct O has key, store {
+    id: UID,
+    x: u64,
+  }
+
+  public struct P<phantom T> has key, store {
+    id: UID,
+  }
+
+  public fun new(x: u64, ctx: &mut TxContext): O {
+    O { id: object::new(ctx), x }
+  }
+
+  public fun new_p<T>(ctx: &mut TxContext): P<T> {
+    P { id: object::new(ctx) }
+  }
+
+  public fun destroy(o: O) {
+    let O { id, x: _ } = o;
+    id.delete();
+  }
+}
+
+//# programmable --sender A --inputs 1 2 3 @B
+//> 0: test::objects::new(Input(0));
+//> 1: test::objects::new(Input(1));
+//> 2: test::objects::new(Input(2));
+//> 3: test::objects::new_p<u64>();
+//> 4: test::objects::new_p<bool>();
+//> 5: TransferObjects([Result(0), Result(1), Result(2), Result(3), Result(4)], Input(3))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "options": { "showType": true } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "options": { "showType": true } }, null, 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "options": { "showType": true } }, "@{obj_2_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "Package": "@{test}" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "MoveModule": { "package": "@{test}", "module": "objects" } } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "StructType": "@{test}::objects::O" }, "options": { "showContent": true } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "StructType": "@{test}::objects::P" }, "options": { "showType": true } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "StructType": "@{test}::objects::P<u64>" }, "options": { "showType": true } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "MatchAny": [{ "StructType": "@{test}::objects::O" }, { "StructType": "@{test}::objects::P<bool>" }] } }]
+}
+
+//# programmable --sender B --inputs object(2,0) object(2,1) @A
+//> 0: test::objects::destroy(Input(0));
+//> 1: TransferObjects([Input(1)], Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "StructType": "@{test}::objects::O" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{A}", { "filter": { "StructType": "@{test}::objects::O" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "AddressOwner": "@{A}" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{B}", { "filter": { "Version": "1" } }]
+}
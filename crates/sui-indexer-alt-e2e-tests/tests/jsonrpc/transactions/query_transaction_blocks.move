@@ -0,0 +1,175 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. All transactions, in ascending and descending order
+// 2. Paginating forwards and backwards with a cursor
+// 3. Filtering by sender, and by sender and recipient
+// 4. Filtering by the function, module or package called
+// 5. Filtering by affected object
+// 6. Filtering by transaction kind
+// 7. Filtering by checkpoint
+// 8. Unsupported and invalid filters
+// 9. Transactions that have not been included in a checkpoint yet are not returned
+
+//# publish
+module test::counter {
+  public struct Counter has key {
+    id: UID,
+    x: u64,
+  }
+
+  fun init(ctx: &mut TxContext) {
+    transfer::share_object(Counter {
+        id: object::new(ctx),
+        x: 0,
+    })
+  }
+
+  public fun inc(c: &mut Counter) { c.x = c.x + 1 }
+  public fun inc_by(c: &mut Counter, x: u64) { c.x = c.x + x }
+}
+
+//# programmable --sender A --inputs object(1,0)
+//> test::counter::inc(Input(0))
+
+//# create-checkpoint
+
+//# programmable --sender B --inputs object(1,0) 42
+//> test::counter::inc_by(Input(0), Input(1))
+
+//# programmable --sender A --inputs 100 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# programmable --sender A --inputs object(1,0)
+//> test::counter::inc(Input(0))
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{}]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{}, null, null, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{}, null, 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{}, "@{digest_2}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{}, "@{digest_5}", 2, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{}, "11111111111111111111111111111111"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "FromAddress": "@{A}" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "FromAndToAddress": { "from": "@{A}", "to": "@{B}" } } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "FromOrToAddress": { "addr": "@{B}" } } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "MoveFunction": { "package": "@{test}", "module": "counter", "function": "inc" } } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "MoveFunction": { "package": "@{test}", "module": "counter" } } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "MoveFunction": { "package": "@{test}" } } }, null, null, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "AffectedObject": "@{obj_1_0}" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "TransactionKind": "SystemTransaction" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "TransactionKindIn": ["ProgrammableTransaction"] } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "Checkpoint": "2" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "Checkpoint": "42" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "ToAddress": "@{B}" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "MoveFunction": { "package": "@{test}", "function": "inc" } } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "MoveFunction": { "package": "@{test}", "module": "not an identifier" } } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryTransactionBlocks",
+  "params": [{ "filter": { "TransactionKind": "ConsensusCommitPrologue" } }]
+}
@@ -83,6 +83,7 @@ impl OffchainCluster {
 
         let client_args = ClientArgs {
             local_ingestion_path: Some(config.data_ingestion_path.clone()),
+            rpc_api_url: None,
             remote_store_url: None,
         };
 
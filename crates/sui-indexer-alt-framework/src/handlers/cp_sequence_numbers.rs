@@ -34,6 +34,8 @@ impl Processor for CpSequenceNumbers {
 
 #[async_trait::async_trait]
 impl Handler for CpSequenceNumbers {
+    type Store = db::Db;
+
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
         Ok(diesel::insert_into(cp_sequence_numbers::table)
             .values(values)
@@ -3,6 +3,7 @@
 
 use crate::ingestion::local_client::LocalIngestionClient;
 use crate::ingestion::remote_client::RemoteIngestionClient;
+use crate::ingestion::rpc_client::RpcIngestionClient;
 use crate::ingestion::Error as IngestionError;
 use crate::ingestion::Result as IngestionResult;
 use crate::metrics::CheckpointLagMetricReporter;
@@ -23,8 +24,14 @@ use url::Url;
 /// Wait at most this long between retries for transient errors.
 const MAX_TRANSIENT_RETRY_INTERVAL: Duration = Duration::from_secs(60);
 
+/// A source of checkpoints for the ingestion service. Implementations are only responsible for
+/// fetching a single checkpoint and classifying failures (see [FetchError]) -- retries, metrics
+/// and deserialization are handled by [IngestionClient], which wraps the implementation.
+///
+/// Besides the built-in sources configured through [crate::ingestion::ClientArgs], custom sources
+/// can be plugged into the indexer using [crate::Indexer::new_with_client].
 #[async_trait::async_trait]
-pub(crate) trait IngestionClientTrait: Send + Sync {
+pub trait IngestionClientTrait: Send + Sync {
     async fn fetch(&self, checkpoint: u64) -> FetchResult;
 }
 
@@ -42,7 +49,16 @@ pub enum FetchError {
     },
 }
 
-pub type FetchResult = Result<Bytes, FetchError>;
+/// Checkpoint data returned by an [IngestionClientTrait] implementation.
+pub enum FetchData {
+    /// A checkpoint serialized as a [Blob] (the format used by checkpoint stores), to be
+    /// deserialized by the ingestion client.
+    Raw(Bytes),
+    /// An already deserialized checkpoint.
+    CheckpointData(CheckpointData),
+}
+
+pub type FetchResult = Result<FetchData, FetchError>;
 
 #[derive(Clone)]
 pub struct IngestionClient {
@@ -55,15 +71,20 @@ pub struct IngestionClient {
 impl IngestionClient {
     pub(crate) fn new_remote(url: Url, metrics: Arc<IndexerMetrics>) -> IngestionResult<Self> {
         let client = Arc::new(RemoteIngestionClient::new(url)?);
-        Ok(Self::new_impl(client, metrics))
+        Ok(Self::new(client, metrics))
     }
 
     pub(crate) fn new_local(path: PathBuf, metrics: Arc<IndexerMetrics>) -> Self {
         let client = Arc::new(LocalIngestionClient::new(path));
-        Self::new_impl(client, metrics)
+        Self::new(client, metrics)
     }
 
-    fn new_impl(client: Arc<dyn IngestionClientTrait>, metrics: Arc<IndexerMetrics>) -> Self {
+    pub(crate) fn new_rpc(url: Url, metrics: Arc<IndexerMetrics>) -> IngestionResult<Self> {
+        let client = Arc::new(RpcIngestionClient::new(url)?);
+        Ok(Self::new(client, metrics))
+    }
+
+    pub(crate) fn new(client: Arc<dyn IngestionClientTrait>, metrics: Arc<IndexerMetrics>) -> Self {
         let checkpoint_lag_reporter = CheckpointLagMetricReporter::new(
             metrics.ingested_checkpoint_timestamp_lag.clone(),
             metrics.latest_ingested_checkpoint_timestamp_lag_ms.clone(),
@@ -133,7 +154,7 @@ impl IngestionClient {
                     return Err(BE::permanent(IngestionError::Cancelled));
                 }
 
-                let bytes = client.fetch(checkpoint).await.map_err(|err| match err {
+                let fetched = client.fetch(checkpoint).await.map_err(|err| match err {
                     FetchError::NotFound => BE::permanent(IngestionError::NotFound(checkpoint)),
                     FetchError::Permanent(error) => {
                         BE::permanent(IngestionError::FetchError(checkpoint, error))
@@ -145,14 +166,19 @@ impl IngestionClient {
                     ),
                 })?;
 
-                self.metrics.total_ingested_bytes.inc_by(bytes.len() as u64);
-                let data: CheckpointData = Blob::from_bytes(&bytes).map_err(|e| {
-                    self.metrics.inc_retry(
-                        checkpoint,
-                        "deserialization",
-                        IngestionError::DeserializationError(checkpoint, e),
-                    )
-                })?;
+                let data: CheckpointData = match fetched {
+                    FetchData::Raw(bytes) => {
+                        self.metrics.total_ingested_bytes.inc_by(bytes.len() as u64);
+                        Blob::from_bytes(&bytes).map_err(|e| {
+                            self.metrics.inc_retry(
+                                checkpoint,
+                                "deserialization",
+                                IngestionError::DeserializationError(checkpoint, e),
+                            )
+                        })?
+                    }
+                    FetchData::CheckpointData(data) => data,
+                };
 
                 Ok(data)
             }
@@ -208,3 +234,47 @@ impl IngestionClient {
         Ok(Arc::new(data))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::test_utils::test_checkpoint_data;
+    use crate::metrics::tests::test_metrics;
+    use std::sync::Mutex;
+
+    /// A custom ingestion source that serves already deserialized checkpoints, failing with a
+    /// transient error the first few times it is asked for a checkpoint.
+    struct FlakyClient {
+        failures: Mutex<u64>,
+    }
+
+    #[async_trait::async_trait]
+    impl IngestionClientTrait for FlakyClient {
+        async fn fetch(&self, checkpoint: u64) -> FetchResult {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(FetchError::Transient {
+                    reason: "flaky",
+                    error: anyhow::anyhow!("Try again"),
+                });
+            }
+
+            let bytes = test_checkpoint_data(checkpoint);
+            Ok(FetchData::CheckpointData(Blob::from_bytes(&bytes).unwrap()))
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_from_custom_client() {
+        let client = IngestionClient::new(
+            Arc::new(FlakyClient {
+                failures: Mutex::new(2),
+            }),
+            test_metrics(),
+        );
+
+        let checkpoint = client.fetch(42, &CancellationToken::new()).await.unwrap();
+        assert_eq!(42, checkpoint.checkpoint_summary.sequence_number);
+    }
+}
@@ -17,6 +17,9 @@ pub enum Error {
     #[error(transparent)]
     ReqwestError(#[from] reqwest::Error),
 
+    #[error(transparent)]
+    RpcClientError(#[from] tonic::Status),
+
     #[error("No subscribers for ingestion service")]
     NoSubscribers,
 
@@ -15,7 +15,7 @@ use tokio_util::sync::CancellationToken;
 use url::Url;
 
 use crate::ingestion::broadcaster::broadcaster;
-use crate::ingestion::client::IngestionClient;
+use crate::ingestion::client::{IngestionClient, IngestionClientTrait};
 use crate::ingestion::error::{Error, Result};
 use crate::ingestion::regulator::regulator;
 use crate::metrics::IndexerMetrics;
@@ -26,6 +26,7 @@ pub mod error;
 mod local_client;
 mod regulator;
 mod remote_client;
+mod rpc_client;
 #[cfg(test)]
 mod test_utils;
 
@@ -39,6 +40,10 @@ pub struct ClientArgs {
     /// If both remote_store_url and local_ingestion_path are provided, remote_store_url will be used.
     #[clap(long, required = true, group = "source")]
     pub local_ingestion_path: Option<PathBuf>,
+
+    /// gRPC API URL of a full node to fetch checkpoints from directly.
+    #[clap(long, required = true, group = "source")]
+    pub rpc_api_url: Option<Url>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -82,20 +87,41 @@ impl IngestionService {
             IngestionClient::new_remote(url.clone(), metrics.clone())?
         } else if let Some(path) = args.local_ingestion_path.as_ref() {
             IngestionClient::new_local(path.clone(), metrics.clone())
+        } else if let Some(url) = args.rpc_api_url.as_ref() {
+            IngestionClient::new_rpc(url.clone(), metrics.clone())?
         } else {
-            panic!("Either remote_store_url or local_ingestion_path must be provided");
+            panic!("One of remote_store_url, local_ingestion_path or rpc_api_url must be provided");
         };
 
+        Ok(Self::new_impl(client, config, cancel))
+    }
+
+    /// Like [Self::new], but fetching checkpoints from a custom source, rather than one of the
+    /// sources described by [ClientArgs].
+    pub(crate) fn with_client(
+        client: Arc<dyn IngestionClientTrait>,
+        config: IngestionConfig,
+        metrics: Arc<IndexerMetrics>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self::new_impl(IngestionClient::new(client, metrics), config, cancel)
+    }
+
+    fn new_impl(
+        client: IngestionClient,
+        config: IngestionConfig,
+        cancel: CancellationToken,
+    ) -> Self {
         let subscribers = Vec::new();
         let (ingest_hi_tx, ingest_hi_rx) = mpsc::unbounded_channel();
-        Ok(Self {
+        Self {
             config,
             client,
             ingest_hi_tx,
             ingest_hi_rx,
             subscribers,
             cancel,
-        })
+        }
     }
 
     /// The client this service uses to fetch checkpoints.
@@ -204,6 +230,7 @@ mod tests {
             ClientArgs {
                 remote_store_url: Some(Url::parse(&uri).unwrap()),
                 local_ingestion_path: None,
+                rpc_api_url: None,
             },
             IngestionConfig {
                 checkpoint_buffer_size,
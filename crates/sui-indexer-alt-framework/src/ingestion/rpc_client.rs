@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ingestion::client::{FetchData, FetchError, FetchResult, IngestionClientTrait};
+use crate::ingestion::Result as IngestionResult;
+use sui_rpc_api::Client;
+use tonic::Code;
+use tracing::{debug, error};
+use url::Url;
+
+/// Fetches checkpoints directly from a full node, using its gRPC API.
+pub(crate) struct RpcIngestionClient {
+    client: Client,
+}
+
+impl RpcIngestionClient {
+    pub(crate) fn new(url: Url) -> IngestionResult<Self> {
+        Ok(Self {
+            client: Client::new(url.as_str())?,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl IngestionClientTrait for RpcIngestionClient {
+    /// Fetch a checkpoint from the full node.
+    ///
+    /// Transient errors include:
+    ///
+    /// - failures to connect to the full node,
+    /// - request timeouts,
+    /// - rate limiting,
+    /// - internal errors on the full node.
+    ///
+    /// Checkpoints that the full node has pruned are treated as permanent errors, because the full
+    /// node will never serve them again.
+    async fn fetch(&self, checkpoint: u64) -> FetchResult {
+        let status = match self.client.get_full_checkpoint(checkpoint).await {
+            Ok(data) => return Ok(FetchData::CheckpointData(data)),
+            Err(status) => status,
+        };
+
+        match status.code() {
+            // Treat missing checkpoints as a special case so we can match on this error type.
+            Code::NotFound => {
+                debug!(checkpoint, "Checkpoint not found");
+                Err(FetchError::NotFound)
+            }
+
+            Code::Unavailable | Code::DeadlineExceeded | Code::Cancelled => {
+                Err(FetchError::Transient {
+                    reason: "unavailable",
+                    error: status.into(),
+                })
+            }
+
+            Code::ResourceExhausted => Err(FetchError::Transient {
+                reason: "too_many_requests",
+                error: status.into(),
+            }),
+
+            // Assume that if the full node is facing difficulties, it will recover eventually.
+            Code::Internal | Code::Unknown | Code::Aborted => Err(FetchError::Transient {
+                reason: "server_error",
+                error: status.into(),
+            }),
+
+            // For everything else, assume it's a permanent error and don't retry.
+            code => {
+                error!(checkpoint, %code, "Permanent error, giving up!");
+                Err(FetchError::Permanent(status.into()))
+            }
+        }
+    }
+}
@@ -9,15 +9,19 @@ use diesel::{
     pg::Pg,
 };
 use diesel_migrations::{embed_migrations, EmbeddedMigrations};
-use ingestion::{client::IngestionClient, ClientArgs, IngestionConfig, IngestionService};
+use ingestion::{
+    client::{IngestionClient, IngestionClientTrait},
+    ClientArgs, IngestionConfig, IngestionService,
+};
 use metrics::IndexerMetrics;
-use models::watermarks::{CommitterWatermark, PrunerWatermark};
+use models::watermarks::CommitterWatermark;
 use pipeline::{
     concurrent::{self, ConcurrentConfig},
     sequential::{self, SequentialConfig},
     Processor,
 };
 use prometheus::Registry;
+use store::{Connection, Store, TransactionalStore};
 use sui_indexer_alt_metrics::db::DbConnectionStatsCollector;
 use sui_pg_db::{temp::TempDb, Db, DbArgs};
 use task::graceful_shutdown;
@@ -32,6 +36,7 @@ pub(crate) mod metrics;
 pub mod models;
 pub mod pipeline;
 pub mod schema;
+pub mod store;
 pub mod task;
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
@@ -60,9 +65,17 @@ pub struct IndexerArgs {
     pub skip_watermark: bool,
 }
 
-pub struct Indexer {
-    /// Connection pool to the database.
-    db: Db,
+/// Where the indexer fetches checkpoints from.
+pub enum IngestionSource {
+    /// One of the built-in sources.
+    Args(ClientArgs),
+    /// A custom source.
+    Client(Arc<dyn IngestionClientTrait>),
+}
+
+pub struct Indexer<S: Store> {
+    /// The store that pipelines write to, and where their watermarks are kept.
+    store: S,
 
     /// Prometheus Metrics.
     metrics: Arc<IndexerMetrics>,
@@ -100,7 +113,7 @@ pub struct Indexer {
     handles: Vec<JoinHandle<()>>,
 }
 
-impl Indexer {
+impl Indexer<Db> {
     /// Create a new instance of the indexer framework. `db_args`, `indexer_args,`, `client_args`,
     /// and `ingestion_config` contain configurations for the following, respectively:
     ///
@@ -125,13 +138,50 @@ impl Indexer {
         registry: &Registry,
         cancel: CancellationToken,
     ) -> Result<Self> {
-        let IndexerArgs {
-            first_checkpoint,
-            last_checkpoint,
-            pipeline,
-            skip_watermark,
-        } = indexer_args;
+        Self::new_impl(
+            db_args,
+            indexer_args,
+            IngestionSource::Args(client_args),
+            ingestion_config,
+            migrations,
+            registry,
+            cancel,
+        )
+        .await
+    }
 
+    /// Like [Self::new], but checkpoints are fetched from a custom source, `client`, rather than
+    /// one of the built-in sources configured through [ClientArgs].
+    pub async fn new_with_client(
+        db_args: DbArgs,
+        indexer_args: IndexerArgs,
+        client: Arc<dyn IngestionClientTrait>,
+        ingestion_config: IngestionConfig,
+        migrations: &'static EmbeddedMigrations,
+        registry: &Registry,
+        cancel: CancellationToken,
+    ) -> Result<Self> {
+        Self::new_impl(
+            db_args,
+            indexer_args,
+            IngestionSource::Client(client),
+            ingestion_config,
+            migrations,
+            registry,
+            cancel,
+        )
+        .await
+    }
+
+    async fn new_impl(
+        db_args: DbArgs,
+        indexer_args: IndexerArgs,
+        source: IngestionSource,
+        ingestion_config: IngestionConfig,
+        migrations: &'static EmbeddedMigrations,
+        registry: &Registry,
+        cancel: CancellationToken,
+    ) -> Result<Self> {
         let db = Db::for_write(db_args)
             .await
             .context("Failed to connect to database")?;
@@ -141,36 +191,12 @@ impl Indexer {
             .await
             .context("Failed to run pending migrations")?;
 
-        let metrics = IndexerMetrics::new(registry);
         registry.register(Box::new(DbConnectionStatsCollector::new(
             Some("indexer_db"),
             db.clone(),
         )))?;
 
-        let ingestion_service = IngestionService::new(
-            client_args,
-            ingestion_config,
-            metrics.clone(),
-            cancel.clone(),
-        )?;
-
-        Ok(Self {
-            db,
-            metrics,
-            ingestion_service,
-            first_checkpoint,
-            last_checkpoint,
-            skip_watermark,
-            enabled_pipelines: if pipeline.is_empty() {
-                None
-            } else {
-                Some(pipeline.into_iter().collect())
-            },
-            added_pipelines: BTreeSet::new(),
-            cancel,
-            first_checkpoint_from_watermark: u64::MAX,
-            handles: vec![],
-        })
+        Self::new_with_store(db, indexer_args, source, ingestion_config, registry, cancel)
     }
 
     pub async fn new_for_testing(migrations: &'static EmbeddedMigrations) -> (Self, TempDb) {
@@ -182,6 +208,7 @@ impl Indexer {
             ClientArgs {
                 remote_store_url: None,
                 local_ingestion_path: Some(tempdir().unwrap().into_path()),
+                rpc_api_url: None,
             },
             IngestionConfig::default(),
             migrations,
@@ -195,7 +222,87 @@ impl Indexer {
 
     /// The database connection pool used by the indexer.
     pub fn db(&self) -> &Db {
-        &self.db
+        &self.store
+    }
+
+    /// Combine the provided `migrations` with the migrations necessary to set up the indexer
+    /// framework. The returned migration source can be passed to [Db::run_migrations] to ensure
+    /// the database's schema is up-to-date for both the indexer framework and the specific
+    /// indexer.
+    pub fn migrations(
+        migrations: &'static EmbeddedMigrations,
+    ) -> impl MigrationSource<Pg> + Send + Sync + 'static {
+        struct Migrations(&'static EmbeddedMigrations);
+        impl MigrationSource<Pg> for Migrations {
+            fn migrations(&self) -> migration::Result<Vec<Box<dyn Migration<Pg>>>> {
+                let mut migrations = MIGRATIONS.migrations()?;
+                migrations.extend(self.0.migrations()?);
+                Ok(migrations)
+            }
+        }
+
+        Migrations(migrations)
+    }
+}
+
+impl<S: Store> Indexer<S> {
+    /// Create a new instance of the indexer framework that writes to `store`, which is expected to
+    /// be ready for use (e.g. any schema it needs has already been set up). `indexer_args`,
+    /// `source` and `ingestion_config` are as described in [Indexer::new].
+    pub fn new_with_store(
+        store: S,
+        indexer_args: IndexerArgs,
+        source: IngestionSource,
+        ingestion_config: IngestionConfig,
+        registry: &Registry,
+        cancel: CancellationToken,
+    ) -> Result<Self> {
+        let IndexerArgs {
+            first_checkpoint,
+            last_checkpoint,
+            pipeline,
+            skip_watermark,
+        } = indexer_args;
+
+        let metrics = IndexerMetrics::new(registry);
+
+        let ingestion_service = match source {
+            IngestionSource::Args(client_args) => IngestionService::new(
+                client_args,
+                ingestion_config,
+                metrics.clone(),
+                cancel.clone(),
+            )?,
+            IngestionSource::Client(client) => IngestionService::with_client(
+                client,
+                ingestion_config,
+                metrics.clone(),
+                cancel.clone(),
+            ),
+        };
+
+        Ok(Self {
+            store,
+            metrics,
+            ingestion_service,
+            first_checkpoint,
+            last_checkpoint,
+            skip_watermark,
+            enabled_pipelines: if pipeline.is_empty() {
+                None
+            } else {
+                Some(pipeline.into_iter().collect())
+            },
+            added_pipelines: BTreeSet::new(),
+            cancel,
+            first_checkpoint_from_watermark: u64::MAX,
+            handles: vec![],
+        })
+    }
+
+    /// The store that pipelines added to this indexer write to.
+    pub fn store(&self) -> &S {
+        &self.store
     }
 
     /// The ingestion client used by the indexer to fetch checkpoints.
@@ -209,11 +316,14 @@ impl Indexer {
     /// Concurrent pipelines commit checkpoint data out-of-order to maximise throughput, and they
     /// keep the watermark table up-to-date with the highest point they can guarantee all data
     /// exists for, for their pipeline.
-    pub async fn concurrent_pipeline<H: concurrent::Handler + Send + Sync + 'static>(
+    pub async fn concurrent_pipeline<H>(
         &mut self,
         handler: H,
         config: ConcurrentConfig,
-    ) -> Result<()> {
+    ) -> Result<()>
+    where
+        H: concurrent::Handler<Store = S> + Send + Sync + 'static,
+    {
         let start_from_pruner_watermark = H::PRUNING_REQUIRES_PROCESSED_VALUES;
         let Some(watermark) = self.add_pipeline::<H>(start_from_pruner_watermark).await? else {
             return Ok(());
@@ -231,7 +341,7 @@ impl Indexer {
             watermark,
             config,
             self.skip_watermark,
-            self.db.clone(),
+            self.store.clone(),
             self.ingestion_service.subscribe().0,
             self.metrics.clone(),
             self.cancel.clone(),
@@ -240,52 +350,6 @@ impl Indexer {
         Ok(())
     }
 
-    /// Adds a new pipeline to this indexer and starts it up. Although their tasks have started,
-    /// they will be idle until the ingestion service starts, and serves it checkpoint data.
-    ///
-    /// Sequential pipelines commit checkpoint data in-order which sacrifices throughput, but may
-    /// be required to handle pipelines that modify data in-place (where each update is not an
-    /// insert, but could be a modification of an existing row, where ordering between updates is
-    /// important).
-    ///
-    /// The pipeline can optionally be configured to lag behind the ingestion service by a fixed
-    /// number of checkpoints (configured by `checkpoint_lag`).
-    pub async fn sequential_pipeline<H: sequential::Handler + Send + Sync + 'static>(
-        &mut self,
-        handler: H,
-        config: SequentialConfig,
-    ) -> Result<()> {
-        let Some(watermark) = self.add_pipeline::<H>(false).await? else {
-            return Ok(());
-        };
-
-        if self.skip_watermark {
-            warn!(
-                pipeline = H::NAME,
-                "--skip-watermarks enabled and ignored for sequential pipeline"
-            );
-        }
-
-        // For a sequential pipeline, data must be written in the order of checkpoints.
-        // Hence, we do not allow the first_checkpoint override to be in arbitrary positions.
-        self.check_first_checkpoint_consistency::<H>(&watermark)?;
-
-        let (checkpoint_rx, watermark_tx) = self.ingestion_service.subscribe();
-
-        self.handles.push(sequential::pipeline(
-            handler,
-            watermark,
-            config,
-            self.db.clone(),
-            checkpoint_rx,
-            watermark_tx,
-            self.metrics.clone(),
-            self.cancel.clone(),
-        ));
-
-        Ok(())
-    }
-
     /// Checks that the first checkpoint override is consistent with the watermark for the pipeline.
     /// If the watermark does not exist, the override can be anything. If the watermark exists, the
     /// override must not leave any gap in the data: it can be in the past, or at the tip of the
@@ -350,25 +414,6 @@ impl Indexer {
         }))
     }
 
-    /// Combine the provided `migrations` with the migrations necessary to set up the indexer
-    /// framework. The returned migration source can be passed to [Db::run_migrations] to ensure
-    /// the database's schema is up-to-date for both the indexer framework and the specific
-    /// indexer.
-    pub fn migrations(
-        migrations: &'static EmbeddedMigrations,
-    ) -> impl MigrationSource<Pg> + Send + Sync + 'static {
-        struct Migrations(&'static EmbeddedMigrations);
-        impl MigrationSource<Pg> for Migrations {
-            fn migrations(&self) -> migration::Result<Vec<Box<dyn Migration<Pg>>>> {
-                let mut migrations = MIGRATIONS.migrations()?;
-                migrations.extend(self.0.migrations()?);
-                Ok(migrations)
-            }
-        }
-
-        Migrations(migrations)
-    }
-
     /// Update the indexer's first checkpoint based on the watermark for the pipeline by adding for
     /// handler `H` (as long as it's enabled). Returns `Ok(None)` if the pipeline is disabled,
     /// `Ok(Some(None))` if the pipeline is enabled but its watermark is not found, and
@@ -394,9 +439,14 @@ impl Indexer {
             }
         }
 
-        let mut conn = self.db.connect().await.context("Failed DB connection")?;
+        let mut conn = self
+            .store
+            .connect()
+            .await
+            .context("Failed to connect to store")?;
 
-        let watermark = CommitterWatermark::get(&mut conn, P::NAME)
+        let watermark = conn
+            .committer_watermark(P::NAME)
             .await
             .with_context(|| format!("Failed to get watermark for {}", P::NAME))?;
 
@@ -404,7 +454,7 @@ impl Indexer {
             // If the pruner of this pipeline requires processed values in order to prune,
             // we must start ingestion from just after the pruner watermark,
             // so that we can process all values needed by the pruner.
-            PrunerWatermark::get(&mut conn, P::NAME, Default::default())
+            conn.pruner_watermark(P::NAME, Default::default())
                 .await
                 .with_context(|| format!("Failed to get pruner watermark for {}", P::NAME))?
                 .map(|w| w.pruner_hi as u64)
@@ -423,6 +473,57 @@ impl Indexer {
     }
 }
 
+impl<T: TransactionalStore> Indexer<T> {
+    /// Adds a new pipeline to this indexer and starts it up. Although their tasks have started,
+    /// they will be idle until the ingestion service starts, and serves it checkpoint data.
+    ///
+    /// Sequential pipelines commit checkpoint data in-order which sacrifices throughput, but may
+    /// be required to handle pipelines that modify data in-place (where each update is not an
+    /// insert, but could be a modification of an existing row, where ordering between updates is
+    /// important).
+    ///
+    /// The pipeline can optionally be configured to lag behind the ingestion service by a fixed
+    /// number of checkpoints (configured by `checkpoint_lag`).
+    pub async fn sequential_pipeline<H>(
+        &mut self,
+        handler: H,
+        config: SequentialConfig,
+    ) -> Result<()>
+    where
+        H: sequential::Handler<Store = T> + Send + Sync + 'static,
+    {
+        let Some(watermark) = self.add_pipeline::<H>(false).await? else {
+            return Ok(());
+        };
+
+        if self.skip_watermark {
+            warn!(
+                pipeline = H::NAME,
+                "--skip-watermarks enabled and ignored for sequential pipeline"
+            );
+        }
+
+        // For a sequential pipeline, data must be written in the order of checkpoints.
+        // Hence, we do not allow the first_checkpoint override to be in arbitrary positions.
+        self.check_first_checkpoint_consistency::<H>(&watermark)?;
+
+        let (checkpoint_rx, watermark_tx) = self.ingestion_service.subscribe();
+
+        self.handles.push(sequential::pipeline(
+            handler,
+            watermark,
+            config,
+            self.store.clone(),
+            checkpoint_rx,
+            watermark_tx,
+            self.metrics.clone(),
+            self.cancel.clone(),
+        ));
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use async_trait::async_trait;
@@ -456,6 +557,8 @@ mod tests {
 
             #[async_trait]
             impl concurrent::Handler for $name {
+                type Store = Db;
+
                 const PRUNING_REQUIRES_PROCESSED_VALUES: bool = $pruning_requires_processed_values;
                 async fn commit(
                     _values: &[Self::Value],
@@ -485,8 +588,12 @@ mod tests {
     async fn test_add_existing_pipeline() {
         let (mut indexer, _temp_db) = Indexer::new_for_testing(&MIGRATIONS).await;
         let watermark = CommitterWatermark::new_for_testing(ConcurrentPipeline1::NAME, 10);
-        watermark
-            .update(&mut indexer.db().connect().await.unwrap())
+        indexer
+            .db()
+            .connect()
+            .await
+            .unwrap()
+            .set_committer_watermark(&watermark)
             .await
             .unwrap();
         indexer
@@ -500,13 +607,21 @@ mod tests {
     async fn test_add_multiple_pipelines() {
         let (mut indexer, _temp_db) = Indexer::new_for_testing(&MIGRATIONS).await;
         let watermark1 = CommitterWatermark::new_for_testing(ConcurrentPipeline1::NAME, 10);
-        watermark1
-            .update(&mut indexer.db().connect().await.unwrap())
+        indexer
+            .db()
+            .connect()
+            .await
+            .unwrap()
+            .set_committer_watermark(&watermark1)
             .await
             .unwrap();
         let watermark2 = CommitterWatermark::new_for_testing(ConcurrentPipeline2::NAME, 20);
-        watermark2
-            .update(&mut indexer.db().connect().await.unwrap())
+        indexer
+            .db()
+            .connect()
+            .await
+            .unwrap()
+            .set_committer_watermark(&watermark2)
             .await
             .unwrap();
 
@@ -526,8 +641,12 @@ mod tests {
     async fn test_add_multiple_pipelines_pruning_requires_processed_values() {
         let (mut indexer, _temp_db) = Indexer::new_for_testing(&MIGRATIONS).await;
         let watermark1 = CommitterWatermark::new_for_testing(ConcurrentPipeline1::NAME, 10);
-        watermark1
-            .update(&mut indexer.db().connect().await.unwrap())
+        indexer
+            .db()
+            .connect()
+            .await
+            .unwrap()
+            .set_committer_watermark(&watermark1)
             .await
             .unwrap();
         indexer
@@ -537,13 +656,20 @@ mod tests {
         assert_eq!(indexer.first_checkpoint_from_watermark, 11);
 
         let watermark3 = CommitterWatermark::new_for_testing(ConcurrentPipeline3::NAME, 20);
-        watermark3
-            .update(&mut indexer.db().connect().await.unwrap())
+        indexer
+            .db()
+            .connect()
+            .await
+            .unwrap()
+            .set_committer_watermark(&watermark3)
             .await
             .unwrap();
-        let pruner_watermark = PrunerWatermark::new_for_testing(ConcurrentPipeline3::NAME, 5);
-        assert!(pruner_watermark
-            .update(&mut indexer.db().connect().await.unwrap())
+        assert!(indexer
+            .db()
+            .connect()
+            .await
+            .unwrap()
+            .set_pruner_watermark(ConcurrentPipeline3::NAME, 5)
             .await
             .unwrap());
         indexer
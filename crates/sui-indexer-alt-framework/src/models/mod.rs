@@ -2,4 +2,4 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod cp_sequence_numbers;
-pub(crate) mod watermarks;
+pub mod watermarks;
@@ -7,7 +7,6 @@ use std::{
     sync::Arc,
 };
 
-use sui_pg_db::Db;
 use tokio::{
     sync::mpsc,
     task::JoinHandle,
@@ -20,12 +19,13 @@ use crate::{
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
     models::watermarks::CommitterWatermark,
     pipeline::{logging::WatermarkLogger, CommitterConfig, WatermarkPart, WARN_PENDING_WATERMARKS},
+    store::{Connection, Store},
 };
 
 use super::Handler;
 
 /// The watermark task is responsible for keeping track of a pipeline's out-of-order commits and
-/// updating its row in the `watermarks` table when a continuous run of checkpoints have landed
+/// updating its watermark in the store when a continuous run of checkpoints have landed
 /// since the last watermark update.
 ///
 /// It receives watermark "parts" that detail the proportion of each checkpoint's data that has
@@ -49,7 +49,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
     config: CommitterConfig,
     skip_watermark: bool,
     mut rx: mpsc::Receiver<Vec<WatermarkPart>>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -103,8 +103,8 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
                         );
                     }
 
-                    let Ok(mut conn) = db.connect().await else {
-                        warn!(pipeline = H::NAME, "Commit watermark task failed to get connection for DB");
+                    let Ok(mut conn) = store.connect().await else {
+                        warn!(pipeline = H::NAME, "Commit watermark task failed to get connection for store");
                         continue;
                     };
 
@@ -190,7 +190,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
 
                         // TODO: If initial_watermark is empty, when we update watermark
                         // for the first time, we should also update the low watermark.
-                        match watermark.update(&mut conn).await {
+                        match conn.set_committer_watermark(&watermark).await {
                             // If there's an issue updating the watermark, log it but keep going,
                             // it's OK for the watermark to lag from a correctness perspective.
                             Err(e) => {
@@ -4,7 +4,6 @@
 use std::{sync::Arc, time::Duration};
 
 use backoff::ExponentialBackoff;
-use sui_pg_db::Db;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
@@ -13,6 +12,7 @@ use tracing::{debug, error, info, warn};
 use crate::{
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
     pipeline::{Break, CommitterConfig, WatermarkPart},
+    store::Store,
     task::TrySpawnStreamExt,
 };
 
@@ -24,8 +24,8 @@ const INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(100);
 /// If the committer needs to retry a commit, it will wait at most this long between retries.
 const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
-/// The committer task is responsible for writing batches of rows to the database. It receives
-/// batches on `rx` and writes them out to the `db` concurrently (`config.write_concurrency`
+/// The committer task is responsible for writing batches of rows to the store. It receives
+/// batches on `rx` and writes them out to the `store` concurrently (`config.write_concurrency`
 /// controls the degree of fan-out).
 ///
 /// The writing of each batch will be repeatedly retried on an exponential back-off until it
@@ -39,7 +39,7 @@ pub(super) fn committer<H: Handler + 'static>(
     skip_watermark: bool,
     rx: mpsc::Receiver<BatchedRows<H>>,
     tx: mpsc::Sender<Vec<WatermarkPart>>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -57,14 +57,14 @@ pub(super) fn committer<H: Handler + 'static>(
                 |BatchedRows { values, watermark }| {
                     let values = Arc::new(values);
                     let tx = tx.clone();
-                    let db = db.clone();
+                    let store = store.clone();
                     let metrics = metrics.clone();
                     let cancel = cancel.clone();
                     let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
 
-                    // Repeatedly try to get a connection to the DB and write the batch. Use an
-                    // exponential backoff in case the failure is due to contention over the DB
-                    // connection pool.
+                    // Repeatedly try to get a connection to the store and write the batch. Use an
+                    // exponential backoff in case the failure is due to contention over the
+                    // store's connection pool.
                     let backoff = ExponentialBackoff {
                         initial_interval: INITIAL_RETRY_INTERVAL,
                         current_interval: INITIAL_RETRY_INTERVAL,
@@ -80,7 +80,7 @@ pub(super) fn committer<H: Handler + 'static>(
                     use backoff::Error as BE;
                     let commit = move || {
                         let values = values.clone();
-                        let db = db.clone();
+                        let store = store.clone();
                         let metrics = metrics.clone();
                         let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
                         async move {
@@ -98,10 +98,10 @@ pub(super) fn committer<H: Handler + 'static>(
                                 .with_label_values(&[H::NAME])
                                 .start_timer();
 
-                            let mut conn = db.connect().await.map_err(|e| {
+                            let mut conn = store.connect().await.map_err(|e| {
                                 warn!(
                                     pipeline = H::NAME,
-                                    "Committed failed to get connection for DB"
+                                    "Committed failed to get connection for store"
                                 );
 
                                 metrics
@@ -5,13 +5,12 @@ use std::{sync::Arc, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use sui_field_count::FieldCount;
-use sui_pg_db::{self as db, Db};
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
 use tracing::info;
 
-use crate::{metrics::IndexerMetrics, models::watermarks::CommitterWatermark};
+use crate::{metrics::IndexerMetrics, models::watermarks::CommitterWatermark, store::Store};
 
 use super::{processor::processor, CommitterConfig, Processor, WatermarkPart, PIPELINE_BUFFER};
 
@@ -32,7 +31,7 @@ mod reader_watermark;
 const MAX_WATERMARK_UPDATES: usize = 10_000;
 
 /// Handlers implement the logic for a given indexing pipeline: How to process checkpoint data (by
-/// implementing [Processor]) into rows for their table, and how to write those rows to the database.
+/// implementing [Processor]) into rows for their table, and how to write those rows to the store.
 ///
 /// The handler is also responsible for tuning the various parameters of the pipeline (provided as
 /// associated values). Reasonable defaults have been chosen to balance concurrency with memory
@@ -44,14 +43,17 @@ const MAX_WATERMARK_UPDATES: usize = 10_000;
 ///   can be done concurrently, to preserve throughput.
 ///
 /// Concurrent handlers can only be used in concurrent pipelines, where checkpoint data is
-/// processed and committed out-of-order and a watermark table is kept up-to-date with the latest
-/// checkpoint below which all data has been committed.
+/// processed and committed out-of-order and a watermark is kept up-to-date in the store with the
+/// latest checkpoint below which all data has been committed.
 ///
 /// Back-pressure is handled through the `MAX_PENDING_SIZE` constant -- if more than this many rows
 /// build up, the collector will stop accepting new checkpoints, which will eventually propagate
 /// back to the ingestion service.
 #[async_trait::async_trait]
 pub trait Handler: Processor<Value: FieldCount> {
+    /// The store that this pipeline writes to.
+    type Store: Store;
+
     /// If at least this many rows are pending, the committer will commit them eagerly.
     const MIN_EAGER_ROWS: usize = 50;
 
@@ -70,18 +72,20 @@ pub trait Handler: Processor<Value: FieldCount> {
     // 2. The name is a bit abstract.
     const PRUNING_REQUIRES_PROCESSED_VALUES: bool = false;
 
-    /// Take a chunk of values and commit them to the database, returning the number of rows
+    /// Take a chunk of values and commit them to the store, returning the number of rows
     /// affected.
-    async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>)
-        -> anyhow::Result<usize>;
+    async fn commit(
+        values: &[Self::Value],
+        conn: &mut <Self::Store as Store>::Connection<'_>,
+    ) -> anyhow::Result<usize>;
 
-    /// Clean up data between checkpoints `_from` and `_to_exclusive` (exclusive) in the database, returning
+    /// Clean up data between checkpoints `_from` and `_to_exclusive` (exclusive) in the store, returning
     /// the number of rows affected. This function is optional, and defaults to not pruning at all.
     async fn prune(
         &self,
         _from: u64,
         _to_exclusive: u64,
-        _conn: &mut db::Connection<'_>,
+        _conn: &mut <Self::Store as Store>::Connection<'_>,
     ) -> anyhow::Result<usize> {
         Ok(0)
     }
@@ -182,9 +186,9 @@ impl Default for PrunerConfig {
 /// either because it received the checkpoints out-of-order or because of variance in processing
 /// time.
 ///
-/// The pipeline also maintains a row in the `watermarks` table for the pipeline which tracks the
-/// watermark below which all data has been committed (modulo pruning), as long as `skip_watermark`
-/// is not true.
+/// The pipeline also maintains a watermark for the pipeline in its store which tracks the
+/// checkpoint below which all data has been committed (modulo pruning), as long as
+/// `skip_watermark` is not true.
 ///
 /// Checkpoint data is fed into the pipeline through the `checkpoint_rx` channel, and internal
 /// channels are created to communicate between its various components. The pipeline can be
@@ -195,7 +199,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
     initial_commit_watermark: Option<CommitterWatermark<'static>>,
     config: ConcurrentConfig,
     skip_watermark: bool,
-    db: Db,
+    store: H::Store,
     checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
@@ -243,7 +247,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
         skip_watermark,
         committer_rx,
         committer_tx,
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         cancel.clone(),
     );
@@ -253,19 +257,25 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
         committer_config,
         skip_watermark,
         watermark_rx,
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         cancel,
     );
 
     let reader_watermark = reader_watermark::<H>(
         pruner_config.clone(),
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         pruner_cancel.clone(),
     );
 
-    let pruner = pruner(handler, pruner_config, db, metrics, pruner_cancel.clone());
+    let pruner = pruner(
+        handler,
+        pruner_config,
+        store,
+        metrics,
+        pruner_cancel.clone(),
+    );
 
     tokio::spawn(async move {
         let (_, _, _, _) = futures::join!(processor, collector, committer, commit_watermark);
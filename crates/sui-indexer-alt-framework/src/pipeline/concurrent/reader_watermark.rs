@@ -3,32 +3,31 @@
 
 use std::sync::Arc;
 
-use sui_pg_db::Db;
 use tokio::{task::JoinHandle, time::interval};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 use crate::{
     metrics::IndexerMetrics,
-    models::watermarks::{ReaderWatermark, StoredWatermark},
+    store::{Connection, Store},
 };
 
 use super::{Handler, PrunerConfig};
 
 /// The reader watermark task is responsible for updating the `reader_lo` and `pruner_timestamp`
-/// values for a pipeline's row in the watermark table, based on the pruner configuration, and the
+/// values for a pipeline's watermark in the store, based on the pruner configuration, and the
 /// committer's progress.
 ///
 /// `reader_lo` is the lowest checkpoint that readers are allowed to read from with a guarantee of
 /// data availability for this pipeline, and `pruner_timestamp` is the timestamp at which this task
-/// last updated that watermark. The timestamp is always fetched from the database (not from the
+/// last updated that watermark. The timestamp is always fetched from the store (not from the
 /// indexer or the reader), to avoid issues with drift between clocks.
 ///
 /// If there is no pruner configuration, this task will immediately exit. Otherwise, the task exits
 /// when the provided cancellation token is triggered.
 pub(super) fn reader_watermark<H: Handler + 'static>(
     config: Option<PrunerConfig>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -48,12 +47,12 @@ pub(super) fn reader_watermark<H: Handler + 'static>(
                 }
 
                 _ = poll.tick() => {
-                    let Ok(mut conn) = db.connect().await else {
-                        warn!(pipeline = H::NAME, "Reader watermark task failed to get connection for DB");
+                    let Ok(mut conn) = store.connect().await else {
+                        warn!(pipeline = H::NAME, "Reader watermark task failed to get connection for store");
                         continue;
                     };
 
-                    let current = match StoredWatermark::get(&mut conn, H::NAME).await {
+                    let current = match conn.reader_watermark(H::NAME).await {
                         Ok(Some(current)) => current,
 
                         Ok(None) => {
@@ -86,7 +85,7 @@ pub(super) fn reader_watermark<H: Handler + 'static>(
                         .with_label_values(&[H::NAME])
                         .set(new_reader_lo as i64);
 
-                    let Ok(updated) = ReaderWatermark::new(H::NAME, new_reader_lo).update(&mut conn).await else {
+                    let Ok(updated) = conn.set_reader_watermark(H::NAME, new_reader_lo).await else {
                         warn!(pipeline = H::NAME, "Failed to update reader watermark");
                         continue;
                     };
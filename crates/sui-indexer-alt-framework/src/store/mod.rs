@@ -0,0 +1,107 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use diesel_async::scoped_futures::ScopedBoxFuture;
+
+use crate::models::watermarks::{CommitterWatermark, PrunerWatermark, ReaderWatermark};
+
+pub mod pg;
+pub mod rocks;
+
+/// A store is where pipelines write the data they index, and where the framework keeps track of
+/// each pipeline's progress (its watermarks). Pipelines get access to the store through
+/// connections, which are used to write data, and read or update watermarks.
+///
+/// The framework comes with a Postgres implementation (for [sui_pg_db::Db]), and an embedded
+/// implementation backed by RocksDB ([rocks::RocksStore]).
+#[async_trait]
+pub trait Store: Clone + Send + Sync + 'static {
+    type Connection<'c>: Connection
+    where
+        Self: 'c;
+
+    /// Get a connection to the store. Can fail if the store is unavailable, or all its
+    /// connections are in use.
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>>;
+}
+
+/// A store that supports running a series of reads and writes atomically. Sequential pipelines
+/// require their store to be transactional, so that their writes and the update to their
+/// watermark land together.
+#[async_trait]
+pub trait TransactionalStore: Store {
+    /// Run `f` in a transaction. The transaction is committed if `f` succeeds and rolled back
+    /// otherwise.
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>;
+}
+
+/// Operations on a pipeline's watermarks that every store's connection must support.
+///
+/// Each watermark is only ever updated by one task per pipeline (the committer watermark by the
+/// committer, the reader watermark by the reader watermark task and the pruner watermark by the
+/// pruner), so implementations only need to guarantee that updates from different tasks don't
+/// clobber each other.
+#[async_trait]
+pub trait Connection: Send {
+    /// Get the committer's high watermark for `pipeline`, or `None` if the pipeline has not
+    /// written a watermark yet.
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark<'static>>>;
+
+    /// Get the reader low watermark for `pipeline`, along with its committer high watermark, or
+    /// `None` if the pipeline has not written a watermark yet.
+    async fn reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<ReaderWatermark<'static>>>;
+
+    /// Get the bounds for the region that the pruner still has to prune for `pipeline`, along
+    /// with a duration to wait before acting on this information, based on the time at which the
+    /// reader low watermark was last updated, and the configured `delay`.
+    ///
+    /// The pruner is allowed to prune the region between the returned `pruner_hi` (inclusive) and
+    /// `reader_lo` (exclusive) after `wait_for` milliseconds have passed since this response was
+    /// returned.
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark<'static>>>;
+
+    /// Upsert the committer's high watermark, as long as it raises the watermark that is already
+    /// stored. Returns a boolean indicating whether the watermark was actually updated or not.
+    async fn set_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+
+    /// Update the reader low watermark for an existing watermark, as long as this raises the
+    /// watermark, and record the time at which the update happened (according to the store).
+    ///
+    /// Returns a boolean indicating whether the watermark was actually updated or not.
+    async fn set_reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+        reader_lo: u64,
+    ) -> anyhow::Result<bool>;
+
+    /// Update the pruner high watermark (only) for an existing watermark.
+    ///
+    /// Returns a boolean indicating whether the watermark was actually updated or not.
+    async fn set_pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        pruner_hi: u64,
+    ) -> anyhow::Result<bool>;
+}
@@ -0,0 +1,144 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Postgres implementation of the [Store], where watermarks live in the `watermarks` table.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use diesel::{dsl::sql, prelude::*, sql_types};
+use diesel_async::{scoped_futures::ScopedBoxFuture, AsyncConnection, RunQueryDsl};
+use sui_pg_db::{self as db, Db};
+
+use crate::{
+    models::watermarks::{CommitterWatermark, PrunerWatermark, ReaderWatermark, StoredWatermark},
+    schema::watermarks,
+};
+
+use super::{Connection, Store, TransactionalStore};
+
+#[async_trait]
+impl Store for Db {
+    type Connection<'c> = db::Connection<'c>;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+        Db::connect(self).await
+    }
+}
+
+#[async_trait]
+impl TransactionalStore for Db {
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>,
+    {
+        let mut conn = Db::connect(self).await?;
+        AsyncConnection::transaction(&mut conn, |conn| f(conn)).await
+    }
+}
+
+#[async_trait]
+impl Connection for db::Connection<'_> {
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark<'static>>> {
+        Ok(watermarks::table
+            .select(CommitterWatermark::as_select())
+            .filter(watermarks::pipeline.eq(pipeline))
+            .first(self)
+            .await
+            .optional()?)
+    }
+
+    async fn reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<ReaderWatermark<'static>>> {
+        Ok(watermarks::table
+            .select(ReaderWatermark::as_select())
+            .filter(watermarks::pipeline.eq(pipeline))
+            .first(self)
+            .await
+            .optional()?)
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark<'static>>> {
+        //     |---------- + delay ---------------------|
+        //                             |--- wait_for ---|
+        //     |-----------------------|----------------|
+        //     ^                       ^
+        //     pruner_timestamp        NOW()
+        let wait_for = sql::<sql_types::BigInt>(&format!(
+            "CAST({} + 1000 * EXTRACT(EPOCH FROM pruner_timestamp - NOW()) AS BIGINT)",
+            delay.as_millis(),
+        ));
+
+        Ok(watermarks::table
+            .select((
+                watermarks::pipeline,
+                wait_for,
+                watermarks::reader_lo,
+                watermarks::pruner_hi,
+            ))
+            .filter(watermarks::pipeline.eq(pipeline))
+            .first(self)
+            .await
+            .optional()?)
+    }
+
+    /// TODO(amnn): Test this (depends on supporting migrations and tempdb).
+    async fn set_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        use diesel::query_dsl::methods::FilterDsl;
+        Ok(diesel::insert_into(watermarks::table)
+            .values(StoredWatermark::from(watermark.clone()))
+            .on_conflict(watermarks::pipeline)
+            .do_update()
+            .set(watermark)
+            .filter(watermarks::checkpoint_hi_inclusive.lt(watermark.checkpoint_hi_inclusive))
+            .execute(self)
+            .await?
+            > 0)
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+        reader_lo: u64,
+    ) -> anyhow::Result<bool> {
+        Ok(diesel::update(watermarks::table)
+            .set((
+                watermarks::reader_lo.eq(reader_lo as i64),
+                watermarks::pruner_timestamp.eq(diesel::dsl::now),
+            ))
+            .filter(watermarks::pipeline.eq(pipeline))
+            .filter(watermarks::reader_lo.lt(reader_lo as i64))
+            .execute(self)
+            .await?
+            > 0)
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        pruner_hi: u64,
+    ) -> anyhow::Result<bool> {
+        Ok(diesel::update(watermarks::table)
+            .set(watermarks::pruner_hi.eq(pruner_hi as i64))
+            .filter(watermarks::pipeline.eq(pipeline))
+            .execute(self)
+            .await?
+            > 0)
+    }
+}
@@ -0,0 +1,441 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Embedded implementation of the [Store], backed by RocksDB, for running pipelines without
+//! operating a database server.
+//!
+//! Pipelines are given their own column families to write to, when the store is opened. They can
+//! then get a typed view of a column family using [RocksStore::map], and read from it directly, but
+//! should write to it through their [RocksConnection], so that writes made by sequential pipelines
+//! are committed atomically with their watermark.
+
+use std::{path::Path, sync::Arc, time::Duration};
+
+use anyhow::{ensure, Context};
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel_async::scoped_futures::ScopedBoxFuture;
+use serde::{Deserialize, Serialize};
+use typed_store::{
+    rocks::{open_cf, DBBatch, DBMap, MetricConf, ReadWriteOptions, RocksDB},
+    Map, TypedStoreError,
+};
+
+use crate::models::watermarks::{CommitterWatermark, PrunerWatermark, ReaderWatermark};
+
+use super::{Connection, Store, TransactionalStore};
+
+const COMMITTER_WATERMARKS: &str = "committer_watermarks";
+const READER_WATERMARKS: &str = "reader_watermarks";
+const PRUNER_WATERMARKS: &str = "pruner_watermarks";
+
+/// A store backed by a RocksDB database on the local filesystem. Instances of [RocksStore] can be
+/// cloned to share access to the same database.
+///
+/// Each of a pipeline's watermarks is kept in its own column family, so that the tasks responsible
+/// for updating them can do so independently of each other.
+#[derive(Clone)]
+pub struct RocksStore {
+    db: Arc<RocksDB>,
+    committer_watermarks: DBMap<String, StoredCommitterWatermark>,
+    reader_watermarks: DBMap<String, StoredReaderWatermark>,
+    pruner_watermarks: DBMap<String, u64>,
+}
+
+/// A connection to a [RocksStore]. Outside of a transaction, writes are applied as soon as they
+/// are made, and inside a transaction, they are staged and applied atomically when the transaction
+/// commits.
+///
+/// Reads always go to the database, so they will not observe writes staged in the current
+/// transaction.
+pub struct RocksConnection<'s> {
+    store: &'s RocksStore,
+
+    /// Writes staged by the current transaction, if there is one.
+    batch: Option<DBBatch>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+struct StoredCommitterWatermark {
+    epoch_hi_inclusive: u64,
+    checkpoint_hi_inclusive: u64,
+    tx_hi: u64,
+    timestamp_ms_hi_inclusive: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+struct StoredReaderWatermark {
+    reader_lo: u64,
+    /// When `reader_lo` was last updated, in milliseconds since the Unix epoch.
+    pruner_timestamp_ms: i64,
+}
+
+impl RocksStore {
+    /// Open (or create) the database at `path`, with a column family for each name in
+    /// `column_families` (in addition to the column families the store needs for its watermarks).
+    pub fn open(path: impl AsRef<Path>, column_families: &[&str]) -> anyhow::Result<Self> {
+        let reserved = [COMMITTER_WATERMARKS, READER_WATERMARKS, PRUNER_WATERMARKS];
+        for cf in column_families {
+            ensure!(
+                !reserved.contains(cf),
+                "Column family {cf:?} is reserved for watermarks",
+            );
+        }
+
+        let mut cfs = reserved.to_vec();
+        cfs.extend_from_slice(column_families);
+
+        let db = open_cf(path, None, MetricConf::new("indexer"), &cfs)
+            .context("Failed to open database")?;
+
+        let rw_options = ReadWriteOptions::default();
+        Ok(Self {
+            committer_watermarks: DBMap::reopen(
+                &db,
+                Some(COMMITTER_WATERMARKS),
+                &rw_options,
+                false,
+            )?,
+            reader_watermarks: DBMap::reopen(&db, Some(READER_WATERMARKS), &rw_options, false)?,
+            pruner_watermarks: DBMap::reopen(&db, Some(PRUNER_WATERMARKS), &rw_options, false)?,
+            db,
+        })
+    }
+
+    /// A typed view of the column family `cf`, which must have been registered when the store was
+    /// opened. Range deletions are visible to reads from the returned map as soon as they are
+    /// written, so that pruned data disappears immediately.
+    pub fn map<K, V>(&self, cf: &str) -> anyhow::Result<DBMap<K, V>> {
+        let rw_options = ReadWriteOptions::default().set_ignore_range_deletions(false);
+        DBMap::reopen(&self.db, Some(cf), &rw_options, false)
+            .with_context(|| format!("Failed to open column family {cf:?}"))
+    }
+
+    fn batch(&self) -> DBBatch {
+        self.committer_watermarks.batch()
+    }
+}
+
+impl RocksConnection<'_> {
+    /// The store this connection is for.
+    pub fn store(&self) -> &RocksStore {
+        self.store
+    }
+
+    /// Write `entries` to `map`, overwriting any existing values for their keys.
+    pub fn insert<K, V>(
+        &mut self,
+        map: &DBMap<K, V>,
+        entries: impl IntoIterator<Item = (K, V)>,
+    ) -> anyhow::Result<()>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        self.write(|batch| batch.insert_batch(map, entries).map(|_| ()))
+    }
+
+    /// Delete `keys` from `map`.
+    pub fn delete<K: Serialize, V>(
+        &mut self,
+        map: &DBMap<K, V>,
+        keys: impl IntoIterator<Item = K>,
+    ) -> anyhow::Result<()> {
+        self.write(|batch| batch.delete_batch(map, keys))
+    }
+
+    /// Delete all keys in `map` between `from` (inclusive) and `to` (exclusive).
+    pub fn delete_range<K: Serialize, V>(
+        &mut self,
+        map: &DBMap<K, V>,
+        from: &K,
+        to: &K,
+    ) -> anyhow::Result<()> {
+        self.write(|batch| batch.schedule_delete_range(map, from, to))
+    }
+
+    /// Add writes to the current transaction's batch, or apply them immediately if there is no
+    /// transaction.
+    fn write(
+        &mut self,
+        f: impl FnOnce(&mut DBBatch) -> Result<(), TypedStoreError>,
+    ) -> anyhow::Result<()> {
+        if let Some(batch) = &mut self.batch {
+            f(batch)?;
+        } else {
+            let mut batch = self.store.batch();
+            f(&mut batch)?;
+            batch.write()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Store for RocksStore {
+    type Connection<'c> = RocksConnection<'c>;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+        Ok(RocksConnection {
+            store: self,
+            batch: None,
+        })
+    }
+}
+
+#[async_trait]
+impl TransactionalStore for RocksStore {
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>,
+    {
+        let mut conn = RocksConnection {
+            store: self,
+            batch: Some(self.batch()),
+        };
+
+        // If `f` fails, its staged writes are dropped along with the connection.
+        let result = f(&mut conn).await?;
+        if let Some(batch) = conn.batch.take() {
+            batch.write()?;
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl Connection for RocksConnection<'_> {
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark<'static>>> {
+        let Some(stored) = self.store.committer_watermarks.get(&pipeline.to_owned())? else {
+            return Ok(None);
+        };
+
+        Ok(Some(CommitterWatermark {
+            pipeline: pipeline.into(),
+            epoch_hi_inclusive: stored.epoch_hi_inclusive as i64,
+            checkpoint_hi_inclusive: stored.checkpoint_hi_inclusive as i64,
+            tx_hi: stored.tx_hi as i64,
+            timestamp_ms_hi_inclusive: stored.timestamp_ms_hi_inclusive as i64,
+        }))
+    }
+
+    async fn reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<ReaderWatermark<'static>>> {
+        let key = pipeline.to_owned();
+        let Some(committer) = self.store.committer_watermarks.get(&key)? else {
+            return Ok(None);
+        };
+
+        let reader = self.store.reader_watermarks.get(&key)?.unwrap_or_default();
+        Ok(Some(ReaderWatermark {
+            pipeline: pipeline.into(),
+            checkpoint_hi_inclusive: committer.checkpoint_hi_inclusive as i64,
+            reader_lo: reader.reader_lo as i64,
+        }))
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark<'static>>> {
+        let key = pipeline.to_owned();
+        if !self.store.committer_watermarks.contains_key(&key)? {
+            return Ok(None);
+        }
+
+        let reader = self.store.reader_watermarks.get(&key)?.unwrap_or_default();
+        let pruner_hi = self.store.pruner_watermarks.get(&key)?.unwrap_or_default();
+
+        // See the Postgres implementation: `wait_for` is how long until `delay` has passed since
+        // the reader low watermark was last updated.
+        let wait_for =
+            delay.as_millis() as i64 + reader.pruner_timestamp_ms - Utc::now().timestamp_millis();
+
+        Ok(Some(PrunerWatermark {
+            pipeline: pipeline.into(),
+            wait_for,
+            reader_lo: reader.reader_lo as i64,
+            pruner_hi: pruner_hi as i64,
+        }))
+    }
+
+    async fn set_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        let key = watermark.pipeline.to_string();
+        let checkpoint_hi_inclusive = watermark.checkpoint_hi_inclusive as u64;
+        if let Some(current) = self.store.committer_watermarks.get(&key)? {
+            if current.checkpoint_hi_inclusive >= checkpoint_hi_inclusive {
+                return Ok(false);
+            }
+        }
+
+        let stored = StoredCommitterWatermark {
+            epoch_hi_inclusive: watermark.epoch_hi_inclusive as u64,
+            checkpoint_hi_inclusive,
+            tx_hi: watermark.tx_hi as u64,
+            timestamp_ms_hi_inclusive: watermark.timestamp_ms_hi_inclusive as u64,
+        };
+
+        let store = self.store;
+        self.insert(&store.committer_watermarks, [(key, stored)])?;
+        Ok(true)
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+        reader_lo: u64,
+    ) -> anyhow::Result<bool> {
+        let key = pipeline.to_owned();
+        if !self.store.committer_watermarks.contains_key(&key)? {
+            return Ok(false);
+        }
+
+        let current = self.store.reader_watermarks.get(&key)?.unwrap_or_default();
+        if current.reader_lo >= reader_lo {
+            return Ok(false);
+        }
+
+        let stored = StoredReaderWatermark {
+            reader_lo,
+            pruner_timestamp_ms: Utc::now().timestamp_millis(),
+        };
+
+        let store = self.store;
+        self.insert(&store.reader_watermarks, [(key, stored)])?;
+        Ok(true)
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        pruner_hi: u64,
+    ) -> anyhow::Result<bool> {
+        let key = pipeline.to_owned();
+        if !self.store.committer_watermarks.contains_key(&key)? {
+            return Ok(false);
+        }
+
+        let store = self.store;
+        self.insert(&store.pruner_watermarks, [(key, pruner_hi)])?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::scoped_futures::ScopedFutureExt;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    const PIPELINE: &str = "pipeline";
+
+    #[tokio::test]
+    async fn test_committer_watermark_only_moves_forward() {
+        let dir = tempdir().unwrap();
+        let store = RocksStore::open(dir.path(), &[]).unwrap();
+        let mut conn = store.connect().await.unwrap();
+
+        assert!(conn.committer_watermark(PIPELINE).await.unwrap().is_none());
+
+        let watermark = CommitterWatermark::new_for_testing(PIPELINE, 10);
+        assert!(conn.set_committer_watermark(&watermark).await.unwrap());
+
+        let watermark = CommitterWatermark::new_for_testing(PIPELINE, 5);
+        assert!(!conn.set_committer_watermark(&watermark).await.unwrap());
+
+        let current = conn.committer_watermark(PIPELINE).await.unwrap().unwrap();
+        assert_eq!(current.checkpoint_hi_inclusive, 10);
+    }
+
+    #[tokio::test]
+    async fn test_reader_and_pruner_watermarks() {
+        let dir = tempdir().unwrap();
+        let store = RocksStore::open(dir.path(), &[]).unwrap();
+        let mut conn = store.connect().await.unwrap();
+
+        // Reader and pruner watermarks can't be set before the committer watermark exists.
+        assert!(!conn.set_reader_watermark(PIPELINE, 5).await.unwrap());
+        assert!(!conn.set_pruner_watermark(PIPELINE, 5).await.unwrap());
+        assert!(conn.reader_watermark(PIPELINE).await.unwrap().is_none());
+
+        let watermark = CommitterWatermark::new_for_testing(PIPELINE, 10);
+        conn.set_committer_watermark(&watermark).await.unwrap();
+
+        assert!(conn.set_reader_watermark(PIPELINE, 5).await.unwrap());
+        assert!(!conn.set_reader_watermark(PIPELINE, 3).await.unwrap());
+        assert!(conn.set_pruner_watermark(PIPELINE, 2).await.unwrap());
+
+        let reader = conn.reader_watermark(PIPELINE).await.unwrap().unwrap();
+        assert_eq!(reader.checkpoint_hi_inclusive, 10);
+        assert_eq!(reader.reader_lo, 5);
+
+        let delay = Duration::from_secs(60);
+        let pruner = conn
+            .pruner_watermark(PIPELINE, delay)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pruner.reader_lo, 5);
+        assert_eq!(pruner.pruner_hi, 2);
+        assert!(pruner.wait_for().is_some_and(|wait_for| wait_for <= delay));
+    }
+
+    #[tokio::test]
+    async fn test_transaction_is_atomic() {
+        let dir = tempdir().unwrap();
+        let store = RocksStore::open(dir.path(), &["data"]).unwrap();
+        let data: DBMap<u64, u64> = store.map("data").unwrap();
+
+        // A failed transaction leaves no trace.
+        let result: anyhow::Result<()> = store
+            .transaction(|conn| {
+                async {
+                    let watermark = CommitterWatermark::new_for_testing(PIPELINE, 1);
+                    conn.set_committer_watermark(&watermark).await?;
+                    conn.insert(&data, [(1, 1)])?;
+                    anyhow::bail!("Failed");
+                }
+                .scope_boxed()
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert!(data.get(&1).unwrap().is_none());
+        let mut conn = store.connect().await.unwrap();
+        assert!(conn.committer_watermark(PIPELINE).await.unwrap().is_none());
+
+        // A successful transaction applies all its writes.
+        store
+            .transaction(|conn| {
+                async {
+                    let watermark = CommitterWatermark::new_for_testing(PIPELINE, 2);
+                    conn.set_committer_watermark(&watermark).await?;
+                    conn.insert(&data, [(2, 2)])?;
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(data.get(&2).unwrap(), Some(2));
+        let watermark = conn.committer_watermark(PIPELINE).await.unwrap().unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 2);
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use diesel::{
+    sql_types::{BigInt, Bytea, SmallInt},
+    QueryableByName,
+};
+use sui_indexer_alt_schema::objects::StoredCoinOwnerKind;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    TypeTag, SUI_FRAMEWORK_PACKAGE_ID,
+};
+
+use crate::{api::query::RawQuery, context::Context};
+
+use super::Error;
+
+/// The position of a coin in `coin_balance_buckets`, at some checkpoint. Coins are listed in
+/// ascending order of coin type, then in descending order of balance bucket, and then from most to
+/// least recently changed, using the object ID as a tie-breaker.
+#[derive(QueryableByName)]
+pub(super) struct CoinBucket {
+    #[diesel(sql_type = Bytea)]
+    object_id: Vec<u8>,
+    #[diesel(sql_type = Bytea)]
+    coin_type: Vec<u8>,
+    #[diesel(sql_type = SmallInt)]
+    coin_balance_bucket: i16,
+    #[diesel(sql_type = BigInt)]
+    cp_sequence_number: i64,
+}
+
+impl CoinBucket {
+    pub(super) fn object_id(&self) -> Result<ObjectID, Error> {
+        ObjectID::from_bytes(&self.object_id).map_err(|e| Error::Conversion(anyhow!(e)))
+    }
+}
+
+/// The position of the coin with ID `object_id` as of checkpoint `cp` (inclusive). If the coin has
+/// since been deleted or transferred to an owner that doesn't hold coins in the index (e.g. it was
+/// shared), its last known position is returned, so that it can still be used as a cursor.
+pub(super) async fn coin_bucket(
+    ctx: &Context,
+    cp: u64,
+    object_id: ObjectID,
+) -> Result<Option<CoinBucket>, Error> {
+    let query = RawQuery::new(
+        r#"
+        SELECT
+            c.object_id,
+            c.coin_type,
+            c.coin_balance_bucket,
+            c.cp_sequence_number
+        FROM
+            coin_balance_buckets c
+        WHERE
+            c.object_id = "#,
+    )
+    .bind::<Bytea, _>(object_id.to_vec())
+    .sql("\n        AND c.cp_sequence_number <= ")
+    .bind::<BigInt, _>(cp as i64)
+    .sql(
+        r#"
+        AND c.coin_balance_bucket IS NOT NULL
+        ORDER BY
+            c.cp_sequence_number DESC
+        LIMIT 1"#,
+    );
+
+    let mut rows: Vec<CoinBucket> = query.results(ctx).await?;
+    Ok(rows.pop())
+}
+
+/// Fetch the positions of up to `limit` coins owned by `owner` as of checkpoint `cp` (inclusive),
+/// optionally restricted to coins of type `coin_type`, strictly after the position `after`.
+///
+/// A coin is owned by an address at a checkpoint if the latest row for it in
+/// `coin_balance_buckets` at or before that checkpoint names the address as its owner: Rows are
+/// written whenever a coin's owner or balance bucket changes, and when it is deleted or wrapped (in
+/// which case the owner is recorded as `NULL`).
+pub(super) async fn owned_coins(
+    ctx: &Context,
+    cp: u64,
+    owner: SuiAddress,
+    coin_type: Option<&TypeTag>,
+    after: Option<&CoinBucket>,
+    limit: usize,
+) -> Result<Vec<CoinBucket>, Error> {
+    let mut query = RawQuery::new(
+        r#"
+        SELECT
+            c.object_id,
+            c.coin_type,
+            c.coin_balance_bucket,
+            c.cp_sequence_number
+        FROM
+            coin_balance_buckets c
+        WHERE
+            c.owner_kind = "#,
+    )
+    .bind::<SmallInt, _>(StoredCoinOwnerKind::Fastpath as i16)
+    .sql("\n        AND c.owner_id = ")
+    .bind::<Bytea, _>(owner.to_vec())
+    .sql("\n        AND c.cp_sequence_number <= ")
+    .bind::<BigInt, _>(cp as i64)
+    .sql(
+        r#"
+        AND NOT EXISTS (
+            SELECT 1
+            FROM coin_balance_buckets n
+            WHERE n.object_id = c.object_id
+            AND n.cp_sequence_number > c.cp_sequence_number
+            AND n.cp_sequence_number <= "#,
+    )
+    .bind::<BigInt, _>(cp as i64)
+    .sql("\n        )");
+
+    if let Some(coin_type) = coin_type {
+        query = query
+            .sql("\n        AND c.coin_type = ")
+            .bind::<Bytea, _>(bcs::to_bytes(coin_type)?);
+    }
+
+    if let Some(CoinBucket {
+        object_id,
+        coin_type,
+        coin_balance_bucket: bucket,
+        cp_sequence_number: seq,
+    }) = after
+    {
+        let eq_ty = || RawQuery::new("c.coin_type = ").bind::<Bytea, _>(coin_type.clone());
+        let eq_bucket =
+            || RawQuery::new(" AND c.coin_balance_bucket = ").bind::<SmallInt, _>(*bucket);
+        let eq_seq = || RawQuery::new(" AND c.cp_sequence_number = ").bind::<BigInt, _>(*seq);
+
+        query = query
+            .sql("\n        AND (\n            c.coin_type > ")
+            .bind::<Bytea, _>(coin_type.clone())
+            .sql("\n        OR  ")
+            .append(eq_ty())
+            .sql(" AND c.coin_balance_bucket < ")
+            .bind::<SmallInt, _>(*bucket)
+            .sql("\n        OR  ")
+            .append(eq_ty())
+            .append(eq_bucket())
+            .sql(" AND c.cp_sequence_number < ")
+            .bind::<BigInt, _>(*seq)
+            .sql("\n        OR  ")
+            .append(eq_ty())
+            .append(eq_bucket())
+            .append(eq_seq())
+            .sql("\n            AND c.object_id > ")
+            .bind::<Bytea, _>(object_id.clone())
+            .sql("\n        )");
+    }
+
+    query = query.sql(
+        r#"
+        ORDER BY
+            c.coin_type,
+            c.coin_balance_bucket DESC,
+            c.cp_sequence_number DESC,
+            c.object_id"#,
+    );
+
+    let query = query
+        .sql("\n        LIMIT ")
+        .bind::<BigInt, _>(limit as i64);
+
+    Ok(query.results(ctx).await?)
+}
+
+/// Find the ID of the live `0x2::coin::CoinMetadata` object for coins of type `coin_type`, as of
+/// checkpoint `cp` (inclusive), if there is one.
+pub(super) async fn coin_metadata_id(
+    ctx: &Context,
+    cp: u64,
+    coin_type: &TypeTag,
+) -> Result<Option<ObjectID>, Error> {
+    #[derive(QueryableByName)]
+    struct ObjectIdRow {
+        #[diesel(sql_type = Bytea)]
+        object_id: Vec<u8>,
+    }
+
+    let query = RawQuery::new(
+        r#"
+        SELECT
+            o.object_id
+        FROM
+            obj_info o
+        WHERE
+            o.package = "#,
+    )
+    .bind::<Bytea, _>(SUI_FRAMEWORK_PACKAGE_ID.to_vec())
+    .sql(
+        r#"
+        AND o.module = 'coin'
+        AND o.name = 'CoinMetadata'
+        AND o.instantiation = "#,
+    )
+    // Type parameters are stored as a BCS-serialized vector of type tags.
+    .bind::<Bytea, _>(bcs::to_bytes([coin_type].as_slice())?)
+    .sql("\n        AND o.cp_sequence_number <= ")
+    .bind::<BigInt, _>(cp as i64)
+    .sql(
+        r#"
+        AND NOT EXISTS (
+            SELECT 1
+            FROM obj_info n
+            WHERE n.object_id = o.object_id
+            AND n.cp_sequence_number > o.cp_sequence_number
+            AND n.cp_sequence_number <= "#,
+    )
+    .bind::<BigInt, _>(cp as i64)
+    .sql(
+        r#"
+        )
+        LIMIT 1"#,
+    );
+
+    let rows: Vec<ObjectIdRow> = query.results(ctx).await?;
+
+    rows.into_iter()
+        .next()
+        .map(|row| ObjectID::from_bytes(&row.object_id).map_err(|e| Error::Conversion(anyhow!(e))))
+        .transpose()
+}
@@ -0,0 +1,400 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::anyhow;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sui_json_rpc_types::{Balance, Coin, CoinPage, Page, SuiCoinMetadata};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    gas_coin::GAS,
+    object::Object,
+    parse_sui_type_tag, TypeTag,
+};
+
+use crate::{
+    context::Context,
+    data::{
+        objects::{CheckpointedObjVersionKey, VersionedObjectKey},
+        reader::ReadError,
+    },
+    error::{internal_error, invalid_params},
+    paginate::page_limit,
+};
+
+use super::{query::checkpoint_watermark, rpc_module::RpcModule};
+
+mod buckets;
+
+/// The pipelines whose tables are read to serve coin and balance queries. All reads are bounded by
+/// the checkpoint that all of these pipelines have reached, so that coin listings, balances and
+/// the contents of the coin objects are consistent with each other.
+const PIPELINES: &[&str] = &[
+    "coin_balance_buckets",
+    "obj_info",
+    "obj_versions",
+    "kv_objects",
+];
+
+/// The number of coins that are loaded at a time to serve a balance query. Balances are calculated
+/// by loading every coin, a page at a time, so that owners with many coins don't require them all
+/// to be held in memory at once.
+const BALANCE_PAGE_SIZE: usize = 1_000;
+
+#[open_rpc(namespace = "suix", tag = "Coins API")]
+#[rpc(server, namespace = "suix")]
+trait CoinsApi {
+    /// Return `Coin<coin_type>` objects owned by an address, in descending order of balance (with
+    /// coins that have recently changed ranked higher amongst coins with similar balances).
+    #[method(name = "getCoins")]
+    async fn get_coins(
+        &self,
+        /// The owner's Sui address.
+        owner: SuiAddress,
+        /// Optional type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC),
+        /// defaults to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// An optional paging cursor. If provided, the query will start from the next coin after
+        /// the specified cursor.
+        cursor: Option<ObjectID>,
+        /// Maximum number of items returned per page, defaults to 50 if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage>;
+
+    /// Return all coin objects owned by an address, grouped by coin type, and in descending order
+    /// of balance within each type.
+    #[method(name = "getAllCoins")]
+    async fn get_all_coins(
+        &self,
+        /// The owner's Sui address.
+        owner: SuiAddress,
+        /// An optional paging cursor. If provided, the query will start from the next coin after
+        /// the specified cursor.
+        cursor: Option<ObjectID>,
+        /// Maximum number of items returned per page, defaults to 50 if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage>;
+
+    /// Return the total coin balance for one coin type, owned by an address.
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        /// The owner's Sui address.
+        owner: SuiAddress,
+        /// Optional type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC),
+        /// defaults to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance>;
+
+    /// Return the total coin balance for each coin type owned by an address.
+    #[method(name = "getAllBalances")]
+    async fn get_all_balances(
+        &self,
+        /// The owner's Sui address.
+        owner: SuiAddress,
+    ) -> RpcResult<Vec<Balance>>;
+
+    /// Return metadata (e.g., symbol, decimals) for a coin. Note that if the coin's metadata was
+    /// wrapped in the transaction that published its marker type, or the latest version of the
+    /// metadata object is wrapped or deleted, it will not be found.
+    #[method(name = "getCoinMetadata")]
+    async fn get_coin_metadata(
+        &self,
+        /// Type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC).
+        coin_type: String,
+    ) -> RpcResult<Option<SuiCoinMetadata>>;
+}
+
+pub(crate) struct Coins(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Invalid coin type {0:?}: {1}")]
+    BadType(String, anyhow::Error),
+
+    #[error("Coin not found: {0}")]
+    CursorNotFound(ObjectID),
+
+    #[error("Coins have not been indexed yet")]
+    NotIndexed,
+
+    #[error("Expected version of object {0} to be available")]
+    Missing(ObjectID),
+
+    #[error("Error converting to response: {0}")]
+    Conversion(anyhow::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] bcs::Error),
+
+    #[error(transparent)]
+    Read(#[from] Arc<ReadError>),
+}
+
+#[async_trait::async_trait]
+impl CoinsApiServer for Coins {
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage> {
+        let Self(ctx) = self;
+        let coin_type = coin_type_or_gas(coin_type).map_err(invalid_params)?;
+        coin_page(ctx, owner, Some(coin_type), cursor, limit).await
+    }
+
+    async fn get_all_coins(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage> {
+        let Self(ctx) = self;
+        coin_page(ctx, owner, None, cursor, limit).await
+    }
+
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance> {
+        let Self(ctx) = self;
+        let coin_type = coin_type_or_gas(coin_type).map_err(invalid_params)?;
+        let coin_type_name = coin_type.to_canonical_string(/* with_prefix */ true);
+
+        let cp = watermark(ctx).await.map_err(internal_error)?;
+        let mut balances = balances(ctx, cp, owner, Some(&coin_type))
+            .await
+            .map_err(internal_error)?;
+
+        Ok(balances
+            .remove(&coin_type_name)
+            .unwrap_or_else(|| Balance::zero(coin_type_name)))
+    }
+
+    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
+        let Self(ctx) = self;
+
+        let cp = watermark(ctx).await.map_err(internal_error)?;
+        let balances = balances(ctx, cp, owner, None)
+            .await
+            .map_err(internal_error)?;
+
+        Ok(balances.into_values().collect())
+    }
+
+    async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<Option<SuiCoinMetadata>> {
+        let Self(ctx) = self;
+        let coin_type = parse_sui_type_tag(&coin_type)
+            .map_err(|e| invalid_params(Error::BadType(coin_type, e)))?;
+
+        let cp = watermark(ctx).await.map_err(internal_error)?;
+        let Some(id) = buckets::coin_metadata_id(ctx, cp, &coin_type)
+            .await
+            .map_err(internal_error)?
+        else {
+            return Ok(None);
+        };
+
+        let Some(object) = objects_at(ctx, cp, &[id])
+            .await
+            .map_err(internal_error)?
+            .pop()
+        else {
+            return Err(internal_error(Error::Missing(id)));
+        };
+
+        let metadata = SuiCoinMetadata::try_from(object)
+            .map_err(|e| internal_error(Error::Conversion(e.into())))?;
+
+        Ok(Some(metadata))
+    }
+}
+
+impl RpcModule for Coins {
+    fn schema(&self) -> Module {
+        CoinsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Parse `coin_type` as a type tag, defaulting to SUI if it is not provided.
+fn coin_type_or_gas(coin_type: Option<String>) -> Result<TypeTag, Error> {
+    let Some(coin_type) = coin_type else {
+        return Ok(GAS::type_tag());
+    };
+
+    parse_sui_type_tag(&coin_type).map_err(|e| Error::BadType(coin_type, e))
+}
+
+/// The checkpoint that coin and balance queries are served at.
+async fn watermark(ctx: &Context) -> Result<u64, Error> {
+    checkpoint_watermark(ctx, PIPELINES)
+        .await?
+        .ok_or(Error::NotIndexed)
+}
+
+/// Fetch a page of coins owned by `owner` (optionally restricted to coins of type `coin_type`),
+/// starting after the coin with ID `cursor`.
+async fn coin_page(
+    ctx: &Context,
+    owner: SuiAddress,
+    coin_type: Option<TypeTag>,
+    cursor: Option<ObjectID>,
+    limit: Option<usize>,
+) -> RpcResult<CoinPage> {
+    let limit = page_limit(limit);
+    let cp = watermark(ctx).await.map_err(internal_error)?;
+
+    let after = if let Some(id) = cursor {
+        let Some(bucket) = buckets::coin_bucket(ctx, cp, id)
+            .await
+            .map_err(internal_error)?
+        else {
+            return Err(invalid_params(Error::CursorNotFound(id)));
+        };
+
+        Some(bucket)
+    } else {
+        None
+    };
+
+    let mut coins = buckets::owned_coins(
+        ctx,
+        cp,
+        owner,
+        coin_type.as_ref(),
+        after.as_ref(),
+        limit + 1,
+    )
+    .await
+    .map_err(internal_error)?;
+
+    let has_next_page = coins.len() > limit;
+    coins.truncate(limit);
+
+    let ids = coins
+        .iter()
+        .map(|c| c.object_id())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(internal_error)?;
+
+    let data = objects_at(ctx, cp, &ids)
+        .await
+        .map_err(internal_error)?
+        .into_iter()
+        .map(coin)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(internal_error)?;
+
+    Ok(Page {
+        data,
+        next_cursor: ids.last().copied(),
+        has_next_page,
+    })
+}
+
+/// Sum the balances of all the coins owned by `owner` at checkpoint `cp` (optionally restricted to
+/// coins of type `coin_type`), grouped by coin type. Balances are calculated from the contents of
+/// each coin, because the index only records the order of magnitude of their balances. Coins are
+/// loaded [BALANCE_PAGE_SIZE] at a time, all as of `cp`.
+async fn balances(
+    ctx: &Context,
+    cp: u64,
+    owner: SuiAddress,
+    coin_type: Option<&TypeTag>,
+) -> Result<BTreeMap<String, Balance>, Error> {
+    let mut balances = BTreeMap::new();
+    let mut after = None;
+
+    loop {
+        let mut coins =
+            buckets::owned_coins(ctx, cp, owner, coin_type, after.as_ref(), BALANCE_PAGE_SIZE)
+                .await?;
+
+        let ids = coins
+            .iter()
+            .map(|c| c.object_id())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for object in objects_at(ctx, cp, &ids).await? {
+            let Coin {
+                coin_type, balance, ..
+            } = coin(object)?;
+
+            let entry = balances
+                .entry(coin_type.clone())
+                .or_insert_with(|| Balance::zero(coin_type));
+
+            entry.coin_object_count += 1;
+            entry.total_balance += balance as u128;
+        }
+
+        if coins.len() < BALANCE_PAGE_SIZE {
+            break;
+        }
+
+        after = coins.pop();
+    }
+
+    Ok(balances)
+}
+
+/// Load the contents of the objects with IDs `ids` as of checkpoint `cp` (inclusive), in the same
+/// order.
+async fn objects_at(ctx: &Context, cp: u64, ids: &[ObjectID]) -> Result<Vec<Object>, Error> {
+    let loader = ctx.loader();
+
+    let versions = loader
+        .load_many(ids.iter().map(|id| CheckpointedObjVersionKey(*id, cp)))
+        .await?;
+
+    let keys = ids
+        .iter()
+        .map(|id| {
+            let stored = versions
+                .get(&CheckpointedObjVersionKey(*id, cp))
+                .ok_or(Error::Missing(*id))?;
+            Ok(VersionedObjectKey(*id, stored.object_version as u64))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let objects = loader.load_many(keys.iter().copied()).await?;
+
+    keys.iter()
+        .map(|key| {
+            let serialized = objects
+                .get(key)
+                .and_then(|stored| stored.serialized_object.as_ref())
+                .ok_or(Error::Missing(key.0))?;
+            Ok(bcs::from_bytes(serialized)?)
+        })
+        .collect()
+}
+
+/// Convert a coin object into its response format.
+fn coin(object: Object) -> Result<Coin, Error> {
+    let (Some(coin_type), Some(coin)) = (object.coin_type_maybe(), object.as_coin_maybe()) else {
+        return Err(Error::Conversion(anyhow!(
+            "Object {} is not a coin",
+            object.id()
+        )));
+    };
+
+    Ok(Coin {
+        coin_type: coin_type.to_canonical_string(/* with_prefix */ true),
+        coin_object_id: object.id(),
+        version: object.version(),
+        digest: object.digest(),
+        balance: coin.balance.value(),
+        previous_transaction: object.previous_transaction,
+    })
+}
@@ -0,0 +1,112 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::sql_types::{Bytea, Text};
+use sui_json_rpc_types::EventFilter;
+use sui_types::{base_types::ObjectID, digests::TransactionDigest, event::Event};
+
+use crate::api::query::RawQuery;
+
+use super::Error;
+
+/// Where to find the transactions that emitted events matching an [EventFilter].
+pub(super) enum EvSelection {
+    /// Transactions that have a row in `table` satisfying `condition`.
+    Index {
+        table: &'static str,
+        condition: RawQuery,
+    },
+
+    /// Only the transaction with this digest.
+    Transaction(TransactionDigest),
+}
+
+/// Translate `filter` into a selection over the event index tables. The index tables only record
+/// which transactions emitted matching events, so the events themselves need to be filtered again
+/// with [matches] once they have been fetched. Filters on time ranges, and combinations of
+/// filters, are not supported.
+pub(super) fn ev_selection(filter: &EventFilter) -> Result<EvSelection, Error> {
+    use EvSelection as S;
+    use EventFilter as F;
+
+    Ok(match filter {
+        F::All([]) => S::Index {
+            table: "ev_struct_inst",
+            condition: RawQuery::new("TRUE"),
+        },
+
+        F::Sender(sender) => S::Index {
+            table: "ev_struct_inst",
+            condition: RawQuery::new("sender = ").bind::<Bytea, _>(sender.to_vec()),
+        },
+
+        F::Transaction(digest) => S::Transaction(*digest),
+
+        F::MoveModule { package, module } => S::Index {
+            table: "ev_emit_mod",
+            condition: RawQuery::new("package = ")
+                .bind::<Bytea, _>(package.to_vec())
+                .sql(" AND module = ")
+                .bind::<Text, _>(module.to_string()),
+        },
+
+        F::MoveEventModule { package, module } => S::Index {
+            table: "ev_struct_inst",
+            condition: RawQuery::new("package = ")
+                .bind::<Bytea, _>(package.to_vec())
+                .sql(" AND module = ")
+                .bind::<Text, _>(module.to_string()),
+        },
+
+        F::MoveEventType(tag) => S::Index {
+            table: "ev_struct_inst",
+            condition: RawQuery::new("package = ")
+                .bind::<Bytea, _>(tag.address.to_vec())
+                .sql(" AND module = ")
+                .bind::<Text, _>(tag.module.to_string())
+                .sql(" AND name = ")
+                .bind::<Text, _>(tag.name.to_string())
+                .sql(" AND instantiation = ")
+                .bind::<Bytea, _>(bcs::to_bytes(&tag.type_params)?),
+        },
+
+        F::Any(_) => {
+            return Err(Error::InvalidFilter(
+                "Combining event filters with Any is not supported".to_owned(),
+            ));
+        }
+
+        F::TimeRange { .. } => {
+            return Err(Error::InvalidFilter(
+                "TimeRange filter is not supported".to_owned(),
+            ));
+        }
+    })
+}
+
+/// Whether `event` (emitted by the transaction with digest `digest`) matches `filter`. This mirrors
+/// the matching that the existing JSON-RPC implementation performs on `SuiEvent`s, but works on
+/// the raw event, so that events can be filtered before their contents are resolved.
+pub(super) fn matches(filter: &EventFilter, digest: &TransactionDigest, event: &Event) -> bool {
+    use EventFilter as F;
+
+    match filter {
+        F::All([]) => true,
+        F::Any(filters) => filters.iter().any(|f| matches(f, digest, event)),
+        F::Sender(sender) => &event.sender == sender,
+        F::Transaction(d) => d == digest,
+
+        F::MoveModule { package, module } => {
+            &event.package_id == package && &event.transaction_module == module
+        }
+
+        F::MoveEventModule { package, module } => {
+            &ObjectID::from(event.type_.address) == package && &event.type_.module == module
+        }
+
+        F::MoveEventType(tag) => &event.type_ == tag,
+
+        // Events are not filtered by time, because the filter is rejected by [ev_selection].
+        F::TimeRange { .. } => false,
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use futures::future;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sui_json_rpc_types::{EventFilter, EventPage, Page};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    digests::TransactionDigest,
+    event::{Event, EventID},
+};
+
+use crate::{
+    context::Context,
+    data::reader::ReadError,
+    error::{internal_error, invalid_params},
+    paginate::page_limit,
+};
+
+use super::{
+    query::{tx_sequence_numbers, tx_watermark},
+    rpc_module::RpcModule,
+    transactions::{self, sui_event, tx_digests, tx_sequence_number},
+};
+
+mod filter;
+
+#[open_rpc(namespace = "suix", tag = "Events API")]
+#[rpc(server, namespace = "suix")]
+trait QueryEventsApi {
+    /// Query events based on their properties (sender, emitting module, type, etc). Returns a
+    /// paginated list of events.
+    ///
+    /// If a cursor is provided, the query will start from the event after the one pointed to by
+    /// this cursor, otherwise pagination starts from the first event that meets the query
+    /// criteria.
+    ///
+    /// The definition of "first" event is changed by the `descending_order` parameter, which is
+    /// optional, and defaults to false, meaning that the oldest event is shown first.
+    #[method(name = "queryEvents")]
+    async fn query_events(
+        &self,
+        /// The event query criteria.
+        query: EventFilter,
+        /// Cursor to start paginating from.
+        cursor: Option<EventID>,
+        /// Maximum number of events to return per page, defaults to 50 if not specified.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (oldest first).
+        descending_order: Option<bool>,
+    ) -> RpcResult<EventPage>;
+}
+
+pub(crate) struct QueryEvents(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+
+    #[error("Transaction not found: {0}")]
+    TransactionNotFound(TransactionDigest),
+
+    #[error("Deserialization error: {0}")]
+    Deserialization(#[from] bcs::Error),
+
+    #[error(transparent)]
+    Transaction(#[from] transactions::Error),
+
+    #[error(transparent)]
+    Read(#[from] Arc<ReadError>),
+}
+
+#[async_trait::async_trait]
+impl QueryEventsApiServer for QueryEvents {
+    async fn query_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<EventPage> {
+        let Self(ctx) = self;
+        let limit = page_limit(limit);
+        let descending = descending_order.unwrap_or(false);
+
+        let selection = filter::ev_selection(&query).map_err(invalid_params)?;
+
+        // Events are indexed by the transaction that emitted them, so the transaction pointed to
+        // by the cursor is included in the range of transactions to fetch: It may have events
+        // after the cursor that still need to be returned.
+        let (mut lo, mut hi) = (None, None);
+        let cursor = if let Some(EventID {
+            tx_digest,
+            event_seq,
+        }) = cursor
+        {
+            let Some(seq) = tx_sequence_number(ctx, tx_digest)
+                .await
+                .map_err(internal_error)?
+            else {
+                return Err(invalid_params(Error::TransactionNotFound(tx_digest)));
+            };
+
+            if descending {
+                hi = Some(seq + 1);
+            } else {
+                lo = Some(seq);
+            }
+
+            Some((seq, event_seq))
+        } else {
+            None
+        };
+
+        // Every transaction returned from the index emitted at least one matching event, except
+        // possibly the cursor's transaction (if all its matching events are at or before the
+        // cursor), so fetching two more transactions than the limit is enough to tell whether
+        // there is another page.
+        // Only look for events from transactions that every pipeline involved in the query has
+        // indexed, so that the index, digest and contents of each transaction are all available.
+        let table = match &selection {
+            filter::EvSelection::Index { table, .. } => *table,
+            filter::EvSelection::Transaction(_) => "tx_digests",
+        };
+
+        let Some(tx_hi) = tx_watermark(ctx, &[table, "tx_digests", "kv_transactions"])
+            .await
+            .map_err(internal_error)?
+        else {
+            return Ok(Page::empty());
+        };
+
+        let hi = Some(hi.map_or(tx_hi, |hi| hi.min(tx_hi)));
+
+        let seqs = match selection {
+            filter::EvSelection::Index { table, condition } => {
+                tx_sequence_numbers(ctx, table, condition, lo, hi, limit + 2, descending)
+                    .await
+                    .map_err(internal_error)?
+            }
+
+            filter::EvSelection::Transaction(digest) => {
+                match tx_sequence_number(ctx, digest)
+                    .await
+                    .map_err(internal_error)?
+                {
+                    Some(seq)
+                        if lo.map_or(true, |lo| lo <= seq) && hi.map_or(true, |hi| seq < hi) =>
+                    {
+                        vec![seq]
+                    }
+                    _ => vec![],
+                }
+            }
+        };
+
+        let mut events = matching_events(ctx, &query, &seqs, cursor, descending, limit + 1)
+            .await
+            .map_err(internal_error)?;
+
+        let has_next_page = events.len() > limit;
+        events.truncate(limit);
+
+        let data =
+            future::try_join_all(events.into_iter().map(|(digest, ix, event, timestamp_ms)| {
+                sui_event(ctx, digest, ix, event, timestamp_ms)
+            }))
+            .await
+            .map_err(internal_error)?;
+
+        let next_cursor = data.last().map(|event| event.id);
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+}
+
+impl RpcModule for QueryEvents {
+    fn schema(&self) -> Module {
+        QueryEventsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Load the events emitted by the transactions with sequence numbers `seqs` (in the order they
+/// are given), and return up to `limit` of them that match `filter` and come strictly after
+/// `cursor` (a transaction sequence number and event sequence number) in the order of
+/// pagination. Each event is returned with its transaction's digest, its position in the
+/// transaction, and the transaction's timestamp.
+async fn matching_events(
+    ctx: &Context,
+    filter: &EventFilter,
+    seqs: &[u64],
+    cursor: Option<(u64, u64)>,
+    descending: bool,
+    limit: usize,
+) -> Result<Vec<(TransactionDigest, u64, Event, u64)>, Error> {
+    let digests = tx_digests(ctx, seqs).await?;
+    let transactions = ctx.loader().load_many(digests.iter().copied()).await?;
+
+    let mut results = vec![];
+    for (seq, digest) in seqs.iter().zip(digests) {
+        let tx = transactions
+            .get(&digest)
+            .ok_or(transactions::Error::NotFound(digest))?;
+
+        let events: Vec<Event> = bcs::from_bytes(&tx.events)?;
+        let mut events: Vec<_> = events
+            .into_iter()
+            .enumerate()
+            .map(|(ix, event)| (ix as u64, event))
+            .collect();
+
+        if descending {
+            events.reverse();
+        }
+
+        for (ix, event) in events {
+            let after_cursor = match cursor {
+                Some((c_seq, c_ix)) if c_seq == *seq => {
+                    if descending {
+                        ix < c_ix
+                    } else {
+                        ix > c_ix
+                    }
+                }
+                _ => true,
+            };
+
+            if !after_cursor || !filter::matches(filter, &digest, &event) {
+                continue;
+            }
+
+            results.push((digest, ix, event, tx.timestamp_ms as u64));
+            if results.len() >= limit {
+                return Ok(results);
+            }
+        }
+    }
+
+    Ok(results)
+}
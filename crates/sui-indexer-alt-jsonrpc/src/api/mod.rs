@@ -1,6 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod coins;
+pub(crate) mod events;
 pub(crate) mod governance;
+pub(crate) mod objects;
+pub(crate) mod query;
 pub(crate) mod rpc_module;
 pub(crate) mod transactions;
@@ -0,0 +1,137 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use diesel::{
+    sql_types::{BigInt, Bytea, SmallInt, Text},
+    QueryableByName,
+};
+use sui_indexer_alt_schema::objects::StoredOwnerKind;
+use sui_json_rpc_types::SuiObjectDataFilter;
+use sui_types::base_types::ObjectID;
+
+use crate::{api::query::RawQuery, context::Context};
+
+use super::Error;
+
+/// Translate `filter` into a SQL condition over the type columns of `obj_info` (aliased as `o`).
+///
+/// Only filters on an object's type, and combinations of them, can be translated. Struct type
+/// filters without type parameters match all instantiations of the type.
+pub(super) fn type_condition(filter: &SuiObjectDataFilter) -> Result<RawQuery, Error> {
+    use SuiObjectDataFilter as F;
+
+    Ok(match filter {
+        F::MatchAll(filters) => combine(filters, " AND ", "TRUE")?,
+        F::MatchAny(filters) => combine(filters, " OR ", "FALSE")?,
+        F::MatchNone(filters) => RawQuery::new("NOT ").append(combine(filters, " OR ", "FALSE")?),
+
+        F::Package(package) => RawQuery::new("o.package = ").bind::<Bytea, _>(package.to_vec()),
+
+        F::MoveModule { package, module } => RawQuery::new("(o.package = ")
+            .bind::<Bytea, _>(package.to_vec())
+            .sql(" AND o.module = ")
+            .bind::<Text, _>(module.to_string())
+            .sql(")"),
+
+        F::StructType(tag) => {
+            let mut condition = RawQuery::new("(o.package = ")
+                .bind::<Bytea, _>(tag.address.to_vec())
+                .sql(" AND o.module = ")
+                .bind::<Text, _>(tag.module.to_string())
+                .sql(" AND o.name = ")
+                .bind::<Text, _>(tag.name.to_string());
+
+            if !tag.type_params.is_empty() {
+                condition = condition
+                    .sql(" AND o.instantiation = ")
+                    .bind::<Bytea, _>(bcs::to_bytes(&tag.type_params)?);
+            }
+
+            condition.sql(")")
+        }
+
+        F::AddressOwner(_)
+        | F::ObjectOwner(_)
+        | F::ObjectId(_)
+        | F::ObjectIds(_)
+        | F::Version(_) => return Err(Error::UnsupportedFilter(filter.clone())),
+    })
+}
+
+/// Fetch the IDs of up to `limit` live objects whose owner is `owner_id` (with kind `owner_kind`),
+/// and whose type matches `type_condition` (see [type_condition]), in ascending order of object
+/// ID, strictly after `cursor`.
+///
+/// An object is live for an owner if the latest row for it in `obj_info` names that owner: Rows
+/// are only written when an object is created, changes owner, or is deleted or wrapped (in which
+/// case its owner is recorded as `NULL`).
+pub(super) async fn owned_object_ids(
+    ctx: &Context,
+    owner_kind: StoredOwnerKind,
+    owner_id: &[u8],
+    type_condition: Option<RawQuery>,
+    cursor: Option<ObjectID>,
+    limit: usize,
+) -> Result<Vec<ObjectID>, Error> {
+    #[derive(QueryableByName)]
+    struct ObjectIdRow {
+        #[diesel(sql_type = Bytea)]
+        object_id: Vec<u8>,
+    }
+
+    let mut query = RawQuery::new(
+        r#"
+        SELECT
+            o.object_id
+        FROM
+            obj_info o
+        WHERE
+            o.owner_kind = "#,
+    )
+    .bind::<SmallInt, _>(owner_kind as i16)
+    .sql("\n        AND o.owner_id = ")
+    .bind::<Bytea, _>(owner_id.to_vec())
+    .sql(
+        r#"
+        AND NOT EXISTS (
+            SELECT 1
+            FROM obj_info n
+            WHERE n.object_id = o.object_id
+            AND n.cp_sequence_number > o.cp_sequence_number
+        )"#,
+    );
+
+    if let Some(condition) = type_condition {
+        query = query.sql("\n        AND ").append(condition);
+    }
+
+    if let Some(cursor) = cursor {
+        query = query
+            .sql("\n        AND o.object_id > ")
+            .bind::<Bytea, _>(cursor.to_vec());
+    }
+
+    let query = query
+        .sql("\n        ORDER BY o.object_id\n        LIMIT ")
+        .bind::<BigInt, _>(limit as i64);
+
+    let rows: Vec<ObjectIdRow> = query.results(ctx).await?;
+
+    rows.into_iter()
+        .map(|row| ObjectID::from_bytes(&row.object_id).map_err(|e| Error::Conversion(anyhow!(e))))
+        .collect()
+}
+
+/// Join the conditions for `filters` with the binary operator `op`, or return `empty` if there
+/// are no filters to combine.
+fn combine(filters: &[SuiObjectDataFilter], op: &str, empty: &str) -> Result<RawQuery, Error> {
+    let conditions = filters
+        .iter()
+        .map(type_condition)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(RawQuery::new("(")
+        .append(RawQuery::join(conditions, op, empty))
+        .sql(")"))
+}
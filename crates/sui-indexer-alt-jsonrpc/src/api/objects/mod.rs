@@ -0,0 +1,341 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use futures::future;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sui_indexer_alt_schema::objects::StoredOwnerKind;
+use sui_json_rpc_types::{
+    DynamicFieldPage, ObjectsPage, Page, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiPastObjectResponse,
+};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+
+use crate::{
+    context::Context,
+    data::{
+        objects::{LatestObjectKey, VersionedObjectKey},
+        reader::ReadError,
+    },
+    error::{internal_error, invalid_params},
+    paginate::{page_limit, QUERY_MAX_RESULT_LIMIT},
+};
+
+use super::rpc_module::RpcModule;
+
+mod filter;
+mod response;
+
+#[open_rpc(namespace = "sui", tag = "Objects API")]
+#[rpc(server, namespace = "sui")]
+trait ObjectsApi {
+    /// Return the object information for the latest version of an object.
+    #[method(name = "getObject")]
+    async fn get_object(
+        &self,
+        /// The ID of the queried object.
+        object_id: ObjectID,
+        /// Options for specifying the content to be returned.
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse>;
+
+    /// Return the object information for the latest versions of multiple objects.
+    #[method(name = "multiGetObjects")]
+    async fn multi_get_objects(
+        &self,
+        /// The IDs of the queried objects.
+        object_ids: Vec<ObjectID>,
+        /// Options for specifying the content to be returned.
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiObjectResponse>>;
+
+    /// Return the object information for a specific version of an object. The version may not be
+    /// available if it has been pruned.
+    #[method(name = "tryGetPastObject")]
+    async fn try_get_past_object(
+        &self,
+        /// The ID of the queried object.
+        object_id: ObjectID,
+        /// The version of the queried object.
+        version: SequenceNumber,
+        /// Options for specifying the content to be returned.
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiPastObjectResponse>;
+}
+
+#[open_rpc(namespace = "suix", tag = "Query Objects API")]
+#[rpc(server, namespace = "suix")]
+trait QueryObjectsApi {
+    /// Return the list of objects owned by an address, in ascending order of object ID. Objects
+    /// can be filtered by their type, but not by any other criteria.
+    #[method(name = "getOwnedObjects")]
+    async fn get_owned_objects(
+        &self,
+        /// The owner's Sui address.
+        address: SuiAddress,
+        /// The objects query criteria.
+        query: Option<SuiObjectResponseQuery>,
+        /// An optional paging cursor. If provided, the query will start from the next item after
+        /// the specified cursor.
+        cursor: Option<ObjectID>,
+        /// Maximum number of items returned per page, defaults to 50 if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage>;
+
+    /// Return the list of dynamic field objects owned by an object, in ascending order of field
+    /// object ID.
+    #[method(name = "getDynamicFields")]
+    async fn get_dynamic_fields(
+        &self,
+        /// The ID of the parent object.
+        parent_object_id: ObjectID,
+        /// An optional paging cursor. If provided, the query will start from the next item after
+        /// the specified cursor.
+        cursor: Option<ObjectID>,
+        /// Maximum number of items returned per page, defaults to 50 if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<DynamicFieldPage>;
+}
+
+pub(crate) struct Objects(pub Context);
+
+pub(crate) struct QueryObjects(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Requested {requested} objects, exceeding maximum of {QUERY_MAX_RESULT_LIMIT}")]
+    TooManyObjects { requested: usize },
+
+    #[error("Filter not supported, only filters on object type are allowed: {0:?}")]
+    UnsupportedFilter(SuiObjectDataFilter),
+
+    #[error("Expected live version of object {0} to be available")]
+    Missing(ObjectID),
+
+    #[error("Error converting to response: {0}")]
+    Conversion(anyhow::Error),
+
+    #[error("Error resolving type information: {0}")]
+    Resolution(anyhow::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] bcs::Error),
+
+    #[error(transparent)]
+    Read(#[from] Arc<ReadError>),
+}
+
+#[async_trait::async_trait]
+impl ObjectsApiServer for Objects {
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse> {
+        let Self(ctx) = self;
+        let options = options.unwrap_or_default();
+
+        let stored = ctx
+            .loader()
+            .load_one(LatestObjectKey(object_id))
+            .await
+            .map_err(internal_error)?;
+
+        response::latest_object(ctx, object_id, stored, &options)
+            .await
+            .map_err(internal_error)
+    }
+
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiObjectResponse>> {
+        let Self(ctx) = self;
+        let options = options.unwrap_or_default();
+
+        if object_ids.len() > QUERY_MAX_RESULT_LIMIT {
+            return Err(invalid_params(Error::TooManyObjects {
+                requested: object_ids.len(),
+            }));
+        }
+
+        let stored = ctx
+            .loader()
+            .load_many(object_ids.iter().copied().map(LatestObjectKey))
+            .await
+            .map_err(internal_error)?;
+
+        let responses = object_ids.into_iter().map(|id| {
+            let stored = stored.get(&LatestObjectKey(id)).cloned();
+            response::latest_object(ctx, id, stored, &options)
+        });
+
+        future::try_join_all(responses)
+            .await
+            .map_err(internal_error)
+    }
+
+    async fn try_get_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiPastObjectResponse> {
+        let Self(ctx) = self;
+        let options = options.unwrap_or_default();
+
+        if let Some(stored) = ctx
+            .loader()
+            .load_one(VersionedObjectKey(object_id, version.value()))
+            .await
+            .map_err(internal_error)?
+        {
+            return response::past_object(ctx, object_id, version, stored, &options)
+                .await
+                .map_err(internal_error);
+        }
+
+        // The version doesn't exist, find out whether that's because the object doesn't exist, or
+        // it never had this version.
+        let Some(latest) = ctx
+            .loader()
+            .load_one(LatestObjectKey(object_id))
+            .await
+            .map_err(internal_error)?
+        else {
+            return Ok(SuiPastObjectResponse::ObjectNotExists(object_id));
+        };
+
+        let latest_version = SequenceNumber::from_u64(latest.object_version as u64);
+        Ok(if version > latest_version {
+            SuiPastObjectResponse::VersionTooHigh {
+                object_id,
+                asked_version: version,
+                latest_version,
+            }
+        } else {
+            SuiPastObjectResponse::VersionNotFound(object_id, version)
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl QueryObjectsApiServer for QueryObjects {
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage> {
+        let Self(ctx) = self;
+        let SuiObjectResponseQuery { filter, options } = query.unwrap_or_default();
+        let options = options.unwrap_or_default();
+        let limit = page_limit(limit);
+
+        let type_condition = filter
+            .as_ref()
+            .map(filter::type_condition)
+            .transpose()
+            .map_err(invalid_params)?;
+
+        let mut ids = filter::owned_object_ids(
+            ctx,
+            StoredOwnerKind::Address,
+            address.as_ref(),
+            type_condition,
+            cursor,
+            limit + 1,
+        )
+        .await
+        .map_err(internal_error)?;
+
+        let has_next_page = ids.len() > limit;
+        ids.truncate(limit);
+        let next_cursor = ids.last().copied();
+
+        let objects = response::live_objects(ctx, &ids)
+            .await
+            .map_err(internal_error)?;
+
+        let data = future::try_join_all(objects.into_iter().map(|object| async {
+            let data = response::object_data(ctx, object, &options).await?;
+            Ok::<_, Error>(SuiObjectResponse::new_with_data(data))
+        }))
+        .await
+        .map_err(internal_error)?;
+
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_dynamic_fields(
+        &self,
+        parent_object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<DynamicFieldPage> {
+        let Self(ctx) = self;
+        let limit = page_limit(limit);
+
+        let mut ids = filter::owned_object_ids(
+            ctx,
+            StoredOwnerKind::Object,
+            parent_object_id.as_ref(),
+            None,
+            cursor,
+            limit + 1,
+        )
+        .await
+        .map_err(internal_error)?;
+
+        let has_next_page = ids.len() > limit;
+        ids.truncate(limit);
+        let next_cursor = ids.last().copied();
+
+        let fields = response::live_objects(ctx, &ids)
+            .await
+            .map_err(internal_error)?;
+
+        let data = future::try_join_all(
+            fields
+                .into_iter()
+                .map(|field| response::dynamic_field_info(ctx, field)),
+        )
+        .await
+        .map_err(internal_error)?;
+
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+}
+
+impl RpcModule for Objects {
+    fn schema(&self) -> Module {
+        ObjectsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl RpcModule for QueryObjects {
+    fn schema(&self) -> Module {
+        QueryObjectsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use move_core_types::{annotated_value::MoveTypeLayout, language_storage::TypeTag};
+use sui_indexer_alt_schema::objects::StoredObject;
+use sui_json_rpc_types::{
+    DynamicFieldInfo, SuiMoveValue, SuiObjectData, SuiObjectDataOptions, SuiObjectRef,
+    SuiObjectResponse, SuiPastObjectResponse,
+};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    digests::ObjectDigest,
+    dynamic_field::{self, visitor as DFV, DynamicFieldName},
+    error::SuiObjectResponseError,
+    object::{bounded_visitor::BoundedVisitor, Object},
+};
+
+use crate::{
+    context::Context,
+    data::objects::{LatestObjVersionKey, VersionedObjectKey},
+};
+
+use super::Error;
+
+/// Convert the latest version of an object, as read from `kv_objects`, into the response format,
+/// including the fields requested in the `options`. Objects that were never found, or that have
+/// been deleted or wrapped, are represented as error responses.
+pub(super) async fn latest_object(
+    ctx: &Context,
+    object_id: ObjectID,
+    stored: Option<StoredObject>,
+    options: &SuiObjectDataOptions,
+) -> Result<SuiObjectResponse, Error> {
+    let Some(stored) = stored else {
+        return Ok(SuiObjectResponse::new_with_error(
+            SuiObjectResponseError::NotExists { object_id },
+        ));
+    };
+
+    let Some(serialized) = &stored.serialized_object else {
+        return Ok(SuiObjectResponse::new_with_error(
+            SuiObjectResponseError::Deleted {
+                object_id,
+                version: SequenceNumber::from_u64(stored.object_version as u64),
+                digest: ObjectDigest::OBJECT_DIGEST_DELETED,
+            },
+        ));
+    };
+
+    let object: Object = bcs::from_bytes(serialized)?;
+    Ok(SuiObjectResponse::new_with_data(
+        object_data(ctx, object, options).await?,
+    ))
+}
+
+/// Convert an object at a specific version, as read from `kv_objects`, into the response format,
+/// including the fields requested in the `options`.
+pub(super) async fn past_object(
+    ctx: &Context,
+    object_id: ObjectID,
+    version: SequenceNumber,
+    stored: StoredObject,
+    options: &SuiObjectDataOptions,
+) -> Result<SuiPastObjectResponse, Error> {
+    let Some(serialized) = &stored.serialized_object else {
+        return Ok(SuiPastObjectResponse::ObjectDeleted(SuiObjectRef::from((
+            object_id,
+            version,
+            ObjectDigest::OBJECT_DIGEST_DELETED,
+        ))));
+    };
+
+    let object: Object = bcs::from_bytes(serialized)?;
+    Ok(SuiPastObjectResponse::VersionFound(
+        object_data(ctx, object, options).await?,
+    ))
+}
+
+/// Build the response for a single live object, including the fields requested in the `options`.
+/// Type information is only resolved if the object's contents were requested.
+///
+/// TODO: Support `showDisplay`, which is currently ignored.
+pub(super) async fn object_data(
+    ctx: &Context,
+    object: Object,
+    options: &SuiObjectDataOptions,
+) -> Result<SuiObjectData, Error> {
+    let layout = match object.struct_tag() {
+        Some(tag) if options.show_content || options.show_bcs => {
+            match ctx
+                .package_resolver()
+                .type_layout(TypeTag::Struct(Box::new(tag.clone())))
+                .await
+                .map_err(|e| Error::Resolution(e.into()))?
+            {
+                MoveTypeLayout::Struct(s) => Some(*s),
+                _ => {
+                    return Err(Error::Resolution(anyhow!(
+                        "Object {} has type {}, which is not a struct",
+                        object.id(),
+                        tag.to_canonical_string(/* with_prefix */ true),
+                    )));
+                }
+            }
+        }
+
+        _ => None,
+    };
+
+    SuiObjectData::try_from((
+        object.compute_object_reference(),
+        object,
+        layout,
+        options.clone(),
+    ))
+    .map_err(Error::Conversion)
+}
+
+/// Load the contents of the latest live versions of the objects with IDs `ids`, in the same
+/// order. The latest version of each object is looked up in `obj_versions`, before its contents
+/// are fetched from `kv_objects`.
+pub(super) async fn live_objects(ctx: &Context, ids: &[ObjectID]) -> Result<Vec<Object>, Error> {
+    let loader = ctx.loader();
+
+    let versions = loader
+        .load_many(ids.iter().copied().map(LatestObjVersionKey))
+        .await?;
+
+    let keys = ids
+        .iter()
+        .map(|id| {
+            let stored = versions
+                .get(&LatestObjVersionKey(*id))
+                .ok_or(Error::Missing(*id))?;
+            Ok(VersionedObjectKey(*id, stored.object_version as u64))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let objects = loader.load_many(keys.iter().copied()).await?;
+
+    keys.iter()
+        .map(|key| {
+            let serialized = objects
+                .get(key)
+                .and_then(|stored| stored.serialized_object.as_ref())
+                .ok_or(Error::Missing(key.0))?;
+            Ok(bcs::from_bytes(serialized)?)
+        })
+        .collect()
+}
+
+/// Describe the dynamic field represented by the `field` object (an instance of
+/// `0x2::dynamic_field::Field`). For dynamic object fields, the description refers to the object
+/// that the field wraps, rather than the field itself.
+pub(super) async fn dynamic_field_info(
+    ctx: &Context,
+    field: Object,
+) -> Result<DynamicFieldInfo, Error> {
+    let Some(move_object) = field.data.try_as_move() else {
+        return Err(Error::Conversion(anyhow!(
+            "Dynamic field {} is not a Move object",
+            field.id(),
+        )));
+    };
+
+    let type_: TypeTag = move_object.type_().clone().into();
+    let layout = ctx
+        .package_resolver()
+        .type_layout(type_)
+        .await
+        .map_err(|e| Error::Resolution(e.into()))?;
+
+    let parsed = DFV::FieldVisitor::deserialize(move_object.contents(), &layout)
+        .map_err(Error::Conversion)?;
+
+    let name_value = BoundedVisitor::deserialize_value(parsed.name_bytes, parsed.name_layout)
+        .map_err(Error::Conversion)?;
+
+    let name = DynamicFieldName {
+        type_: parsed.name_layout.into(),
+        value: SuiMoveValue::from(name_value).to_json_value(),
+    };
+
+    let bcs_name = parsed.name_bytes.to_owned();
+    let info = match parsed
+        .value_metadata()
+        .map_err(|e| Error::Conversion(e.into()))?
+    {
+        DFV::ValueMetadata::DynamicField(object_type) => dynamic_field::DynamicFieldInfo {
+            name,
+            bcs_name,
+            type_: parsed.kind,
+            object_type: object_type.to_canonical_string(/* with_prefix */ true),
+            object_id: field.id(),
+            version: field.version(),
+            digest: field.digest(),
+        },
+
+        DFV::ValueMetadata::DynamicObjectField(object_id) => {
+            let Some(object) = live_objects(ctx, &[object_id]).await?.pop() else {
+                return Err(Error::Missing(object_id));
+            };
+
+            let Some(object_type) = object.type_() else {
+                return Err(Error::Conversion(anyhow!(
+                    "Dynamic object field {object_id} is not a Move object",
+                )));
+            };
+
+            dynamic_field::DynamicFieldInfo {
+                name,
+                bcs_name,
+                type_: parsed.kind,
+                object_type: object_type.to_canonical_string(/* with_prefix */ true),
+                object_id,
+                version: object.version(),
+                digest: object.digest(),
+            }
+        }
+    };
+
+    Ok(info.into())
+}
@@ -0,0 +1,229 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for building the raw SQL queries that back filtered and paginated JSON-RPC methods.
+//! Queries are assembled from fragments of SQL and values, and the values are always passed to the
+//! database as bind parameters, rather than being interpolated into the query.
+
+use std::sync::Arc;
+
+use diesel::{
+    pg::Pg,
+    query_builder::{BoxedSqlQuery, SqlQuery},
+    serialize::ToSql,
+    sql_query,
+    sql_types::{Array, BigInt, HasSqlType, Nullable, Text},
+    QueryableByName,
+};
+
+use crate::{context::Context, data::reader::ReadError};
+
+type BoxedQuery = BoxedSqlQuery<'static, Pg, SqlQuery>;
+
+/// A raw SQL query, or a fragment of one. Fragments can be appended to each other, and placeholders
+/// for their bind parameters are only numbered once the whole query has been assembled.
+#[derive(Default)]
+pub(crate) struct RawQuery {
+    parts: Vec<Part>,
+}
+
+enum Part {
+    Sql(String),
+    Bind(Box<dyn FnOnce(BoxedQuery) -> BoxedQuery + Send>),
+}
+
+impl RawQuery {
+    pub(crate) fn new(sql: impl Into<String>) -> Self {
+        Self::default().sql(sql)
+    }
+
+    /// Append `sql` to the query.
+    pub(crate) fn sql(mut self, sql: impl Into<String>) -> Self {
+        self.parts.push(Part::Sql(sql.into()));
+        self
+    }
+
+    /// Append a placeholder to the query, for the bind parameter `value`, of SQL type `ST`.
+    pub(crate) fn bind<ST, V>(mut self, value: V) -> Self
+    where
+        Pg: HasSqlType<ST>,
+        ST: Send + 'static,
+        V: ToSql<ST, Pg> + Send + 'static,
+    {
+        self.parts
+            .push(Part::Bind(Box::new(move |q| q.bind::<ST, V>(value))));
+        self
+    }
+
+    /// Append the fragment `other` to the query.
+    pub(crate) fn append(mut self, other: RawQuery) -> Self {
+        self.parts.extend(other.parts);
+        self
+    }
+
+    /// Join `fragments` with the SQL `sep`, or return `empty` if there are no fragments to join.
+    pub(crate) fn join(
+        fragments: impl IntoIterator<Item = RawQuery>,
+        sep: &str,
+        empty: &str,
+    ) -> Self {
+        let mut joined: Option<RawQuery> = None;
+        for fragment in fragments {
+            joined = Some(match joined {
+                None => fragment,
+                Some(joined) => joined.sql(sep).append(fragment),
+            });
+        }
+
+        joined.unwrap_or_else(|| RawQuery::new(empty))
+    }
+
+    /// Run the query, and return all the rows it produces.
+    pub(crate) async fn results<U>(self, ctx: &Context) -> Result<Vec<U>, Arc<ReadError>>
+    where
+        U: QueryableByName<Pg> + Send + 'static,
+    {
+        let mut conn = ctx.reader().connect().await.map_err(Arc::new)?;
+        conn.results(self.into_boxed()).await.map_err(Arc::new)
+    }
+
+    fn into_boxed(self) -> BoxedQuery {
+        let mut query = sql_query("").into_boxed();
+        let mut binds = 0;
+        for part in self.parts {
+            query = match part {
+                Part::Sql(sql) => query.sql(sql),
+                Part::Bind(bind) => {
+                    binds += 1;
+                    bind(query.sql(format!("${binds}")))
+                }
+            };
+        }
+
+        query
+    }
+}
+
+/// Fetch up to `limit` distinct transaction sequence numbers from the rows of `table` that
+/// satisfy `condition`, bounded below by `lo` (inclusive) and above by `hi` (exclusive), in
+/// ascending order, or descending order if `descending` is true.
+pub(crate) async fn tx_sequence_numbers(
+    ctx: &Context,
+    table: &str,
+    condition: RawQuery,
+    lo: Option<u64>,
+    hi: Option<u64>,
+    limit: usize,
+    descending: bool,
+) -> Result<Vec<u64>, Arc<ReadError>> {
+    #[derive(QueryableByName)]
+    struct TxSequenceNumber {
+        #[diesel(sql_type = BigInt)]
+        tx_sequence_number: i64,
+    }
+
+    let mut query = RawQuery::new(format!(
+        r#"
+        SELECT DISTINCT
+            tx_sequence_number
+        FROM
+            {table}
+        WHERE
+            ("#
+    ))
+    .append(condition)
+    .sql(")");
+
+    if let Some(lo) = lo {
+        query = query
+            .sql("\n        AND tx_sequence_number >= ")
+            .bind::<BigInt, _>(lo as i64);
+    }
+
+    if let Some(hi) = hi {
+        query = query
+            .sql("\n        AND tx_sequence_number < ")
+            .bind::<BigInt, _>(hi as i64);
+    }
+
+    let order = if descending { "DESC" } else { "ASC" };
+    let query = query
+        .sql(format!(
+            "\n        ORDER BY tx_sequence_number {order}\n        LIMIT "
+        ))
+        .bind::<BigInt, _>(limit as i64);
+
+    let rows: Vec<TxSequenceNumber> = query.results(ctx).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| row.tx_sequence_number as u64)
+        .collect())
+}
+
+/// The highest checkpoint that all of `pipelines` have been written up to, or `None` if any of them
+/// has not written any data yet. Queries that read from the tables of multiple pipelines can bound
+/// their reads by this checkpoint to get a consistent view across those tables.
+pub(crate) async fn checkpoint_watermark(
+    ctx: &Context,
+    pipelines: &[&str],
+) -> Result<Option<u64>, Arc<ReadError>> {
+    Ok(watermark(ctx, pipelines)
+        .await?
+        .map(|w| w.checkpoint_hi_inclusive as u64))
+}
+
+/// The sequence number of the first transaction that some of `pipelines` have not been written up
+/// to yet, or `None` if any of them has not written any data yet. Transactions before this bound
+/// can be read consistently from the tables of all of these pipelines.
+pub(crate) async fn tx_watermark(
+    ctx: &Context,
+    pipelines: &[&str],
+) -> Result<Option<u64>, Arc<ReadError>> {
+    Ok(watermark(ctx, pipelines).await?.map(|w| w.tx_hi as u64))
+}
+
+struct Watermark {
+    checkpoint_hi_inclusive: i64,
+    tx_hi: i64,
+}
+
+/// The lowest of the high watermarks of `pipelines`, or `None` if any of them has no watermark.
+async fn watermark(ctx: &Context, pipelines: &[&str]) -> Result<Option<Watermark>, Arc<ReadError>> {
+    #[derive(QueryableByName)]
+    struct WatermarkRow {
+        #[diesel(sql_type = Nullable<BigInt>)]
+        checkpoint_hi_inclusive: Option<i64>,
+        #[diesel(sql_type = Nullable<BigInt>)]
+        tx_hi: Option<i64>,
+        #[diesel(sql_type = BigInt)]
+        pipelines: i64,
+    }
+
+    let names: Vec<String> = pipelines.iter().map(|p| p.to_string()).collect();
+    let query = RawQuery::new(
+        r#"
+        SELECT
+            MIN(checkpoint_hi_inclusive) AS checkpoint_hi_inclusive,
+            MIN(tx_hi) AS tx_hi,
+            COUNT(*) AS pipelines
+        FROM
+            watermarks
+        WHERE
+            pipeline = ANY("#,
+    )
+    .bind::<Array<Text>, _>(names)
+    .sql(")");
+
+    let rows: Vec<WatermarkRow> = query.results(ctx).await?;
+    Ok(match rows.as_slice() {
+        [WatermarkRow {
+            checkpoint_hi_inclusive: Some(checkpoint_hi_inclusive),
+            tx_hi: Some(tx_hi),
+            pipelines: count,
+        }] if *count as usize == pipelines.len() => Some(Watermark {
+            checkpoint_hi_inclusive: *checkpoint_hi_inclusive,
+            tx_hi: *tx_hi,
+        }),
+        _ => None,
+    })
+}
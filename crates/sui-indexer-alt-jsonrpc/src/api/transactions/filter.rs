@@ -0,0 +1,159 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use diesel::sql_types::{Array, Bytea, SmallInt, Text};
+use move_core_types::identifier::Identifier;
+use sui_indexer_alt_schema::transactions::StoredKind;
+use sui_json_rpc_types::TransactionFilter;
+
+use crate::api::query::RawQuery;
+
+use super::Error;
+
+/// Where to find the transactions that match a [TransactionFilter].
+pub(super) enum TxSelection {
+    /// Transactions that have a row in `table` satisfying `condition`.
+    Index {
+        table: &'static str,
+        condition: RawQuery,
+    },
+
+    /// Transactions in the checkpoint with this sequence number.
+    Checkpoint(u64),
+}
+
+/// Translate `filter` into a selection over the transaction index tables. Like the existing
+/// JSON-RPC implementation, filters on input and changed objects are not supported (use
+/// `AffectedObject` instead), and neither are filters on the recipients of a transaction alone
+/// (use `FromOrToAddress` instead).
+pub(super) fn tx_selection(filter: Option<&TransactionFilter>) -> Result<TxSelection, Error> {
+    use TransactionFilter as F;
+    use TxSelection as S;
+
+    Ok(match filter {
+        None => S::Index {
+            table: "tx_digests",
+            condition: RawQuery::new("TRUE"),
+        },
+
+        Some(F::Checkpoint(seq)) => S::Checkpoint(*seq),
+
+        Some(F::MoveFunction {
+            package,
+            module,
+            function,
+        }) => {
+            let mut condition = RawQuery::new("package = ").bind::<Bytea, _>(package.to_vec());
+            match (module, function) {
+                (Some(module), function) => {
+                    condition = condition
+                        .sql(" AND module = ")
+                        .bind::<Text, _>(identifier(module)?.to_owned());
+                    if let Some(function) = function {
+                        condition = condition
+                            .sql(" AND function = ")
+                            .bind::<Text, _>(identifier(function)?.to_owned());
+                    }
+                }
+
+                (None, Some(_)) => {
+                    return Err(Error::InvalidFilter(
+                        "Function cannot be present without Module".to_owned(),
+                    ));
+                }
+
+                (None, None) => {}
+            }
+
+            S::Index {
+                table: "tx_calls",
+                condition,
+            }
+        }
+
+        Some(F::AffectedObject(object)) => S::Index {
+            table: "tx_affected_objects",
+            condition: RawQuery::new("affected = ").bind::<Bytea, _>(object.to_vec()),
+        },
+
+        Some(F::FromAddress(from)) => S::Index {
+            table: "tx_affected_addresses",
+            condition: RawQuery::new("sender = ")
+                .bind::<Bytea, _>(from.to_vec())
+                .sql(" AND affected = ")
+                .bind::<Bytea, _>(from.to_vec()),
+        },
+
+        Some(F::FromAndToAddress { from, to }) => S::Index {
+            table: "tx_affected_addresses",
+            condition: RawQuery::new("sender = ")
+                .bind::<Bytea, _>(from.to_vec())
+                .sql(" AND affected = ")
+                .bind::<Bytea, _>(to.to_vec()),
+        },
+
+        Some(F::FromOrToAddress { addr }) => S::Index {
+            table: "tx_affected_addresses",
+            condition: RawQuery::new("affected = ").bind::<Bytea, _>(addr.to_vec()),
+        },
+
+        Some(F::TransactionKind(kind)) => S::Index {
+            table: "tx_kinds",
+            condition: RawQuery::new("tx_kind = ").bind::<SmallInt, _>(tx_kind(kind)? as i16),
+        },
+
+        Some(F::TransactionKindIn(kinds)) => {
+            let kinds: BTreeSet<_> = kinds
+                .iter()
+                .map(|k| Ok(tx_kind(k)? as i16))
+                .collect::<Result<_, Error>>()?;
+
+            S::Index {
+                table: "tx_kinds",
+                condition: RawQuery::new("tx_kind = ANY(")
+                    .bind::<Array<SmallInt>, _>(kinds.into_iter().collect::<Vec<_>>())
+                    .sql(")"),
+            }
+        }
+
+        Some(F::InputObject(_) | F::ChangedObject(_)) => {
+            return Err(Error::InvalidFilter(
+                "InputObject and ChangedObject filters are not supported, please use \
+                 AffectedObject instead"
+                    .to_owned(),
+            ));
+        }
+
+        Some(F::ToAddress(_)) => {
+            return Err(Error::InvalidFilter(
+                "ToAddress filter is not supported, please use FromOrToAddress instead".to_owned(),
+            ));
+        }
+    })
+}
+
+/// Check that `ident` is a valid Move identifier.
+fn identifier(ident: &str) -> Result<&str, Error> {
+    if Identifier::is_valid(ident) {
+        Ok(ident)
+    } else {
+        Err(Error::InvalidFilter(format!(
+            "Invalid identifier: {ident:?}"
+        )))
+    }
+}
+
+/// Transactions are only indexed as either programmable or system transactions, so those are the
+/// only kinds that can be filtered on.
+fn tx_kind(kind: &str) -> Result<StoredKind, Error> {
+    match kind {
+        "ProgrammableTransaction" => Ok(StoredKind::ProgrammableTransaction),
+        "SystemTransaction" => Ok(StoredKind::SystemTransaction),
+        _ => Err(Error::InvalidFilter(format!(
+            "Unsupported transaction kind {kind:?}, expected \"ProgrammableTransaction\" or \
+             \"SystemTransaction\"",
+        ))),
+    }
+}
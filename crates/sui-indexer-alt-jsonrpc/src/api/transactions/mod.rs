@@ -0,0 +1,397 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::anyhow;
+use futures::future;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use move_core_types::annotated_value::{MoveDatatypeLayout, MoveTypeLayout};
+use sui_indexer_alt_schema::transactions::StoredTransaction;
+use sui_json_rpc_types::{
+    Page, SuiEvent, SuiTransactionBlock, SuiTransactionBlockData, SuiTransactionBlockEvents,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionBlocksPage,
+};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    digests::TransactionDigest,
+    effects::TransactionEffects,
+    error::SuiError,
+    event::Event,
+    messages_checkpoint::{CheckpointContents, CheckpointSummary},
+    signature::GenericSignature,
+    transaction::TransactionData,
+};
+
+use crate::{
+    context::Context,
+    data::{checkpoints::CheckpointKey, reader::ReadError, transactions::TxDigestKey},
+    error::{internal_error, invalid_params},
+    paginate::page_limit,
+};
+
+use super::{
+    query::{tx_sequence_numbers, tx_watermark, RawQuery},
+    rpc_module::RpcModule,
+};
+
+mod filter;
+
+#[open_rpc(namespace = "sui", tag = "Transactions API")]
+#[rpc(server, namespace = "sui")]
+trait TransactionsApi {
+    /// Fetch a transaction by its transaction digest.
+    #[method(name = "getTransactionBlock")]
+    async fn get_transaction_block(
+        &self,
+        /// The digest of the queried transaction.
+        digest: TransactionDigest,
+        /// Options controlling the output format.
+        options: SuiTransactionBlockResponseOptions,
+    ) -> RpcResult<SuiTransactionBlockResponse>;
+}
+
+#[open_rpc(namespace = "suix", tag = "Query Transactions API")]
+#[rpc(server, namespace = "suix")]
+trait QueryTransactionsApi {
+    /// Query transactions based on their properties (sender, affected addresses, function calls,
+    /// etc). Returns a paginated list of transactions.
+    ///
+    /// If a cursor is provided, the query will start from the transaction after the one pointed
+    /// to by this cursor, otherwise pagination starts from the first transaction that meets the
+    /// query criteria.
+    ///
+    /// The definition of "first" transaction is changed by the `descending_order` parameter,
+    /// which is optional, and defaults to false, meaning that the oldest transaction is shown
+    /// first.
+    #[method(name = "queryTransactionBlocks")]
+    async fn query_transaction_blocks(
+        &self,
+        /// The query criteria, and the output options.
+        query: SuiTransactionBlockResponseQuery,
+        /// Cursor to start paginating from.
+        cursor: Option<TransactionDigest>,
+        /// Maximum number of transactions to return per page, defaults to 50 if not specified.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (oldest first).
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage>;
+}
+
+pub(crate) struct Transactions(pub Context);
+
+pub(crate) struct QueryTransactions(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Transaction not found: {0}")]
+    NotFound(TransactionDigest),
+
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+
+    #[error("Checkpoint {0} not found")]
+    CheckpointNotFound(u64),
+
+    #[error("Digest for transaction {0} not found")]
+    DigestNotFound(u64),
+
+    #[error("Error converting to response: {0}")]
+    Conversion(SuiError),
+
+    #[error("Error resolving type information: {0}")]
+    Resolution(anyhow::Error),
+
+    #[error("Deserialization error: {0}")]
+    Deserialization(#[from] bcs::Error),
+
+    #[error(transparent)]
+    Read(#[from] Arc<ReadError>),
+}
+
+#[async_trait::async_trait]
+impl TransactionsApiServer for Transactions {
+    async fn get_transaction_block(
+        &self,
+        digest: TransactionDigest,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> RpcResult<SuiTransactionBlockResponse> {
+        let Self(ctx) = self;
+        let Some(stored) = ctx
+            .loader()
+            .load_one(digest)
+            .await
+            .map_err(internal_error)?
+        else {
+            return Err(invalid_params(Error::NotFound(digest)));
+        };
+
+        response(ctx, &stored, &options)
+            .await
+            .map_err(internal_error)
+    }
+}
+
+#[async_trait::async_trait]
+impl QueryTransactionsApiServer for QueryTransactions {
+    async fn query_transaction_blocks(
+        &self,
+        query: SuiTransactionBlockResponseQuery,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage> {
+        let Self(ctx) = self;
+        let SuiTransactionBlockResponseQuery { filter, options } = query;
+        let options = options.unwrap_or_default();
+        let limit = page_limit(limit);
+        let descending = descending_order.unwrap_or(false);
+
+        let selection = filter::tx_selection(filter.as_ref()).map_err(invalid_params)?;
+
+        // Translate the cursor into bounds on the transaction sequence numbers to fetch.
+        let (mut lo, mut hi) = (None, None);
+        if let Some(digest) = cursor {
+            let Some(seq) = tx_sequence_number(ctx, digest)
+                .await
+                .map_err(internal_error)?
+            else {
+                return Err(invalid_params(Error::NotFound(digest)));
+            };
+
+            if descending {
+                hi = Some(seq);
+            } else {
+                lo = Some(seq + 1);
+            }
+        }
+
+        let (table, condition) = match selection {
+            filter::TxSelection::Index { table, condition } => (table, condition),
+            filter::TxSelection::Checkpoint(seq) => {
+                let Some(range) = checkpoint_tx_range(ctx, seq)
+                    .await
+                    .map_err(internal_error)?
+                else {
+                    return Ok(Page::empty());
+                };
+
+                lo = Some(lo.map_or(range.start, |lo| lo.max(range.start)));
+                hi = Some(hi.map_or(range.end, |hi| hi.min(range.end)));
+                ("tx_digests", RawQuery::new("TRUE"))
+            }
+        };
+
+        // Only look for transactions that every pipeline involved in the query has indexed, so
+        // that the index, digest and contents of each transaction are all available.
+        let Some(tx_hi) = tx_watermark(ctx, &[table, "tx_digests", "kv_transactions"])
+            .await
+            .map_err(internal_error)?
+        else {
+            return Ok(Page::empty());
+        };
+
+        hi = Some(hi.map_or(tx_hi, |hi| hi.min(tx_hi)));
+
+        let mut seqs = tx_sequence_numbers(ctx, table, condition, lo, hi, limit + 1, descending)
+            .await
+            .map_err(internal_error)?;
+
+        let has_next_page = seqs.len() > limit;
+        seqs.truncate(limit);
+
+        let digests = tx_digests(ctx, &seqs).await.map_err(internal_error)?;
+        let transactions = ctx
+            .loader()
+            .load_many(digests.iter().copied())
+            .await
+            .map_err(internal_error)?;
+
+        let data = future::try_join_all(digests.iter().map(|digest| {
+            let transactions = &transactions;
+            let options = &options;
+            async move {
+                let stored = transactions.get(digest).ok_or(Error::NotFound(*digest))?;
+                response(ctx, stored, options).await
+            }
+        }))
+        .await
+        .map_err(internal_error)?;
+
+        Ok(Page {
+            data,
+            next_cursor: digests.last().copied(),
+            has_next_page,
+        })
+    }
+}
+
+impl RpcModule for Transactions {
+    fn schema(&self) -> Module {
+        TransactionsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl RpcModule for QueryTransactions {
+    fn schema(&self) -> Module {
+        QueryTransactionsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Find the sequence number of the transaction with digest `digest`, by locating it in the
+/// contents of the checkpoint it was included in. Returns `None` if the transaction does not
+/// exist.
+pub(crate) async fn tx_sequence_number(
+    ctx: &Context,
+    digest: TransactionDigest,
+) -> Result<Option<u64>, Error> {
+    let Some(tx) = ctx.loader().load_one(digest).await? else {
+        return Ok(None);
+    };
+
+    let cp_sequence_number = tx.cp_sequence_number as u64;
+    let Some(checkpoint) = ctx
+        .loader()
+        .load_one(CheckpointKey(cp_sequence_number))
+        .await?
+    else {
+        return Err(Error::CheckpointNotFound(cp_sequence_number));
+    };
+
+    let summary: CheckpointSummary = bcs::from_bytes(&checkpoint.checkpoint_summary)?;
+    let contents: CheckpointContents = bcs::from_bytes(&checkpoint.checkpoint_contents)?;
+
+    Ok(contents
+        .enumerate_transactions(&summary)
+        .find(|(_, digests)| digests.transaction == digest)
+        .map(|(seq, _)| seq))
+}
+
+/// The range of sequence numbers of transactions in the checkpoint with sequence number `seq`, or
+/// `None` if the checkpoint does not exist.
+async fn checkpoint_tx_range(ctx: &Context, seq: u64) -> Result<Option<Range<u64>>, Error> {
+    let Some(checkpoint) = ctx.loader().load_one(CheckpointKey(seq)).await? else {
+        return Ok(None);
+    };
+
+    let summary: CheckpointSummary = bcs::from_bytes(&checkpoint.checkpoint_summary)?;
+    let contents: CheckpointContents = bcs::from_bytes(&checkpoint.checkpoint_contents)?;
+
+    let hi = summary.network_total_transactions;
+    Ok(Some(hi - contents.size() as u64..hi))
+}
+
+/// Fetch the digests for the transactions with sequence numbers `seqs`, in the same order.
+pub(crate) async fn tx_digests(
+    ctx: &Context,
+    seqs: &[u64],
+) -> Result<Vec<TransactionDigest>, Error> {
+    let stored = ctx
+        .loader()
+        .load_many(seqs.iter().copied().map(TxDigestKey))
+        .await?;
+
+    seqs.iter()
+        .map(|seq| {
+            let stored = stored
+                .get(&TxDigestKey(*seq))
+                .ok_or(Error::DigestNotFound(*seq))?;
+
+            TransactionDigest::try_from(stored.tx_digest.clone()).map_err(Error::Conversion)
+        })
+        .collect()
+}
+
+/// Convert the representation of a transaction from the database into the response format,
+/// including the fields requested in the `options`.
+pub(crate) async fn response(
+    ctx: &Context,
+    tx: &StoredTransaction,
+    options: &SuiTransactionBlockResponseOptions,
+) -> Result<SuiTransactionBlockResponse, Error> {
+    use Error as E;
+
+    let digest = TransactionDigest::try_from(tx.tx_digest.clone()).map_err(E::Conversion)?;
+    let mut response = SuiTransactionBlockResponse::new(digest);
+
+    if options.show_input {
+        let data: TransactionData = bcs::from_bytes(&tx.raw_transaction)?;
+        let tx_signatures: Vec<GenericSignature> = bcs::from_bytes(&tx.user_signatures)?;
+        response.transaction = Some(SuiTransactionBlock {
+            data: SuiTransactionBlockData::try_from_with_package_resolver(
+                data,
+                ctx.package_resolver(),
+            )
+            .await
+            .map_err(E::Resolution)?,
+            tx_signatures,
+        })
+    }
+
+    if options.show_raw_input {
+        response.raw_transaction = tx.raw_transaction.clone();
+    }
+
+    if options.show_effects {
+        let effects: TransactionEffects = bcs::from_bytes(&tx.raw_effects)?;
+        response.effects = Some(effects.try_into().map_err(E::Conversion)?);
+    }
+
+    if options.show_raw_effects {
+        response.raw_effects = tx.raw_effects.clone();
+    }
+
+    if options.show_events {
+        let events: Vec<Event> = bcs::from_bytes(&tx.events)?;
+        let mut sui_events = Vec::with_capacity(events.len());
+
+        for (ix, event) in events.into_iter().enumerate() {
+            let timestamp_ms = tx.timestamp_ms as u64;
+            sui_events.push(sui_event(ctx, digest, ix as u64, event, timestamp_ms).await?);
+        }
+
+        response.events = Some(SuiTransactionBlockEvents { data: sui_events });
+    }
+
+    Ok(response)
+}
+
+/// Convert the `ix`-th event emitted by transaction `digest` into the response format, resolving
+/// the layout of its type to present its contents.
+pub(crate) async fn sui_event(
+    ctx: &Context,
+    digest: TransactionDigest,
+    ix: u64,
+    event: Event,
+    timestamp_ms: u64,
+) -> Result<SuiEvent, Error> {
+    use Error as E;
+
+    let layout = match ctx
+        .package_resolver()
+        .type_layout(event.type_.clone().into())
+        .await
+        .map_err(|e| E::Resolution(e.into()))?
+    {
+        MoveTypeLayout::Struct(s) => MoveDatatypeLayout::Struct(s),
+        MoveTypeLayout::Enum(e) => MoveDatatypeLayout::Enum(e),
+        _ => {
+            return Err(E::Resolution(anyhow!(
+                "Event {ix} from {digest} is not a struct or enum: {}",
+                event.type_.to_canonical_string(/* with_prefix */ true)
+            )));
+        }
+    };
+
+    SuiEvent::try_from(event, digest, ix, Some(timestamp_ms), layout).map_err(E::Conversion)
+}
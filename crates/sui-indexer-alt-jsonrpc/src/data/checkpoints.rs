@@ -0,0 +1,52 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use async_graphql::dataloader::Loader;
+use diesel::{ExpressionMethods, QueryDsl};
+use sui_indexer_alt_schema::{checkpoints::StoredCheckpoint, schema::kv_checkpoints};
+
+use super::reader::{ReadError, Reader};
+
+/// Key for fetching a checkpoint's summary and contents by its sequence number.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub(crate) struct CheckpointKey(pub u64);
+
+#[async_trait::async_trait]
+impl Loader<CheckpointKey> for Reader {
+    type Value = StoredCheckpoint;
+    type Error = Arc<ReadError>;
+
+    async fn load(
+        &self,
+        keys: &[CheckpointKey],
+    ) -> Result<HashMap<CheckpointKey, Self::Value>, Self::Error> {
+        use kv_checkpoints::dsl as c;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        let seqs: BTreeSet<_> = keys.iter().map(|CheckpointKey(s)| *s as i64).collect();
+        let checkpoints: Vec<StoredCheckpoint> = conn
+            .results(c::kv_checkpoints.filter(c::sequence_number.eq_any(seqs)))
+            .await
+            .map_err(Arc::new)?;
+
+        let seq_to_stored: HashMap<_, _> = checkpoints
+            .into_iter()
+            .map(|stored| (stored.sequence_number as u64, stored))
+            .collect();
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| Some((*key, seq_to_stored.get(&key.0).cloned()?)))
+            .collect())
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod checkpoints;
+pub(crate) mod objects;
 pub(crate) mod package_resolver;
 pub(crate) mod reader;
 pub mod system_package_task;
@@ -0,0 +1,229 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use async_graphql::dataloader::Loader;
+use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use sui_indexer_alt_schema::{
+    objects::{StoredObjVersion, StoredObject},
+    schema::{kv_objects, obj_versions},
+};
+use sui_types::base_types::ObjectID;
+
+use super::reader::{ReadError, Reader};
+
+/// Key for fetching the latest version of an object from `kv_objects`. The latest version may not
+/// contain the object's contents, if it was deleted or wrapped.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub(crate) struct LatestObjectKey(pub ObjectID);
+
+/// Key for fetching an object at a specific version from `kv_objects`.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub(crate) struct VersionedObjectKey(pub ObjectID, pub u64);
+
+/// Key for fetching the version and digest of the latest live version of an object from
+/// `obj_versions`.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub(crate) struct LatestObjVersionKey(pub ObjectID);
+
+/// Key for fetching the version and digest of the latest version of an object from
+/// `obj_versions`, as of a given checkpoint (inclusive). Used to read objects consistently with
+/// other tables at a fixed checkpoint.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub(crate) struct CheckpointedObjVersionKey(pub ObjectID, pub u64);
+
+#[async_trait::async_trait]
+impl Loader<LatestObjectKey> for Reader {
+    type Value = StoredObject;
+    type Error = Arc<ReadError>;
+
+    async fn load(
+        &self,
+        keys: &[LatestObjectKey],
+    ) -> Result<HashMap<LatestObjectKey, Self::Value>, Self::Error> {
+        use kv_objects::dsl as o;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        let ids: BTreeSet<_> = keys.iter().map(|LatestObjectKey(id)| id.to_vec()).collect();
+        let objects: Vec<StoredObject> = conn
+            .results(
+                o::kv_objects
+                    .filter(o::object_id.eq_any(ids))
+                    .distinct_on(o::object_id)
+                    .order_by(o::object_id)
+                    .then_order_by(o::object_version.desc()),
+            )
+            .await
+            .map_err(Arc::new)?;
+
+        let id_to_stored: HashMap<_, _> = objects
+            .into_iter()
+            .map(|stored| (stored.object_id.clone(), stored))
+            .collect();
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| {
+                let slice: &[u8] = key.0.as_ref();
+                Some((*key, id_to_stored.get(slice).cloned()?))
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Loader<VersionedObjectKey> for Reader {
+    type Value = StoredObject;
+    type Error = Arc<ReadError>;
+
+    async fn load(
+        &self,
+        keys: &[VersionedObjectKey],
+    ) -> Result<HashMap<VersionedObjectKey, Self::Value>, Self::Error> {
+        use kv_objects::dsl as o;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        let mut query = o::kv_objects.into_boxed();
+        for VersionedObjectKey(id, version) in keys {
+            query = query.or_filter(
+                o::object_id
+                    .eq(id.to_vec())
+                    .and(o::object_version.eq(*version as i64)),
+            );
+        }
+
+        let objects: Vec<StoredObject> = conn.results(query).await.map_err(Arc::new)?;
+
+        let key_to_stored: HashMap<_, _> = objects
+            .into_iter()
+            .map(|stored| ((stored.object_id.clone(), stored.object_version), stored))
+            .collect();
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| {
+                let VersionedObjectKey(id, version) = key;
+                let stored = key_to_stored.get(&(id.to_vec(), *version as i64))?;
+                Some((*key, stored.clone()))
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Loader<LatestObjVersionKey> for Reader {
+    type Value = StoredObjVersion;
+    type Error = Arc<ReadError>;
+
+    async fn load(
+        &self,
+        keys: &[LatestObjVersionKey],
+    ) -> Result<HashMap<LatestObjVersionKey, Self::Value>, Self::Error> {
+        use obj_versions::dsl as v;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        let ids: BTreeSet<_> = keys
+            .iter()
+            .map(|LatestObjVersionKey(id)| id.to_vec())
+            .collect();
+
+        let versions: Vec<StoredObjVersion> = conn
+            .results(
+                v::obj_versions
+                    .filter(v::object_id.eq_any(ids))
+                    .distinct_on(v::object_id)
+                    .order_by(v::object_id)
+                    .then_order_by(v::cp_sequence_number.desc())
+                    .then_order_by(v::object_version.desc()),
+            )
+            .await
+            .map_err(Arc::new)?;
+
+        let id_to_stored: HashMap<_, _> = versions
+            .into_iter()
+            .map(|stored| (stored.object_id.clone(), stored))
+            .collect();
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| {
+                let slice: &[u8] = key.0.as_ref();
+                Some((*key, id_to_stored.get(slice).cloned()?))
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Loader<CheckpointedObjVersionKey> for Reader {
+    type Value = StoredObjVersion;
+    type Error = Arc<ReadError>;
+
+    async fn load(
+        &self,
+        keys: &[CheckpointedObjVersionKey],
+    ) -> Result<HashMap<CheckpointedObjVersionKey, Self::Value>, Self::Error> {
+        use obj_versions::dsl as v;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // Keys are almost always requested at the same checkpoint, so issue one query per
+        // distinct checkpoint.
+        let mut cp_to_ids: BTreeMap<u64, BTreeSet<Vec<u8>>> = BTreeMap::new();
+        for CheckpointedObjVersionKey(id, cp) in keys {
+            cp_to_ids.entry(*cp).or_default().insert(id.to_vec());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        let mut key_to_stored = HashMap::new();
+        for (cp, ids) in cp_to_ids {
+            let versions: Vec<StoredObjVersion> = conn
+                .results(
+                    v::obj_versions
+                        .filter(v::object_id.eq_any(ids))
+                        .filter(v::cp_sequence_number.le(cp as i64))
+                        .distinct_on(v::object_id)
+                        .order_by(v::object_id)
+                        .then_order_by(v::cp_sequence_number.desc())
+                        .then_order_by(v::object_version.desc()),
+                )
+                .await
+                .map_err(Arc::new)?;
+
+            for stored in versions {
+                key_to_stored.insert((stored.object_id.clone(), cp), stored);
+            }
+        }
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| {
+                let CheckpointedObjVersionKey(id, cp) = key;
+                let stored = key_to_stored.get(&(id.to_vec(), *cp))?;
+                Some((*key, stored.clone()))
+            })
+            .collect())
+    }
+}
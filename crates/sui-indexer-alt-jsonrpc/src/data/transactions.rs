@@ -8,11 +8,18 @@ use std::{
 
 use async_graphql::dataloader::Loader;
 use diesel::{ExpressionMethods, QueryDsl};
-use sui_indexer_alt_schema::{schema::kv_transactions, transactions::StoredTransaction};
+use sui_indexer_alt_schema::{
+    schema::{kv_transactions, tx_digests},
+    transactions::{StoredTransaction, StoredTxDigest},
+};
 use sui_types::digests::TransactionDigest;
 
 use super::reader::{ReadError, Reader};
 
+/// Key for fetching a transaction's digest by its sequence number.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub(crate) struct TxDigestKey(pub u64);
+
 #[async_trait::async_trait]
 impl Loader<TransactionDigest> for Reader {
     type Value = StoredTransaction;
@@ -50,3 +57,38 @@ impl Loader<TransactionDigest> for Reader {
             .collect())
     }
 }
+
+#[async_trait::async_trait]
+impl Loader<TxDigestKey> for Reader {
+    type Value = StoredTxDigest;
+    type Error = Arc<ReadError>;
+
+    async fn load(
+        &self,
+        keys: &[TxDigestKey],
+    ) -> Result<HashMap<TxDigestKey, Self::Value>, Self::Error> {
+        use tx_digests::dsl as t;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        let seqs: BTreeSet<_> = keys.iter().map(|TxDigestKey(s)| *s as i64).collect();
+        let digests: Vec<StoredTxDigest> = conn
+            .results(t::tx_digests.filter(t::tx_sequence_number.eq_any(seqs)))
+            .await
+            .map_err(Arc::new)?;
+
+        let seq_to_stored: HashMap<_, _> = digests
+            .into_iter()
+            .map(|stored| (stored.tx_sequence_number as u64, stored))
+            .collect();
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| Some((*key, seq_to_stored.get(&key.0).cloned()?)))
+            .collect())
+    }
+}
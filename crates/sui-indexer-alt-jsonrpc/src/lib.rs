@@ -5,8 +5,11 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use anyhow::Context as _;
+use api::coins::Coins;
+use api::events::QueryEvents;
+use api::objects::{Objects, QueryObjects};
 use api::rpc_module::RpcModule;
-use api::transactions::Transactions;
+use api::transactions::{QueryTransactions, Transactions};
 use data::system_package_task::{SystemPackageTask, SystemPackageTaskArgs};
 use jsonrpsee::server::{RpcServiceBuilder, ServerBuilder};
 use metrics::middleware::MetricsLayer;
@@ -29,6 +32,7 @@ mod context;
 pub mod data;
 mod error;
 mod metrics;
+mod paginate;
 
 #[derive(clap::Args, Debug, Clone)]
 pub struct RpcArgs {
@@ -209,7 +213,12 @@ pub async fn start_rpc(
         cancel.child_token(),
     );
 
+    rpc.add_module(Coins(context.clone()))?;
     rpc.add_module(Governance(context.clone()))?;
+    rpc.add_module(QueryEvents(context.clone()))?;
+    rpc.add_module(Objects(context.clone()))?;
+    rpc.add_module(QueryObjects(context.clone()))?;
+    rpc.add_module(QueryTransactions(context.clone()))?;
     rpc.add_module(Transactions(context.clone()))?;
 
     let h_rpc = rpc.run().await.context("Failed to start RPC service")?;
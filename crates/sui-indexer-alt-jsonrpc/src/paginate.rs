@@ -0,0 +1,16 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// The maximum number of results that can be returned in a single page, or fetched by a single
+/// multi-get request.
+pub(crate) const QUERY_MAX_RESULT_LIMIT: usize = 50;
+
+/// Interpret the `limit` requested for a page of results. Like the existing JSON-RPC
+/// implementation, requests that don't specify a limit, or that ask for more than the maximum
+/// page size, are served a page of the maximum size.
+pub(crate) fn page_limit(limit: Option<usize>) -> usize {
+    match limit {
+        Some(limit) if limit > 0 && limit <= QUERY_MAX_RESULT_LIMIT => limit,
+        _ => QUERY_MAX_RESULT_LIMIT,
+    }
+}
@@ -12,6 +12,7 @@ use sui_indexer_alt_schema::{
     epochs::StoredEpochStart,
     schema::{kv_epoch_starts, kv_genesis},
 };
+use sui_pg_db::Db;
 use sui_types::{
     full_checkpoint_content::CheckpointData,
     sui_system_state::{get_sui_system_state, SuiSystemStateTrait},
@@ -30,7 +31,7 @@ use crate::Indexer;
 /// Can be cancelled via the `cancel` token, or through an interrupt signal (which will also cancel
 /// the token).
 pub async fn bootstrap(
-    indexer: &Indexer,
+    indexer: &Indexer<Db>,
     retry_interval: Duration,
     cancel: CancellationToken,
 ) -> Result<StoredGenesis> {
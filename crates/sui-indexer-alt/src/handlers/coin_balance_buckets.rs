@@ -157,6 +157,8 @@ impl Processor for CoinBalanceBuckets {
 
 #[async_trait::async_trait]
 impl Handler for CoinBalanceBuckets {
+    type Store = db::Db;
+
     const PRUNING_REQUIRES_PROCESSED_VALUES: bool = true;
 
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
@@ -36,6 +36,8 @@ impl Processor for KvCheckpoints {
 
 #[async_trait::async_trait]
 impl Handler for KvCheckpoints {
+    type Store = db::Db;
+
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
         Ok(diesel::insert_into(kv_checkpoints::table)
             .values(values)
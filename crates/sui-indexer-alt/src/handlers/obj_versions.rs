@@ -44,6 +44,8 @@ impl Processor for ObjVersions {
 
 #[async_trait::async_trait]
 impl Handler for ObjVersions {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
 
@@ -57,6 +57,8 @@ impl Processor for TxCalls {
 
 #[async_trait::async_trait]
 impl Handler for TxCalls {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
 
@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal external signer, used to test the external signer keystore. It signs with the keys
+//! of the file based keystore given as its only argument:
+//!
+//! `mock-external-signer <keystore path>`
+
+use anyhow::anyhow;
+use fastcrypto::encoding::{Base64, Encoding};
+use std::io::{stdin, stdout};
+use std::path::PathBuf;
+use sui_keys::external_signer::{SignerRequest, SignerResponse};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, Signature};
+
+fn main() -> Result<(), anyhow::Error> {
+    let keystore_path = std::env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow!("Usage: mock-external-signer <keystore path>"))?;
+    let keystore = FileBasedKeystore::new(&PathBuf::from(keystore_path))?;
+
+    let request: SignerRequest = serde_json::from_reader(stdin())?;
+    let response = match request {
+        SignerRequest::Keys => SignerResponse::Keys {
+            keys: keystore.keys().iter().map(|k| k.encode_base64()).collect(),
+        },
+        SignerRequest::Sign {
+            address, message, ..
+        } => match sign(&keystore, &address, &message) {
+            Ok(signature) => SignerResponse::Signature { signature },
+            Err(e) => SignerResponse::Error {
+                error: e.to_string(),
+            },
+        },
+    };
+    serde_json::to_writer(stdout(), &response)?;
+    Ok(())
+}
+
+fn sign(
+    keystore: &FileBasedKeystore,
+    address: &SuiAddress,
+    message: &str,
+) -> Result<String, anyhow::Error> {
+    let message = Base64::decode(message).map_err(|e| anyhow!("Invalid message: {e}"))?;
+    let signature = Signature::new_hashed(&message, keystore.get_key(address)?);
+    Ok(Base64::encode(signature))
+}
@@ -0,0 +1,95 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Protocol spoken between an [crate::keystore::ExternalKeystore] and the signer process it
+//! delegates to.
+//!
+//! For every request the signer command is spawned once, receives a single JSON encoded
+//! [SignerRequest] on stdin, and must write a single JSON encoded [SignerResponse] to stdout
+//! before exiting. Byte strings and keys are Base64 encoded. Anything the signer writes to
+//! stderr is passed through to the user, so it can be used for prompts and diagnostics.
+
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use sui_types::base_types::SuiAddress;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// List the public keys the signer can sign with, as Base64 encoded `flag || pubkey`.
+    Keys,
+    /// Sign `message` with the key for `address`.
+    Sign {
+        address: SuiAddress,
+        /// The bytes to sign. For transactions and other intent messages this is the Blake2b256
+        /// digest of the BCS serialized intent message.
+        message: String,
+        /// The BCS serialized intent message `message` is the digest of, if any, so that the
+        /// signer can inspect or display what it is signing.
+        intent_message: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SignerResponse {
+    Keys {
+        keys: Vec<String>,
+    },
+    /// A Base64 encoded Sui signature, `flag || signature || pubkey`.
+    Signature {
+        signature: String,
+    },
+    Error {
+        error: String,
+    },
+}
+
+/// Runs `command` with `args`, sends it `request` and returns its response. A
+/// [SignerResponse::Error] is turned into an error.
+pub fn send_request(
+    command: &Path,
+    args: &[String],
+    request: &SignerRequest,
+) -> Result<SignerResponse, anyhow::Error> {
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("Cannot start external signer {}", command.display()))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow!("Cannot write to external signer {}", command.display()))?;
+    serde_json::to_writer(&mut stdin, request)?;
+    writeln!(stdin)?;
+    // Close stdin so that the signer sees the end of the request.
+    drop(stdin);
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "External signer {} exited with {}",
+            command.display(),
+            output.status
+        );
+    }
+    let response: SignerResponse = serde_json::from_slice(&output.stdout).with_context(|| {
+        format!(
+            "Cannot deserialize the response of external signer {}",
+            command.display()
+        )
+    })?;
+    match response {
+        SignerResponse::Error { error } => {
+            bail!("External signer {} failed: {error}", command.display())
+        }
+        response => Ok(response),
+    }
+}
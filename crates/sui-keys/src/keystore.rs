@@ -1,33 +1,45 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::external_signer::{send_request, SignerRequest, SignerResponse};
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::random_names::{random_name, random_names};
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use anyhow::{anyhow, bail, ensure, Context};
 use bip32::DerivationPath;
 use bip39::{Language, Mnemonic, Seed};
-use rand::{rngs::StdRng, SeedableRng};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::hash::HashFunction;
+use fastcrypto::traits::ToFromBytes;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use shared_crypto::intent::{Intent, IntentMessage};
 use std::collections::{BTreeMap, HashSet};
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
-use std::fs;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Write as _};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
-    enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
+    enum_dispatch, DefaultHash, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme,
+    SuiKeyPair, SuiSignature,
 };
+use tempfile::NamedTempFile;
+use zeroize::Zeroizing;
 
 #[derive(Serialize, Deserialize)]
 #[enum_dispatch(AccountKeystore)]
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    Encrypted(EncryptedKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -137,6 +149,18 @@ pub trait AccountKeystore: Send + Sync {
     }
 }
 
+impl Keystore {
+    /// Opens the keystore at `path`, as an [EncryptedKeystore] if the file is encrypted and as a
+    /// [FileBasedKeystore] otherwise.
+    pub fn open(path: &Path) -> Result<Self, anyhow::Error> {
+        if EncryptedKeystore::is_encrypted(path) {
+            Ok(Keystore::Encrypted(EncryptedKeystore::new(path)?))
+        } else {
+            Ok(Keystore::File(FileBasedKeystore::new(path)?))
+        }
+    }
+}
+
 impl Display for Keystore {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
@@ -150,6 +174,16 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::Encrypted(encrypted) => {
+                writeln!(writer, "Keystore Type : Encrypted")?;
+                write!(writer, "Keystore Path : {:?}", encrypted.path)?;
+                write!(f, "{}", writer)
+            }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Signer Command : {:?}", external.config.command)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -309,7 +343,7 @@ impl AccountKeystore for FileBasedKeystore {
 }
 
 impl FileBasedKeystore {
-    pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+    pub fn new(path: &Path) -> Result<Self, anyhow::Error> {
         let keys = if path.exists() {
             let reader =
                 BufReader::new(File::open(path).with_context(|| {
@@ -330,67 +364,8 @@ impl FileBasedKeystore {
             BTreeMap::new()
         };
 
-        // check aliases
-        let mut aliases_path = path.clone();
-        aliases_path.set_extension("aliases");
-
-        let aliases = if aliases_path.exists() {
-            let reader = BufReader::new(File::open(&aliases_path).with_context(|| {
-                format!(
-                    "Cannot open aliases file in keystore: {}",
-                    aliases_path.display()
-                )
-            })?);
-
-            let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
-                format!(
-                    "Cannot deserialize aliases file in keystore: {}",
-                    aliases_path.display(),
-                )
-            })?;
-
-            aliases
-                .into_iter()
-                .map(|alias| {
-                    let key = PublicKey::decode_base64(&alias.public_key_base64);
-                    key.map(|k| (Into::<SuiAddress>::into(&k), alias))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| {
-                    anyhow!(
-                        "Invalid aliases file in keystore: {}. {}",
-                        aliases_path.display(),
-                        e
-                    )
-                })?
-        } else if keys.is_empty() {
-            BTreeMap::new()
-        } else {
-            let names: Vec<String> = random_names(HashSet::new(), keys.len());
-            let aliases = keys
-                .iter()
-                .zip(names)
-                .map(|((sui_address, skp), alias)| {
-                    let public_key_base64 = skp.public().encode_base64();
-                    (
-                        *sui_address,
-                        Alias {
-                            alias,
-                            public_key_base64,
-                        },
-                    )
-                })
-                .collect::<BTreeMap<_, _>>();
-            let aliases_store = serde_json::to_string_pretty(&aliases.values().collect::<Vec<_>>())
-                .with_context(|| {
-                    format!(
-                        "Cannot serialize aliases to file in keystore: {}",
-                        aliases_path.display()
-                    )
-                })?;
-            fs::write(aliases_path, aliases_store)?;
-            aliases
-        };
+        let public_keys = keys.values().map(|k| k.public()).collect::<Vec<_>>();
+        let aliases = read_aliases(&aliases_path(path), &public_keys)?;
 
         Ok(Self {
             keys,
@@ -403,20 +378,13 @@ impl FileBasedKeystore {
         self.path = Some(path.to_path_buf());
     }
 
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn save_aliases(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
-            let aliases_store =
-                serde_json::to_string_pretty(&self.aliases.values().collect::<Vec<_>>())
-                    .with_context(|| {
-                        format!(
-                            "Cannot serialize aliases to file in keystore: {}",
-                            path.display()
-                        )
-                    })?;
-
-            let mut aliases_path = path.clone();
-            aliases_path.set_extension("aliases");
-            fs::write(aliases_path, aliases_store)?
+            write_aliases(&aliases_path(path), &self.aliases)?;
         }
         Ok(())
     }
@@ -434,7 +402,7 @@ impl FileBasedKeystore {
                     .collect::<Vec<_>>(),
             )
             .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
-            fs::write(path, store)?;
+            write_atomically(path, store.as_bytes())?;
         }
         Ok(())
     }
@@ -602,6 +570,738 @@ impl InMemKeystore {
     }
 }
 
+/// Configuration of an [ExternalKeystore], as stored in the client config:
+///
+/// ```yaml
+/// keystore:
+///   External:
+///     command: /usr/local/bin/my-signer
+///     args: ["--profile", "mainnet"]
+///     aliases_path: /home/me/.sui/sui_config/my-signer.aliases
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ExternalSignerConfig {
+    /// The signer executable, spawned once per request.
+    pub command: PathBuf,
+    /// Arguments passed to the signer on every invocation.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// File the aliases of the signer's keys are kept in.
+    pub aliases_path: PathBuf,
+}
+
+/// A keystore that never holds private keys: signing is delegated to an external process (an HSM
+/// bridge, a remote signing service, a hardware wallet...) speaking the protocol described in
+/// [crate::external_signer]. The signer is asked for its public keys when the keystore is loaded.
+pub struct ExternalKeystore {
+    config: ExternalSignerConfig,
+    keys: BTreeMap<SuiAddress, PublicKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.config.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        ExternalKeystore::new(ExternalSignerConfig::deserialize(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.sign(address, msg, None)
+            .map_err(|e| signature::Error::from_source(e.to_string()))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent, msg);
+        let bytes =
+            bcs::to_bytes(&intent_msg).map_err(|e| signature::Error::from_source(e.to_string()))?;
+        let mut hasher = DefaultHash::default();
+        hasher.update(&bytes);
+        let digest = hasher.finalize().digest;
+
+        let signature = self
+            .sign(address, &digest, Some(&bytes))
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        signature
+            .verify_secure(&intent_msg, *address, signature.scheme())
+            .map_err(|e| {
+                signature::Error::from_source(format!(
+                    "Invalid signature from external signer: {e}"
+                ))
+            })?;
+        Ok(signature)
+    }
+
+    fn add_key(
+        &mut self,
+        _alias: Option<String>,
+        _keypair: SuiKeyPair,
+    ) -> Result<(), anyhow::Error> {
+        bail!("Keys cannot be added to an external signer keystore, add them to the signer instead")
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys.values().cloned().collect()
+    }
+
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.addresses_with_alias()
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        bail!("The key for address {address} is held by an external signer and cannot be accessed")
+    }
+
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
+        write_aliases(&self.config.aliases_path, &self.aliases)?;
+        Ok(new_alias_name)
+    }
+}
+
+impl ExternalKeystore {
+    /// Loads the keys of the signer described by `config`. Keys that do not have an alias yet
+    /// are given a random one.
+    pub fn new(config: ExternalSignerConfig) -> Result<Self, anyhow::Error> {
+        let response = send_request(&config.command, &config.args, &SignerRequest::Keys)?;
+        let SignerResponse::Keys { keys } = response else {
+            bail!(
+                "Unexpected response from external signer {}: {response:?}",
+                config.command.display()
+            );
+        };
+        let keys = keys
+            .iter()
+            .map(|k| {
+                let pk = PublicKey::decode_base64(k)?;
+                Ok((SuiAddress::from(&pk), pk))
+            })
+            .collect::<Result<BTreeMap<_, _>, FastCryptoError>>()
+            .map_err(|e| {
+                anyhow!(
+                    "Invalid public key from external signer {}: {e}",
+                    config.command.display()
+                )
+            })?;
+
+        let public_keys = keys.values().cloned().collect::<Vec<_>>();
+        let mut aliases = read_aliases(&config.aliases_path, &public_keys)?;
+        aliases.retain(|address, _| keys.contains_key(address));
+        let missing = keys
+            .iter()
+            .filter(|(address, _)| !aliases.contains_key(address))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let names = random_names(
+                aliases.values().map(|a| a.alias.clone()).collect(),
+                missing.len(),
+            );
+            for ((address, pk), alias) in missing.into_iter().zip(names) {
+                aliases.insert(
+                    *address,
+                    Alias {
+                        alias,
+                        public_key_base64: pk.encode_base64(),
+                    },
+                );
+            }
+            write_aliases(&config.aliases_path, &aliases)?;
+        }
+
+        Ok(Self {
+            config,
+            keys,
+            aliases,
+        })
+    }
+
+    pub fn config(&self) -> &ExternalSignerConfig {
+        &self.config
+    }
+
+    /// Asks the signer to sign `message` with the key of `address`, and checks that the
+    /// signature is for that key.
+    fn sign(
+        &self,
+        address: &SuiAddress,
+        message: &[u8],
+        intent_message: Option<&[u8]>,
+    ) -> Result<Signature, anyhow::Error> {
+        let public_key = self
+            .keys
+            .get(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))?;
+        let request = SignerRequest::Sign {
+            address: *address,
+            message: Base64::encode(message),
+            intent_message: intent_message.map(Base64::encode),
+        };
+        let response = send_request(&self.config.command, &self.config.args, &request)?;
+        let SignerResponse::Signature { signature } = response else {
+            bail!("Unexpected response from external signer: {response:?}");
+        };
+        let signature = Base64::decode(&signature)
+            .map_err(|e| anyhow!("Invalid signature from external signer: {e}"))
+            .and_then(|bytes| {
+                <Signature as ToFromBytes>::from_bytes(&bytes)
+                    .map_err(|e| anyhow!("Invalid signature from external signer: {e}"))
+            })?;
+        ensure!(
+            signature.public_key_bytes() == public_key.as_ref(),
+            "External signer signed with a different key than the one for address {address}"
+        );
+        Ok(signature)
+    }
+}
+
+/// Environment variable holding the passphrase of an encrypted keystore. When it is not set, the
+/// passphrase is requested interactively the first time a private key is needed.
+pub const SUI_KEYSTORE_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+
+const ENCRYPTED_KEYSTORE_VERSION: u64 = 1;
+/// Plaintext sealed under the derived key, used to tell a wrong passphrase apart from a corrupted
+/// keystore, and to check the passphrase of a keystore that holds no keys.
+const PASSPHRASE_CHECK: &[u8] = b"sui-encrypted-keystore";
+const ENCRYPTION_KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const SALT_LENGTH: usize = 32;
+
+/// On-disk representation of an [EncryptedKeystore].
+#[derive(Serialize, Deserialize)]
+struct EncryptedKeystoreFile {
+    version: u64,
+    kdf: ScryptParams,
+    check: SealedData,
+    keys: Vec<EncryptedKey>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct ScryptParams {
+    salt: String,
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+/// AES-256-GCM ciphertext, with its nonce, both Base64 encoded.
+#[derive(Serialize, Deserialize, Clone)]
+struct SealedData {
+    nonce: String,
+    ciphertext: String,
+}
+
+/// A private key sealed under the keystore's encryption key. The public key is kept in the clear
+/// so that addresses can be listed without the passphrase, and is bound to the ciphertext as
+/// associated data.
+#[derive(Serialize, Deserialize, Clone)]
+struct EncryptedKey {
+    public_key: String,
+    #[serde(flatten)]
+    sealed: SealedData,
+}
+
+struct UnlockedKeys {
+    encryption_key: Zeroizing<[u8; ENCRYPTION_KEY_LENGTH]>,
+    keys: BTreeMap<SuiAddress, SuiKeyPair>,
+}
+
+/// A keystore whose private keys are encrypted at rest with a key derived from a passphrase
+/// (scrypt + AES-256-GCM). Public keys and aliases are readable without the passphrase; the keys
+/// are decrypted once, the first time one of them is needed, and kept in memory afterwards.
+pub struct EncryptedKeystore {
+    path: PathBuf,
+    kdf: ScryptParams,
+    check: SealedData,
+    encrypted: BTreeMap<SuiAddress, EncryptedKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+    unlocked: OnceLock<UnlockedKeys>,
+}
+
+impl Serialize for EncryptedKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.path.to_str().unwrap_or(""))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        EncryptedKeystore::new(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for EncryptedKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(Signature::new_hashed(
+            msg,
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+
+    fn add_key(&mut self, alias: Option<String>, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        self.unlocked_keys()?;
+        let address: SuiAddress = (&keypair.public()).into();
+        let alias = self.create_alias(alias)?;
+        let unlocked = self
+            .unlocked
+            .get_mut()
+            .expect("Keystore was unlocked above");
+        let public_key = keypair.public().encode_base64();
+        let sealed = seal(
+            &unlocked.encryption_key,
+            &Zeroizing::new(keypair.to_bytes()),
+            public_key.as_bytes(),
+        )?;
+        self.encrypted.insert(
+            address,
+            EncryptedKey {
+                public_key: public_key.clone(),
+                sealed,
+            },
+        );
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64: public_key,
+            },
+        );
+        unlocked.keys.insert(address, keypair);
+        self.save()
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.encrypted
+            .values()
+            .filter_map(|k| PublicKey::decode_base64(&k.public_key).ok())
+            .collect()
+    }
+
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.addresses_with_alias()
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    /// Decrypts the keystore on first use, prompting for the passphrase if it is not provided
+    /// through [SUI_KEYSTORE_PASSPHRASE_ENV].
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        if !self.encrypted.contains_key(address) {
+            bail!("Cannot find key for address: [{address}]");
+        }
+        self.unlocked_keys()?
+            .keys
+            .get(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))
+    }
+
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
+        write_aliases(&aliases_path(&self.path), &self.aliases)?;
+        Ok(new_alias_name)
+    }
+}
+
+impl EncryptedKeystore {
+    /// Opens an existing encrypted keystore. The keystore starts out locked: no passphrase is
+    /// needed until a private key is accessed.
+    pub fn new(path: &Path) -> Result<Self, anyhow::Error> {
+        let reader = BufReader::new(
+            File::open(path)
+                .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?,
+        );
+        let file: EncryptedKeystoreFile = serde_json::from_reader(reader).with_context(|| {
+            format!(
+                "Cannot deserialize the encrypted keystore file: {}",
+                path.display()
+            )
+        })?;
+        ensure!(
+            file.version == ENCRYPTED_KEYSTORE_VERSION,
+            "Unsupported encrypted keystore version {} in {}",
+            file.version,
+            path.display()
+        );
+
+        let encrypted = file
+            .keys
+            .into_iter()
+            .map(|k| {
+                let pk = PublicKey::decode_base64(&k.public_key)?;
+                Ok((SuiAddress::from(&pk), k))
+            })
+            .collect::<Result<BTreeMap<_, _>, FastCryptoError>>()
+            .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?;
+        let public_keys = encrypted
+            .values()
+            .map(|k| PublicKey::decode_base64(&k.public_key))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?;
+        let aliases = read_aliases(&aliases_path(path), &public_keys)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            kdf: file.kdf,
+            check: file.check,
+            encrypted,
+            aliases,
+            unlocked: OnceLock::new(),
+        })
+    }
+
+    /// Encrypts the keys and aliases of `keystore` under `passphrase`, overwriting its keystore
+    /// file in place. The returned keystore is already unlocked.
+    pub fn from_file_keystore(
+        keystore: &FileBasedKeystore,
+        passphrase: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let path = keystore
+            .path
+            .clone()
+            .ok_or_else(|| anyhow!("Cannot encrypt a keystore that has no file path"))?;
+        let keys = keystore
+            .keys
+            .iter()
+            .map(|(address, kp)| (*address, kp.copy()))
+            .collect();
+        let keystore = Self::create(path, passphrase, keys, keystore.aliases.clone())?;
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    /// Returns true if the file at `path` holds an encrypted keystore, as opposed to a plain
+    /// [FileBasedKeystore].
+    pub fn is_encrypted(path: &Path) -> bool {
+        File::open(path)
+            .ok()
+            .and_then(|f| {
+                serde_json::from_reader::<_, EncryptedKeystoreFile>(BufReader::new(f)).ok()
+            })
+            .is_some()
+    }
+
+    /// Decrypts every key in the keystore with `passphrase`. Does nothing if the keystore is
+    /// already unlocked.
+    pub fn unlock(&self, passphrase: &str) -> Result<(), anyhow::Error> {
+        if self.unlocked.get().is_some() {
+            return Ok(());
+        }
+        let unlocked = self.decrypt(passphrase)?;
+        // Another thread may have unlocked the keystore in the meantime, with the same keys.
+        let _ = self.unlocked.set(unlocked);
+        Ok(())
+    }
+
+    /// Unlocks the keystore with the passphrase from [SUI_KEYSTORE_PASSPHRASE_ENV], or prompts for
+    /// it if the variable is not set.
+    pub fn ensure_unlocked(&self) -> Result<(), anyhow::Error> {
+        self.unlocked_keys().map(|_| ())
+    }
+
+    /// Re-encrypts the keystore under `new_passphrase`, with a fresh salt, and saves it.
+    pub fn change_passphrase(&mut self, new_passphrase: &str) -> Result<(), anyhow::Error> {
+        let keys = self
+            .unlocked_keys()?
+            .keys
+            .iter()
+            .map(|(address, kp)| (*address, kp.copy()))
+            .collect();
+        let keystore = Self::create(
+            self.path.clone(),
+            new_passphrase,
+            keys,
+            self.aliases.clone(),
+        )?;
+        keystore.save()?;
+        *self = keystore;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        write_aliases(&aliases_path(&self.path), &self.aliases)?;
+        let file = EncryptedKeystoreFile {
+            version: ENCRYPTED_KEYSTORE_VERSION,
+            kdf: self.kdf.clone(),
+            check: self.check.clone(),
+            keys: self.encrypted.values().cloned().collect(),
+        };
+        let store = serde_json::to_string_pretty(&file).with_context(|| {
+            format!("Cannot serialize keystore to file: {}", self.path.display())
+        })?;
+        write_atomically(&self.path, store.as_bytes())?;
+        Ok(())
+    }
+
+    fn create(
+        path: PathBuf,
+        passphrase: &str,
+        keys: BTreeMap<SuiAddress, SuiKeyPair>,
+        aliases: BTreeMap<SuiAddress, Alias>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut salt = [0u8; SALT_LENGTH];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let params = scrypt::Params::recommended();
+        let kdf = ScryptParams {
+            salt: Base64::encode(salt),
+            log_n: params.log_n(),
+            r: params.r(),
+            p: params.p(),
+        };
+        let encryption_key = derive_encryption_key(passphrase, &kdf)?;
+        let check = seal(&encryption_key, PASSPHRASE_CHECK, &[])?;
+        let encrypted = keys
+            .iter()
+            .map(|(address, kp)| {
+                let public_key = kp.public().encode_base64();
+                let bytes = Zeroizing::new(kp.to_bytes());
+                let sealed = seal(&encryption_key, &bytes, public_key.as_bytes())?;
+                Ok((*address, EncryptedKey { public_key, sealed }))
+            })
+            .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?;
+
+        Ok(Self {
+            path,
+            kdf,
+            check,
+            encrypted,
+            aliases,
+            unlocked: OnceLock::from(UnlockedKeys {
+                encryption_key,
+                keys,
+            }),
+        })
+    }
+
+    fn unlocked_keys(&self) -> Result<&UnlockedKeys, anyhow::Error> {
+        if let Some(unlocked) = self.unlocked.get() {
+            return Ok(unlocked);
+        }
+        let passphrase = Zeroizing::new(match std::env::var(SUI_KEYSTORE_PASSPHRASE_ENV) {
+            Ok(passphrase) => passphrase,
+            Err(_) => inquire::Password::new(&format!(
+                "Enter the passphrase for keystore {}:",
+                self.path.display()
+            ))
+            .without_confirmation()
+            .prompt()
+            .map_err(|e| anyhow!("Cannot read the keystore passphrase: {e}"))?,
+        });
+        self.unlock(&passphrase)?;
+        Ok(self.unlocked.get().expect("Keystore was unlocked above"))
+    }
+
+    fn decrypt(&self, passphrase: &str) -> Result<UnlockedKeys, anyhow::Error> {
+        let encryption_key = derive_encryption_key(passphrase, &self.kdf)?;
+        unseal(&encryption_key, &self.check, &[])
+            .map_err(|_| anyhow!("Incorrect passphrase for keystore {}", self.path.display()))?;
+        let keys = self
+            .encrypted
+            .iter()
+            .map(|(address, k)| {
+                let bytes = unseal(&encryption_key, &k.sealed, k.public_key.as_bytes())?;
+                let kp = SuiKeyPair::from_bytes(&bytes)
+                    .map_err(|e| anyhow!("Invalid key for address {address}: {e}"))?;
+                ensure!(
+                    SuiAddress::from(&kp.public()) == *address,
+                    "Key for address {address} does not match its public key"
+                );
+                Ok((*address, kp))
+            })
+            .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()
+            .with_context(|| format!("Corrupted keystore file: {}", self.path.display()))?;
+        Ok(UnlockedKeys {
+            encryption_key,
+            keys,
+        })
+    }
+}
+
+fn derive_encryption_key(
+    passphrase: &str,
+    kdf: &ScryptParams,
+) -> Result<Zeroizing<[u8; ENCRYPTION_KEY_LENGTH]>, anyhow::Error> {
+    let salt = Base64::decode(&kdf.salt).map_err(|e| anyhow!("Invalid keystore salt: {e}"))?;
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p)
+        .map_err(|e| anyhow!("Invalid keystore key derivation parameters: {e}"))?;
+    let mut key = Zeroizing::new([0u8; ENCRYPTION_KEY_LENGTH]);
+    scrypt::scrypt(passphrase.as_bytes(), &salt, &params, key.as_mut())
+        .map_err(|e| anyhow!("Cannot derive the keystore encryption key: {e}"))?;
+    Ok(key)
+}
+
+fn seal(
+    key: &[u8; ENCRYPTION_KEY_LENGTH],
+    msg: &[u8],
+    aad: &[u8],
+) -> Result<SealedData, anyhow::Error> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| anyhow!("{e}"))?;
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg, aad })
+        .map_err(|_| anyhow!("Cannot encrypt keystore entry"))?;
+    Ok(SealedData {
+        nonce: Base64::encode(nonce),
+        ciphertext: Base64::encode(ciphertext),
+    })
+}
+
+fn unseal(
+    key: &[u8; ENCRYPTION_KEY_LENGTH],
+    sealed: &SealedData,
+    aad: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| anyhow!("{e}"))?;
+    let nonce = Base64::decode(&sealed.nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+    ensure!(nonce.len() == NONCE_LENGTH, "Invalid nonce length");
+    let ciphertext =
+        Base64::decode(&sealed.ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+    cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| anyhow!("Cannot decrypt keystore entry"))
+}
+
 fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
@@ -613,6 +1313,103 @@ fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     Ok(alias.to_string())
 }
 
+/// Replaces the file at `path` with `contents` without ever leaving it partially written: the
+/// contents are written and synced to a temporary file in the same directory, which is then
+/// renamed over `path`.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut file = NamedTempFile::new_in(dir)
+        .with_context(|| format!("Cannot create a temporary file in {}", dir.display()))?;
+    file.write_all(contents)
+        .and_then(|_| file.as_file().sync_all())
+        .with_context(|| format!("Cannot write to {}", file.path().display()))?;
+    file.persist(path)
+        .with_context(|| format!("Cannot replace {}", path.display()))?;
+    Ok(())
+}
+
+fn aliases_path(path: &Path) -> PathBuf {
+    let mut aliases_path = path.to_path_buf();
+    aliases_path.set_extension("aliases");
+    aliases_path
+}
+
+/// Reads the aliases file at `aliases_path`. If there is none, random aliases are generated for
+/// `public_keys` and written out.
+fn read_aliases(
+    aliases_path: &Path,
+    public_keys: &[PublicKey],
+) -> Result<BTreeMap<SuiAddress, Alias>, anyhow::Error> {
+    if aliases_path.exists() {
+        let reader = BufReader::new(File::open(aliases_path).with_context(|| {
+            format!(
+                "Cannot open aliases file in keystore: {}",
+                aliases_path.display()
+            )
+        })?);
+
+        let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
+            format!(
+                "Cannot deserialize aliases file in keystore: {}",
+                aliases_path.display(),
+            )
+        })?;
+
+        aliases
+            .into_iter()
+            .map(|alias| {
+                let key = PublicKey::decode_base64(&alias.public_key_base64);
+                key.map(|k| (Into::<SuiAddress>::into(&k), alias))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .map_err(|e| {
+                anyhow!(
+                    "Invalid aliases file in keystore: {}. {}",
+                    aliases_path.display(),
+                    e
+                )
+            })
+    } else if public_keys.is_empty() {
+        Ok(BTreeMap::new())
+    } else {
+        let names: Vec<String> = random_names(HashSet::new(), public_keys.len());
+        let aliases = public_keys
+            .iter()
+            .zip(names)
+            .map(|(pk, alias)| {
+                let public_key_base64 = pk.encode_base64();
+                (
+                    SuiAddress::from(pk),
+                    Alias {
+                        alias,
+                        public_key_base64,
+                    },
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        write_aliases(aliases_path, &aliases)?;
+        Ok(aliases)
+    }
+}
+
+fn write_aliases(
+    aliases_path: &Path,
+    aliases: &BTreeMap<SuiAddress, Alias>,
+) -> Result<(), anyhow::Error> {
+    let aliases_store = serde_json::to_string_pretty(&aliases.values().collect::<Vec<_>>())
+        .with_context(|| {
+            format!(
+                "Cannot serialize aliases to file in keystore: {}",
+                aliases_path.display()
+            )
+        })?;
+    write_atomically(aliases_path, aliases_store.as_bytes())?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::keystore::validate_alias;
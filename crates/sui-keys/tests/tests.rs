@@ -6,11 +6,15 @@ use std::str::FromStr;
 
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::EncodeDecodeBase64;
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
 use sui_keys::key_derive::generate_new_key;
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use sui_keys::keystore::{
+    AccountKeystore, EncryptedKeystore, ExternalKeystore, ExternalSignerConfig, FileBasedKeystore,
+    InMemKeystore, Keystore,
+};
+use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignature, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -266,3 +270,147 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+#[test]
+fn encrypted_keystore_round_trip_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("my_alias".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    let private_key = keystore.get_key(&address).unwrap().encode_base64();
+
+    let encrypted = EncryptedKeystore::from_file_keystore(&keystore, "passphrase").unwrap();
+    assert_eq!(vec![address], encrypted.addresses());
+    assert!(EncryptedKeystore::is_encrypted(&keystore_path));
+    // The private key is no longer stored in the clear.
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    assert!(!contents.contains(&private_key));
+    // The keystore is replaced atomically, without leaving temporary files behind.
+    let mut files = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect::<Vec<_>>();
+    files.sort();
+    assert_eq!(vec!["sui.aliases", "sui.keystore"], files);
+    // A plain keystore cannot read the encrypted file.
+    assert!(FileBasedKeystore::new(&keystore_path).is_err());
+
+    // Public keys and aliases are available without the passphrase.
+    let keystore = Keystore::open(&keystore_path).unwrap();
+    let Keystore::Encrypted(encrypted) = &keystore else {
+        panic!("Expected an encrypted keystore");
+    };
+    assert_eq!(vec![address], keystore.addresses());
+    assert_eq!(vec!["my_alias"], keystore.alias_names());
+
+    encrypted.unlock("passphrase").unwrap();
+    assert_eq!(
+        private_key,
+        keystore.get_key(&address).unwrap().encode_base64()
+    );
+}
+
+#[test]
+fn encrypted_keystore_wrong_passphrase_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    EncryptedKeystore::from_file_keystore(&keystore, "passphrase").unwrap();
+
+    let encrypted = EncryptedKeystore::new(&keystore_path).unwrap();
+    assert!(encrypted.unlock("not the passphrase").is_err());
+    assert!(encrypted.unlock("passphrase").is_ok());
+}
+
+#[test]
+fn encrypted_keystore_change_passphrase_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let mut encrypted = EncryptedKeystore::from_file_keystore(&keystore, "old").unwrap();
+    let (address, _, _) = encrypted
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+    encrypted.change_passphrase("new").unwrap();
+
+    let encrypted = EncryptedKeystore::new(&keystore_path).unwrap();
+    assert!(encrypted.unlock("old").is_err());
+    encrypted.unlock("new").unwrap();
+    assert_eq!(
+        address,
+        SuiAddress::from(&encrypted.get_key(&address).unwrap().public())
+    );
+}
+
+#[test]
+fn external_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    // The mock signer signs with the keys of a regular keystore.
+    let signer_keystore_path = temp_dir.path().join("signer.keystore");
+    let mut signer_keystore = FileBasedKeystore::new(&signer_keystore_path).unwrap();
+    let (address, _, _) = signer_keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let (other_address, _, _) = signer_keystore
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+
+    let config = ExternalSignerConfig {
+        command: env!("CARGO_BIN_EXE_mock-external-signer").into(),
+        args: vec![signer_keystore_path.to_str().unwrap().to_string()],
+        aliases_path: temp_dir.path().join("external.aliases"),
+    };
+    let mut keystore = Keystore::from(ExternalKeystore::new(config.clone()).unwrap());
+    let mut addresses = keystore.addresses();
+    addresses.sort();
+    let mut expected = vec![address, other_address];
+    expected.sort();
+    assert_eq!(expected, addresses);
+    assert!(config.aliases_path.exists());
+
+    // Private keys never leave the signer.
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore
+        .add_key(None, signer_keystore.get_key(&address).unwrap().copy())
+        .is_err());
+
+    let msg = PersonalMessage {
+        message: b"hello".to_vec(),
+    };
+    let intent = Intent::personal_message();
+    for address in [address, other_address] {
+        let signature = keystore
+            .sign_secure(&address, &msg, intent.clone())
+            .unwrap();
+        signature
+            .verify_secure(
+                &IntentMessage::new(intent.clone(), &msg),
+                address,
+                signature.scheme(),
+            )
+            .unwrap();
+    }
+    assert!(keystore
+        .sign_secure(&SuiAddress::random_for_testing_only(), &msg, intent)
+        .is_err());
+
+    // Aliases are kept across loads.
+    let alias = keystore
+        .update_alias(
+            &keystore.get_alias_by_address(&address).unwrap(),
+            Some("signer"),
+        )
+        .unwrap();
+    let keystore = ExternalKeystore::new(config).unwrap();
+    assert_eq!(alias, keystore.get_alias_by_address(&address).unwrap());
+}
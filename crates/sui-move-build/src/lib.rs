@@ -8,6 +8,7 @@ use std::{
     io::Write,
     path::Path,
     str::FromStr,
+    sync::Arc,
 };
 
 use fastcrypto::encoding::Base64;
@@ -22,6 +23,7 @@ use move_compiler::{
     editions::Edition,
     linters::LINT_WARNING_PREFIX,
     shared::files::MappedFiles,
+    Compiler, FullyCompiledProgram,
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -153,11 +155,12 @@ impl BuildConfig {
 
     fn compile_package<W: Write>(
         resolution_graph: ResolvedGraph,
+        pre_compiled_deps: Option<Arc<FullyCompiledProgram>>,
         writer: &mut W,
     ) -> anyhow::Result<(MoveCompiledPackage, FnInfoMap)> {
         let build_plan = BuildPlan::create(resolution_graph)?;
         let mut fn_info = None;
-        let compiled_pkg = build_plan.compile_with_driver(writer, |compiler| {
+        let driver = |compiler: Compiler| {
             let (files, units_res) = compiler.build()?;
             match units_res {
                 Ok((units, warning_diags)) => {
@@ -177,7 +180,13 @@ impl BuildConfig {
                     anyhow::bail!("Compilation error");
                 }
             }
-        })?;
+        };
+        let compiled_pkg = match pre_compiled_deps {
+            Some(deps) => {
+                build_plan.compile_with_driver_and_pre_compiled_deps(deps, writer, driver)?
+            }
+            None => build_plan.compile_with_driver(writer, driver)?,
+        };
         Ok((compiled_pkg, fn_info.unwrap()))
     }
 
@@ -256,6 +265,25 @@ pub fn build_from_resolution_graph(
     run_bytecode_verifier: bool,
     print_diags_to_stderr: bool,
     chain_id: Option<String>,
+) -> SuiResult<CompiledPackage> {
+    build_from_resolution_graph_with_pre_compiled_deps(
+        resolution_graph,
+        None,
+        run_bytecode_verifier,
+        print_diags_to_stderr,
+        chain_id,
+    )
+}
+
+/// Like `build_from_resolution_graph`, but if `pre_compiled_deps` is provided, the dependencies of
+/// the package that are available as source are not compiled again: they are taken from
+/// `pre_compiled_deps`, as returned by `BuildPlan::compile_source_deps`.
+pub fn build_from_resolution_graph_with_pre_compiled_deps(
+    resolution_graph: ResolvedGraph,
+    pre_compiled_deps: Option<Arc<FullyCompiledProgram>>,
+    run_bytecode_verifier: bool,
+    print_diags_to_stderr: bool,
+    chain_id: Option<String>,
 ) -> SuiResult<CompiledPackage> {
     let (published_at, dependency_ids) = gather_published_ids(&resolution_graph, chain_id);
 
@@ -293,9 +321,9 @@ pub fn build_from_resolution_graph(
     }
 
     let result = if print_diags_to_stderr {
-        BuildConfig::compile_package(resolution_graph, &mut std::io::stderr())
+        BuildConfig::compile_package(resolution_graph, pre_compiled_deps, &mut std::io::stderr())
     } else {
-        BuildConfig::compile_package(resolution_graph, &mut std::io::sink())
+        BuildConfig::compile_package(resolution_graph, pre_compiled_deps, &mut std::io::sink())
     };
     // write build failure diagnostics to stderr, convert `error` to `String` using `Debug`
     // format to include anyhow's error context chain.
@@ -2,12 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::manage_package::resolve_lock_file_path;
+use crate::watch::watch_package;
 use clap::Parser;
 use move_cli::base;
-use move_package::BuildConfig as MoveBuildConfig;
+use move_compiler::FullyCompiledProgram;
+use move_package::{resolution::resolution_graph::ResolvedGraph, BuildConfig as MoveBuildConfig};
 use serde_json::json;
-use std::{fs, path::Path};
-use sui_move_build::{check_invalid_dependencies, check_unpublished_dependencies, BuildConfig};
+use std::{fs, path::Path, sync::Arc};
+use sui_move_build::{
+    build_from_resolution_graph_with_pre_compiled_deps, check_invalid_dependencies,
+    check_unpublished_dependencies, BuildConfig,
+};
 
 const LAYOUTS_DIR: &str = "layouts";
 const STRUCT_LAYOUTS_FILENAME: &str = "struct_layouts.yaml";
@@ -34,6 +39,10 @@ pub struct Build {
     /// and events.
     #[clap(long, global = true)]
     pub generate_struct_layouts: bool,
+    /// Keep running, and rebuild the package whenever a source file of the package or of its
+    /// local dependencies changes.
+    #[clap(long)]
+    pub watch: bool,
     /// The chain ID, if resolved. Required when the dump_bytecode_as_base64 is true,
     /// for automated address management, where package addresses are resolved for the
     /// respective chain in the Move.lock file.
@@ -49,6 +58,34 @@ impl Build {
     ) -> anyhow::Result<()> {
         let rerooted_path = base::reroot_path(path)?;
         let build_config = resolve_lock_file_path(build_config, Some(&rerooted_path))?;
+        if self.watch {
+            let config = BuildConfig {
+                config: build_config,
+                run_bytecode_verifier: true,
+                print_diags_to_stderr: true,
+                chain_id: self.chain_id.clone(),
+            };
+            return watch_package(
+                &rerooted_path,
+                || {
+                    Ok(config
+                        .clone()
+                        .resolution_graph(&rerooted_path, self.chain_id.clone())?)
+                },
+                |state| {
+                    let pre_compiled_deps = state.pre_compiled_deps()?;
+                    Self::build_resolved(
+                        &rerooted_path,
+                        state.resolution_graph.clone(),
+                        Some(pre_compiled_deps),
+                        self.with_unpublished_dependencies,
+                        self.dump_bytecode_as_base64,
+                        self.generate_struct_layouts,
+                        self.chain_id.clone(),
+                    )
+                },
+            );
+        }
         Self::execute_internal(
             &rerooted_path,
             build_config,
@@ -67,13 +104,42 @@ impl Build {
         generate_struct_layouts: bool,
         chain_id: Option<String>,
     ) -> anyhow::Result<()> {
-        let pkg = BuildConfig {
+        let resolution_graph = BuildConfig {
             config,
             run_bytecode_verifier: true,
             print_diags_to_stderr: true,
-            chain_id,
+            chain_id: chain_id.clone(),
         }
-        .build(rerooted_path)?;
+        .resolution_graph(rerooted_path, chain_id.clone())?;
+        Self::build_resolved(
+            rerooted_path,
+            resolution_graph,
+            None,
+            with_unpublished_deps,
+            dump_bytecode_as_base64,
+            generate_struct_layouts,
+            chain_id,
+        )
+    }
+
+    /// Builds the package at `rerooted_path`, whose dependencies have been resolved into
+    /// `resolution_graph` and, optionally, compiled into `pre_compiled_deps`.
+    fn build_resolved(
+        rerooted_path: &Path,
+        resolution_graph: ResolvedGraph,
+        pre_compiled_deps: Option<Arc<FullyCompiledProgram>>,
+        with_unpublished_deps: bool,
+        dump_bytecode_as_base64: bool,
+        generate_struct_layouts: bool,
+        chain_id: Option<String>,
+    ) -> anyhow::Result<()> {
+        let pkg = build_from_resolution_graph_with_pre_compiled_deps(
+            resolution_graph,
+            pre_compiled_deps,
+            /* run_bytecode_verifier */ true,
+            /* print_diags_to_stderr */ true,
+            chain_id,
+        )?;
         if dump_bytecode_as_base64 {
             check_invalid_dependencies(&pkg.dependency_ids.invalid)?;
             if !with_unpublished_deps {
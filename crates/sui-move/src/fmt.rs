@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use move_cli::base::fmt;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::path::Path;
+
+#[derive(Parser)]
+#[group(id = "sui-move-fmt")]
+pub struct Fmt {
+    #[clap(flatten)]
+    pub fmt: fmt::Fmt,
+}
+
+impl Fmt {
+    pub fn execute(self, path: Option<&Path>, config: MoveBuildConfig) -> anyhow::Result<()> {
+        self.fmt.execute(path, config)
+    }
+}
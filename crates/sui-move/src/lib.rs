@@ -10,16 +10,19 @@ use sui_move_build::set_sui_flavor;
 pub mod build;
 pub mod coverage;
 pub mod disassemble;
+pub mod fmt;
 pub mod manage_package;
 pub mod migrate;
 pub mod new;
 pub mod unit_test;
+pub mod watch;
 
 #[derive(Parser)]
 pub enum Command {
     Build(build::Build),
     Coverage(coverage::Coverage),
     Disassemble(disassemble::Disassemble),
+    Fmt(fmt::Fmt),
     ManagePackage(manage_package::ManagePackage),
     Migrate(migrate::Migrate),
     New(new::New),
@@ -45,6 +48,7 @@ pub fn execute_move_command(
         Command::Build(c) => c.execute(package_path, build_config),
         Command::Coverage(c) => c.execute(package_path, build_config),
         Command::Disassemble(c) => c.execute(package_path, build_config),
+        Command::Fmt(c) => c.execute(package_path, build_config),
         Command::ManagePackage(c) => c.execute(package_path, build_config),
         Command::Migrate(c) => c.execute(package_path, build_config),
         Command::New(c) => c.execute(package_path),
@@ -1,12 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::watch::watch_package;
 use clap::Parser;
 use move_cli::base::{
     self,
     test::{self, UnitTestResult},
 };
-use move_package::BuildConfig;
+use move_compiler::{diagnostics::Diagnostics, FullyCompiledProgram};
+use move_package::{resolution::resolution_graph::ResolvedGraph, BuildConfig};
 use move_unit_test::{extensions::set_extension_hook, UnitTestingConfig};
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 use once_cell::sync::Lazy;
@@ -28,6 +30,10 @@ const MAX_UNIT_TEST_INSTRUCTIONS: u64 = 1_000_000;
 pub struct Test {
     #[clap(flatten)]
     pub test: test::Test,
+    /// Keep running, and run the tests again whenever a source file of the package or of its
+    /// local dependencies changes.
+    #[clap(long)]
+    pub watch: bool,
 }
 
 impl Test {
@@ -47,6 +53,33 @@ impl Test {
         // find manifest file directory from a given path or (if missing) from current dir
         let rerooted_path = base::reroot_path(path)?;
         let unit_test_config = self.test.unit_test_config();
+        if self.watch {
+            let build_config = test_build_config(build_config, save_disassembly);
+            watch_package(
+                &rerooted_path,
+                || {
+                    build_config.clone().resolution_graph_for_package(
+                        &rerooted_path,
+                        None,
+                        &mut Vec::new(),
+                    )
+                },
+                |state| {
+                    // tests matching the filter are run again, but only the packages that
+                    // changed since the previous run are compiled again
+                    let pre_compiled_deps = state.pre_compiled_deps()?;
+                    run_move_unit_tests_with_resolution_graph(
+                        &rerooted_path,
+                        state.resolution_graph.clone(),
+                        Some(pre_compiled_deps),
+                        Some(unit_test_config.clone()),
+                        compute_coverage,
+                    )
+                    .map(|_| ())
+                },
+            )?;
+            return Ok(UnitTestResult::Success);
+        }
         run_move_unit_tests(
             &rerooted_path,
             build_config,
@@ -79,16 +112,10 @@ pub fn run_move_unit_tests(
     // bind the extension hook if it has not yet been done
     Lazy::force(&SET_EXTENSION_HOOK);
 
-    let config = config
-        .unwrap_or_else(|| UnitTestingConfig::default_with_bound(Some(MAX_UNIT_TEST_INSTRUCTIONS)));
-
     let result = move_cli::base::test::run_move_unit_tests(
         path,
         build_config,
-        UnitTestingConfig {
-            report_stacktrace_on_abort: true,
-            ..config
-        },
+        unit_testing_config(config),
         sui_move_natives::all_natives(
             /* silent */ false,
             &ProtocolConfig::get_for_max_version_UNSAFE(),
@@ -98,14 +125,64 @@ pub fn run_move_unit_tests(
         save_disassembly,
         &mut std::io::stdout(),
     );
-    result.map(|(test_result, warning_diags)| {
-        if test_result == UnitTestResult::Success {
-            if let Some(diags) = warning_diags {
-                decorate_warnings(diags, None);
-            }
+    result.map(decorate_test_result)
+}
+
+/// Like [run_move_unit_tests], for a package whose dependencies have already been resolved with
+/// the build config returned by [test_build_config] and, optionally, compiled. Compilation errors
+/// are reported as a [UnitTestResult::Failure] instead of terminating the process.
+pub fn run_move_unit_tests_with_resolution_graph(
+    path: &Path,
+    resolution_graph: ResolvedGraph,
+    pre_compiled_deps: Option<Arc<FullyCompiledProgram>>,
+    config: Option<UnitTestingConfig>,
+    compute_coverage: bool,
+) -> anyhow::Result<UnitTestResult> {
+    // bind the extension hook if it has not yet been done
+    Lazy::force(&SET_EXTENSION_HOOK);
+
+    let result = move_cli::base::test::run_move_unit_tests_with_resolution_graph(
+        path,
+        resolution_graph,
+        pre_compiled_deps,
+        unit_testing_config(config),
+        sui_move_natives::all_natives(
+            /* silent */ false,
+            &ProtocolConfig::get_for_max_version_UNSAFE(),
+        ),
+        Some(initial_cost_schedule_for_unit_tests()),
+        compute_coverage,
+        &mut std::io::stdout(),
+    );
+    result.map(decorate_test_result)
+}
+
+fn unit_testing_config(config: Option<UnitTestingConfig>) -> UnitTestingConfig {
+    let config = config
+        .unwrap_or_else(|| UnitTestingConfig::default_with_bound(Some(MAX_UNIT_TEST_INSTRUCTIONS)));
+    UnitTestingConfig {
+        report_stacktrace_on_abort: true,
+        ..config
+    }
+}
+
+fn decorate_test_result(
+    (test_result, warning_diags): (UnitTestResult, Option<Diagnostics>),
+) -> UnitTestResult {
+    if test_result == UnitTestResult::Success {
+        if let Some(diags) = warning_diags {
+            decorate_warnings(diags, None);
         }
-        test_result
-    })
+    }
+    test_result
+}
+
+/// The build config unit tests are compiled with.
+pub fn test_build_config(mut build_config: BuildConfig, save_disassembly: bool) -> BuildConfig {
+    build_config.test_mode = true;
+    build_config.dev_mode = true;
+    build_config.save_disassembly = save_disassembly;
+    build_config
 }
 
 fn new_testing_object_and_natives_cost_runtime(ext: &mut NativeContextExtensions) {
@@ -0,0 +1,306 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context;
+use colored::Colorize;
+use move_command_line_common::env::MOVE_HOME;
+use move_compiler::{
+    diagnostics::report_diagnostics_to_buffer_with_env_color, FullyCompiledProgram,
+};
+use move_package::{
+    compilation::build_plan::BuildPlan, resolution::resolution_graph::ResolvedGraph,
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    collections::BTreeSet,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// How long to wait for more file events after the first one, so that a save touching several
+/// files triggers a single run.
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
+
+const MANIFEST_FILE: &str = "Move.toml";
+
+/// The state of a watched package that is reused across runs.
+pub struct PackageState {
+    /// The resolved dependencies of the package, kept until a `Move.toml` changes.
+    pub resolution_graph: ResolvedGraph,
+    /// The dependencies of the package that are available as source, compiled the first time
+    /// they are needed and kept until one of their files changes.
+    pre_compiled_deps: Option<Arc<FullyCompiledProgram>>,
+}
+
+impl PackageState {
+    fn new(resolution_graph: ResolvedGraph) -> Self {
+        Self {
+            resolution_graph,
+            pre_compiled_deps: None,
+        }
+    }
+
+    /// The dependencies of the package that are available as source, compiled on their own so
+    /// that only the root package needs to be compiled again while they do not change.
+    pub fn pre_compiled_deps(&mut self) -> anyhow::Result<Arc<FullyCompiledProgram>> {
+        if let Some(deps) = &self.pre_compiled_deps {
+            return Ok(deps.clone());
+        }
+        let build_plan = BuildPlan::create(self.resolution_graph.clone())?;
+        let deps = match build_plan.compile_source_deps()? {
+            Ok(deps) => Arc::new(deps),
+            Err((files, diags)) => {
+                let buffer = report_diagnostics_to_buffer_with_env_color(&files, diags);
+                std::io::stderr().write_all(&buffer)?;
+                anyhow::bail!("Compilation error in dependencies");
+            }
+        };
+        self.pre_compiled_deps = Some(deps.clone());
+        Ok(deps)
+    }
+}
+
+/// Runs `run` on the state of the package at `path`, and again every time a Move source file of
+/// the package or one of its local dependencies changes, until the process is interrupted.
+///
+/// The package's dependencies are resolved with `resolve` once, and the resulting graph is reused
+/// across runs until a `Move.toml` changes. Similarly, the dependencies compiled through
+/// [PackageState::pre_compiled_deps] are reused until a source file of one of them changes, so
+/// that changes to the root package only rebuild the root package. Dependencies fetched into
+/// `MOVE_HOME` are not watched, and neither are test sources unless the graph was resolved in
+/// test mode. Errors from `run` are reported without stopping the watch, as are errors resolving
+/// the dependencies.
+pub fn watch_package(
+    path: &Path,
+    resolve: impl Fn() -> anyhow::Result<ResolvedGraph>,
+    mut run: impl FnMut(&mut PackageState) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        match res {
+            Ok(event) => {
+                // The receiver only goes away when watching stops.
+                let _ = sender.send(event.paths);
+            }
+            Err(e) => eprintln!("{}", format!("[watch] Error: {e}").red()),
+        }
+    })
+    .context("Cannot start watching for file changes")?;
+
+    let mut resolved: Option<(PackageState, WatchedPackages)> = None;
+    loop {
+        if resolved.is_none() {
+            match resolve() {
+                Ok(graph) => {
+                    let packages = WatchedPackages::new(&graph);
+                    packages.watch(&mut watcher)?;
+                    resolved = Some((PackageState::new(graph), packages));
+                }
+                Err(e) => {
+                    eprintln!("{}", format!("[watch] {e:?}").red());
+                    // Wait for the manifest of the root package to be fixed.
+                    watcher
+                        .watch(path, RecursiveMode::Recursive)
+                        .with_context(|| format!("Cannot watch {}", path.display()))?;
+                }
+            }
+        }
+
+        if let Some((state, _)) = &mut resolved {
+            if let Err(e) = run(state) {
+                eprintln!("{}", format!("[watch] {e}").red());
+            }
+        }
+
+        eprintln!(
+            "{}",
+            "[watch] Waiting for changes, press Ctrl-C to stop".dimmed()
+        );
+        let changed = loop {
+            let changed = wait_for_changes(&receiver)?;
+            let relevant = changed
+                .into_iter()
+                .filter(|path| match &resolved {
+                    Some((_, packages)) => packages.is_relevant(path),
+                    None => is_manifest(path) || is_move_source(path),
+                })
+                .collect::<BTreeSet<_>>();
+            if !relevant.is_empty() {
+                break relevant;
+            }
+        };
+
+        for path in &changed {
+            eprintln!(
+                "{}",
+                format!("[watch] Changed: {}", path.display()).dimmed()
+            );
+        }
+        if changed.iter().any(|path| is_manifest(path)) {
+            // Dependencies may have changed, resolve them again and watch the new set of packages.
+            if let Some((_, packages)) = resolved.take() {
+                packages.unwatch(&mut watcher);
+            }
+            let _ = watcher.unwatch(path);
+        } else if let Some((state, packages)) = &mut resolved {
+            if changed.iter().any(|path| packages.is_in_dependency(path)) {
+                state.pre_compiled_deps = None;
+            }
+        }
+    }
+}
+
+/// Blocks until a file changes, then collects the changes made until no more happen for the
+/// debounce interval.
+fn wait_for_changes(receiver: &Receiver<Vec<PathBuf>>) -> anyhow::Result<BTreeSet<PathBuf>> {
+    let mut changed = BTreeSet::new();
+    changed.extend(
+        receiver
+            .recv()
+            .context("Stopped watching for file changes")?,
+    );
+    while let Ok(paths) = receiver.recv_timeout(DEBOUNCE_INTERVAL) {
+        changed.extend(paths);
+    }
+    Ok(changed)
+}
+
+/// The root directories of the packages to watch: the root package and its dependencies that
+/// are not managed by the package system.
+struct WatchedPackages {
+    root: PathBuf,
+    dependencies: Vec<PathBuf>,
+    include_tests: bool,
+}
+
+impl WatchedPackages {
+    fn new(graph: &ResolvedGraph) -> Self {
+        let move_home = Path::new(MOVE_HOME.as_str());
+        let root = graph.package_table[&graph.root_package()]
+            .package_path
+            .clone();
+        let dependencies = graph
+            .package_table
+            .values()
+            .map(|package| package.package_path.clone())
+            .filter(|path| *path != root && !path.starts_with(move_home))
+            .collect();
+        Self {
+            root,
+            dependencies,
+            include_tests: graph.build_options.test_mode,
+        }
+    }
+
+    fn roots(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.root).chain(&self.dependencies)
+    }
+
+    fn watch(&self, watcher: &mut RecommendedWatcher) -> anyhow::Result<()> {
+        for root in self.roots() {
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .with_context(|| format!("Cannot watch {}", root.display()))?;
+        }
+        Ok(())
+    }
+
+    fn unwatch(&self, watcher: &mut RecommendedWatcher) {
+        for root in self.roots() {
+            let _ = watcher.unwatch(root);
+        }
+    }
+
+    /// The root directory of the watched package `path` belongs to. Local dependencies can be
+    /// nested in the directory of another package, so this is the longest root directory
+    /// containing `path`.
+    fn package_root(&self, path: &Path) -> Option<&PathBuf> {
+        self.roots()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+    }
+
+    /// Whether a change to `path` affects the build: Move sources and manifests of the watched
+    /// packages, but not build outputs, lock files (which builds update) or, when not testing,
+    /// test sources.
+    fn is_relevant(&self, path: &Path) -> bool {
+        let Some(root) = self.package_root(path) else {
+            return false;
+        };
+        let Ok(relative) = path.strip_prefix(root) else {
+            return false;
+        };
+        if relative.starts_with("build") {
+            return false;
+        }
+        if relative.starts_with("tests") && !self.include_tests {
+            return false;
+        }
+        is_manifest(path) || is_move_source(path)
+    }
+
+    /// Whether `path` belongs to one of the dependencies rather than to the root package.
+    fn is_in_dependency(&self, path: &Path) -> bool {
+        self.package_root(path)
+            .is_some_and(|root| *root != self.root)
+    }
+}
+
+fn is_manifest(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == MANIFEST_FILE)
+}
+
+fn is_move_source(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "move")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WatchedPackages;
+    use std::path::{Path, PathBuf};
+
+    fn packages(include_tests: bool) -> WatchedPackages {
+        WatchedPackages {
+            root: PathBuf::from("/pkg"),
+            dependencies: vec![PathBuf::from("/dep"), PathBuf::from("/pkg/deps/nested")],
+            include_tests,
+        }
+    }
+
+    #[test]
+    fn relevant_changes() {
+        let packages = packages(/* include_tests */ false);
+        assert!(packages.is_relevant(Path::new("/pkg/sources/a.move")));
+        assert!(packages.is_relevant(Path::new("/pkg/Move.toml")));
+        assert!(packages.is_relevant(Path::new("/dep/sources/b.move")));
+        assert!(packages.is_relevant(Path::new("/pkg/deps/nested/sources/c.move")));
+
+        // build outputs, lock files, other files and files outside the watched packages
+        assert!(!packages.is_relevant(Path::new("/pkg/build/pkg/sources/a.move")));
+        assert!(!packages.is_relevant(Path::new("/pkg/deps/nested/build/c.move")));
+        assert!(!packages.is_relevant(Path::new("/pkg/Move.lock")));
+        assert!(!packages.is_relevant(Path::new("/pkg/sources/notes.md")));
+        assert!(!packages.is_relevant(Path::new("/other/sources/d.move")));
+
+        // test sources are only relevant when testing
+        assert!(!packages.is_relevant(Path::new("/pkg/tests/a_tests.move")));
+        assert!(!packages.is_relevant(Path::new("/dep/tests/b_tests.move")));
+        let packages = self::packages(/* include_tests */ true);
+        assert!(packages.is_relevant(Path::new("/pkg/tests/a_tests.move")));
+        assert!(packages.is_relevant(Path::new("/dep/tests/b_tests.move")));
+    }
+
+    #[test]
+    fn dependency_changes() {
+        let packages = packages(/* include_tests */ true);
+        assert!(!packages.is_in_dependency(Path::new("/pkg/sources/a.move")));
+        assert!(!packages.is_in_dependency(Path::new("/pkg/deps/b.move")));
+        assert!(packages.is_in_dependency(Path::new("/dep/sources/b.move")));
+        assert!(packages.is_in_dependency(Path::new("/pkg/deps/nested/sources/c.move")));
+    }
+}
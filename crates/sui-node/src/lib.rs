@@ -47,6 +47,7 @@ use tap::tap::TapFallible;
 use tokio::runtime::Handle;
 use tokio::sync::{broadcast, mpsc, watch, Mutex};
 use tokio::task::{JoinHandle, JoinSet};
+use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
 use tracing::{debug, error, warn};
 use tracing::{error_span, info, Instrument};
@@ -228,6 +229,8 @@ pub struct SuiNode {
     validator_components: Mutex<Option<ValidatorComponents>>,
     /// The http server responsible for serving JSON-RPC as well as the experimental rest service
     _http_server: Option<sui_http::ServerHandle>,
+    /// Cancelled when the node shuts down, to end the streams the RPC service has open
+    rpc_shutdown: CancellationToken,
     state: Arc<AuthorityState>,
     transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     registry_service: RegistryService,
@@ -767,6 +770,7 @@ impl SuiNode {
             None
         };
 
+        let rpc_shutdown = CancellationToken::new();
         let http_server = build_http_server(
             state.clone(),
             state_sync_store,
@@ -775,6 +779,7 @@ impl SuiNode {
             &prometheus_registry,
             custom_rpc_runtime,
             software_version,
+            rpc_shutdown.clone(),
         )
         .await?;
 
@@ -840,6 +845,7 @@ impl SuiNode {
             config,
             validator_components: Mutex::new(validator_components),
             _http_server: http_server,
+            rpc_shutdown,
             state,
             transaction_orchestrator,
             registry_service,
@@ -1855,6 +1861,7 @@ impl SuiNode {
     }
 
     async fn shutdown(&self) {
+        self.rpc_shutdown.cancel();
         if let Some(validator_components) = &*self.validator_components.lock().await {
             validator_components.consensus_manager.shutdown().await;
         }
@@ -2022,6 +2029,7 @@ pub async fn build_http_server(
     prometheus_registry: &Registry,
     _custom_runtime: Option<Handle>,
     software_version: &'static str,
+    rpc_shutdown: CancellationToken,
 ) -> Result<Option<sui_http::ServerHandle>> {
     // Validators do not expose these APIs
     if config.consensus_config().is_some() {
@@ -2115,6 +2123,7 @@ pub async fn build_http_server(
         }
 
         rpc_service.with_metrics(RpcMetrics::new(prometheus_registry));
+        rpc_service.with_shutdown_signal(rpc_shutdown);
 
         if let Some(transaction_orchestrator) = transaction_orchestrator {
             rpc_service.with_executor(transaction_orchestrator.clone())
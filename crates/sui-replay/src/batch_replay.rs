@@ -187,6 +187,7 @@ async fn execute_transaction(
                 None,
                 None,
                 None,
+                None,
             )
             .await;
         match result {
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::replay::ProtocolVersionSummary;
 use crate::types::ReplayEngineError;
 use crate::types::EPOCH_CHANGE_STRUCT_TAG;
 use async_trait::async_trait;
@@ -9,11 +10,19 @@ use lru::LruCache;
 use move_core_types::language_storage::StructTag;
 use parking_lot::RwLock;
 use rand::Rng;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use sui_archival::reader::ArchiveReader;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::authority::NodeStateDump;
+use sui_framework::BuiltInFramework;
 use sui_json_rpc_api::QUERY_MAX_RESULT_LIMIT;
+use sui_json_rpc_types::BcsEvent;
 use sui_json_rpc_types::EventFilter;
 use sui_json_rpc_types::SuiEvent;
 use sui_json_rpc_types::SuiGetPastObjectRequest;
@@ -21,15 +30,31 @@ use sui_json_rpc_types::SuiObjectData;
 use sui_json_rpc_types::SuiObjectDataOptions;
 use sui_json_rpc_types::SuiObjectResponse;
 use sui_json_rpc_types::SuiPastObjectResponse;
+use sui_json_rpc_types::SuiTransactionBlockEffects;
 use sui_json_rpc_types::SuiTransactionBlockResponse;
 use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
 use sui_sdk::SuiClient;
+use sui_storage::blob::Blob;
 use sui_types::base_types::{ObjectID, SequenceNumber, VersionNumber};
-use sui_types::digests::TransactionDigest;
+use sui_types::committee::EpochId;
+use sui_types::digests::{
+    get_mainnet_chain_identifier, get_testnet_chain_identifier, ChainIdentifier, TransactionDigest,
+};
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
+use sui_types::event::{EventID, SystemEpochInfoEvent};
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::inner_temporary_store::WrittenObjects;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Object;
+use sui_types::storage::{ReadStore, SharedInMemoryStore};
+use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
 use sui_types::transaction::SenderSignedData;
 use sui_types::transaction::TransactionDataAPI;
-use sui_types::transaction::{EndOfEpochTransactionKind, TransactionKind};
+use sui_types::transaction::{ChangeEpoch, EndOfEpochTransactionKind, TransactionKind};
+use sui_types::{is_system_package, SUI_SYSTEM_STATE_OBJECT_ID};
+
+/// Extension of the checkpoint files read by `CheckpointFetcher`
+const CHECKPOINT_FILE_SUFFIX: &str = "chk";
 
 /// This trait defines the interfaces for fetching data from some local or remote store
 #[async_trait]
@@ -96,13 +121,14 @@ pub(crate) trait DataFetcher {
 pub enum Fetchers {
     Remote(RemoteFetcher),
     NodeStateDump(NodeStateDumpFetcher),
+    Checkpoint(CheckpointFetcher),
 }
 
 impl Fetchers {
     pub fn as_remote(&self) -> &RemoteFetcher {
         match self {
             Fetchers::Remote(q) => q,
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            _ => panic!("not a remote fetcher"),
         }
     }
 
@@ -114,14 +140,21 @@ impl Fetchers {
                 q.clear_cache_for_new_task();
                 q
             }
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            _ => panic!("not a remote fetcher"),
         }
     }
 
     pub fn as_node_state_dump(&self) -> &NodeStateDumpFetcher {
         match self {
-            Fetchers::Remote(_) => panic!("not a node state dump fetcher"),
             Fetchers::NodeStateDump(q) => q,
+            _ => panic!("not a node state dump fetcher"),
+        }
+    }
+
+    pub fn as_checkpoint(&self) -> &CheckpointFetcher {
+        match self {
+            Fetchers::Checkpoint(q) => q,
+            _ => panic!("not a checkpoint fetcher"),
         }
     }
 }
@@ -136,6 +169,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_versioned(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_versioned(objects).await,
+            Fetchers::Checkpoint(q) => q.multi_get_versioned(objects).await,
         }
     }
 
@@ -146,6 +180,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_latest(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_latest(objects).await,
+            Fetchers::Checkpoint(q) => q.multi_get_latest(objects).await,
         }
     }
 
@@ -156,6 +191,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_checkpoint_txs(id).await,
             Fetchers::NodeStateDump(q) => q.get_checkpoint_txs(id).await,
+            Fetchers::Checkpoint(q) => q.get_checkpoint_txs(id).await,
         }
     }
 
@@ -166,6 +202,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_transaction(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_transaction(tx_digest).await,
+            Fetchers::Checkpoint(q) => q.get_transaction(tx_digest).await,
         }
     }
 
@@ -176,6 +213,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_loaded_child_objects(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_loaded_child_objects(tx_digest).await,
+            Fetchers::Checkpoint(q) => q.get_loaded_child_objects(tx_digest).await,
         }
     }
 
@@ -183,6 +221,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_latest_checkpoint_sequence_number().await,
             Fetchers::NodeStateDump(q) => q.get_latest_checkpoint_sequence_number().await,
+            Fetchers::Checkpoint(q) => q.get_latest_checkpoint_sequence_number().await,
         }
     }
 
@@ -200,6 +239,10 @@ impl DataFetcher for Fetchers {
                 q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
                     .await
             }
+            Fetchers::Checkpoint(q) => {
+                q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
+                    .await
+            }
         }
     }
 
@@ -210,6 +253,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
+            Fetchers::Checkpoint(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
         }
     }
 
@@ -220,12 +264,14 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_change_events(reverse).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_change_events(reverse).await,
+            Fetchers::Checkpoint(q) => q.get_epoch_change_events(reverse).await,
         }
     }
     async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
         match self {
             Fetchers::Remote(q) => q.get_chain_id().await,
             Fetchers::NodeStateDump(q) => q.get_chain_id().await,
+            Fetchers::Checkpoint(q) => q.get_chain_id().await,
         }
     }
     async fn get_child_object(
@@ -236,6 +282,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_child_object(object_id, version_upper_bound).await,
             Fetchers::NodeStateDump(q) => q.get_child_object(object_id, version_upper_bound).await,
+            Fetchers::Checkpoint(q) => q.get_child_object(object_id, version_upper_bound).await,
         }
     }
 }
@@ -824,3 +871,1116 @@ impl DataFetcher for NodeStateDumpFetcher {
         unimplemented!("get child object is not implemented for state dump");
     }
 }
+
+/// A transaction read from a checkpoint file or archive. Its input and output objects are kept in
+/// the fetcher's object pool instead.
+#[derive(Clone, Debug)]
+pub struct CheckpointTransactionInfo {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: u64,
+    pub transaction: SenderSignedData,
+    pub effects: TransactionEffects,
+    /// Archives do not hold events, so this is `None` for transactions read from one
+    pub events: Option<TransactionEvents>,
+}
+
+#[derive(Clone, Debug)]
+struct EpochStartInfo {
+    protocol_version: u64,
+    start_timestamp_ms: u64,
+    /// Only known once the transaction which started the epoch has been replayed, if it was read
+    /// from an archive, as it comes from the transaction's events.
+    reference_gas_price: Option<u64>,
+    /// The transaction which started the epoch
+    epoch_change_tx: TransactionDigest,
+    /// The first checkpoint of the epoch, if the checkpoint before it is in the checkpoint files
+    first_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// Serves the data needed to replay transactions from local checkpoint data, without access to a
+/// full node. The checkpoints come from either:
+///
+/// - A directory of `CheckpointData` blobs named `<sequence number>.chk`, as written by the
+///   checkpoint ingestion pipeline (see `new`). These hold the transactions' events and input
+///   and output objects.
+/// - A `sui-archival` archive (see `from_archive`). This holds the checkpoints' transactions and
+///   effects, but no events or objects, so the objects are instead read from a formal snapshot
+///   and recorded as each transaction is replayed (see `record_replayed`).
+///
+/// Everything in the checkpoints is loaded into memory, so they should only cover the range of
+/// interest.
+///
+/// A transaction can be replayed if the checkpoints contain it, the objects it reads, and the end
+/// of epoch transaction that started its epoch. Objects that were last written before the first
+/// checkpoint, and the state at the start of the epoch, can instead come from the store of a
+/// database restored from a formal snapshot (e.g. with `sui-tool download-formal-snapshot`).
+#[derive(Clone)]
+pub struct CheckpointFetcher {
+    transactions: Arc<HashMap<TransactionDigest, CheckpointTransactionInfo>>,
+    checkpoint_txs: Arc<BTreeMap<CheckpointSequenceNumber, Vec<TransactionDigest>>>,
+    /// Every version of every object read or written by the transactions in the checkpoints, or
+    /// by the transactions replayed so far if the checkpoints came from an archive
+    object_pool: Arc<RwLock<BTreeMap<(ObjectID, SequenceNumber), Object>>>,
+    epochs: Arc<RwLock<BTreeMap<EpochId, EpochStartInfo>>>,
+    /// For each system package, its versions and the first epoch each version was used in
+    system_package_versions: Arc<RwLock<BTreeMap<ObjectID, BTreeMap<EpochId, SequenceNumber>>>>,
+    chain_id: Option<String>,
+    snapshot: Option<Arc<AuthorityPerpetualTables>>,
+}
+
+impl CheckpointFetcher {
+    /// Loads the checkpoint files in `checkpoint_path`, and opens the snapshot store at
+    /// `snapshot_path` if there is one. The chain is identified by the genesis checkpoint if it is
+    /// in the checkpoint files, and by `chain` (`mainnet` or `testnet`) otherwise.
+    pub fn new(
+        checkpoint_path: &Path,
+        snapshot_path: Option<&Path>,
+        chain: Option<&str>,
+    ) -> Result<Self, ReplayEngineError> {
+        let mut transactions = HashMap::new();
+        let mut checkpoint_txs = BTreeMap::new();
+        let mut object_pool = BTreeMap::new();
+        let mut epochs = BTreeMap::new();
+        let mut system_package_versions: BTreeMap<ObjectID, BTreeMap<EpochId, SequenceNumber>> =
+            BTreeMap::new();
+        let mut chain_id = chain_identifier(chain);
+
+        let files = checkpoint_files(checkpoint_path)?;
+        if files.is_empty() {
+            return Err(ReplayEngineError::UnableToReadCheckpoint {
+                path: checkpoint_path.display().to_string(),
+                err: "no checkpoint files found".to_string(),
+            });
+        }
+
+        for path in files {
+            let checkpoint = read_checkpoint(&path)?;
+            let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+            let timestamp_ms = checkpoint.checkpoint_summary.timestamp_ms;
+            if sequence_number == 0 {
+                chain_id = Some(
+                    ChainIdentifier::from(*checkpoint.checkpoint_summary.digest()).to_string(),
+                );
+            }
+
+            let mut digests = vec![];
+            for tx in checkpoint.transactions {
+                let digest = *tx.effects.transaction_digest();
+                let executed_epoch = tx.effects.executed_epoch();
+                let kind = tx.transaction.transaction_data().kind();
+
+                if let Some(change) = change_epoch(kind) {
+                    epochs.insert(
+                        change.epoch,
+                        EpochStartInfo {
+                            protocol_version: change.protocol_version.as_u64(),
+                            start_timestamp_ms: change.epoch_start_timestamp_ms,
+                            reference_gas_price: Some(reference_gas_price(
+                                tx.events.as_ref(),
+                                change.epoch,
+                            )?),
+                            epoch_change_tx: digest,
+                            first_checkpoint: Some(sequence_number + 1),
+                        },
+                    );
+                }
+
+                // System packages written at genesis are used from the first epoch, and those
+                // written by an end of epoch transaction from the next epoch.
+                let first_epoch = if matches!(kind, TransactionKind::Genesis(_)) {
+                    executed_epoch
+                } else {
+                    executed_epoch + 1
+                };
+                for object in &tx.output_objects {
+                    if object.is_package() && is_system_package(object.id()) {
+                        system_package_versions
+                            .entry(object.id())
+                            .or_default()
+                            .insert(first_epoch, object.version());
+                    }
+                }
+
+                for object in tx.input_objects.into_iter().chain(tx.output_objects) {
+                    object_pool.insert((object.id(), object.version()), object);
+                }
+
+                transactions.insert(
+                    digest,
+                    CheckpointTransactionInfo {
+                        checkpoint: sequence_number,
+                        timestamp_ms,
+                        transaction: tx.transaction.into_data(),
+                        effects: tx.effects,
+                        events: tx.events,
+                    },
+                );
+                digests.push(digest);
+            }
+            checkpoint_txs.insert(sequence_number, digests);
+        }
+
+        let snapshot =
+            snapshot_path.map(|path| Arc::new(AuthorityPerpetualTables::open(path, None)));
+        if let Some(snapshot) = &snapshot {
+            add_snapshot_epoch(snapshot, &mut epochs, &mut system_package_versions)?;
+        }
+
+        Ok(Self {
+            transactions: Arc::new(transactions),
+            checkpoint_txs: Arc::new(checkpoint_txs),
+            object_pool: Arc::new(RwLock::new(object_pool)),
+            epochs: Arc::new(RwLock::new(epochs)),
+            system_package_versions: Arc::new(RwLock::new(system_package_versions)),
+            chain_id,
+            snapshot,
+        })
+    }
+
+    /// Reads `checkpoints` from `archive`, and opens the store at `snapshot_path`, restored from a
+    /// formal snapshot, which the objects are read from. The snapshot holds the state at the start
+    /// of an epoch, so `checkpoints` must start with that epoch's first checkpoint, and the
+    /// transactions in them must be replayed in order (see `record_replayed`), to derive the
+    /// objects that later transactions read. The chain is identified as in `new`.
+    pub async fn from_archive(
+        archive: &ArchiveReader,
+        checkpoints: RangeInclusive<CheckpointSequenceNumber>,
+        snapshot_path: &Path,
+        chain: Option<&str>,
+    ) -> Result<Self, ReplayEngineError> {
+        let archive_err =
+            |e: anyhow::Error| ReplayEngineError::UnableToReadArchive { err: e.to_string() };
+        archive.sync_manifest_once().await.map_err(archive_err)?;
+
+        // The checkpoint before the range is read too, to check that the range starts an epoch.
+        let start = *checkpoints.start();
+        let store = SharedInMemoryStore::default();
+        archive
+            .read(
+                store.clone(),
+                start.saturating_sub(1)..checkpoints.end() + 1,
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                false,
+            )
+            .await
+            .map_err(archive_err)?;
+        let mut fetcher = Self::from_checkpoint_store(&store, checkpoints, chain)?;
+
+        let snapshot = Arc::new(AuthorityPerpetualTables::open(snapshot_path, None));
+        let epoch = add_snapshot_epoch(
+            &snapshot,
+            &mut fetcher.epochs.write(),
+            &mut fetcher.system_package_versions.write(),
+        )?;
+        let starts_epoch = start == 0
+            || store
+                .get_checkpoint_by_sequence_number(start - 1)
+                .is_some_and(|previous| previous.end_of_epoch_data.is_some());
+        let first_epoch = store
+            .get_checkpoint_by_sequence_number(start)
+            .map(|first| first.epoch);
+        if !starts_epoch || first_epoch != Some(epoch) {
+            return Err(ReplayEngineError::GeneralError {
+                err: format!(
+                    "checkpoint {start} is not the first checkpoint of epoch {epoch}, which the \
+                    snapshot holds the starting state of"
+                ),
+            });
+        }
+
+        fetcher.snapshot = Some(snapshot);
+        Ok(fetcher)
+    }
+
+    /// Indexes `checkpoints` in `store`, which holds their summaries, transactions and effects.
+    fn from_checkpoint_store(
+        store: &SharedInMemoryStore,
+        checkpoints: RangeInclusive<CheckpointSequenceNumber>,
+        chain: Option<&str>,
+    ) -> Result<Self, ReplayEngineError> {
+        let mut transactions = HashMap::new();
+        let mut checkpoint_txs = BTreeMap::new();
+        let mut epochs = BTreeMap::new();
+        let mut chain_id = chain_identifier(chain);
+
+        for sequence_number in checkpoints {
+            let not_found = || ReplayEngineError::CheckpointNotFound {
+                checkpoint: sequence_number,
+            };
+            let summary = store
+                .get_checkpoint_by_sequence_number(sequence_number)
+                .ok_or_else(not_found)?;
+            let contents = store
+                .get_full_checkpoint_contents_by_sequence_number(sequence_number)
+                .ok_or_else(not_found)?;
+            if sequence_number == 0 {
+                chain_id = Some(ChainIdentifier::from(*summary.digest()).to_string());
+            }
+
+            let mut digests = vec![];
+            for tx in contents.iter() {
+                let digest = *tx.effects.transaction_digest();
+                if let Some(change) = change_epoch(tx.transaction.transaction_data().kind()) {
+                    epochs.insert(
+                        change.epoch,
+                        EpochStartInfo {
+                            protocol_version: change.protocol_version.as_u64(),
+                            start_timestamp_ms: change.epoch_start_timestamp_ms,
+                            reference_gas_price: None,
+                            epoch_change_tx: digest,
+                            first_checkpoint: Some(sequence_number + 1),
+                        },
+                    );
+                }
+
+                transactions.insert(
+                    digest,
+                    CheckpointTransactionInfo {
+                        checkpoint: sequence_number,
+                        timestamp_ms: summary.timestamp_ms,
+                        transaction: tx.transaction.data().clone(),
+                        effects: tx.effects.clone(),
+                        events: None,
+                    },
+                );
+                digests.push(digest);
+            }
+            checkpoint_txs.insert(sequence_number, digests);
+        }
+
+        Ok(Self {
+            transactions: Arc::new(transactions),
+            checkpoint_txs: Arc::new(checkpoint_txs),
+            object_pool: Default::default(),
+            epochs: Arc::new(RwLock::new(epochs)),
+            system_package_versions: Default::default(),
+            chain_id,
+            snapshot: None,
+        })
+    }
+
+    /// Records the outputs of replaying `tx_digest`: the objects it wrote, and if it started an
+    /// epoch, that epoch's reference gas price and system packages. Archives hold neither objects
+    /// nor events, so this is how the state after the snapshot becomes known.
+    pub fn record_replayed(
+        &self,
+        tx_digest: &TransactionDigest,
+        written: &WrittenObjects,
+        events: &TransactionEvents,
+    ) -> Result<(), ReplayEngineError> {
+        let tx = self.transaction(tx_digest)?;
+        {
+            let mut object_pool = self.object_pool.write();
+            for object in written.values() {
+                object_pool.insert((object.id(), object.version()), object.clone());
+            }
+        }
+
+        let Some(change) = change_epoch(tx.transaction.transaction_data().kind()) else {
+            return Ok(());
+        };
+        let reference_gas_price = reference_gas_price(Some(events), change.epoch)?;
+        if let Some(info) = self.epochs.write().get_mut(&change.epoch) {
+            info.reference_gas_price = Some(reference_gas_price);
+        }
+        let mut system_package_versions = self.system_package_versions.write();
+        for object in written.values() {
+            if object.is_package() && is_system_package(object.id()) {
+                system_package_versions
+                    .entry(object.id())
+                    .or_default()
+                    .insert(change.epoch, object.version());
+            }
+        }
+        Ok(())
+    }
+
+    /// The transactions in the checkpoints, in execution order
+    pub fn transactions_in_order(&self) -> Vec<TransactionDigest> {
+        self.checkpoint_txs.values().flatten().copied().collect()
+    }
+
+    pub fn transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<&CheckpointTransactionInfo, ReplayEngineError> {
+        self.transactions
+            .get(tx_digest)
+            .ok_or(ReplayEngineError::TransactionNotFound { digest: *tx_digest })
+    }
+
+    /// The range of epochs and checkpoints that each protocol version was used in, for the epochs
+    /// that started in the checkpoint files or the snapshot.
+    pub fn protocol_version_epoch_table(&self) -> BTreeMap<u64, ProtocolVersionSummary> {
+        let mut table = BTreeMap::new();
+        let mut current: Option<ProtocolVersionSummary> = None;
+        for (epoch, info) in self.epochs.read().iter() {
+            if let Some(summary) = current
+                .as_mut()
+                .filter(|s| s.protocol_version == info.protocol_version)
+            {
+                summary.epoch_end = *epoch;
+                continue;
+            }
+
+            if let Some(mut summary) = current.take() {
+                summary.checkpoint_end = info.first_checkpoint.map(|c| c - 1);
+                table.insert(summary.protocol_version, summary);
+            }
+            current = Some(ProtocolVersionSummary {
+                protocol_version: info.protocol_version,
+                epoch_start: *epoch,
+                epoch_end: *epoch,
+                checkpoint_start: info.first_checkpoint,
+                checkpoint_end: None,
+                epoch_change_tx: info.epoch_change_tx,
+            });
+        }
+
+        if let Some(mut summary) = current {
+            summary.checkpoint_end = self.checkpoint_txs.keys().next_back().copied();
+            table.insert(summary.protocol_version, summary);
+        }
+        table
+    }
+
+    /// The versions of the system packages used in each protocol version, for the epochs that
+    /// started in the checkpoint files or the snapshot.
+    pub fn system_package_table(&self) -> BTreeMap<u64, BTreeMap<ObjectID, SequenceNumber>> {
+        let system_package_versions = self.system_package_versions.read();
+        self.epochs
+            .read()
+            .iter()
+            .map(|(epoch, info)| {
+                let packages = system_package_versions
+                    .iter()
+                    .filter_map(|(id, versions)| {
+                        let (_, version) = versions.range(..=*epoch).next_back()?;
+                        Some((*id, *version))
+                    })
+                    .collect();
+                (info.protocol_version, packages)
+            })
+            .collect()
+    }
+
+    fn get_object(
+        &self,
+        id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        if let Some(object) = self.object_pool.read().get(&(*id, version)) {
+            return Ok(Some(object.clone()));
+        }
+        match &self.snapshot {
+            Some(snapshot) => Ok(snapshot.get_object_by_key_fallible(id, version)?),
+            None => Ok(None),
+        }
+    }
+
+    /// The highest version of the object that is at most `version`
+    fn get_object_at_or_before(
+        &self,
+        id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        let local = self
+            .object_pool
+            .read()
+            .range((*id, SequenceNumber::MIN)..=(*id, version))
+            .next_back()
+            .map(|(_, object)| object.clone());
+        let snapshot = match &self.snapshot {
+            Some(snapshot) => snapshot.find_object_lt_or_eq_version(*id, version)?,
+            None => None,
+        };
+        Ok(local
+            .into_iter()
+            .chain(snapshot)
+            .max_by_key(|o| o.version()))
+    }
+}
+
+#[async_trait]
+impl DataFetcher for CheckpointFetcher {
+    async fn multi_get_versioned(
+        &self,
+        objects: &[(ObjectID, SequenceNumber)],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        objects
+            .iter()
+            .map(|(id, version)| {
+                self.get_object(id, *version)?
+                    .ok_or(ReplayEngineError::ObjectVersionNotFound {
+                        id: *id,
+                        version: *version,
+                    })
+            })
+            .collect()
+    }
+
+    async fn multi_get_latest(
+        &self,
+        objects: &[ObjectID],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        objects
+            .iter()
+            .map(|id| {
+                self.get_object_at_or_before(id, SequenceNumber::MAX)?
+                    .ok_or(ReplayEngineError::ObjectNotExist { id: *id })
+            })
+            .collect()
+    }
+
+    async fn get_checkpoint_txs(
+        &self,
+        id: u64,
+    ) -> Result<Vec<TransactionDigest>, ReplayEngineError> {
+        self.checkpoint_txs
+            .get(&id)
+            .cloned()
+            .ok_or(ReplayEngineError::CheckpointNotFound { checkpoint: id })
+    }
+
+    async fn get_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, ReplayEngineError> {
+        let tx = self.transaction(tx_digest)?;
+        let mut response = SuiTransactionBlockResponse::new(*tx_digest);
+        response.raw_transaction = bcs::to_bytes(&tx.transaction)
+            .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?;
+        response.effects = Some(SuiTransactionBlockEffects::try_from(tx.effects.clone())?);
+        response.checkpoint = Some(tx.checkpoint);
+        response.timestamp_ms = Some(tx.timestamp_ms);
+        Ok(response)
+    }
+
+    async fn get_loaded_child_objects(
+        &self,
+        _: &TransactionDigest,
+    ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
+        Ok(vec![])
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, ReplayEngineError> {
+        Ok(*self
+            .checkpoint_txs
+            .keys()
+            .next_back()
+            .expect("Checkpoint fetcher must have at least one checkpoint"))
+    }
+
+    async fn fetch_random_transaction(
+        &self,
+        checkpoint_id_start: Option<u64>,
+        checkpoint_id_end: Option<u64>,
+    ) -> Result<TransactionDigest, ReplayEngineError> {
+        let checkpoint_id_start = checkpoint_id_start.unwrap_or(0);
+        let txs: Vec<_> = self
+            .checkpoint_txs
+            .range(checkpoint_id_start..=checkpoint_id_end.unwrap_or(u64::MAX))
+            .flat_map(|(_, txs)| txs)
+            .collect();
+        if txs.is_empty() {
+            return Err(ReplayEngineError::CheckpointNotFound {
+                checkpoint: checkpoint_id_start,
+            });
+        }
+
+        Ok(*txs[rand::thread_rng().gen_range(0..txs.len())])
+    }
+
+    async fn get_epoch_start_timestamp_and_rgp(
+        &self,
+        epoch_id: u64,
+    ) -> Result<(u64, u64), ReplayEngineError> {
+        self.epochs
+            .read()
+            .get(&epoch_id)
+            .and_then(|info| Some((info.start_timestamp_ms, info.reference_gas_price?)))
+            .ok_or(ReplayEngineError::EventNotFound { epoch: epoch_id })
+    }
+
+    async fn get_epoch_change_events(
+        &self,
+        reverse: bool,
+    ) -> Result<Vec<SuiEvent>, ReplayEngineError> {
+        let mut epoch_change_events = vec![];
+        let epochs: Vec<_> = self.epochs.read().values().cloned().collect();
+        for info in epochs {
+            // Epochs which started in the snapshot have no transaction in the checkpoint files
+            let Ok(tx) = self.transaction(&info.epoch_change_tx) else {
+                continue;
+            };
+            let events = tx.events.iter().flat_map(|events| &events.data);
+            for (event_seq, event) in events.enumerate() {
+                if !event.is_system_epoch_info_event() {
+                    continue;
+                }
+                let epoch_info: SystemEpochInfoEvent = bcs::from_bytes(&event.contents)
+                    .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?;
+                epoch_change_events.push(SuiEvent {
+                    id: EventID {
+                        tx_digest: info.epoch_change_tx,
+                        event_seq: event_seq as u64,
+                    },
+                    package_id: event.package_id,
+                    transaction_module: event.transaction_module.clone(),
+                    sender: event.sender,
+                    type_: event.type_.clone(),
+                    parsed_json: serde_json::to_value(epoch_info)
+                        .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?,
+                    bcs: BcsEvent::new(event.contents.clone()),
+                    timestamp_ms: Some(tx.timestamp_ms),
+                });
+            }
+        }
+
+        if reverse {
+            epoch_change_events.reverse();
+        }
+        Ok(epoch_change_events)
+    }
+
+    async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
+        self.chain_id
+            .clone()
+            .ok_or(ReplayEngineError::UnableToGetChainId {
+                err:
+                    "the genesis checkpoint is not in the checkpoint files, and no chain was given"
+                        .to_string(),
+            })
+    }
+
+    async fn get_child_object(
+        &self,
+        object_id: &ObjectID,
+        version_upper_bound: VersionNumber,
+    ) -> Result<Object, ReplayEngineError> {
+        self.get_object_at_or_before(object_id, version_upper_bound)?
+            .ok_or(ReplayEngineError::ObjectNotExist { id: *object_id })
+    }
+}
+
+/// The checkpoint files in `dir`, in checkpoint order
+fn checkpoint_files(dir: &Path) -> Result<Vec<PathBuf>, ReplayEngineError> {
+    let read_err = |err: std::io::Error| ReplayEngineError::UnableToReadCheckpoint {
+        path: dir.display().to_string(),
+        err: err.to_string(),
+    };
+
+    let mut files = BTreeMap::new();
+    for entry in std::fs::read_dir(dir).map_err(read_err)? {
+        let path = entry.map_err(read_err)?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(CHECKPOINT_FILE_SUFFIX) {
+            continue;
+        }
+        let sequence_number = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<CheckpointSequenceNumber>().ok());
+        if let Some(sequence_number) = sequence_number {
+            files.insert(sequence_number, path);
+        }
+    }
+    Ok(files.into_values().collect())
+}
+
+fn read_checkpoint(path: &Path) -> Result<CheckpointData, ReplayEngineError> {
+    let read_err = |err: String| ReplayEngineError::UnableToReadCheckpoint {
+        path: path.display().to_string(),
+        err,
+    };
+    let bytes = std::fs::read(path).map_err(|e| read_err(e.to_string()))?;
+    Blob::from_bytes::<CheckpointData>(&bytes).map_err(|e| read_err(e.to_string()))
+}
+
+/// The identifier of `chain`, if it is `mainnet` or `testnet`
+fn chain_identifier(chain: Option<&str>) -> Option<String> {
+    match chain {
+        Some("mainnet") => Some(get_mainnet_chain_identifier().to_string()),
+        Some("testnet") => Some(get_testnet_chain_identifier().to_string()),
+        _ => None,
+    }
+}
+
+/// Adds the start of the epoch that `snapshot` holds the state at, and the versions of the system
+/// packages it used, returning the epoch.
+fn add_snapshot_epoch(
+    snapshot: &AuthorityPerpetualTables,
+    epochs: &mut BTreeMap<EpochId, EpochStartInfo>,
+    system_package_versions: &mut BTreeMap<ObjectID, BTreeMap<EpochId, SequenceNumber>>,
+) -> Result<EpochId, ReplayEngineError> {
+    // The snapshot holds the state at the start of the epoch after the one it was taken in,
+    // including the system state and the system packages of that epoch.
+    let system_state = get_sui_system_state(snapshot)?;
+    let epoch = system_state.epoch();
+    let system_state_object = snapshot
+        .get_object_fallible(&SUI_SYSTEM_STATE_OBJECT_ID)?
+        .ok_or(ReplayEngineError::ObjectNotExist {
+            id: SUI_SYSTEM_STATE_OBJECT_ID,
+        })?;
+    epochs.entry(epoch).or_insert(EpochStartInfo {
+        protocol_version: system_state.protocol_version(),
+        start_timestamp_ms: system_state.epoch_start_timestamp_ms(),
+        reference_gas_price: Some(system_state.reference_gas_price()),
+        epoch_change_tx: system_state_object.previous_transaction,
+        first_checkpoint: None,
+    });
+
+    for id in BuiltInFramework::all_package_ids() {
+        if let Some(package) = snapshot.get_object_fallible(&id)? {
+            system_package_versions
+                .entry(id)
+                .or_default()
+                .entry(epoch)
+                .or_insert(package.version());
+        }
+    }
+    Ok(epoch)
+}
+
+fn change_epoch(kind: &TransactionKind) -> Option<&ChangeEpoch> {
+    match kind {
+        TransactionKind::ChangeEpoch(change) => Some(change),
+        TransactionKind::EndOfEpochTransaction(kinds) => kinds.iter().find_map(|kind| match kind {
+            EndOfEpochTransactionKind::ChangeEpoch(change) => Some(change),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// The reference gas price of `epoch`, from the event emitted by the transaction which started it
+fn reference_gas_price(
+    events: Option<&TransactionEvents>,
+    epoch: EpochId,
+) -> Result<u64, ReplayEngineError> {
+    events
+        .into_iter()
+        .flat_map(|events| &events.data)
+        .filter(|event| event.is_system_epoch_info_event())
+        .filter_map(|event| bcs::from_bytes::<SystemEpochInfoEvent>(&event.contents).ok())
+        .find(|epoch_info| epoch_info.epoch == epoch)
+        .map(|epoch_info| epoch_info.reference_gas_price)
+        .ok_or(ReplayEngineError::EventNotFound { epoch })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::ident_str;
+    use sui_protocol_config::ProtocolVersion;
+    use sui_storage::blob::BlobEncoding;
+    use sui_types::base_types::ExecutionData;
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::effects::TestEffectsBuilder;
+    use sui_types::event::Event;
+    use sui_types::full_checkpoint_content::CheckpointTransaction;
+    use sui_types::messages_checkpoint::{
+        FullCheckpointContents, VerifiedCheckpoint, VerifiedCheckpointContents,
+    };
+    use sui_types::storage::WriteStore;
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+    use sui_types::transaction::{Transaction, TransactionData};
+    use sui_types::SUI_SYSTEM_ADDRESS;
+    use tempfile::TempDir;
+
+    fn write_checkpoints(checkpoints: &[CheckpointData]) -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for checkpoint in checkpoints {
+            let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+            let blob = Blob::encode(checkpoint, BlobEncoding::Bcs).unwrap();
+            let path = dir
+                .path()
+                .join(format!("{sequence_number}.{CHECKPOINT_FILE_SUFFIX}"));
+            std::fs::write(path, blob.to_bytes()).unwrap();
+        }
+        dir
+    }
+
+    /// A store holding the checkpoints as read from an archive: their summaries, transactions
+    /// and effects.
+    fn archive_store(checkpoints: &[CheckpointData]) -> SharedInMemoryStore {
+        let store = SharedInMemoryStore::default();
+        for checkpoint in checkpoints {
+            let summary = VerifiedCheckpoint::new_unchecked(checkpoint.checkpoint_summary.clone());
+            let contents = FullCheckpointContents::from_contents_and_execution_data(
+                checkpoint.checkpoint_contents.clone(),
+                checkpoint
+                    .transactions
+                    .iter()
+                    .map(|tx| ExecutionData::new(tx.transaction.clone(), tx.effects.clone())),
+            );
+            store.insert_checkpoint(&summary).unwrap();
+            store
+                .insert_checkpoint_contents(
+                    &summary,
+                    VerifiedCheckpointContents::new_unchecked(contents),
+                )
+                .unwrap();
+        }
+        store
+    }
+
+    /// An end of epoch transaction starting `epoch`, and the event it emits with the epoch's
+    /// reference gas price.
+    fn change_epoch_transaction(
+        epoch: EpochId,
+        protocol_version: u64,
+        start_timestamp_ms: u64,
+        reference_gas_price: u64,
+    ) -> CheckpointTransaction {
+        let kind = EndOfEpochTransactionKind::new_change_epoch(
+            epoch,
+            ProtocolVersion::new(protocol_version),
+            0,
+            0,
+            0,
+            0,
+            start_timestamp_ms,
+            vec![],
+        );
+        let data = TransactionData::new(
+            TransactionKind::EndOfEpochTransaction(vec![kind]),
+            SuiAddress::default(),
+            random_object_ref(),
+            1,
+            1,
+        );
+        let transaction = Transaction::new(SenderSignedData::new(data, vec![]));
+
+        let epoch_info = SystemEpochInfoEvent {
+            epoch,
+            protocol_version,
+            reference_gas_price,
+            ..Default::default()
+        };
+        let event = Event::new(
+            &SUI_SYSTEM_ADDRESS,
+            ident_str!("sui_system_state_inner"),
+            SuiAddress::default(),
+            StructTag {
+                address: SUI_SYSTEM_ADDRESS,
+                module: ident_str!("sui_system_state_inner").to_owned(),
+                name: ident_str!("SystemEpochInfoEvent").to_owned(),
+                type_params: vec![],
+            },
+            bcs::to_bytes(&epoch_info).unwrap(),
+        );
+        let events = TransactionEvents { data: vec![event] };
+
+        let effects = TestEffectsBuilder::new(transaction.data())
+            .with_events_digest(events.digest())
+            .build();
+        CheckpointTransaction {
+            transaction,
+            effects,
+            events: Some(events),
+            input_objects: vec![],
+            output_objects: vec![],
+        }
+    }
+
+    fn digests(checkpoint: &CheckpointData) -> Vec<TransactionDigest> {
+        checkpoint
+            .transactions
+            .iter()
+            .map(|tx| *tx.effects.transaction_digest())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn checkpoint_files_are_indexed() {
+        let mut builder = TestCheckpointDataBuilder::new(0);
+        let genesis = builder.build_checkpoint();
+        builder = builder
+            .start_transaction(0)
+            .create_owned_object(0)
+            .finish_transaction()
+            .start_transaction(1)
+            .create_owned_object(1)
+            .finish_transaction();
+        let first = builder.build_checkpoint();
+        builder = builder
+            .start_transaction(0)
+            .mutate_object(0)
+            .finish_transaction();
+        let second = builder.build_checkpoint();
+
+        let dir = write_checkpoints(&[genesis.clone(), first.clone(), second.clone()]);
+        // Files that are not checkpoint files are ignored.
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        std::fs::write(
+            dir.path().join(format!("latest.{CHECKPOINT_FILE_SUFFIX}")),
+            "",
+        )
+        .unwrap();
+
+        let fetcher = CheckpointFetcher::new(dir.path(), None, None).unwrap();
+        assert_eq!(
+            fetcher.get_checkpoint_txs(1).await.unwrap(),
+            digests(&first)
+        );
+        assert_eq!(
+            fetcher.get_checkpoint_txs(2).await.unwrap(),
+            digests(&second)
+        );
+        assert!(fetcher.get_checkpoint_txs(0).await.unwrap().is_empty());
+        assert!(matches!(
+            fetcher.get_checkpoint_txs(3).await,
+            Err(ReplayEngineError::CheckpointNotFound { checkpoint: 3 })
+        ));
+        assert_eq!(
+            fetcher
+                .get_latest_checkpoint_sequence_number()
+                .await
+                .unwrap(),
+            2
+        );
+
+        for digest in digests(&first) {
+            let tx = fetcher.transaction(&digest).unwrap();
+            assert_eq!(tx.checkpoint, 1);
+            let response = fetcher.get_transaction(&digest).await.unwrap();
+            assert_eq!(response.checkpoint, Some(1));
+        }
+        let missing = TransactionDigest::random();
+        assert!(matches!(
+            fetcher.transaction(&missing),
+            Err(ReplayEngineError::TransactionNotFound { digest }) if digest == missing
+        ));
+
+        // The chain is identified by the genesis checkpoint.
+        assert_eq!(
+            fetcher.get_chain_id().await.unwrap(),
+            ChainIdentifier::from(*genesis.checkpoint_summary.digest()).to_string()
+        );
+
+        // Without the genesis checkpoint, the chain has to be given.
+        let dir = write_checkpoints(&[first.clone()]);
+        let fetcher = CheckpointFetcher::new(dir.path(), None, None).unwrap();
+        assert!(matches!(
+            fetcher.get_chain_id().await,
+            Err(ReplayEngineError::UnableToGetChainId { .. })
+        ));
+        let fetcher = CheckpointFetcher::new(dir.path(), None, Some("mainnet")).unwrap();
+        assert_eq!(
+            fetcher.get_chain_id().await.unwrap(),
+            get_mainnet_chain_identifier().to_string()
+        );
+
+        let empty = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            CheckpointFetcher::new(empty.path(), None, None),
+            Err(ReplayEngineError::UnableToReadCheckpoint { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn epoch_start_and_reference_gas_price() {
+        let mut builder = TestCheckpointDataBuilder::new(0);
+        let mut first = builder.build_checkpoint();
+        first
+            .transactions
+            .push(change_epoch_transaction(1, 10, 1_000, 750));
+        let mut builder = builder.with_epoch(1);
+        let mut second = builder.build_checkpoint();
+        second
+            .transactions
+            .push(change_epoch_transaction(2, 11, 2_000, 800));
+        let mut builder = builder.with_epoch(2);
+        let mut third = builder.build_checkpoint();
+        third
+            .transactions
+            .push(change_epoch_transaction(3, 11, 3_000, 900));
+
+        let dir = write_checkpoints(&[first.clone(), second.clone(), third.clone()]);
+        let fetcher = CheckpointFetcher::new(dir.path(), None, Some("testnet")).unwrap();
+
+        assert_eq!(
+            fetcher.get_epoch_start_timestamp_and_rgp(1).await.unwrap(),
+            (1_000, 750)
+        );
+        assert_eq!(
+            fetcher.get_epoch_start_timestamp_and_rgp(3).await.unwrap(),
+            (3_000, 900)
+        );
+        // The start of epoch 0 is not in the checkpoint files.
+        assert!(matches!(
+            fetcher.get_epoch_start_timestamp_and_rgp(0).await,
+            Err(ReplayEngineError::EventNotFound { epoch: 0 })
+        ));
+
+        // Epochs that share a protocol version are merged, and each range of checkpoints starts
+        // after the checkpoint with the end of epoch transaction.
+        let table = fetcher.protocol_version_epoch_table();
+        assert_eq!(table.len(), 2);
+        let v10 = &table[&10];
+        assert_eq!((v10.epoch_start, v10.epoch_end), (1, 1));
+        assert_eq!(
+            (v10.checkpoint_start, v10.checkpoint_end),
+            (Some(1), Some(1))
+        );
+        assert_eq!(v10.epoch_change_tx, digests(&first)[0]);
+        let v11 = &table[&11];
+        assert_eq!((v11.epoch_start, v11.epoch_end), (2, 3));
+        assert_eq!(
+            (v11.checkpoint_start, v11.checkpoint_end),
+            (Some(2), Some(2))
+        );
+        assert_eq!(v11.epoch_change_tx, digests(&second)[0]);
+
+        let change_txs: Vec<_> = [&first, &second, &third]
+            .into_iter()
+            .map(|checkpoint| digests(checkpoint)[0])
+            .collect();
+        let events = fetcher.get_epoch_change_events(false).await.unwrap();
+        let event_txs: Vec<_> = events.iter().map(|e| e.id.tx_digest).collect();
+        assert_eq!(event_txs, change_txs);
+        let events = fetcher.get_epoch_change_events(true).await.unwrap();
+        let event_txs: Vec<_> = events.iter().rev().map(|e| e.id.tx_digest).collect();
+        assert_eq!(event_txs, change_txs);
+    }
+
+    #[tokio::test]
+    async fn object_version_lookup() {
+        let id = TestCheckpointDataBuilder::derive_object_id(0);
+        // The second transaction only bumps the sender's gas coin, so the object skips a version
+        // when it is next modified.
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_owned_object(0)
+            .finish_transaction()
+            .start_transaction(0)
+            .create_owned_object(1)
+            .finish_transaction();
+        let first = builder.build_checkpoint();
+        builder = builder
+            .start_transaction(0)
+            .mutate_object(0)
+            .finish_transaction();
+        let second = builder.build_checkpoint();
+
+        let version = |checkpoint: &CheckpointData| {
+            checkpoint.transactions[0]
+                .output_objects
+                .iter()
+                .find(|o| o.id() == id)
+                .unwrap()
+                .version()
+        };
+        let created = version(&first);
+        let mutated = version(&second);
+        assert!(created < mutated);
+
+        let dir = write_checkpoints(&[first, second]);
+        let fetcher = CheckpointFetcher::new(dir.path(), None, Some("testnet")).unwrap();
+
+        // Exact versions are found, whether they were written or read by a transaction.
+        let objects = fetcher
+            .multi_get_versioned(&[(id, created), (id, mutated)])
+            .await
+            .unwrap();
+        assert_eq!(objects[0].version(), created);
+        assert_eq!(objects[1].version(), mutated);
+
+        let missing = created.next();
+        assert_ne!(missing, mutated);
+        assert!(matches!(
+            fetcher.multi_get_versioned(&[(id, missing)]).await,
+            Err(ReplayEngineError::ObjectVersionNotFound { version, .. }) if version == missing
+        ));
+
+        // Child objects are read at the highest version no greater than the bound.
+        let child = fetcher.get_child_object(&id, missing).await.unwrap();
+        assert_eq!(child.version(), created);
+        let child = fetcher
+            .get_child_object(&id, SequenceNumber::MAX)
+            .await
+            .unwrap();
+        assert_eq!(child.version(), mutated);
+        assert!(matches!(
+            fetcher.get_child_object(&id, SequenceNumber::MIN).await,
+            Err(ReplayEngineError::ObjectNotExist { .. })
+        ));
+
+        let latest = fetcher.multi_get_latest(&[id]).await.unwrap();
+        assert_eq!(latest[0].version(), mutated);
+        let unknown = TestCheckpointDataBuilder::derive_object_id(2);
+        assert!(matches!(
+            fetcher.multi_get_latest(&[unknown]).await,
+            Err(ReplayEngineError::ObjectNotExist { id }) if id == unknown
+        ));
+    }
+
+    #[tokio::test]
+    async fn archived_checkpoints_need_replayed_outputs() {
+        let id = TestCheckpointDataBuilder::derive_object_id(0);
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_owned_object(0)
+            .finish_transaction();
+        let mut first = builder.build_checkpoint();
+        first
+            .transactions
+            .push(change_epoch_transaction(1, 10, 1_000, 750));
+        let mut builder = builder
+            .with_epoch(1)
+            .start_transaction(0)
+            .mutate_object(0)
+            .finish_transaction();
+        let second = builder.build_checkpoint();
+        let store = archive_store(&[first.clone(), second.clone()]);
+
+        assert!(matches!(
+            CheckpointFetcher::from_checkpoint_store(&store, 0..=2, Some("testnet")),
+            Err(ReplayEngineError::CheckpointNotFound { checkpoint: 2 })
+        ));
+        let fetcher =
+            CheckpointFetcher::from_checkpoint_store(&store, 0..=1, Some("testnet")).unwrap();
+
+        let order: Vec<_> = digests(&first)
+            .into_iter()
+            .chain(digests(&second))
+            .collect();
+        assert_eq!(fetcher.transactions_in_order(), order);
+        let tx = fetcher.transaction(&order[2]).unwrap();
+        assert_eq!(tx.checkpoint, 1);
+        assert!(tx.events.is_none());
+
+        // Neither the objects nor the reference gas price of the new epoch are in the archive.
+        let created = &first.transactions[0].output_objects;
+        let object = created.iter().find(|o| o.id() == id).unwrap();
+        assert!(matches!(
+            fetcher.multi_get_versioned(&[(id, object.version())]).await,
+            Err(ReplayEngineError::ObjectVersionNotFound { .. })
+        ));
+        assert!(matches!(
+            fetcher.get_epoch_start_timestamp_and_rgp(1).await,
+            Err(ReplayEngineError::EventNotFound { epoch: 1 })
+        ));
+
+        // They become known as the transactions are replayed.
+        let written = created.iter().map(|o| (o.id(), o.clone())).collect();
+        fetcher
+            .record_replayed(&order[0], &written, &TransactionEvents::default())
+            .unwrap();
+        let objects = fetcher
+            .multi_get_versioned(&[(id, object.version())])
+            .await
+            .unwrap();
+        assert_eq!(objects[0].version(), object.version());
+
+        let events = first.transactions[1].events.clone().unwrap();
+        fetcher
+            .record_replayed(&order[1], &WrittenObjects::new(), &events)
+            .unwrap();
+        assert_eq!(
+            fetcher.get_epoch_start_timestamp_and_rgp(1).await.unwrap(),
+            (1_000, 750)
+        );
+    }
+}
@@ -23,9 +23,12 @@ use crate::replay::ProtocolVersionSummary;
 use move_vm_config::runtime::get_default_output_filepath;
 use std::env;
 use std::io::BufRead;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::str::FromStr;
-use sui_config::node::ExpensiveSafetyCheckConfig;
+use sui_archival::reader::{ArchiveReader, ArchiveReaderMetrics};
+use sui_config::node::{ArchiveReaderConfig, ExpensiveSafetyCheckConfig};
+use sui_config::object_storage_config::ObjectStoreConfig;
 use sui_protocol_config::Chain;
 use sui_types::digests::TransactionDigest;
 use tracing::{error, info};
@@ -39,6 +42,7 @@ pub mod fuzz_mutations;
 mod replay;
 #[cfg(test)]
 mod tests;
+mod trace;
 pub mod transaction_provider;
 pub mod types;
 
@@ -108,6 +112,10 @@ pub enum ReplayToolCommand {
         /// denied.
         #[arg(long, num_args = 2..)]
         config_objects: Option<Vec<String>>,
+        /// Optional directory to write the trace of each command of the transaction to, for the
+        /// Move trace debugger. Requires the `tracing` feature.
+        #[arg(long)]
+        trace: Option<PathBuf>,
     },
 
     /// Replay transactions listed in a file
@@ -141,6 +149,66 @@ pub enum ReplayToolCommand {
         show_effects: bool,
     },
 
+    /// Replay a transaction from local checkpoint files, without any network access
+    #[command(name = "lc")]
+    ReplayFromCheckpoints {
+        #[arg(long, short)]
+        tx_digest: String,
+        #[arg(
+            long,
+            short,
+            help = "The directory of checkpoint files (`<sequence_number>.chk`) to replay from. \
+            It must contain the checkpoints of the transaction and of any transactions that created \
+            the objects it reads, as well as the checkpoints of the epoch changes it depends on."
+        )]
+        checkpoint_path: PathBuf,
+        #[arg(
+            long,
+            help = "The path of a database restored from a formal snapshot with \
+            `sui-tool download-formal-snapshot`. Objects that are not written by any of the local \
+            checkpoints are read from this database."
+        )]
+        snapshot_path: Option<PathBuf>,
+        #[arg(long)]
+        show_effects: bool,
+        /// Optional directory to write the trace of each command of the transaction to, for the
+        /// Move trace debugger. Requires the `tracing` feature.
+        #[arg(long)]
+        trace: Option<PathBuf>,
+    },
+
+    /// Replay a transaction from a `sui-archival` checkpoint archive, starting from the state in a
+    /// formal snapshot. Archives hold transactions and effects but no objects, so every transaction
+    /// from the start of the snapshot's epoch up to this one is replayed to derive them.
+    #[command(name = "la")]
+    ReplayFromArchive {
+        #[arg(long, short)]
+        tx_digest: String,
+        #[command(flatten)]
+        object_store_config: ObjectStoreConfig,
+        #[arg(
+            long,
+            help = "The path of a database restored from a formal snapshot with \
+            `sui-tool download-formal-snapshot`. It holds the state at the start of an epoch, and \
+            `--start-checkpoint` must be that epoch's first checkpoint."
+        )]
+        snapshot_path: PathBuf,
+        /// The first checkpoint to replay, which must start the snapshot's epoch
+        #[arg(long)]
+        start_checkpoint: u64,
+        /// The checkpoint of the transaction
+        #[arg(long)]
+        end_checkpoint: u64,
+        #[arg(long, default_value = "5")]
+        download_concurrency: NonZeroUsize,
+        #[arg(long)]
+        show_effects: bool,
+        /// Optional directory to write the trace of each command of the transaction to, for the
+        /// Move trace debugger. Requires the `tracing` feature.
+        #[arg(long)]
+        trace: Option<PathBuf>,
+    },
+
     /// Replay multiple transactions from JSON files that contain the sandbox persisted state.
     #[command(name = "brd")]
     BatchReplayFromSandbox {
@@ -237,6 +305,7 @@ pub async fn execute_replay_command(
                 None,
                 None,
                 None,
+                None,
             )
             .await?;
 
@@ -291,6 +360,86 @@ pub async fn execute_replay_command(
             info!("Execution finished successfully. Local and on-chain effects match.");
             Some((1u64, 1u64))
         }
+        ReplayToolCommand::ReplayFromCheckpoints {
+            tx_digest,
+            checkpoint_path,
+            snapshot_path,
+            show_effects,
+            trace,
+        } => {
+            check_trace_supported(&trace)?;
+            let tx_digest = TransactionDigest::from_str(&tx_digest)?;
+            info!("Executing tx: {}", tx_digest);
+            let mut lx = LocalExec::new_for_checkpoints(
+                &checkpoint_path,
+                snapshot_path.as_deref(),
+                chain.as_deref(),
+            )?
+            .init_for_execution()
+            .await?;
+            let sandbox_state = lx
+                .execute_transaction(
+                    &tx_digest,
+                    safety,
+                    use_authority,
+                    None,
+                    None,
+                    None,
+                    trace,
+                    None,
+                )
+                .await?;
+            if show_effects {
+                println!("{:#?}", sandbox_state.local_exec_effects);
+            }
+
+            sandbox_state.check_effects()?;
+
+            info!("Execution finished successfully. Local and on-chain effects match.");
+            Some((1u64, 1u64))
+        }
+        ReplayToolCommand::ReplayFromArchive {
+            tx_digest,
+            object_store_config,
+            snapshot_path,
+            start_checkpoint,
+            end_checkpoint,
+            download_concurrency,
+            show_effects,
+            trace,
+        } => {
+            check_trace_supported(&trace)?;
+            let tx_digest = TransactionDigest::from_str(&tx_digest)?;
+            let config = ArchiveReaderConfig {
+                remote_store_config: object_store_config,
+                download_concurrency,
+                use_for_pruning_watermark: false,
+            };
+            let metrics = ArchiveReaderMetrics::new(&prometheus::Registry::default());
+            let archive = ArchiveReader::new(config, &metrics)?;
+
+            info!("Executing tx: {}", tx_digest);
+            let mut lx = LocalExec::new_for_archive(
+                &archive,
+                start_checkpoint..=end_checkpoint,
+                &snapshot_path,
+                chain.as_deref(),
+            )
+            .await?
+            .init_for_execution()
+            .await?;
+            let sandbox_state = lx
+                .execute_transaction_from_archive(&tx_digest, safety, use_authority, trace)
+                .await?;
+            if show_effects {
+                println!("{:#?}", sandbox_state.local_exec_effects);
+            }
+
+            sandbox_state.check_effects()?;
+
+            info!("Execution finished successfully. Local and on-chain effects match.");
+            Some((1u64, 1u64))
+        }
         ReplayToolCommand::ReplayBatch {
             path,
             terminate_early,
@@ -369,6 +518,7 @@ pub async fn execute_replay_command(
                 executor_version,
                 protocol_version,
                 output_path,
+                None,
                 parse_configs_versions(config_objects),
             )
             .await?;
@@ -383,7 +533,9 @@ pub async fn execute_replay_command(
             executor_version,
             protocol_version,
             config_objects,
+            trace,
         } => {
+            check_trace_supported(&trace)?;
             let tx_digest = TransactionDigest::from_str(&tx_digest)?;
             info!("Executing tx: {}", tx_digest);
             let sandbox_state = LocalExec::replay_with_network_config(
@@ -394,6 +546,7 @@ pub async fn execute_replay_command(
                 executor_version,
                 protocol_version,
                 None,
+                trace,
                 parse_configs_versions(config_objects),
             )
             .await?;
@@ -605,3 +758,15 @@ fn parse_configs_versions(
             .collect(),
     )
 }
+
+/// Traces are only recorded by the Move VM when it is built with the `tracing` feature.
+fn check_trace_supported(trace: &Option<PathBuf>) -> anyhow::Result<()> {
+    if trace.is_some() {
+        move_vm_profiler::tracing_feature_disabled! {
+            anyhow::bail!(
+                "tracing feature is not enabled, rebuild or reinstall with --features tracing"
+            );
+        };
+    }
+    Ok(())
+}
@@ -4,12 +4,14 @@
 use crate::chain_from_chain_id;
 use crate::{
     data_fetcher::{
-        extract_epoch_and_version, DataFetcher, Fetchers, NodeStateDumpFetcher, RemoteFetcher,
+        extract_epoch_and_version, CheckpointFetcher, DataFetcher, Fetchers, NodeStateDumpFetcher,
+        RemoteFetcher,
     },
     displays::{
         transaction_displays::{transform_command_results_to_annotated, FullPTB},
         Pretty,
     },
+    trace::write_transaction_trace,
     types::*,
 };
 use futures::executor::block_on;
@@ -25,10 +27,12 @@ use serde::{Deserialize, Serialize};
 use similar::{ChangeTag, TextDiff};
 use std::{
     collections::{BTreeMap, HashSet},
-    path::PathBuf,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
     sync::Arc,
     sync::Mutex,
 };
+use sui_archival::reader::ArchiveReader;
 use sui_config::node::ExpensiveSafetyCheckConfig;
 use sui_core::authority::NodeStateDump;
 use sui_execution::Executor;
@@ -36,7 +40,7 @@ use sui_framework::BuiltInFramework;
 use sui_json_rpc_types::{
     SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
 };
-use sui_protocol_config::{Chain, ProtocolConfig};
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_types::in_memory_storage::InMemoryStorage;
 use sui_types::message_envelope::Message;
@@ -49,6 +53,7 @@ use sui_types::{
     digests::{ObjectDigest, TransactionDigest},
     error::{ExecutionError, SuiError, SuiResult},
     executable_transaction::VerifiedExecutableTransaction,
+    execution_trace::TransactionTrace,
     gas::SuiGasStatus,
     inner_temporary_store::InnerTemporaryStore,
     metrics::LimitsMetrics,
@@ -242,6 +247,9 @@ pub struct LocalExec {
     // Whether or not to enable the gas profiler, the PathBuf contains either a user specified
     // filepath or the default current directory and name format for the profile output
     pub enable_profiler: Option<PathBuf>,
+    // Whether or not to trace the execution of programmable transactions, the PathBuf contains
+    // the directory that the trace of each command is written to
+    pub trace_output: Option<PathBuf>,
     pub config_and_versions: Option<Vec<(ObjectID, SequenceNumber)>>,
     // Retry policies due to RPC errors
     pub num_retries_for_timeout: u32,
@@ -326,6 +334,7 @@ impl LocalExec {
         executor_version: Option<i64>,
         protocol_version: Option<i64>,
         enable_profiler: Option<PathBuf>,
+        trace_output: Option<PathBuf>,
         config_and_versions: Option<Vec<(ObjectID, SequenceNumber)>>,
     ) -> Result<ExecutionSandboxState, ReplayEngineError> {
         info!("Using RPC URL: {}", rpc_url);
@@ -340,6 +349,7 @@ impl LocalExec {
                 executor_version,
                 protocol_version,
                 enable_profiler,
+                trace_output,
                 config_and_versions,
             )
             .await
@@ -390,6 +400,7 @@ impl LocalExec {
             executor_version: None,
             protocol_version: None,
             enable_profiler: None,
+            trace_output: None,
             config_and_versions: None,
         })
     }
@@ -433,10 +444,126 @@ impl LocalExec {
             executor_version: None,
             protocol_version: None,
             enable_profiler: None,
+            trace_output: None,
             config_and_versions: None,
         })
     }
 
+    /// Replays from the checkpoint files in `checkpoint_path`, and optionally the store restored
+    /// from a formal snapshot at `snapshot_path`, without any network access.
+    /// See `CheckpointFetcher` for what these need to contain.
+    pub fn new_for_checkpoints(
+        checkpoint_path: &Path,
+        snapshot_path: Option<&Path>,
+        chain: Option<&str>,
+    ) -> Result<Self, ReplayEngineError> {
+        let fetcher = CheckpointFetcher::new(checkpoint_path, snapshot_path, chain)?;
+        Ok(Self::new_for_checkpoint_fetcher(fetcher))
+    }
+
+    /// Replays from `checkpoints` in `archive`, with objects from the store restored from a
+    /// formal snapshot at `snapshot_path`. Transactions have to be replayed in order with
+    /// `execute_transaction_from_archive`. See `CheckpointFetcher::from_archive` for what these
+    /// need to contain.
+    pub async fn new_for_archive(
+        archive: &ArchiveReader,
+        checkpoints: RangeInclusive<u64>,
+        snapshot_path: &Path,
+        chain: Option<&str>,
+    ) -> Result<Self, ReplayEngineError> {
+        let fetcher =
+            CheckpointFetcher::from_archive(archive, checkpoints, snapshot_path, chain).await?;
+        Ok(Self::new_for_checkpoint_fetcher(fetcher))
+    }
+
+    fn new_for_checkpoint_fetcher(fetcher: CheckpointFetcher) -> Self {
+        // Use a throwaway metrics registry for local execution.
+        let registry = prometheus::Registry::new();
+        let metrics = Arc::new(LimitsMetrics::new(&registry));
+
+        Self {
+            client: None,
+            protocol_version_epoch_table: BTreeMap::new(),
+            protocol_version_system_package_table: BTreeMap::new(),
+            current_protocol_version: 0,
+            exec_store_events: Arc::new(Mutex::new(Vec::new())),
+            metrics,
+            storage: Storage::default(),
+            fetcher: Fetchers::Checkpoint(fetcher),
+            // TODO: make these configurable
+            num_retries_for_timeout: RPC_TIMEOUT_ERR_NUM_RETRIES,
+            sleep_period_for_timeout: RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
+            executor_version: None,
+            protocol_version: None,
+            enable_profiler: None,
+            trace_output: None,
+            config_and_versions: None,
+        }
+    }
+
+    /// Replays the transactions read from an archive in order, up to and including `tx_digest`.
+    /// The archive holds no objects, so the objects each transaction reads are the ones in the
+    /// snapshot, or those written by replaying the transactions before it, which therefore must
+    /// all match their on-chain effects.
+    pub async fn execute_transaction_from_archive(
+        &mut self,
+        tx_digest: &TransactionDigest,
+        expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+        use_authority: bool,
+        trace_output: Option<PathBuf>,
+    ) -> Result<ExecutionSandboxState, ReplayEngineError> {
+        let fetcher = self.fetcher.as_checkpoint().clone();
+        // Fail early rather than after replaying all the checkpoints.
+        fetcher.transaction(tx_digest)?;
+
+        for digest in fetcher.transactions_in_order() {
+            if digest == *tx_digest {
+                return self
+                    .execute_transaction(
+                        tx_digest,
+                        expensive_safety_check_config,
+                        use_authority,
+                        None,
+                        None,
+                        None,
+                        trace_output,
+                        None,
+                    )
+                    .await;
+            }
+
+            // Executing as an authority does not capture the written objects, so the
+            // transactions before the target are always executed directly.
+            let sandbox_state = self
+                .execute_transaction(
+                    &digest,
+                    expensive_safety_check_config.clone(),
+                    false,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
+            sandbox_state.check_effects()?;
+            let store = sandbox_state
+                .local_exec_temporary_store
+                .as_ref()
+                .expect("Local execution must capture the temporary store");
+            fetcher.record_replayed(&digest, &store.written, &store.events)?;
+
+            // The next epoch's reference gas price and system packages are only known now.
+            if matches!(
+                sandbox_state.transaction_info.kind,
+                TransactionKind::ChangeEpoch(_) | TransactionKind::EndOfEpochTransaction(_)
+            ) {
+                self.populate_protocol_version_tables().await?;
+            }
+        }
+        Err(ReplayEngineError::TransactionNotFound { digest: *tx_digest })
+    }
+
     pub async fn multi_download_and_store(
         &mut self,
         objs: &[(ObjectID, SequenceNumber)],
@@ -686,6 +813,7 @@ impl LocalExec {
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await
                 .map(|q| q.check_effects())
@@ -764,21 +892,48 @@ impl LocalExec {
             )
             .expect("Failed to create gas status")
         };
-        let (inner_store, gas_status, effects, result) = executor.execute_transaction_to_effects(
-            &self,
-            protocol_config,
-            metrics.clone(),
-            expensive_checks,
-            &certificate_deny_set,
-            &tx_info.executed_epoch,
-            tx_info.epoch_start_timestamp,
-            CheckedInputObjects::new_for_replay(input_objects.clone()),
-            tx_info.gas.clone(),
-            gas_status,
-            transaction_kind.clone(),
-            tx_info.sender,
-            *tx_digest,
-        );
+        let mut trace = self.trace_output.is_some().then(TransactionTrace::default);
+        let (inner_store, gas_status, effects, result) = executor
+            .execute_transaction_to_effects_with_trace(
+                &self,
+                protocol_config,
+                metrics.clone(),
+                expensive_checks,
+                &certificate_deny_set,
+                &tx_info.executed_epoch,
+                tx_info.epoch_start_timestamp,
+                CheckedInputObjects::new_for_replay(input_objects.clone()),
+                tx_info.gas.clone(),
+                gas_status,
+                transaction_kind.clone(),
+                tx_info.sender,
+                *tx_digest,
+                &mut trace,
+            );
+
+        if let Some(trace_output) = &self.trace_output {
+            match trace {
+                None => warn!(
+                    "Not writing a trace of {}: execution layer v{} does not support tracing",
+                    tx_digest,
+                    execution_version(ov, protocol_config)
+                ),
+                Some(trace) if trace.commands.is_empty() => warn!(
+                    "Not writing a trace of {}: it executed no commands (only the commands of \
+                     programmable transactions are traced)",
+                    tx_digest
+                ),
+                Some(trace) => {
+                    let trace_dir = trace_output.join(tx_digest.to_string());
+                    write_transaction_trace(self, &trace_dir, trace)?;
+                    info!(
+                        "Wrote the trace of {} to {}",
+                        tx_digest,
+                        trace_dir.display()
+                    );
+                }
+            }
+        }
 
         if let Err(err) = self.pretty_print_for_tracing(
             &gas_status,
@@ -865,7 +1020,7 @@ impl LocalExec {
         tx_digest: &TransactionDigest,
         expensive_safety_check_config: ExpensiveSafetyCheckConfig,
     ) -> Result<ExecutionSandboxState, ReplayEngineError> {
-        if self.is_remote_replay() {
+        if !matches!(self.fetcher, Fetchers::NodeStateDump(_)) {
             assert!(
             !self.protocol_version_system_package_table.is_empty()
                 || !self.protocol_version_epoch_table.is_empty(),
@@ -873,10 +1028,13 @@ impl LocalExec {
         );
         }
 
-        let tx_info = if self.is_remote_replay() {
-            self.resolve_tx_components(tx_digest).await?
-        } else {
-            self.resolve_tx_components_from_dump(tx_digest).await?
+        let tx_info = match self.fetcher {
+            Fetchers::Remote(_) => self.resolve_tx_components(tx_digest).await?,
+            Fetchers::NodeStateDump(_) => self.resolve_tx_components_from_dump(tx_digest).await?,
+            Fetchers::Checkpoint(_) => {
+                self.resolve_tx_components_from_checkpoints(tx_digest)
+                    .await?
+            }
         };
         self.execution_engine_execute_with_tx_info_impl(
             &tx_info,
@@ -1006,11 +1164,13 @@ impl LocalExec {
         executor_version: Option<i64>,
         protocol_version: Option<i64>,
         enable_profiler: Option<PathBuf>,
+        trace_output: Option<PathBuf>,
         config_and_versions: Option<Vec<(ObjectID, SequenceNumber)>>,
     ) -> Result<ExecutionSandboxState, ReplayEngineError> {
         self.executor_version = executor_version;
         self.protocol_version = protocol_version;
         self.enable_profiler = enable_profiler;
+        self.trace_output = trace_output;
         self.config_and_versions = config_and_versions;
         if use_authority {
             self.certificate_execute(tx_digest, expensive_safety_check_config.clone())
@@ -1096,7 +1256,7 @@ impl LocalExec {
         protocol_version: u64,
     ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
         match &self.fetcher {
-            Fetchers::Remote(_) => Ok(self
+            Fetchers::Remote(_) | Fetchers::Checkpoint(_) => Ok(self
                 .protocol_version_system_package_table
                 .get(&protocol_version)
                 .ok_or(ReplayEngineError::FrameworkObjectVersionTableNotPopulated {
@@ -1119,6 +1279,10 @@ impl LocalExec {
     pub async fn protocol_ver_to_epoch_map(
         &self,
     ) -> Result<BTreeMap<u64, ProtocolVersionSummary>, ReplayEngineError> {
+        if let Fetchers::Checkpoint(fetcher) = &self.fetcher {
+            return Ok(fetcher.protocol_version_epoch_table());
+        }
+
         let mut range_map = BTreeMap::new();
         let epoch_change_events = self.fetcher.get_epoch_change_events(false).await?;
 
@@ -1217,6 +1381,13 @@ impl LocalExec {
     pub async fn populate_protocol_version_tables(&mut self) -> Result<(), ReplayEngineError> {
         self.protocol_version_epoch_table = self.protocol_ver_to_epoch_map().await?;
 
+        // The checkpoints and snapshot record which system packages each epoch used, so there is
+        // no need to trace back through their history.
+        if let Fetchers::Checkpoint(fetcher) = &self.fetcher {
+            self.protocol_version_system_package_table = fetcher.system_package_table();
+            return Ok(());
+        }
+
         let system_package_revisions = self.system_package_versions().await?;
 
         // This can be more efficient but small footprint so okay for now
@@ -1466,83 +1637,12 @@ impl LocalExec {
         assert!(self.is_remote_replay());
         // Fetch full transaction content
         let tx_info = self.fetcher.get_transaction(tx_digest).await?;
-        let sender = match tx_info.clone().transaction.unwrap().data {
-            sui_json_rpc_types::SuiTransactionBlockData::V1(tx) => tx.sender,
-        };
-        let SuiTransactionBlockEffects::V1(effects) = tx_info.clone().effects.unwrap();
-
-        let config_objects = self.add_config_objects_if_needed(effects.status());
-
-        let raw_tx_bytes = tx_info.clone().raw_transaction;
-        let orig_tx: SenderSignedData = bcs::from_bytes(&raw_tx_bytes).unwrap();
-        let input_objs = orig_tx
-            .transaction_data()
-            .input_objects()
-            .map_err(|e| ReplayEngineError::UserInputError { err: e })?;
-        let tx_kind_orig = orig_tx.transaction_data().kind();
-
-        // Download the objects at the version right before the execution of this TX
-        let modified_at_versions: Vec<(ObjectID, SequenceNumber)> = effects.modified_at_versions();
+        let orig_tx: SenderSignedData = bcs::from_bytes(&tx_info.raw_transaction).unwrap();
+        let effects = tx_info.effects.unwrap();
 
-        let shared_object_refs: Vec<ObjectRef> = effects
-            .shared_objects()
-            .iter()
-            .map(|so_ref| {
-                if so_ref.digest == ObjectDigest::OBJECT_DIGEST_DELETED {
-                    unimplemented!(
-                        "Replay of deleted shared object transactions is not supported yet"
-                    );
-                } else {
-                    so_ref.to_object_ref()
-                }
-            })
-            .collect();
-        let gas_data = match tx_info.clone().transaction.unwrap().data {
-            sui_json_rpc_types::SuiTransactionBlockData::V1(tx) => tx.gas_data,
-        };
-        let gas_object_refs: Vec<_> = gas_data
-            .payment
-            .iter()
-            .map(|obj_ref| obj_ref.to_object_ref())
-            .collect();
-        let receiving_objs = orig_tx
-            .transaction_data()
-            .receiving_objects()
-            .into_iter()
-            .map(|(obj_id, version, _)| (obj_id, version))
-            .collect();
-
-        let epoch_id = effects.executed_epoch;
-        let chain = chain_from_chain_id(self.fetcher.get_chain_id().await?.as_str());
-
-        // Extract the epoch start timestamp
-        let (epoch_start_timestamp, reference_gas_price) = self
-            .get_epoch_start_timestamp_and_rgp(epoch_id, tx_digest)
-            .await?;
-
-        Ok(OnChainTransactionInfo {
-            kind: tx_kind_orig.clone(),
-            sender,
-            modified_at_versions,
-            input_objects: input_objs,
-            shared_object_refs,
-            gas: gas_object_refs,
-            gas_budget: gas_data.budget,
-            gas_price: gas_data.price,
-            executed_epoch: epoch_id,
-            dependencies: effects.dependencies().to_vec(),
-            effects: SuiTransactionBlockEffects::V1(effects),
-            receiving_objs,
-            config_objects,
-            // Find the protocol version for this epoch
-            // This assumes we already initialized the protocol version table `protocol_version_epoch_table`
-            protocol_version: self.get_protocol_config(epoch_id, chain).await?.version,
-            tx_digest: *tx_digest,
-            epoch_start_timestamp,
-            sender_signed_data: orig_tx.clone(),
-            reference_gas_price,
-            chain,
-        })
+        // This assumes we already initialized the protocol version table `protocol_version_epoch_table`
+        self.resolve_tx_components_from_parts(tx_digest, orig_tx, effects, None)
+            .await
     }
 
     async fn resolve_tx_components_from_dump(
@@ -1552,26 +1652,46 @@ impl LocalExec {
         assert!(!self.is_remote_replay());
 
         let dp = self.fetcher.as_node_state_dump();
-
-        let sender = dp
-            .node_state_dump
-            .sender_signed_data
-            .transaction_data()
-            .sender();
         let orig_tx = dp.node_state_dump.sender_signed_data.clone();
         let effects = dp.node_state_dump.computed_effects.clone();
         let effects = SuiTransactionBlockEffects::try_from(effects).unwrap();
-        // Config objects don't show up in the node state dump so they need to be provided.
-        let config_objects = self.add_config_objects_if_needed(effects.status());
 
-        // Fetch full transaction content
-        //let tx_info = self.fetcher.get_transaction(tx_digest).await?;
+        // The dump records the protocol version it was taken at, so there is no need to look it up.
+        let protocol_version = dp.node_state_dump.protocol_version.into();
+        self.resolve_tx_components_from_parts(tx_digest, orig_tx, effects, Some(protocol_version))
+            .await
+    }
+
+    async fn resolve_tx_components_from_checkpoints(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<OnChainTransactionInfo, ReplayEngineError> {
+        let tx = self.fetcher.as_checkpoint().transaction(tx_digest)?;
+        let orig_tx = tx.transaction.clone();
+        let effects = SuiTransactionBlockEffects::try_from(tx.effects.clone())?;
+
+        // This assumes we already initialized the protocol version table `protocol_version_epoch_table`
+        self.resolve_tx_components_from_parts(tx_digest, orig_tx, effects, None)
+            .await
+    }
+
+    /// Gather what is needed to replay `tx_digest` from the original transaction and its effects,
+    /// regardless of where they were fetched from. The protocol version is looked up for the epoch
+    /// the transaction executed in, unless `protocol_version` is provided.
+    async fn resolve_tx_components_from_parts(
+        &self,
+        tx_digest: &TransactionDigest,
+        orig_tx: SenderSignedData,
+        effects: SuiTransactionBlockEffects,
+        protocol_version: Option<ProtocolVersion>,
+    ) -> Result<OnChainTransactionInfo, ReplayEngineError> {
+        let tx_data = orig_tx.transaction_data();
+        // Config objects don't show up in the transaction's inputs so they need to be provided.
+        let config_objects = self.add_config_objects_if_needed(effects.status());
 
-        let input_objs = orig_tx
-            .transaction_data()
+        let input_objs = tx_data
             .input_objects()
             .map_err(|e| ReplayEngineError::UserInputError { err: e })?;
-        let tx_kind_orig = orig_tx.transaction_data().kind();
 
         // Download the objects at the version right before the execution of this TX
         let modified_at_versions: Vec<(ObjectID, SequenceNumber)> = effects.modified_at_versions();
@@ -1581,41 +1701,42 @@ impl LocalExec {
             .iter()
             .map(|so_ref| {
                 if so_ref.digest == ObjectDigest::OBJECT_DIGEST_DELETED {
-                    unimplemented!(
-                        "Replay of deleted shared object transactions is not supported yet"
-                    );
+                    Err(ReplayEngineError::TransactionNotSupported {
+                        digest: *tx_digest,
+                        reason: "Replay of deleted shared object transactions is not supported yet"
+                            .to_string(),
+                    })
                 } else {
-                    so_ref.to_object_ref()
+                    Ok(so_ref.to_object_ref())
                 }
             })
-            .collect();
-        let gas_data = orig_tx.transaction_data().gas_data();
-        let gas_object_refs: Vec<_> = gas_data.clone().payment.into_iter().collect();
-        let receiving_objs = orig_tx
-            .transaction_data()
+            .collect::<Result<_, _>>()?;
+        let gas_data = tx_data.gas_data();
+        let receiving_objs = tx_data
             .receiving_objects()
             .into_iter()
             .map(|(obj_id, version, _)| (obj_id, version))
             .collect();
 
-        let epoch_id = dp.node_state_dump.executed_epoch;
-
+        let epoch_id = effects.executed_epoch();
         let chain = chain_from_chain_id(self.fetcher.get_chain_id().await?.as_str());
+        let protocol_version = match protocol_version {
+            Some(protocol_version) => protocol_version,
+            None => self.get_protocol_config(epoch_id, chain).await?.version,
+        };
 
-        let protocol_config =
-            ProtocolConfig::get_for_version(dp.node_state_dump.protocol_version.into(), chain);
         // Extract the epoch start timestamp
         let (epoch_start_timestamp, reference_gas_price) = self
             .get_epoch_start_timestamp_and_rgp(epoch_id, tx_digest)
             .await?;
 
         Ok(OnChainTransactionInfo {
-            kind: tx_kind_orig.clone(),
-            sender,
+            kind: tx_data.kind().clone(),
+            sender: tx_data.sender(),
             modified_at_versions,
             input_objects: input_objs,
             shared_object_refs,
-            gas: gas_object_refs,
+            gas: gas_data.payment.clone(),
             gas_budget: gas_data.budget,
             gas_price: gas_data.price,
             executed_epoch: epoch_id,
@@ -1623,7 +1744,7 @@ impl LocalExec {
             effects,
             receiving_objs,
             config_objects,
-            protocol_version: protocol_config.version,
+            protocol_version,
             tx_digest: *tx_digest,
             epoch_start_timestamp,
             sender_signed_data: orig_tx.clone(),
@@ -1647,7 +1768,13 @@ impl LocalExec {
         // correct transaction dependency for a deleted shared object.
         if !deleted_shared_objects.is_empty() {
             for tx_digest in tx_info.dependencies.iter() {
-                let tx_info = self.resolve_tx_components(tx_digest).await?;
+                let tx_info = match self.fetcher {
+                    Fetchers::Checkpoint(_) => {
+                        self.resolve_tx_components_from_checkpoints(tx_digest)
+                            .await?
+                    }
+                    _ => self.resolve_tx_components(tx_digest).await?,
+                };
                 for (obj_id, version, _) in tx_info.shared_object_refs.iter() {
                     deleted_shared_info_map.insert(*obj_id, (tx_info.tx_digest, *version));
                 }
@@ -2148,15 +2275,12 @@ pub fn get_executor(
     enable_profiler: Option<PathBuf>,
 ) -> Arc<dyn Executor + Send + Sync> {
     let protocol_config = executor_version_override
-        .map(|q| {
-            let ver = if q < 0 {
-                ProtocolConfig::get_for_max_version_UNSAFE().execution_version()
-            } else {
-                q as u64
-            };
-
+        .map(|_| {
             let mut c = protocol_config.clone();
-            c.set_execution_version_for_testing(ver);
+            c.set_execution_version_for_testing(execution_version(
+                executor_version_override,
+                protocol_config,
+            ));
             c
         })
         .unwrap_or(protocol_config.clone());
@@ -2166,6 +2290,18 @@ pub fn get_executor(
         .expect("Creating an executor should not fail here")
 }
 
+/// The version of the execution layer that `get_executor` uses for `protocol_config`.
+fn execution_version(
+    executor_version_override: Option<i64>,
+    protocol_config: &ProtocolConfig,
+) -> u64 {
+    match executor_version_override {
+        Some(q) if q < 0 => ProtocolConfig::get_for_max_version_UNSAFE().execution_version(),
+        Some(q) => q as u64,
+        None => protocol_config.execution_version_as_option().unwrap_or(0),
+    }
+}
+
 fn parse_effect_error_for_denied_coins(status: &SuiExecutionStatus) -> Option<String> {
     let SuiExecutionStatus::Failure { error } = status else {
         return None;
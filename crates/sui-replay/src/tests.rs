@@ -149,6 +149,7 @@ async fn execute_replay(url: &str, tx: &TransactionDigest) -> Result<(), ReplayE
             None,
             None,
             None,
+            None,
         )
         .await?
         .check_effects()?;
@@ -165,6 +166,7 @@ async fn execute_replay(url: &str, tx: &TransactionDigest) -> Result<(), ReplayE
             None,
             None,
             None,
+            None,
         )
         .await?
         .check_effects()?;
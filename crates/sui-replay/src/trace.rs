@@ -0,0 +1,284 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use move_binary_format::CompiledModule;
+use move_bytecode_source_map::utils::serialize_to_json_string;
+use move_command_line_common::files::MOVE_BYTECODE_EXTENSION;
+use move_disassembler::disassembler::Disassembler;
+use move_ir_types::location::Spanned;
+use sui_types::base_types::ObjectID;
+use sui_types::execution_trace::{FramePackage, TransactionTrace};
+use sui_types::storage::BackingPackageStore;
+use tracing::warn;
+
+use crate::types::ReplayEngineError;
+
+/// Writes the trace of each command of a transaction to `trace_dir`, in a `command_<index>`
+/// directory that can be opened with the Move trace debugger:
+/// - `trace.json` holds the trace of the command;
+/// - `sources/<package_id>/<module>.mvb` holds the disassembled bytecode of the modules of the
+///   packages that the command executed code from;
+/// - `source_maps/<package_id>/<module>.json` holds the source maps of this bytecode.
+pub(crate) fn write_transaction_trace(
+    store: &dyn BackingPackageStore,
+    trace_dir: &Path,
+    trace: TransactionTrace,
+) -> Result<(), ReplayEngineError> {
+    for (idx, command_trace) in trace.commands.into_iter().enumerate() {
+        let command_dir = trace_dir.join(format!("command_{idx}"));
+        std::fs::create_dir_all(&command_dir).map_err(|e| write_error(&command_dir, e))?;
+
+        let packages: BTreeSet<ObjectID> = command_trace
+            .events
+            .iter()
+            .filter_map(FramePackage::from_event)
+            .map(|frame_package| frame_package.package_id)
+            .collect();
+        for package_id in packages {
+            write_package_bytecode(store, &command_dir, package_id)?;
+        }
+
+        let trace_path = command_dir.join("trace.json");
+        std::fs::write(&trace_path, command_trace.to_json().to_string())
+            .map_err(|e| write_error(&trace_path, e))?;
+    }
+    Ok(())
+}
+
+/// Writes the disassembled bytecode of the modules of package `package_id`, along with their
+/// source maps, to the `sources` and `source_maps` directories of `command_dir`.
+fn write_package_bytecode(
+    store: &dyn BackingPackageStore,
+    command_dir: &Path,
+    package_id: ObjectID,
+) -> Result<(), ReplayEngineError> {
+    // Packages published by the transaction itself might not be available
+    let package = match store.get_package_object(&package_id) {
+        Ok(Some(package)) => package,
+        Ok(None) => {
+            warn!("Package {package_id} not found, its bytecode is not included in the trace");
+            return Ok(());
+        }
+        Err(err) => {
+            warn!(
+                "Unable to get package {package_id}, its bytecode is not included in the trace: \
+                 {err}"
+            );
+            return Ok(());
+        }
+    };
+
+    let sources_dir = command_dir.join("sources").join(package_id.to_string());
+    let source_maps_dir = command_dir.join("source_maps").join(package_id.to_string());
+    for dir in [&sources_dir, &source_maps_dir] {
+        std::fs::create_dir_all(dir).map_err(|e| write_error(dir, e))?;
+    }
+
+    for (name, bytes) in package.move_package().serialized_module_map() {
+        // this deserializes a module to the max version of the bytecode but it's OK here because
+        // it's not run as part of the deterministic replicated state machine.
+        let module = CompiledModule::deserialize_with_defaults(bytes).map_err(|e| {
+            ReplayEngineError::GeneralError {
+                err: format!("Unable to deserialize module {package_id}::{name}: {e}"),
+            }
+        })?;
+        let (disassembly, source_map) =
+            Disassembler::from_module(&module, Spanned::unsafe_no_loc(()).loc)?
+                .disassemble_with_source_map()?;
+
+        let source_path = sources_dir
+            .join(name)
+            .with_extension(MOVE_BYTECODE_EXTENSION);
+        std::fs::write(&source_path, disassembly).map_err(|e| write_error(&source_path, e))?;
+
+        let source_map_path = source_maps_dir.join(name).with_extension("json");
+        std::fs::write(&source_map_path, serialize_to_json_string(&source_map)?)
+            .map_err(|e| write_error(&source_map_path, e))?;
+    }
+    Ok(())
+}
+
+fn write_error(path: &Path, err: std::io::Error) -> ReplayEngineError {
+    ReplayEngineError::UnableToWriteTrace {
+        path: path.display().to_string(),
+        err: err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use move_binary_format::file_format::FunctionDefinitionIndex;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::annotated_value::MoveValue;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::ModuleId;
+    use move_trace_format::format::{MoveTrace, MoveTraceBuilder};
+    use sui_framework::BuiltInFramework;
+    use sui_types::base_types::SequenceNumber;
+    use sui_types::execution_trace::{FramePackageTracer, SuiTraceEvent};
+    use sui_types::in_memory_storage::InMemoryStorage;
+    use sui_types::MOVE_STDLIB_PACKAGE_ID;
+
+    use super::*;
+
+    /// A command that calls `<address>::<module>::f` for each of `calls`, traced the way the
+    /// adapter traces a command, followed by `external` events.
+    fn command_trace(
+        packages: &BTreeMap<AccountAddress, (ObjectID, SequenceNumber)>,
+        calls: &[(AccountAddress, &str)],
+        external: Vec<SuiTraceEvent>,
+    ) -> MoveTrace {
+        let mut builder =
+            MoveTraceBuilder::new_with_tracer(Box::new(FramePackageTracer::new(packages.clone())));
+        for (address, module) in calls {
+            let frame_id = builder.current_trace_offset();
+            builder.open_frame(
+                frame_id,
+                FunctionDefinitionIndex(0),
+                "f".to_string(),
+                ModuleId::new(*address, Identifier::new(*module).unwrap()),
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                false,
+                1_000,
+            );
+            builder.close_frame(frame_id, vec![], 1_000);
+        }
+        for event in external {
+            builder.external(event);
+        }
+        builder.into_trace()
+    }
+
+    fn read_dir_names(dir: &Path) -> BTreeSet<String> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn command_traces_layout() {
+        let stdlib = BuiltInFramework::get_package_by_id(&MOVE_STDLIB_PACKAGE_ID).genesis_object();
+        let stdlib_modules: BTreeSet<String> = stdlib
+            .data
+            .try_as_package()
+            .unwrap()
+            .serialized_module_map()
+            .keys()
+            .cloned()
+            .collect();
+        let store = InMemoryStorage::new(vec![stdlib.clone()]);
+
+        // A package that is not in the store, such as one published by the transaction itself.
+        let published = ObjectID::from_single_byte(0xab);
+        let packages = BTreeMap::from([
+            (
+                AccountAddress::from(MOVE_STDLIB_PACKAGE_ID),
+                (MOVE_STDLIB_PACKAGE_ID, stdlib.version()),
+            ),
+            (
+                AccountAddress::from(published),
+                (published, SequenceNumber::from_u64(1)),
+            ),
+        ]);
+        let written = ObjectID::from_single_byte(0xcd);
+        let trace = TransactionTrace {
+            commands: vec![
+                command_trace(
+                    &packages,
+                    &[
+                        (AccountAddress::from(MOVE_STDLIB_PACKAGE_ID), "vector"),
+                        (AccountAddress::from(published), "m"),
+                    ],
+                    vec![],
+                ),
+                command_trace(
+                    &packages,
+                    &[],
+                    vec![SuiTraceEvent::ObjectWrite {
+                        object_id: written,
+                        value: MoveValue::U64(7),
+                    }],
+                ),
+            ],
+        };
+
+        let trace_dir = tempfile::tempdir().unwrap();
+        write_transaction_trace(&store, trace_dir.path(), trace).unwrap();
+
+        assert_eq!(
+            read_dir_names(trace_dir.path()),
+            BTreeSet::from(["command_0".to_string(), "command_1".to_string()]),
+        );
+
+        // The first command has the bytecode and source maps of the packages it called into
+        // that are available, one file per module.
+        let command_0 = trace_dir.path().join("command_0");
+        assert_eq!(
+            read_dir_names(&command_0),
+            BTreeSet::from([
+                "source_maps".to_string(),
+                "sources".to_string(),
+                "trace.json".to_string(),
+            ]),
+        );
+        for (dir, extension) in [
+            ("sources", MOVE_BYTECODE_EXTENSION),
+            ("source_maps", "json"),
+        ] {
+            assert_eq!(
+                read_dir_names(&command_0.join(dir)),
+                BTreeSet::from([MOVE_STDLIB_PACKAGE_ID.to_string()]),
+            );
+            let expected: BTreeSet<String> = stdlib_modules
+                .iter()
+                .map(|module| format!("{module}.{extension}"))
+                .collect();
+            assert_eq!(
+                read_dir_names(&command_0.join(dir).join(MOVE_STDLIB_PACKAGE_ID.to_string())),
+                expected,
+            );
+        }
+
+        let trace_0: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(command_0.join("trace.json")).unwrap()).unwrap();
+        let frame_packages: Vec<FramePackage> = trace_0["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|event| {
+                serde_json::from_value(event.get("External")?.get("FramePackage")?.clone()).ok()
+            })
+            .collect();
+        assert_eq!(
+            frame_packages
+                .iter()
+                .map(|frame_package| (frame_package.frame_id, frame_package.package_id))
+                .collect::<Vec<_>>(),
+            vec![(0, MOVE_STDLIB_PACKAGE_ID), (3, published)],
+        );
+
+        // The second command did not call any Move function, so only its trace is written.
+        let command_1 = trace_dir.path().join("command_1");
+        assert_eq!(
+            read_dir_names(&command_1),
+            BTreeSet::from(["trace.json".to_string()]),
+        );
+        let trace_1: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(command_1.join("trace.json")).unwrap()).unwrap();
+        let events = trace_1["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0]["External"]["ObjectWrite"]["object_id"],
+            serde_json::to_value(written).unwrap(),
+        );
+    }
+}
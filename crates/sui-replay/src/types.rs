@@ -199,6 +199,21 @@ pub enum ReplayEngineError {
 
     #[error("Unable to get chain id: {}", err)]
     UnableToGetChainId { err: String },
+
+    #[error("Unable to read checkpoint file at {}: {}", path, err)]
+    UnableToReadCheckpoint { path: String, err: String },
+
+    #[error("Checkpoint {checkpoint} not found in local checkpoints")]
+    CheckpointNotFound { checkpoint: u64 },
+
+    #[error("Transaction {digest} not found in local checkpoints")]
+    TransactionNotFound { digest: TransactionDigest },
+
+    #[error("Unable to read checkpoints from archive: {}", err)]
+    UnableToReadArchive { err: String },
+
+    #[error("Unable to write trace file at {}: {}", path, err)]
+    UnableToWriteTrace { path: String, err: String },
 }
 
 impl From<SuiObjectResponseError> for ReplayEngineError {
@@ -22,8 +22,10 @@ use tap::Pipe;
 
 use crate::rest::accounts::AccountOwnedObjectInfo;
 use crate::rest::accounts::ListAccountOwnedObjectsQueryParameters;
+use crate::rest::accounts::ListBalancesQueryParameters;
 use crate::rest::checkpoints::ListCheckpointsPaginationParameters;
 use crate::rest::coins::CoinInfo;
+use crate::rest::events::ListEventsQueryParameters;
 use crate::rest::health::Threshold;
 use crate::rest::objects::DynamicFieldInfo;
 use crate::rest::objects::ListDynamicFieldsQueryParameters;
@@ -36,9 +38,11 @@ use crate::rest::transactions::ListTransactionsCursorParameters;
 use crate::rest::transactions::ResolveTransactionQueryParameters;
 use crate::rest::transactions::ResolveTransactionResponse;
 use crate::rest::transactions::TransactionSimulationResponse;
+use crate::types::Balance;
 use crate::types::CheckpointResponse;
 use crate::types::ExecuteTransactionOptions;
 use crate::types::ExecuteTransactionResponse;
+use crate::types::IndexedEvent;
 use crate::types::NodeInfo;
 use crate::types::TransactionResponse;
 use crate::types::X_SUI_CHAIN;
@@ -123,6 +127,43 @@ impl Client {
         self.json(request).await
     }
 
+    pub async fn get_balance(
+        &self,
+        account: Address,
+        coin_type: &StructTag,
+    ) -> Result<Response<Balance>> {
+        let url = self
+            .url()
+            .join(&format!("accounts/{account}/balances/{coin_type}"))?;
+
+        let request = self.inner.get(url);
+
+        self.json(request).await
+    }
+
+    pub async fn list_balances(
+        &self,
+        account: Address,
+        parameters: &ListBalancesQueryParameters,
+    ) -> Result<Response<Vec<Balance>>> {
+        let url = self.url().join(&format!("accounts/{account}/balances"))?;
+
+        let request = self.inner.get(url).query(parameters);
+
+        self.json(request).await
+    }
+
+    pub async fn list_events(
+        &self,
+        parameters: &ListEventsQueryParameters,
+    ) -> Result<Response<Vec<IndexedEvent>>> {
+        let url = self.url().join("events")?;
+
+        let request = self.inner.get(url).query(parameters);
+
+        self.json(request).await
+    }
+
     pub async fn get_object(&self, object_id: ObjectId) -> Result<Response<Object>> {
         let url = self.url().join(&format!("objects/{object_id}"))?;
 
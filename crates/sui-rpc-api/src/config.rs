@@ -22,6 +22,14 @@ pub struct Config {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enable_indexing: Option<bool>,
 
+    /// Maximum number of checkpoint subscriptions that can be open at the same time
+    ///
+    /// Requests for new subscriptions are rejected while this many are open.
+    ///
+    /// Defaults to `100`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_checkpoint_subscriptions: Option<usize>,
+
     // Only include this till we have another field that isn't set with a non-default value for
     // testing
     #[doc(hidden)]
@@ -37,4 +45,8 @@ impl Config {
     pub fn enable_indexing(&self) -> bool {
         self.enable_indexing.unwrap_or(false)
     }
+
+    pub fn max_checkpoint_subscriptions(&self) -> usize {
+        self.max_checkpoint_subscriptions.unwrap_or(100)
+    }
 }
@@ -45,7 +45,18 @@ impl axum::response::IntoResponse for RpcServiceError {
 
 impl From<RpcServiceError> for tonic::Status {
     fn from(value: RpcServiceError) -> Self {
-        tonic::Status::new(tonic::Code::Unknown, value.message.as_deref().unwrap_or(""))
+        // Map status codes that clients are expected to act on (e.g. retry on missing data) to
+        // their gRPC equivalents.
+        let code = match value.status {
+            StatusCode::BAD_REQUEST => tonic::Code::InvalidArgument,
+            StatusCode::NOT_FOUND => tonic::Code::NotFound,
+            StatusCode::GONE => tonic::Code::OutOfRange,
+            StatusCode::TOO_MANY_REQUESTS => tonic::Code::ResourceExhausted,
+            StatusCode::INTERNAL_SERVER_ERROR => tonic::Code::Internal,
+            StatusCode::SERVICE_UNAVAILABLE => tonic::Code::Unavailable,
+            _ => tonic::Code::Unknown,
+        };
+        tonic::Status::new(code, value.message.as_deref().unwrap_or(""))
     }
 }
 
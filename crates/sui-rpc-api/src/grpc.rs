@@ -193,6 +193,156 @@ impl crate::proto::node::node_service_server::NodeService for crate::RpcService
             .map_err(Into::into)
     }
 
+    type SubscribeCheckpointsStream = std::pin::Pin<
+        Box<
+            dyn tokio_stream::Stream<
+                    Item = Result<crate::proto::node::SubscribeCheckpointsResponse, tonic::Status>,
+                > + Send,
+        >,
+    >;
+
+    async fn subscribe_checkpoints(
+        &self,
+        request: tonic::Request<crate::proto::node::SubscribeCheckpointsRequest>,
+    ) -> std::result::Result<tonic::Response<Self::SubscribeCheckpointsStream>, tonic::Status> {
+        use tokio_stream::StreamExt;
+
+        let request = request.into_inner();
+        let options = request.options.unwrap_or_default().into();
+
+        let checkpoints = self.subscribe_checkpoints(request.start_sequence_number, options)?;
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(checkpoints).map(|checkpoint| {
+            Ok::<_, tonic::Status>(crate::proto::node::SubscribeCheckpointsResponse {
+                checkpoint: Some(checkpoint?.into()),
+            })
+        });
+
+        Ok(tonic::Response::new(Box::pin(stream)))
+    }
+
+    async fn get_balance(
+        &self,
+        request: tonic::Request<crate::proto::node::GetBalanceRequest>,
+    ) -> std::result::Result<tonic::Response<crate::proto::node::GetBalanceResponse>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let owner = request
+            .owner
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing owner"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid owner"))?;
+        let coin_type = request
+            .coin_type
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing coin_type"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid coin_type"))?;
+
+        let balance = self.get_balance(owner, coin_type)?;
+
+        crate::proto::node::GetBalanceResponse {
+            balance: Some(balance.into()),
+        }
+        .pipe(tonic::Response::new)
+        .pipe(Ok)
+    }
+
+    async fn list_balances(
+        &self,
+        request: tonic::Request<crate::proto::node::ListBalancesRequest>,
+    ) -> std::result::Result<tonic::Response<crate::proto::node::ListBalancesResponse>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let owner = request
+            .owner
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing owner"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid owner"))?;
+        let start = request
+            .page_token
+            .map(|token| bcs::from_bytes::<sui_sdk_types::StructTag>(&token))
+            .transpose()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid page_token"))?;
+
+        let (balances, next) = self.list_balances(owner, start, page_size(request.page_size))?;
+
+        crate::proto::node::ListBalancesResponse {
+            balances: balances.into_iter().map(Into::into).collect(),
+            next_page_token: next
+                .map(|coin_type| bcs::to_bytes(&coin_type))
+                .transpose()
+                .map_err(crate::RpcServiceError::from)?
+                .map(Into::into),
+        }
+        .pipe(tonic::Response::new)
+        .pipe(Ok)
+    }
+
+    async fn list_events(
+        &self,
+        request: tonic::Request<crate::proto::node::ListEventsRequest>,
+    ) -> std::result::Result<tonic::Response<crate::proto::node::ListEventsResponse>, tonic::Status>
+    {
+        use crate::types::EventFilter;
+
+        let request = request.into_inner();
+        let invalid = |field: &str| {
+            tonic::Status::new(tonic::Code::InvalidArgument, format!("invalid {field}"))
+        };
+
+        let filter =
+            match (
+                &request.transaction_digest,
+                &request.sender,
+                &request.package,
+                &request.module,
+                &request.event_type,
+            ) {
+                (Some(digest), None, None, None, None) => EventFilter::Transaction(
+                    digest
+                        .try_into()
+                        .map_err(|_| invalid("transaction_digest"))?,
+                ),
+                (None, Some(sender), None, None, None) => {
+                    EventFilter::Sender(sender.try_into().map_err(|_| invalid("sender"))?)
+                }
+                (None, None, Some(package), Some(module), None) => EventFilter::Module {
+                    package: package.try_into().map_err(|_| invalid("package"))?,
+                    module: module.try_into().map_err(|_| invalid("module"))?,
+                },
+                (None, None, None, None, Some(event_type)) => {
+                    EventFilter::Type(event_type.try_into().map_err(|_| invalid("event_type"))?)
+                }
+                _ => return Err(tonic::Status::new(
+                    tonic::Code::InvalidArgument,
+                    "exactly one of `transaction_digest`, `sender`, `package` and `module`, or \
+                     `event_type` must be provided",
+                )),
+            };
+
+        let start = request
+            .page_token
+            .map(|token| bcs::from_bytes::<sui_types::storage::EventPosition>(&token))
+            .transpose()
+            .map_err(|_| invalid("page_token"))?;
+
+        let (events, next) = self.list_events(filter, start, page_size(request.page_size))?;
+
+        crate::proto::node::ListEventsResponse {
+            events: events.into_iter().map(Into::into).collect(),
+            next_page_token: next
+                .map(|position| bcs::to_bytes(&position))
+                .transpose()
+                .map_err(crate::RpcServiceError::from)?
+                .map(Into::into),
+        }
+        .pipe(tonic::Response::new)
+        .pipe(Ok)
+    }
+
     async fn execute_transaction(
         &self,
         request: tonic::Request<crate::proto::node::ExecuteTransactionRequest>,
@@ -274,3 +424,11 @@ impl crate::proto::node::node_service_server::NodeService for crate::RpcService
             .map_err(Into::into)
     }
 }
+
+/// The number of items to return in a page of results, given the `page_size` requested by a
+/// client.
+fn page_size(page_size: Option<u32>) -> usize {
+    page_size
+        .map(|size| (size as usize).clamp(1, crate::rest::MAX_PAGE_SIZE))
+        .unwrap_or(crate::rest::DEFAULT_PAGE_SIZE)
+}
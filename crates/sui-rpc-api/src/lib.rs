@@ -8,6 +8,7 @@ use std::sync::Arc;
 use sui_types::storage::RpcStateReader;
 use sui_types::transaction_executor::TransactionExecutor;
 use tap::Pipe;
+use tokio_util::sync::CancellationToken;
 
 pub mod client;
 mod config;
@@ -37,18 +38,27 @@ pub struct RpcService {
     software_version: &'static str,
     metrics: Option<Arc<RpcMetrics>>,
     config: Config,
+    /// Places for the checkpoint subscriptions that can still be opened.
+    checkpoint_subscriptions: Arc<tokio::sync::Semaphore>,
+    /// Cancelled when the service shuts down, which ends all open subscriptions.
+    shutdown: CancellationToken,
 }
 
 impl RpcService {
     pub fn new(reader: Arc<dyn RpcStateReader>, software_version: &'static str) -> Self {
         let chain_id = reader.get_chain_identifier().unwrap();
+        let config = Config::default();
         Self {
             reader: StateReader::new(reader),
             executor: None,
             chain_id,
             software_version,
             metrics: None,
-            config: Config::default(),
+            checkpoint_subscriptions: Arc::new(tokio::sync::Semaphore::new(
+                config.max_checkpoint_subscriptions(),
+            )),
+            config,
+            shutdown: CancellationToken::new(),
         }
     }
 
@@ -57,9 +67,18 @@ impl RpcService {
     }
 
     pub fn with_config(&mut self, config: Config) {
+        self.checkpoint_subscriptions = Arc::new(tokio::sync::Semaphore::new(
+            config.max_checkpoint_subscriptions(),
+        ));
         self.config = config;
     }
 
+    /// Shut the service down when `signal` is cancelled. Open subscriptions end, so that the
+    /// server serving them can shut down gracefully.
+    pub fn with_shutdown_signal(&mut self, signal: CancellationToken) {
+        self.shutdown = signal;
+    }
+
     pub fn with_executor(&mut self, executor: Arc<dyn TransactionExecutor + Send + Sync>) {
         self.executor = Some(executor);
     }
@@ -146,7 +165,9 @@ impl RpcService {
 
     pub async fn start_service(self, socket_address: std::net::SocketAddr) {
         let listener = tokio::net::TcpListener::bind(socket_address).await.unwrap();
+        let shutdown = self.shutdown.clone();
         axum::serve(listener, self.into_router().await)
+            .with_graceful_shutdown(shutdown.cancelled_owned())
             .await
             .unwrap();
     }
@@ -368,6 +368,120 @@ pub struct FullCheckpointObject {
     #[prost(message, optional, tag = "5")]
     pub object_bcs: ::core::option::Option<super::super::types::Bcs>,
 }
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SubscribeCheckpointsRequest {
+    /// The sequence number of the first checkpoint to stream. If it has already
+    /// been executed, the stream starts by catching up from storage.
+    ///
+    /// Defaults to the checkpoint after the most recently executed checkpoint if
+    /// not included
+    #[prost(uint64, optional, tag = "1")]
+    pub start_sequence_number: ::core::option::Option<u64>,
+    /// Which parts of each checkpoint to include in the stream. Only the sequence
+    /// number and digest of each checkpoint (and the digests of its transactions)
+    /// are included by default.
+    #[prost(message, optional, tag = "2")]
+    pub options: ::core::option::Option<GetFullCheckpointOptions>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeCheckpointsResponse {
+    /// The next checkpoint in the stream, in order of sequence number
+    #[prost(message, optional, tag = "1")]
+    pub checkpoint: ::core::option::Option<GetFullCheckpointResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceRequest {
+    /// Required. The address that owns the coins.
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// Required. The type of the coins to look up the balance of, eg `0x2::sui::SUI`.
+    #[prost(message, optional, tag = "2")]
+    pub coin_type: ::core::option::Option<super::super::types::StructTag>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceResponse {
+    /// The total balance of the coins of the requested type owned by the address.
+    /// This is zero if the address doesn't own any coins of that type.
+    #[prost(message, optional, tag = "1")]
+    pub balance: ::core::option::Option<super::super::types::Balance>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBalancesRequest {
+    /// Required. The address that owns the coins.
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// The maximum number of balances to return.
+    ///
+    /// Defaults to 50 if not included, and is capped at 100.
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListBalances` call, to continue
+    /// listing balances from.
+    #[prost(bytes = "bytes", optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBalancesResponse {
+    /// The balances of each coin type owned by the address, in order of coin
+    /// type.
+    #[prost(message, repeated, tag = "1")]
+    pub balances: ::prost::alloc::vec::Vec<super::super::types::Balance>,
+    /// A token to retrieve the next page of balances with, if there are any.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEventsRequest {
+    /// List the events emitted by this transaction.
+    #[prost(message, optional, tag = "1")]
+    pub transaction_digest: ::core::option::Option<super::super::types::Digest>,
+    /// List the events emitted by transactions sent by this address.
+    #[prost(message, optional, tag = "2")]
+    pub sender: ::core::option::Option<super::super::types::Address>,
+    /// List the events emitted by transactions whose entry point is in this
+    /// package. Must be provided along with `module`.
+    #[prost(message, optional, tag = "3")]
+    pub package: ::core::option::Option<super::super::types::ObjectId>,
+    /// List the events emitted by transactions whose entry point is in this
+    /// module. Must be provided along with `package`.
+    #[prost(message, optional, tag = "4")]
+    pub module: ::core::option::Option<super::super::types::Identifier>,
+    /// List the events of exactly this type.
+    #[prost(message, optional, tag = "5")]
+    pub event_type: ::core::option::Option<super::super::types::StructTag>,
+    /// The maximum number of events to return.
+    ///
+    /// Defaults to 50 if not included, and is capped at 100.
+    #[prost(uint32, optional, tag = "6")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListEvents` call, to continue
+    /// listing events from.
+    #[prost(bytes = "bytes", optional, tag = "7")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEventsResponse {
+    /// The events that match the request, in the order they were emitted.
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<IndexedEvent>,
+    /// A token to retrieve the next page of events with, if there are any.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IndexedEvent {
+    /// The sequence number of the checkpoint that the event was emitted in.
+    #[prost(uint64, optional, tag = "1")]
+    pub checkpoint: ::core::option::Option<u64>,
+    /// The digest of the transaction that emitted the event.
+    #[prost(message, optional, tag = "2")]
+    pub transaction_digest: ::core::option::Option<super::super::types::Digest>,
+    /// The index of the event in the events emitted by its transaction.
+    #[prost(uint32, optional, tag = "3")]
+    pub event_index: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "4")]
+    pub event: ::core::option::Option<super::super::types::Event>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct BalanceChange {
     #[prost(message, optional, tag = "1")]
@@ -690,6 +804,104 @@ pub mod node_service_client {
                 .insert(GrpcMethod::new("sui.node.v2.NodeService", "GetFullCheckpoint"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn subscribe_checkpoints(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeCheckpointsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::SubscribeCheckpointsResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/SubscribeCheckpoints",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.node.v2.NodeService", "SubscribeCheckpoints"),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn get_balance(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBalanceResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/GetBalance",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "GetBalance"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_balances(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBalancesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/ListBalances",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "ListBalances"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListEventsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/ListEvents",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "ListEvents"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn execute_transaction(
             &mut self,
             request: impl tonic::IntoRequest<super::ExecuteTransactionRequest>,
@@ -773,6 +985,43 @@ pub mod node_service_server {
             tonic::Response<super::GetFullCheckpointResponse>,
             tonic::Status,
         >;
+        /// Server streaming response type for the SubscribeCheckpoints method.
+        type SubscribeCheckpointsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::SubscribeCheckpointsResponse,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn subscribe_checkpoints(
+            &self,
+            request: tonic::Request<super::SubscribeCheckpointsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeCheckpointsStream>,
+            tonic::Status,
+        >;
+        async fn get_balance(
+            &self,
+            request: tonic::Request<super::GetBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBalanceResponse>,
+            tonic::Status,
+        >;
+        async fn list_balances(
+            &self,
+            request: tonic::Request<super::ListBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBalancesResponse>,
+            tonic::Status,
+        >;
+        async fn list_events(
+            &self,
+            request: tonic::Request<super::ListEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListEventsResponse>,
+            tonic::Status,
+        >;
         async fn execute_transaction(
             &self,
             request: tonic::Request<super::ExecuteTransactionRequest>,
@@ -1128,6 +1377,189 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/sui.node.v2.NodeService/SubscribeCheckpoints" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeCheckpointsSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::ServerStreamingService<
+                        super::SubscribeCheckpointsRequest,
+                    > for SubscribeCheckpointsSvc<T> {
+                        type Response = super::SubscribeCheckpointsResponse;
+                        type ResponseStream = T::SubscribeCheckpointsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeCheckpointsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::subscribe_checkpoints(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeCheckpointsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/GetBalance" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBalanceSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetBalanceRequest>
+                    for GetBalanceSvc<T> {
+                        type Response = super::GetBalanceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBalanceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_balance(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetBalanceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/ListBalances" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListBalancesSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::ListBalancesRequest>
+                    for ListBalancesSvc<T> {
+                        type Response = super::ListBalancesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBalancesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::list_balances(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListBalancesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/ListEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListEventsSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::ListEventsRequest>
+                    for ListEventsSvc<T> {
+                        type Response = super::ListEventsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::list_events(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/sui.node.v2.NodeService/ExecuteTransaction" => {
                     #[allow(non_camel_case_types)]
                     struct ExecuteTransactionSvc<T: NodeService>(pub Arc<T>);
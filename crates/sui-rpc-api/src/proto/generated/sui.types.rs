@@ -261,6 +261,13 @@ pub struct GasCostSummary {
     pub non_refundable_storage_fee: ::core::option::Option<u64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Balance {
+    #[prost(message, optional, tag = "1")]
+    pub coin_type: ::core::option::Option<StructTag>,
+    #[prost(uint64, optional, tag = "2")]
+    pub balance: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Identifier {
     #[prost(string, optional, tag = "1")]
     pub identifier: ::core::option::Option<::prost::alloc::string::String>,
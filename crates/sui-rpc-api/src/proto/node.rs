@@ -974,6 +974,187 @@ impl GetFullCheckpointRequest {
     }
 }
 
+//
+// SubscribeCheckpointsRequest
+//
+
+impl SubscribeCheckpointsRequest {
+    pub fn latest() -> Self {
+        Self {
+            start_sequence_number: None,
+            options: None,
+        }
+    }
+
+    pub fn from_sequence_number(start_sequence_number: u64) -> Self {
+        Self {
+            start_sequence_number: Some(start_sequence_number),
+            options: None,
+        }
+    }
+
+    pub fn with_options(mut self, options: GetFullCheckpointOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+}
+
+//
+// Balance
+//
+
+impl From<crate::types::Balance> for super::types::Balance {
+    fn from(crate::types::Balance { coin_type, balance }: crate::types::Balance) -> Self {
+        Self {
+            coin_type: Some(coin_type.into()),
+            balance: Some(balance),
+        }
+    }
+}
+
+impl TryFrom<&super::types::Balance> for crate::types::Balance {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        super::types::Balance { coin_type, balance }: &super::types::Balance,
+    ) -> Result<Self, Self::Error> {
+        let coin_type = coin_type
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("coin_type"))?
+            .pipe(TryInto::try_into)?;
+        let balance = balance.ok_or_else(|| TryFromProtoError::missing("balance"))?;
+
+        Ok(Self { coin_type, balance })
+    }
+}
+
+//
+// GetBalanceRequest
+//
+
+impl GetBalanceRequest {
+    pub fn new<A, T>(owner: A, coin_type: T) -> Self
+    where
+        A: Into<super::types::Address>,
+        T: Into<super::types::StructTag>,
+    {
+        Self {
+            owner: Some(owner.into()),
+            coin_type: Some(coin_type.into()),
+        }
+    }
+}
+
+//
+// ListBalancesRequest
+//
+
+impl ListBalancesRequest {
+    pub fn new<A: Into<super::types::Address>>(owner: A) -> Self {
+        Self {
+            owner: Some(owner.into()),
+            page_size: None,
+            page_token: None,
+        }
+    }
+
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn with_page_token<T: Into<prost::bytes::Bytes>>(mut self, page_token: T) -> Self {
+        self.page_token = Some(page_token.into());
+        self
+    }
+}
+
+//
+// ListEventsRequest
+//
+
+impl ListEventsRequest {
+    pub fn new(filter: crate::types::EventFilter) -> Self {
+        use crate::types::EventFilter;
+
+        let mut request = Self::default();
+        match filter {
+            EventFilter::Transaction(digest) => request.transaction_digest = Some(digest.into()),
+            EventFilter::Sender(sender) => request.sender = Some(sender.into()),
+            EventFilter::Module { package, module } => {
+                request.package = Some(package.into());
+                request.module = Some(module.into());
+            }
+            EventFilter::Type(event_type) => request.event_type = Some(event_type.into()),
+        }
+
+        request
+    }
+
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn with_page_token<T: Into<prost::bytes::Bytes>>(mut self, page_token: T) -> Self {
+        self.page_token = Some(page_token.into());
+        self
+    }
+}
+
+//
+// IndexedEvent
+//
+
+impl From<crate::types::IndexedEvent> for IndexedEvent {
+    fn from(
+        crate::types::IndexedEvent {
+            checkpoint,
+            transaction_digest,
+            event_index,
+            event,
+        }: crate::types::IndexedEvent,
+    ) -> Self {
+        Self {
+            checkpoint: Some(checkpoint),
+            transaction_digest: Some(transaction_digest.into()),
+            event_index: Some(event_index),
+            event: Some(event.into()),
+        }
+    }
+}
+
+impl TryFrom<&IndexedEvent> for crate::types::IndexedEvent {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        IndexedEvent {
+            checkpoint,
+            transaction_digest,
+            event_index,
+            event,
+        }: &IndexedEvent,
+    ) -> Result<Self, Self::Error> {
+        let checkpoint = checkpoint.ok_or_else(|| TryFromProtoError::missing("checkpoint"))?;
+        let transaction_digest = transaction_digest
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("transaction_digest"))?
+            .pipe(TryInto::try_into)?;
+        let event_index = event_index.ok_or_else(|| TryFromProtoError::missing("event_index"))?;
+        let event = event
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("event"))?
+            .pipe(TryInto::try_into)?;
+
+        Ok(Self {
+            checkpoint,
+            transaction_digest,
+            event_index,
+            event,
+        })
+    }
+}
+
 //
 // TransactionResponse
 //
@@ -3,6 +3,7 @@
 
 use super::{ApiEndpoint, RouteHandler};
 use crate::reader::StateReader;
+use crate::types::Balance;
 use crate::Result;
 use crate::{rest::PageCursor, RpcService, RpcServiceError};
 use axum::extract::Query;
@@ -93,3 +94,66 @@ pub struct AccountOwnedObjectInfo {
     #[serde(rename = "type")]
     pub type_: StructTag,
 }
+
+pub struct GetBalance;
+
+impl ApiEndpoint<RpcService> for GetBalance {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/accounts/{account}/balances/{coin_type}"
+    }
+
+    fn handler(&self) -> RouteHandler<RpcService> {
+        RouteHandler::new(self.method(), get_balance)
+    }
+}
+
+async fn get_balance(
+    Path((address, coin_type)): Path<(Address, StructTag)>,
+    State(state): State<RpcService>,
+) -> Result<Json<Balance>> {
+    state.get_balance(address, coin_type).map(Json)
+}
+
+pub struct ListBalances;
+
+impl ApiEndpoint<RpcService> for ListBalances {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/accounts/{account}/balances"
+    }
+
+    fn handler(&self) -> RouteHandler<RpcService> {
+        RouteHandler::new(self.method(), list_balances)
+    }
+}
+
+async fn list_balances(
+    Path(address): Path<Address>,
+    Query(parameters): Query<ListBalancesQueryParameters>,
+    State(state): State<RpcService>,
+) -> Result<(PageCursor<StructTag>, Json<Vec<Balance>>)> {
+    let (balances, cursor) = state.list_balances(address, parameters.start, parameters.limit())?;
+
+    Ok((PageCursor(cursor), Json(balances)))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ListBalancesQueryParameters {
+    pub limit: Option<u32>,
+    pub start: Option<StructTag>,
+}
+
+impl ListBalancesQueryParameters {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|l| (l as usize).clamp(1, crate::rest::MAX_PAGE_SIZE))
+            .unwrap_or(crate::rest::DEFAULT_PAGE_SIZE)
+    }
+}
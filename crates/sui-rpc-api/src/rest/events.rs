@@ -0,0 +1,201 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use sui_sdk_types::{
+    Address, CheckpointSequenceNumber, Identifier, ObjectId, StructTag, TransactionDigest,
+};
+use sui_types::storage::EventPosition;
+use tap::Pipe;
+
+use super::{ApiEndpoint, RouteHandler};
+use crate::rest::PageCursor;
+use crate::types::EventFilter;
+use crate::types::IndexedEvent;
+use crate::Result;
+use crate::RpcService;
+use crate::RpcServiceError;
+
+pub struct ListEvents;
+
+impl ApiEndpoint<RpcService> for ListEvents {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/events"
+    }
+
+    fn handler(&self) -> RouteHandler<RpcService> {
+        RouteHandler::new(self.method(), list_events)
+    }
+}
+
+async fn list_events(
+    Query(parameters): Query<ListEventsQueryParameters>,
+    State(state): State<RpcService>,
+) -> Result<(PageCursor<EventCursor>, Json<Vec<IndexedEvent>>)> {
+    let filter = parameters.filter()?;
+    let limit = parameters.limit();
+    let start = parameters.start.map(EventPosition::from);
+
+    let (events, cursor) = state.list_events(filter, start, limit)?;
+
+    Ok((PageCursor(cursor.map(EventCursor::from)), Json(events)))
+}
+
+/// Query parameters for listing events.
+///
+/// Exactly one filter must be provided: either `transaction`, `sender`, `type`, or both `package`
+/// and `module`.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ListEventsQueryParameters {
+    /// List the events emitted by this transaction.
+    pub transaction: Option<TransactionDigest>,
+    /// List the events emitted by transactions sent by this address.
+    pub sender: Option<Address>,
+    /// List the events emitted by transactions whose entry point is in this package.
+    pub package: Option<ObjectId>,
+    /// List the events emitted by transactions whose entry point is in this module.
+    pub module: Option<Identifier>,
+    /// List the events of exactly this type.
+    #[serde(rename = "type")]
+    pub event_type: Option<StructTag>,
+    pub limit: Option<u32>,
+    pub start: Option<EventCursor>,
+}
+
+impl ListEventsQueryParameters {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|l| (l as usize).clamp(1, crate::rest::MAX_PAGE_SIZE))
+            .unwrap_or(crate::rest::DEFAULT_PAGE_SIZE)
+    }
+
+    pub fn filter(&self) -> Result<EventFilter> {
+        let filter = match (
+            self.transaction,
+            self.sender,
+            self.package,
+            &self.module,
+            &self.event_type,
+        ) {
+            (Some(transaction), None, None, None, None) => EventFilter::Transaction(transaction),
+            (None, Some(sender), None, None, None) => EventFilter::Sender(sender),
+            (None, None, Some(package), Some(module), None) => EventFilter::Module {
+                package,
+                module: module.clone(),
+            },
+            (None, None, None, None, Some(event_type)) => EventFilter::Type(event_type.clone()),
+            _ => {
+                return Err(RpcServiceError::new(
+                    StatusCode::BAD_REQUEST,
+                    "exactly one of `transaction`, `sender`, `package` and `module`, or `type` \
+                     must be provided",
+                ))
+            }
+        };
+
+        Ok(filter)
+    }
+}
+
+/// A Cursor that points at a specific event in history.
+///
+/// Has the format of: `<checkpoint>.<transaction>.<event>`
+/// where `<checkpoint>` is the sequence number of a checkpoint, `<transaction>` is the index of a
+/// transaction in that checkpoint, and `<event>` is the index of an event emitted by that
+/// transaction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EventCursor {
+    checkpoint: CheckpointSequenceNumber,
+    transaction_index: u32,
+    event_index: u32,
+}
+
+impl std::fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self.checkpoint, self.transaction_index, self.event_index
+        )
+    }
+}
+
+impl std::str::FromStr for EventCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parts = s.split('.');
+        let (Some(checkpoint), Some(transaction_index), Some(event_index), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            anyhow::bail!(
+                "invalid event cursor {s:?}, expected <checkpoint>.<transaction>.<event>"
+            );
+        };
+
+        Self {
+            checkpoint: checkpoint.parse()?,
+            transaction_index: transaction_index.parse()?,
+            event_index: event_index.parse()?,
+        }
+        .pipe(Ok)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EventCursor {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde_with::DeserializeAs;
+        serde_with::DisplayFromStr::deserialize_as(deserializer)
+    }
+}
+
+impl serde::Serialize for EventCursor {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde_with::SerializeAs;
+        serde_with::DisplayFromStr::serialize_as(self, serializer)
+    }
+}
+
+impl From<EventPosition> for EventCursor {
+    fn from(
+        EventPosition {
+            checkpoint,
+            transaction_index,
+            event_index,
+        }: EventPosition,
+    ) -> Self {
+        Self {
+            checkpoint,
+            transaction_index,
+            event_index,
+        }
+    }
+}
+
+impl From<EventCursor> for EventPosition {
+    fn from(
+        EventCursor {
+            checkpoint,
+            transaction_index,
+            event_index,
+        }: EventCursor,
+    ) -> Self {
+        Self {
+            checkpoint,
+            transaction_index,
+            event_index,
+        }
+    }
+}
@@ -18,6 +18,7 @@ pub mod checkpoints;
 pub mod coins;
 mod committee;
 pub mod content_type;
+pub mod events;
 pub mod health;
 pub mod info;
 pub mod objects;
@@ -33,6 +34,8 @@ pub const ENDPOINTS: &[&dyn ApiEndpoint<RpcService>] = &[
     &health::HealthCheck,
     &checkpoints::GetCheckpoint,
     &accounts::ListAccountObjects,
+    &accounts::GetBalance,
+    &accounts::ListBalances,
     &objects::GetObject,
     &objects::GetObjectWithVersion,
     &objects::ListDynamicFields,
@@ -49,6 +52,7 @@ pub const ENDPOINTS: &[&dyn ApiEndpoint<RpcService>] = &[
     &transactions::SimulateTransaction,
     &transactions::ResolveTransaction,
     &coins::GetCoinInfo,
+    &events::ListEvents,
 ];
 
 pub fn build_rest_router(service: RpcService) -> axum::Router {
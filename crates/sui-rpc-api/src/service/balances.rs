@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::types::Balance;
+use crate::Result;
+use crate::RpcService;
+use crate::RpcServiceError;
+use sui_sdk_types::Address;
+use sui_sdk_types::StructTag;
+use sui_types::sui_sdk_types_conversions::struct_tag_core_to_sdk;
+use sui_types::sui_sdk_types_conversions::struct_tag_sdk_to_core;
+
+impl RpcService {
+    pub fn get_balance(&self, owner: Address, coin_type: StructTag) -> Result<Balance> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+
+        let balance = indexes
+            .get_balance(&owner.into(), &struct_tag_sdk_to_core(coin_type.clone())?)?
+            .map(|info| info.balance)
+            .unwrap_or(0);
+
+        Ok(Balance { coin_type, balance })
+    }
+
+    /// List up to `limit` of the balances owned by `owner`, in order of coin type, starting from
+    /// (and including) the `start` coin type. Also returns the coin type to start the next page
+    /// from, if there is one.
+    pub fn list_balances(
+        &self,
+        owner: Address,
+        start: Option<StructTag>,
+        limit: usize,
+    ) -> Result<(Vec<Balance>, Option<StructTag>)> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+
+        let start = start.map(struct_tag_sdk_to_core).transpose()?;
+
+        let mut balances = indexes
+            .balance_iter(&owner.into(), start)?
+            .take(limit + 1)
+            .map(|item| {
+                let (coin_type, info) = item?;
+                Ok(Balance {
+                    coin_type: struct_tag_core_to_sdk(coin_type)?,
+                    balance: info.balance,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let next = if balances.len() > limit {
+            // SAFETY: We've already verified that balances is greater than limit, which is
+            // gaurenteed to be >= 1.
+            Some(balances.pop().unwrap().coin_type)
+        } else {
+            None
+        };
+
+        Ok((balances, next))
+    }
+}
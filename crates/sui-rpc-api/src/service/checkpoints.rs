@@ -15,6 +15,14 @@ use sui_sdk_types::CheckpointSequenceNumber;
 use sui_sdk_types::SignedCheckpointSummary;
 use tap::Pipe;
 
+/// How often a checkpoint subscription that has caught up with the tip of the chain checks for
+/// newly executed checkpoints.
+const SUBSCRIPTION_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// The number of checkpoints that can be queued for a subscriber before the subscription stops
+/// reading further checkpoints and waits for the subscriber to catch up.
+const SUBSCRIPTION_BUFFER_SIZE: usize = 16;
+
 impl RpcService {
     pub fn get_checkpoint(
         &self,
@@ -168,6 +176,106 @@ impl RpcService {
         }
         .pipe(Ok)
     }
+
+    /// Stream checkpoints in order of sequence number, starting from `start` (or the checkpoint
+    /// after the latest executed checkpoint, if `start` is not provided). Checkpoints that have
+    /// already been executed are read from storage, after which new checkpoints are sent as they
+    /// are executed.
+    ///
+    /// Checkpoints are queued in a bounded buffer, so a slow subscriber will cause the
+    /// subscription to pause rather than accumulate checkpoints in memory. The subscription ends
+    /// after it sends an error, once the receiver has been dropped, or when the service shuts
+    /// down. At most `Config::max_checkpoint_subscriptions` subscriptions can be open at once.
+    pub fn subscribe_checkpoints(
+        &self,
+        start: Option<CheckpointSequenceNumber>,
+        options: GetFullCheckpointOptions,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<FullCheckpointResponse>>> {
+        let mut next = match start {
+            Some(start) => {
+                let oldest_checkpoint = self
+                    .reader
+                    .inner()
+                    .get_lowest_available_checkpoint_objects()?;
+                if start < oldest_checkpoint {
+                    return Err(crate::RpcServiceError::new(
+                        axum::http::StatusCode::GONE,
+                        "Old checkpoints have been pruned",
+                    ));
+                }
+
+                start
+            }
+            None => {
+                self.reader
+                    .inner()
+                    .get_latest_checkpoint_sequence_number()?
+                    + 1
+            }
+        };
+
+        let permit = self
+            .checkpoint_subscriptions
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| {
+                crate::RpcServiceError::new(
+                    axum::http::StatusCode::TOO_MANY_REQUESTS,
+                    format!(
+                        "Too many checkpoint subscriptions. Limit: {}",
+                        self.config.max_checkpoint_subscriptions()
+                    ),
+                )
+            })?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+        let service = self.clone();
+
+        tokio::spawn(async move {
+            // The subscription holds its place until the task ends.
+            let _permit = permit;
+            loop {
+                let latest = match service
+                    .reader
+                    .inner()
+                    .get_latest_checkpoint_sequence_number()
+                {
+                    Ok(latest) => latest,
+                    Err(e) => {
+                        let _ = tx.send(Err(e.into())).await;
+                        return;
+                    }
+                };
+
+                if next > latest {
+                    tokio::select! {
+                        _ = tokio::time::sleep(SUBSCRIPTION_POLL_INTERVAL) => continue,
+                        _ = tx.closed() => return,
+                        _ = service.shutdown.cancelled() => return,
+                    }
+                }
+
+                let checkpoint =
+                    service.get_full_checkpoint(CheckpointId::SequenceNumber(next), &options);
+                let failed = checkpoint.is_err();
+
+                // Sending waits for space in the buffer, which applies backpressure from the
+                // subscriber to the reads from storage.
+                tokio::select! {
+                    sent = tx.send(checkpoint) => {
+                        if sent.is_err() || failed {
+                            return;
+                        }
+                    }
+                    _ = service.shutdown.cancelled() => return,
+                }
+
+                next += 1;
+            }
+        });
+
+        Ok(rx)
+    }
 }
 
 fn transaction_to_checkpoint_transaction(
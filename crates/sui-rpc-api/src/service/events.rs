@@ -0,0 +1,175 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::rest::transactions::TransactionNotFoundError;
+use crate::types::EventFilter;
+use crate::types::IndexedEvent;
+use crate::Result;
+use crate::RpcService;
+use crate::RpcServiceError;
+use sui_sdk_types::Event;
+use sui_sdk_types::TransactionDigest;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::storage::EventPosition;
+use sui_types::storage::EventSelector;
+use sui_types::sui_sdk_types_conversions::struct_tag_sdk_to_core;
+
+impl RpcService {
+    /// List up to `limit` of the events selected by `filter`, in the order they were emitted,
+    /// starting from (and including) the event at position `start`. Also returns the position to
+    /// start the next page from, if there is one.
+    pub fn list_events(
+        &self,
+        filter: EventFilter,
+        start: Option<EventPosition>,
+        limit: usize,
+    ) -> Result<(Vec<IndexedEvent>, Option<EventPosition>)> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+
+        let selector = match filter {
+            EventFilter::Transaction(digest) => {
+                return self.list_transaction_events(digest, start, limit)
+            }
+            EventFilter::Sender(sender) => EventSelector::Sender(sender.into()),
+            EventFilter::Module { package, module } => EventSelector::Module {
+                package: package.into(),
+                module: move_core_types::identifier::Identifier::new(module.into_inner())?,
+            },
+            EventFilter::Type(event_type) => {
+                EventSelector::Type(struct_tag_sdk_to_core(event_type)?)
+            }
+        };
+
+        let mut positions = indexes
+            .event_iter(selector, start)?
+            .take(limit + 1)
+            .collect::<Vec<_>>();
+
+        let next = if positions.len() > limit {
+            positions.pop().map(|(position, _)| position)
+        } else {
+            None
+        };
+
+        // Consecutive events are often emitted by the same transaction, so only load the events
+        // of each transaction once.
+        let mut transaction_events: HashMap<TransactionDigest, Vec<Event>> = HashMap::new();
+        let events = positions
+            .into_iter()
+            .map(|(position, digest)| {
+                let digest = digest.into();
+                let events = match transaction_events.entry(digest) {
+                    std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                    std::collections::hash_map::Entry::Vacant(entry) => {
+                        entry.insert(self.transaction_events(digest)?)
+                    }
+                };
+
+                let event = events
+                    .get(position.event_index as usize)
+                    .cloned()
+                    .ok_or_else(|| {
+                        RpcServiceError::new(
+                            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                            format!(
+                                "Unable to find event {} of transaction {digest}",
+                                position.event_index
+                            ),
+                        )
+                    })?;
+
+                Ok(IndexedEvent {
+                    checkpoint: position.checkpoint,
+                    transaction_digest: digest,
+                    event_index: position.event_index,
+                    event,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((events, next))
+    }
+
+    fn list_transaction_events(
+        &self,
+        digest: TransactionDigest,
+        start: Option<EventPosition>,
+        limit: usize,
+    ) -> Result<(Vec<IndexedEvent>, Option<EventPosition>)> {
+        let checkpoint = self
+            .reader
+            .get_transaction_checkpoint(&digest.into())
+            .ok_or(TransactionNotFoundError(digest))?;
+
+        let transaction_index = self
+            .reader
+            .inner()
+            .get_checkpoint_contents_by_sequence_number(checkpoint)
+            .and_then(|contents| {
+                contents
+                    .iter()
+                    .position(|digests| digests.transaction == digest.into())
+            })
+            .ok_or(TransactionNotFoundError(digest))? as u32;
+
+        let mut events = self
+            .transaction_events(digest)?
+            .into_iter()
+            .enumerate()
+            .map(|(event_index, event)| {
+                let position = EventPosition {
+                    checkpoint,
+                    transaction_index,
+                    event_index: event_index as u32,
+                };
+
+                let event = IndexedEvent {
+                    checkpoint,
+                    transaction_digest: digest,
+                    event_index: event_index as u32,
+                    event,
+                };
+
+                (position, event)
+            })
+            .skip_while(|(position, _)| start.is_some_and(|start| *position < start))
+            .take(limit + 1)
+            .collect::<Vec<_>>();
+
+        let next = if events.len() > limit {
+            events.pop().map(|(position, _)| position)
+        } else {
+            None
+        };
+
+        Ok((events.into_iter().map(|(_, event)| event).collect(), next))
+    }
+
+    /// Load the events emitted by the transaction with the given `digest`.
+    fn transaction_events(&self, digest: TransactionDigest) -> Result<Vec<Event>> {
+        let effects = self
+            .reader
+            .inner()
+            .get_transaction_effects(&digest.into())
+            .ok_or(TransactionNotFoundError(digest))?;
+
+        let Some(events_digest) = effects.events_digest() else {
+            return Ok(vec![]);
+        };
+
+        let events: sui_sdk_types::TransactionEvents = self
+            .reader
+            .inner()
+            .get_events(events_digest)
+            .ok_or(TransactionNotFoundError(digest))?
+            .try_into()?;
+
+        Ok(events.0)
+    }
+}
@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod balances;
 pub(crate) mod checkpoints;
 mod committee;
+mod events;
 mod health;
 mod info;
 pub(crate) mod objects;
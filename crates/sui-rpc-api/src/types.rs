@@ -632,3 +632,39 @@ pub struct FullCheckpointObject {
     pub object: Option<sui_sdk_types::Object>,
     pub object_bcs: Option<Vec<u8>>,
 }
+
+/// The total balance of the coins of a single type owned by an address.
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Balance {
+    pub coin_type: sui_sdk_types::StructTag,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    pub balance: u64,
+}
+
+/// An event, along with where in the history of the chain it was emitted.
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct IndexedEvent {
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    pub checkpoint: sui_sdk_types::CheckpointSequenceNumber,
+    pub transaction_digest: sui_sdk_types::TransactionDigest,
+    pub event_index: u32,
+    pub event: sui_sdk_types::Event,
+}
+
+/// The criteria used to select the events to list.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventFilter {
+    /// Events emitted by a transaction.
+    Transaction(sui_sdk_types::TransactionDigest),
+    /// Events emitted by transactions sent by an address.
+    Sender(sui_sdk_types::Address),
+    /// Events emitted by transactions whose entry point is in a module.
+    Module {
+        package: sui_sdk_types::ObjectId,
+        module: sui_sdk_types::Identifier,
+    },
+    /// Events of exactly this type.
+    Type(sui_sdk_types::StructTag),
+}
@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Traces of the execution of programmable transactions. A transaction is traced one command at a
+//! time: the trace of a command holds the events recorded by the Move VM while it executes the
+//! Move functions that the command calls, and the `SuiTraceEvent`s of the command, which are
+//! recorded as external events.
+
+use std::collections::BTreeMap;
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::MoveValue;
+use move_trace_format::format::{MoveTrace, TraceEvent, TraceIndex};
+use move_trace_format::interface::{Tracer, Writer};
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::{ObjectID, SequenceNumber};
+use crate::object::Owner;
+
+/// The traces of the commands of a programmable transaction, in the order they were executed.
+/// Execution stops at the first command that fails, so if the transaction failed, the trace of
+/// the command that failed is the last one.
+#[derive(Debug, Default)]
+pub struct TransactionTrace {
+    pub commands: Vec<MoveTrace>,
+}
+
+/// Events that are recorded in the trace of a command, in addition to those of the Move VM.
+#[derive(Clone, Debug, Serialize)]
+pub enum SuiTraceEvent {
+    /// Recorded right after a frame is opened.
+    FramePackage(FramePackage),
+    /// An object that the command read. Recorded after the events of the Move VM.
+    ObjectLoad {
+        object_id: ObjectID,
+        version: SequenceNumber,
+        owner: Owner,
+        kind: ObjectLoadKind,
+    },
+    /// An input object or child object (such as a dynamic field) that the command modified or
+    /// added, with its value after the command. Recorded after the events of the Move VM. Child
+    /// objects that the command removed are not recorded.
+    ObjectWrite {
+        object_id: ObjectID,
+        value: MoveValue,
+    },
+}
+
+/// The on-chain package that the module of the function in frame `frame_id` was loaded from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FramePackage {
+    pub frame_id: TraceIndex,
+    pub package_id: ObjectID,
+    pub version: SequenceNumber,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub enum ObjectLoadKind {
+    /// An input object, or the gas coin, passed to the command as an argument.
+    Input,
+    /// An object loaded by the Move functions that the command called, such as a dynamic field
+    /// or a received object.
+    Runtime,
+}
+
+/// Records a `SuiTraceEvent::FramePackage` event for every frame that is opened, for the modules
+/// of the packages in `packages`, which maps the runtime IDs of packages to the storage ID and
+/// version of the package that their modules are loaded from.
+pub struct FramePackageTracer {
+    packages: BTreeMap<AccountAddress, (ObjectID, SequenceNumber)>,
+}
+
+impl FramePackageTracer {
+    pub fn new(packages: BTreeMap<AccountAddress, (ObjectID, SequenceNumber)>) -> Self {
+        Self { packages }
+    }
+}
+
+impl FramePackage {
+    /// The `FramePackage` recorded by `event`, if it is a `SuiTraceEvent::FramePackage` event.
+    pub fn from_event(event: &TraceEvent) -> Option<Self> {
+        let TraceEvent::External(value) = event else {
+            return None;
+        };
+        serde_json::from_value(value.get("FramePackage")?.clone()).ok()
+    }
+}
+
+impl Tracer for FramePackageTracer {
+    fn notify(&mut self, event: &TraceEvent, mut writer: Writer<'_>) {
+        let TraceEvent::OpenFrame { frame, .. } = event else {
+            return;
+        };
+
+        if let Some((package_id, version)) = self.packages.get(frame.module.address()) {
+            writer.push(SuiTraceEvent::FramePackage(FramePackage {
+                frame_id: frame.frame_id,
+                package_id: *package_id,
+                version: *version,
+            }));
+        }
+    }
+}
@@ -49,6 +49,7 @@ pub mod executable_transaction;
 pub mod execution;
 pub mod execution_config_utils;
 pub mod execution_status;
+pub mod execution_trace;
 pub mod full_checkpoint_content;
 pub mod gas;
 pub mod gas_coin;
@@ -16,6 +16,7 @@ use crate::messages_checkpoint::{
     CheckpointContents, CheckpointSequenceNumber, FullCheckpointContents, VerifiedCheckpoint,
 };
 use crate::transaction::VerifiedTransaction;
+use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::StructTag;
 use move_core_types::language_storage::TypeTag;
 use serde::Deserialize;
@@ -630,6 +631,21 @@ pub trait RpcIndexes: Send + Sync {
     ) -> Result<Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>>;
 
     fn get_coin_info(&self, coin_type: &StructTag) -> Result<Option<CoinInfo>>;
+
+    fn get_balance(&self, owner: &SuiAddress, coin_type: &StructTag)
+        -> Result<Option<BalanceInfo>>;
+
+    fn balance_iter(
+        &self,
+        owner: &SuiAddress,
+        cursor: Option<StructTag>,
+    ) -> Result<Box<dyn Iterator<Item = Result<(StructTag, BalanceInfo)>> + '_>>;
+
+    fn event_iter(
+        &self,
+        selector: EventSelector,
+        cursor: Option<EventPosition>,
+    ) -> Result<Box<dyn Iterator<Item = (EventPosition, TransactionDigest)> + '_>>;
 }
 
 pub struct AccountOwnedObjectInfo {
@@ -675,3 +691,32 @@ pub struct CoinInfo {
     pub coin_metadata_object_id: Option<ObjectID>,
     pub treasury_object_id: Option<ObjectID>,
 }
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct BalanceInfo {
+    pub balance: u64,
+}
+
+/// A criteria that events can be looked up by.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum EventSelector {
+    /// Events emitted by transactions sent by this address.
+    Sender(SuiAddress),
+    /// Events emitted by transactions whose entry point is in this module.
+    Module {
+        package: ObjectID,
+        module: Identifier,
+    },
+    /// Events of exactly this type.
+    Type(StructTag),
+}
+
+/// The position of an event in the history of the chain, which events are ordered by.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct EventPosition {
+    pub checkpoint: CheckpointSequenceNumber,
+    /// Index of the transaction that emitted the event within its checkpoint.
+    pub transaction_index: u32,
+    /// Index of the event within the events emitted by its transaction.
+    pub event_index: u32,
+}
@@ -6,11 +6,12 @@ use crate::{
     client_ptb::ptb::PTB,
     displays::Pretty,
     key_identity::{get_identity_address, KeyIdentity},
+    transaction_bundle::TransactionBundle,
     upgrade_compatibility::check_compatibility,
     verifier_meter::{AccumulatingMeter, Accumulator},
 };
 use std::{
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
     fs,
     path::{Path, PathBuf},
@@ -78,7 +79,8 @@ use sui_types::{
     signature::GenericSignature,
     sui_serde,
     transaction::{
-        SenderSignedData, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+        Argument, Command, SenderSignedData, Transaction, TransactionData, TransactionDataAPI,
+        TransactionKind,
     },
 };
 
@@ -106,6 +108,9 @@ static USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_V
 /// Only to be used within CLI
 pub const GAS_SAFE_OVERHEAD: u64 = 1000;
 
+/// The largest gas budget `sui client sponsor-tx` pays for unless told otherwise (0.5 SUI).
+pub const DEFAULT_MAX_SPONSORED_GAS_BUDGET: u64 = 500_000_000;
+
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
 pub enum SuiClientCommands {
@@ -199,13 +204,60 @@ pub enum SuiClientCommands {
         #[clap(long)]
         signatures: Vec<String>,
     },
-    /// Execute a combined serialized SenderSignedData string.
+    /// Execute a combined serialized SenderSignedData string. For a sponsored transaction, the
+    /// signatures are checked to come from exactly its sender and its gas owner before submission.
     ExecuteCombinedSignedTx {
         /// BCS serialized sender signed data, as base64 encoded string. This is the output of sui client command using --serialize-signed-transaction.
         #[clap(long)]
         signed_tx_bytes: String,
     },
 
+    /// Execute a transaction bundle signed with `sui keytool sign-bundle`. The bundle must have
+    /// been built for the active network, and the owned objects it uses must not have changed
+    /// since it was created.
+    SubmitBundle {
+        /// Path to the signed transaction bundle.
+        #[clap(long)]
+        bundle: PathBuf,
+    },
+    /// Sponsor a transaction with gas owned by the active address. Takes the unsigned transaction
+    /// of a sender (the output of a sui client command run with --serialize-unsigned-transaction),
+    /// replaces its gas payment with coins of the sponsor, and signs it as the sponsor. The output
+    /// is a partially signed transaction to hand to the sender for `sui client sign-sponsored-tx`.
+    SponsorTx {
+        /// BCS serialized transaction data bytes of the sender, as base64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+        /// IDs of the sponsor's gas objects to pay with. If not provided, a gas object with at
+        /// least the gas budget is selected.
+        #[clap(long, num_args(1..))]
+        gas: Vec<ObjectID>,
+        /// The gas budget of the sponsored transaction. Defaults to the budget of the sender's
+        /// transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+        /// The largest gas budget the sponsor accepts to pay for, in MIST.
+        #[clap(long, default_value_t = DEFAULT_MAX_SPONSORED_GAS_BUDGET)]
+        max_gas_budget: u64,
+        /// The largest gas price the sponsor accepts to pay, in MIST. Defaults to the reference
+        /// gas price.
+        #[clap(long)]
+        max_gas_price: Option<u64>,
+    },
+    /// Add the sender's signature to a transaction sponsored with `sui client sponsor-tx`. The
+    /// output is the fully signed transaction, to submit with
+    /// `sui client execute-combined-signed-tx --signed-tx-bytes <SIGNED_TX_BYTES>`.
+    SignSponsoredTx {
+        /// BCS serialized sender signed data carrying the sponsor's signature, as base64 encoded
+        /// string. This is the output of `sui client sponsor-tx`.
+        #[clap(long)]
+        signed_tx_bytes: String,
+        /// The unsigned transaction originally handed to the sponsor. If provided, the sponsored
+        /// transaction is checked to differ from it only in its gas payment.
+        #[clap(long)]
+        original_tx_bytes: Option<String>,
+    },
+
     /// Request gas coin from faucet. By default, it will use the active address and the active network.
     #[clap[name = "faucet"]]
     Faucet {
@@ -558,6 +610,11 @@ pub enum SuiClientCommands {
         /// Optional protocol version to use, if not specified defaults to the one originally used for the transaction.
         #[arg(long, short, allow_hyphen_values = true)]
         protocol_version: Option<i64>,
+
+        /// If specified, writes the trace of each command of the transaction to this directory,
+        /// which can be opened with the Move trace debugger. Requires the `tracing` feature.
+        #[arg(long)]
+        trace: Option<PathBuf>,
     },
 
     /// Replay transactions listed in a file.
@@ -615,6 +672,12 @@ pub struct Opts {
     /// `sui client execute-combined-signed-tx --signed-tx-bytes <SIGNED_TX_BYTES>`.
     #[arg(long, required = false)]
     pub serialize_signed_transaction: bool,
+    /// Instead of executing the transaction, output an unsigned transaction bundle: a JSON document
+    /// with the transaction data, the transaction decoded, snapshots of its input objects and the
+    /// chain identifier. The bundle can be reviewed and signed on an offline machine with
+    /// `sui keytool sign-bundle`, and executed with `sui client submit-bundle`.
+    #[arg(long, required = false)]
+    pub serialize_unsigned_bundle: bool,
 }
 
 /// Global options with gas
@@ -637,6 +700,7 @@ impl Opts {
             dev_inspect: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            serialize_unsigned_bundle: false,
         }
     }
     /// Uses the passed gas_budget for the gas budget variable, sets dry run to true,
@@ -648,6 +712,7 @@ impl Opts {
             dev_inspect: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            serialize_unsigned_bundle: false,
         }
     }
 }
@@ -713,6 +778,7 @@ impl SuiClientCommands {
                 ptb_info: _,
                 executor_version,
                 protocol_version,
+                trace,
             } => {
                 let cmd = ReplayToolCommand::ReplayTransaction {
                     tx_digest,
@@ -720,6 +786,7 @@ impl SuiClientCommands {
                     executor_version,
                     protocol_version,
                     config_objects: None,
+                    trace,
                 };
 
                 let rpc = context.config.get_active_env()?.rpc.clone();
@@ -1621,10 +1688,133 @@ impl SuiClientCommands {
                         .to_vec()
                         .map_err(|_| anyhow!("Invalid Base64 encoding"))?
                 ).map_err(|_| anyhow!("Failed to parse SenderSignedData bytes, check if it matches the output of sui client commands with --serialize-signed-transaction"))?;
+                if data.transaction_data().is_sponsored_tx() {
+                    check_sponsored_tx_signers(&data)?;
+                }
                 let transaction = Envelope::<SenderSignedData, EmptySignInfo>::new(data);
                 let response = context.execute_transaction_may_fail(transaction).await?;
                 SuiClientCommandResult::TransactionBlock(response)
             }
+            SuiClientCommands::SubmitBundle { bundle } => {
+                let bundle = TransactionBundle::read(&bundle)?;
+                let tx_data = bundle.transaction_data()?;
+                let client = context.get_client().await?;
+                let chain_id = client.read_api().get_chain_identifier().await?;
+                ensure!(
+                    bundle.chain_id == chain_id,
+                    "The bundle was built for chain {}, but the active environment is chain {chain_id}",
+                    bundle.chain_id
+                );
+                bundle.check_freshness(&client, &tx_data).await?;
+                let transaction = bundle.signed_transaction()?;
+                let response = context.execute_transaction_may_fail(transaction).await?;
+                SuiClientCommandResult::TransactionBlock(response)
+            }
+            SuiClientCommands::SponsorTx {
+                tx_bytes,
+                gas,
+                gas_budget,
+                max_gas_budget,
+                max_gas_price,
+            } => {
+                let sponsor = context.active_address()?;
+                let tx_data = decode_tx_data(tx_bytes)?;
+                ensure!(
+                    tx_data.sender() != sponsor,
+                    "The active address {sponsor} is the sender of the transaction, switch to the sponsor's address to sponsor it"
+                );
+                check_sponsorable_kind(tx_data.kind())?;
+                let gas_budget = gas_budget.unwrap_or(tx_data.gas_budget());
+                ensure!(
+                    gas_budget <= max_gas_budget,
+                    "The gas budget {gas_budget} is above the maximum gas budget {max_gas_budget} the sponsor accepts, set --max-gas-budget to sponsor it"
+                );
+                let max_gas_price = match max_gas_price {
+                    Some(max_gas_price) => max_gas_price,
+                    None => context.get_reference_gas_price().await?,
+                };
+                ensure!(
+                    tx_data.gas_price() <= max_gas_price,
+                    "The gas price {} is above the maximum gas price {max_gas_price} the sponsor accepts, set --max-gas-price to sponsor it",
+                    tx_data.gas_price()
+                );
+                eprintln!(
+                    "Sponsoring a transaction of {} with a gas budget of {gas_budget} and a gas price of {}:\n{}",
+                    tx_data.sender(),
+                    tx_data.gas_price(),
+                    tx_data.kind()
+                );
+                let gas_payment = if gas.is_empty() {
+                    let input_objects = tx_data
+                        .input_objects()?
+                        .iter()
+                        .map(|o| o.object_id())
+                        .collect();
+                    let (_, gas_object) = context
+                        .gas_for_owner_budget(sponsor, gas_budget, input_objects)
+                        .await?;
+                    vec![gas_object.object_ref()]
+                } else {
+                    let mut gas_payment = vec![];
+                    for id in gas {
+                        gas_payment.push(context.get_object_ref(id).await?);
+                    }
+                    gas_payment
+                };
+                let sponsored_tx_data = TransactionData::new_with_gas_coins_allow_sponsor(
+                    tx_data.kind().clone(),
+                    tx_data.sender(),
+                    gas_payment,
+                    gas_budget,
+                    tx_data.gas_price(),
+                    sponsor,
+                );
+                let signature = context.config.keystore.sign_secure(
+                    &sponsor,
+                    &sponsored_tx_data,
+                    Intent::sui_transaction(),
+                )?;
+                SuiClientCommandResult::SerializedSignedTransaction(
+                    SenderSignedData::new_from_sender_signature(sponsored_tx_data, signature),
+                )
+            }
+            SuiClientCommands::SignSponsoredTx {
+                signed_tx_bytes,
+                original_tx_bytes,
+            } => {
+                let mut data = decode_sender_signed_data(signed_tx_bytes)?;
+                let tx_data = data.transaction_data().clone();
+                ensure!(
+                    tx_data.is_sponsored_tx(),
+                    "The transaction is not sponsored: its gas owner is its sender"
+                );
+                if let Some(original_tx_bytes) = original_tx_bytes {
+                    let original = decode_tx_data(original_tx_bytes)?;
+                    ensure!(
+                        original.sender() == tx_data.sender()
+                            && original.kind() == tx_data.kind()
+                            && original.expiration() == tx_data.expiration(),
+                        "The sponsored transaction differs from the original transaction in more than its gas payment"
+                    );
+                }
+                let sponsor_signed = data
+                    .tx_signatures()
+                    .iter()
+                    .any(|sig| SuiAddress::try_from(sig).ok() == Some(tx_data.gas_owner()));
+                ensure!(
+                    sponsor_signed,
+                    "The transaction does not carry a signature from its gas owner {}",
+                    tx_data.gas_owner()
+                );
+                let signature = context.config.keystore.sign_secure(
+                    &tx_data.sender(),
+                    &tx_data,
+                    Intent::sui_transaction(),
+                )?;
+                data.add_signature(signature);
+                check_sponsored_tx_signers(&data)?;
+                SuiClientCommandResult::SerializedSignedTransaction(data)
+            }
             SuiClientCommands::NewEnv {
                 alias,
                 rpc,
@@ -2144,6 +2334,13 @@ impl Display for SuiClientCommandResult {
                     fastcrypto::encoding::Base64::encode(bcs::to_bytes(tx_data).unwrap())
                 )?;
             }
+            SuiClientCommandResult::SerializedUnsignedBundle(bundle) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    serde_json::to_string_pretty(bundle).map_err(|_| fmt::Error)?
+                )?;
+            }
             SuiClientCommandResult::SerializedSignedTransaction(sender_signed_tx) => {
                 writeln!(
                     writer,
@@ -2398,6 +2595,7 @@ impl SuiClientCommandResult {
             | SuiClientCommandResult::Objects(_)
             | SuiClientCommandResult::RawObject(_)
             | SuiClientCommandResult::SerializedSignedTransaction(_)
+            | SuiClientCommandResult::SerializedUnsignedBundle(_)
             | SuiClientCommandResult::SerializedUnsignedTransaction(_)
             | SuiClientCommandResult::Switch(_)
             | SuiClientCommandResult::SyncClientState
@@ -2548,6 +2746,7 @@ pub enum SuiClientCommandResult {
     Objects(Vec<SuiObjectResponse>),
     RawObject(SuiObjectResponse),
     SerializedSignedTransaction(SenderSignedData),
+    SerializedUnsignedBundle(TransactionBundle),
     SerializedUnsignedTransaction(TransactionData),
     Switch(SwitchResponse),
     SyncClientState,
@@ -2766,6 +2965,94 @@ fn format_balance(
     format!("{whole}.{fractional}{suffix}")
 }
 
+fn decode_tx_data(tx_bytes: String) -> Result<TransactionData, anyhow::Error> {
+    bcs::from_bytes(
+        &Base64::try_from(tx_bytes)
+            .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+            .to_vec()
+            .map_err(|_| anyhow!("Invalid Base64 encoding"))?,
+    )
+    .map_err(|_| anyhow!("Failed to parse tx bytes, check if it matches the output of sui client commands with --serialize-unsigned-transaction"))
+}
+
+fn decode_sender_signed_data(signed_tx_bytes: String) -> Result<SenderSignedData, anyhow::Error> {
+    bcs::from_bytes(
+        &Base64::try_from(signed_tx_bytes)
+            .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+            .to_vec()
+            .map_err(|_| anyhow!("Invalid Base64 encoding"))?,
+    )
+    .map_err(|_| anyhow!("Failed to parse SenderSignedData bytes, check if it matches the output of sui client commands with --serialize-signed-transaction"))
+}
+
+/// Checks that the sponsor of a transaction of kind `kind` only pays for its gas: the transaction
+/// must be a programmable transaction that does not use the gas coin, which belongs to the sponsor,
+/// as an argument of any of its commands.
+fn check_sponsorable_kind(kind: &TransactionKind) -> Result<(), anyhow::Error> {
+    let TransactionKind::ProgrammableTransaction(pt) = kind else {
+        bail!("Only programmable transactions can be sponsored");
+    };
+    for (idx, command) in pt.commands.iter().enumerate() {
+        let uses_gas_coin = match command {
+            Command::MoveCall(call) => call.arguments.contains(&Argument::GasCoin),
+            Command::TransferObjects(objs, addr) => {
+                objs.contains(&Argument::GasCoin) || *addr == Argument::GasCoin
+            }
+            Command::SplitCoins(coin, amounts) => {
+                *coin == Argument::GasCoin || amounts.contains(&Argument::GasCoin)
+            }
+            Command::MergeCoins(target, coins) => {
+                *target == Argument::GasCoin || coins.contains(&Argument::GasCoin)
+            }
+            Command::MakeMoveVec(_, args) => args.contains(&Argument::GasCoin),
+            Command::Publish(_, _) => false,
+            Command::Upgrade(_, _, _, ticket) => *ticket == Argument::GasCoin,
+        };
+        ensure!(
+            !uses_gas_coin,
+            "Command {idx} of the transaction uses the gas coin, which would belong to the sponsor: {command}"
+        );
+    }
+    Ok(())
+}
+
+/// Checks that a sponsored transaction carries exactly one signature from each of its sender and
+/// its gas owner, and no other.
+fn check_sponsored_tx_signers(data: &SenderSignedData) -> Result<(), anyhow::Error> {
+    let expected = data
+        .transaction_data()
+        .signers()
+        .iter()
+        .copied()
+        .collect::<BTreeSet<_>>();
+    let mut signers = BTreeSet::new();
+    for sig in data.tx_signatures() {
+        let signer = SuiAddress::try_from(sig)
+            .map_err(|e| anyhow!("Cannot derive the signer of a signature: {e}"))?;
+        ensure!(
+            signers.insert(signer),
+            "The transaction carries more than one signature from {signer}"
+        );
+    }
+    let missing = expected.difference(&signers).collect::<Vec<_>>();
+    ensure!(
+        missing.is_empty(),
+        "The transaction is missing signatures from: {}",
+        missing
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let unexpected = signers.difference(&expected).collect::<Vec<_>>();
+    ensure!(
+        unexpected.is_empty(),
+        "The transaction carries signatures from addresses that are neither its sender nor its gas owner: {}",
+        unexpected.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", ")
+    );
+    Ok(())
+}
+
 /// Helper function to reduce code duplication for executing dry run
 pub async fn execute_dry_run(
     context: &mut WalletContext,
@@ -2876,16 +3163,26 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         gas_budget,
         serialize_unsigned_transaction,
         serialize_signed_transaction,
+        serialize_unsigned_bundle,
     ) = (
         opts.dry_run,
         opts.dev_inspect,
         opts.gas_budget,
         opts.serialize_unsigned_transaction,
         opts.serialize_signed_transaction,
+        opts.serialize_unsigned_bundle,
     );
     ensure!(
-        !serialize_unsigned_transaction || !serialize_signed_transaction,
-        "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
+        [
+            serialize_unsigned_transaction,
+            serialize_signed_transaction,
+            serialize_unsigned_bundle
+        ]
+        .iter()
+        .filter(|set| **set)
+        .count()
+            <= 1,
+        "Cannot specify more than one of the flags: --serialize-unsigned-transaction, --serialize-signed-transaction and --serialize-unsigned-bundle."
     );
     let gas_price = if let Some(gas_price) = gas_price {
         gas_price
@@ -2963,6 +3260,10 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         Ok(SuiClientCommandResult::SerializedUnsignedTransaction(
             tx_data,
         ))
+    } else if serialize_unsigned_bundle {
+        Ok(SuiClientCommandResult::SerializedUnsignedBundle(
+            TransactionBundle::new(&client, tx_data).await?,
+        ))
     } else {
         let signature = context.config.keystore.sign_secure(
             &tx_data.sender(),
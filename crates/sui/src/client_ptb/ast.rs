@@ -38,6 +38,7 @@ pub const DRY_RUN: &str = "dry-run";
 pub const DEV_INSPECT: &str = "dev-inspect";
 pub const SERIALIZE_UNSIGNED: &str = "serialize-unsigned-transaction";
 pub const SERIALIZE_SIGNED: &str = "serialize-signed-transaction";
+pub const SERIALIZE_UNSIGNED_BUNDLE: &str = "serialize-unsigned-bundle";
 
 // Types
 pub const U8: &str = "u8";
@@ -78,6 +79,7 @@ pub const COMMANDS: &[&str] = &[
     DEV_INSPECT,
     SERIALIZE_UNSIGNED,
     SERIALIZE_SIGNED,
+    SERIALIZE_UNSIGNED_BUNDLE,
 ];
 
 pub fn is_keyword(s: &str) -> bool {
@@ -110,6 +112,7 @@ pub struct ProgramMetadata {
     pub summary_set: bool,
     pub serialize_unsigned_set: bool,
     pub serialize_signed_set: bool,
+    pub serialize_unsigned_bundle_set: bool,
     pub gas_object_id: Option<Spanned<ObjectID>>,
     pub json_set: bool,
     pub dry_run_set: bool,
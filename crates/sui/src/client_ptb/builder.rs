@@ -19,19 +19,28 @@ use move_binary_format::{
 use move_core_types::parsing::{
     address::{NumericalAddress, ParsedAddress},
     parser::NumberFormat,
+    types::{ParsedFqName, ParsedModuleId, ParsedStructType, ParsedType},
 };
 use move_core_types::{
     account_address::AccountAddress, annotated_value::MoveTypeLayout, ident_str,
 };
 use move_package::BuildConfig;
-use std::{collections::BTreeMap, path::Path};
+use std::{collections::BTreeMap, path::Path, str::FromStr};
+use sui_graphql_rpc::{
+    config::MoveRegistryConfig,
+    move_registry::{AppInfo, AppRecord, MoveRegistryError, Name, VersionedName},
+};
 use sui_json::{is_receiving_argument, primitive_type};
-use sui_json_rpc_types::{SuiObjectData, SuiObjectDataOptions, SuiRawData};
+use sui_json_rpc_types::{SuiObjectData, SuiObjectDataOptions, SuiPastObjectResponse, SuiRawData};
 use sui_move::manage_package::resolve_lock_file_path;
+use sui_protocol_config::Chain;
 use sui_sdk::apis::ReadApi;
 use sui_types::{
-    base_types::{is_primitive_type_tag, ObjectID, TxContext, TxContextKind},
-    move_package::MovePackage,
+    base_types::{is_primitive_type_tag, ObjectID, SequenceNumber, TxContext, TxContextKind},
+    digests::ChainIdentifier,
+    dynamic_field::Field,
+    error::SuiObjectResponseError,
+    move_package::{MovePackage, UpgradeCap},
     object::Owner,
     programmable_transaction_builder::ProgrammableTransactionBuilder,
     resolve_address,
@@ -222,6 +231,13 @@ pub struct PTBBuilder<'a> {
     /// The arguments that we have resolved. This is a map from identifiers to the actual
     /// transaction arguments.
     resolved_arguments: BTreeMap<String, Tx::Argument>,
+    /// Move Registry names that have been resolved so far, mapped to the ID of the package they
+    /// resolved to. Names without a version are pinned to the latest version of their package the
+    /// first time they are resolved, so every use of the same name in the PTB agrees.
+    mvr_names: BTreeMap<String, ObjectID>,
+    /// The Move Registry of the chain the reader is connected to, looked up the first time a name
+    /// needs to be resolved.
+    mvr_config: Option<MoveRegistryConfig>,
     /// Read API for reading objects from chain. Needed for object resolution.
     reader: &'a ReadApi,
     /// The last command that we have added. This is used to support assignment commands.
@@ -281,6 +297,8 @@ impl<'a> PTBBuilder<'a> {
             identifiers: BTreeMap::new(),
             arguments_to_resolve: BTreeMap::new(),
             resolved_arguments: BTreeMap::new(),
+            mvr_names: BTreeMap::new(),
+            mvr_config: None,
             ptb: ProgrammableTransactionBuilder::new(),
             reader,
             last_command: None,
@@ -351,6 +369,30 @@ impl<'a> PTBBuilder<'a> {
         self.finish(program.warn_shadows_set)
     }
 
+    /// Resolve Move Registry names, as found by [mvr_names_in_program], without building a PTB,
+    /// returning the ID and version of the package each name refers to.
+    pub async fn resolve_mvr_names(
+        mut self,
+        names: BTreeMap<String, Span>,
+    ) -> Result<Vec<(String, ObjectID, SequenceNumber)>, Vec<PTBError>> {
+        let mut resolved = vec![];
+        for (name, loc) in names {
+            let package = match self.resolve_mvr_name(&name, loc).await {
+                Ok(package_id) => self.resolve_to_package(package_id, loc).await,
+                Err(e) => Err(e),
+            };
+            match package {
+                Ok(package) => resolved.push((name, package.id(), package.version())),
+                Err(e) => self.errors.push(e),
+            }
+        }
+
+        if !self.errors.is_empty() {
+            return Err(self.errors);
+        }
+        Ok(resolved)
+    }
+
     /// Add a single PTB command to the PTB that we are building up.
     /// Errors are added to the `errors` field of the PTBBuilder.
     async fn handle_command(&mut self, sp!(span, command): Spanned<ParsedPTBCommand>) {
@@ -439,6 +481,278 @@ impl<'a> PTBBuilder<'a> {
         .map_err(|e| err!(loc, "{e}"))
     }
 
+    /// Resolve a Move Registry name (`@org/app` or `@org/app/<version>`) to the ID of the package
+    /// it refers to, by reading the name's record from the registry on-chain. Unversioned names
+    /// resolve to the latest version of the package.
+    async fn resolve_mvr_name(&mut self, name: &str, loc: Span) -> PTBResult<ObjectID> {
+        if let Some(package_id) = self.mvr_names.get(name) {
+            return Ok(*package_id);
+        }
+
+        let versioned = VersionedName::from_str(name).map_err(|e| err!(loc, "{e}"))?;
+        let config = self.mvr_config(loc).await?;
+        let record_id = versioned.name.to_dynamic_field_id(&config).map_err(|e| {
+            err!(
+                loc,
+                "Failed to derive the Move Registry record for '{name}': {e}"
+            )
+        })?;
+
+        let response = self
+            .reader
+            .get_object_with_options(record_id, SuiObjectDataOptions::bcs_lossless())
+            .await
+            .map_err(|e| {
+                err!(
+                    loc,
+                    "Failed to read the Move Registry record for '{name}': {e}"
+                )
+            })?;
+        let object = match response.into_object() {
+            Ok(object) => object,
+            Err(
+                SuiObjectResponseError::NotExists { .. } | SuiObjectResponseError::Deleted { .. },
+            ) => error!(loc, "{}", MoveRegistryError::NameNotFound(name.to_string())),
+            Err(e) => error!(
+                loc,
+                "Failed to read the Move Registry record for '{name}': {e}"
+            ),
+        };
+
+        let Some(SuiRawData::MoveObject(record)) = &object.bcs else {
+            error!(
+                loc,
+                "{}",
+                MoveRegistryError::FailedToDeserializeRecord(record_id)
+            );
+        };
+        let record: Field<Name, AppRecord> = record.deserialize().map_err(|_| {
+            err!(
+                loc,
+                "{}",
+                MoveRegistryError::FailedToDeserializeRecord(record_id)
+            )
+        })?;
+
+        let Some(AppInfo {
+            package_address: Some(package_address),
+            upgrade_cap_id,
+            ..
+        }) = record.value.app_info
+        else {
+            error!(
+                loc,
+                "Move Registry name '{name}' is not associated with a package on this network"
+            );
+        };
+
+        let package_id = match upgrade_cap_id {
+            Some(cap_id) => {
+                self.resolve_mvr_version(cap_id.bytes, versioned.version, name, loc)
+                    .await?
+            }
+
+            // Without an upgrade cap there is no history to search, so the registered package is
+            // the only one the name can refer to.
+            None => {
+                let package_id = ObjectID::from(package_address);
+                let package = self.resolve_to_package(package_id, loc).await?;
+                if let Some(version) = versioned.version {
+                    if package.version().value() != version {
+                        error!(
+                            loc,
+                            "Version {version} of '{name}' does not exist, the only version \
+                             available is {}",
+                            package.version().value(),
+                        );
+                    }
+                }
+                package_id
+            }
+        };
+
+        self.mvr_names.insert(name.to_string(), package_id);
+        Ok(package_id)
+    }
+
+    /// The Move Registry of the chain the reader is connected to. The registry is only deployed on
+    /// mainnet, so names cannot be resolved in environments connected to other chains.
+    async fn mvr_config(&mut self, loc: Span) -> PTBResult<MoveRegistryConfig> {
+        if let Some(config) = &self.mvr_config {
+            return Ok(config.clone());
+        }
+
+        let chain_id = self.reader.get_chain_identifier().await.map_err(|e| {
+            err!(
+                loc,
+                "Failed to read the chain identifier of the network: {e}"
+            )
+        })?;
+        let chain =
+            ChainIdentifier::from_chain_short_id(&chain_id).map_or(Chain::Unknown, |id| id.chain());
+        let Some(config) = MoveRegistryConfig::for_chain(chain) else {
+            error!(
+                loc => help: {
+                    "Switch to an environment connected to mainnet, or use the package's address \
+                     instead of its name."
+                },
+                "Move Registry names can only be resolved on mainnet, but the active environment \
+                 is connected to chain '{chain_id}'"
+            );
+        };
+
+        self.mvr_config = Some(config.clone());
+        Ok(config)
+    }
+
+    /// Find the package that was at `version` in the history of the package family governed by
+    /// the upgrade cap `cap_id`, or the latest package in the family if `version` is `None`.
+    async fn resolve_mvr_version(
+        &self,
+        cap_id: ObjectID,
+        version: Option<u64>,
+        name: &str,
+        loc: Span,
+    ) -> PTBResult<ObjectID> {
+        let object = self
+            .reader
+            .get_object_with_options(cap_id, SuiObjectDataOptions::bcs_lossless())
+            .await
+            .map_err(|e| err!(loc, "{e}"))?
+            .into_object()
+            .map_err(|e| err!(loc, "{e}"))?;
+
+        let mut cap_version = object.version;
+        let mut cap = upgrade_cap(&object, loc)?;
+
+        let Some(version) = version else {
+            return Ok(cap.package.bytes);
+        };
+
+        if version > cap.version {
+            error!(
+                loc,
+                "Version {version} of '{name}' does not exist, the latest version is {}",
+                cap.version,
+            );
+        }
+
+        // Every upgrade modifies the upgrade cap, so walking back through its history visits each
+        // version of the package in turn.
+        while cap.version > version {
+            let Some(before) = cap_version.one_before() else {
+                break;
+            };
+
+            // Nodes that prune object history cannot serve older versions of the cap, which leaves
+            // no way to reach older versions of the package.
+            let history_unavailable = |reason: String| {
+                err!(
+                    loc => help: {
+                        "Use a full node that keeps object history, or refer to the package by its \
+                         address instead."
+                    },
+                    "Unable to find version {version} of '{name}': the history of its upgrade \
+                     cap '{cap_id}' before version {} is not available from this node ({reason})",
+                    before.value(),
+                )
+            };
+            let object = match self
+                .reader
+                .try_get_object_before_version(cap_id, before)
+                .await
+                .map_err(|e| history_unavailable(e.to_string()))?
+            {
+                SuiPastObjectResponse::VersionFound(object) => object,
+                SuiPastObjectResponse::ObjectNotExists(_)
+                | SuiPastObjectResponse::ObjectDeleted(_)
+                | SuiPastObjectResponse::VersionNotFound(_, _)
+                | SuiPastObjectResponse::VersionTooHigh { .. } => {
+                    return Err(history_unavailable(
+                        "the object version was not found".into(),
+                    ));
+                }
+            };
+
+            cap_version = object.version;
+            cap = upgrade_cap(&object, loc)?;
+        }
+
+        if cap.version != version {
+            error!(loc, "Unable to find version {version} of '{name}'");
+        }
+
+        Ok(cap.package.bytes)
+    }
+
+    /// Resolve a parsed type into a type tag. Types that are addressed by a Move Registry name are
+    /// rewritten to use the ID of the package that originally defined the type.
+    async fn resolve_type(&mut self, sp!(loc, ty): Spanned<ParsedType>) -> PTBResult<TypeTag> {
+        self.resolve_mvr_types(ty, loc)
+            .await?
+            .into_type_tag(&resolve_address)
+            .map_err(|e| err!(loc, "{e}"))
+    }
+
+    #[async_recursion]
+    async fn resolve_mvr_types(&mut self, ty: ParsedType, loc: Span) -> PTBResult<ParsedType> {
+        Ok(match ty {
+            ParsedType::Vector(ty) => {
+                ParsedType::Vector(Box::new(self.resolve_mvr_types(*ty, loc).await?))
+            }
+
+            ParsedType::Struct(ParsedStructType {
+                fq_name:
+                    ParsedFqName {
+                        module:
+                            ParsedModuleId {
+                                address,
+                                name: module,
+                            },
+                        name,
+                    },
+                type_args,
+            }) => {
+                let mut resolved_args = vec![];
+                for ty in type_args {
+                    resolved_args.push(self.resolve_mvr_types(ty, loc).await?);
+                }
+
+                let address = match address {
+                    ParsedAddress::Named(mvr) if mvr.starts_with('@') => {
+                        let package_id = self.resolve_mvr_name(&mvr, loc).await?;
+                        let package = self.resolve_to_package(package_id, loc).await?;
+                        let Some(origin) = package
+                            .type_origin_map()
+                            .get(&(module.clone(), name.clone()))
+                            .copied()
+                        else {
+                            error!(loc, "Type '{module}::{name}' not found in '{mvr}'");
+                        };
+                        ParsedAddress::Numerical(NumericalAddress::new(
+                            origin.into_bytes(),
+                            NumberFormat::Hex,
+                        ))
+                    }
+                    address => address,
+                };
+
+                ParsedType::Struct(ParsedStructType {
+                    fq_name: ParsedFqName {
+                        module: ParsedModuleId {
+                            address,
+                            name: module,
+                        },
+                        name,
+                    },
+                    type_args: resolved_args,
+                })
+            }
+
+            ty => ty,
+        })
+    }
+
     /// Resolves the argument to the move call based on the type information of the function being
     /// called.
     async fn resolve_move_call_arg(
@@ -832,10 +1146,8 @@ impl<'a> PTBBuilder<'a> {
                 self.arguments_to_resolve
                     .insert(i, ArgWithHistory::Unresolved(arg_w_loc));
             }
-            ParsedPTBCommand::MakeMoveVec(sp!(ty_loc, ty_arg), sp!(_, args)) => {
-                let ty_arg = ty_arg
-                    .into_type_tag(&resolve_address)
-                    .map_err(|e| err!(ty_loc, "{e}"))?;
+            ParsedPTBCommand::MakeMoveVec(ty_arg, sp!(_, args)) => {
+                let ty_arg = self.resolve_type(ty_arg).await?;
                 let mut vec_args: Vec<Tx::Argument> = vec![];
                 if is_primitive_type_tag(&ty_arg) {
                     for arg in args.into_iter() {
@@ -889,25 +1201,27 @@ impl<'a> PTBBuilder<'a> {
 
                 if let Some(sp!(ty_loc, in_ty_args)) = in_ty_args {
                     for t in in_ty_args.into_iter() {
-                        ty_args.push(
-                            t.into_type_tag(&resolve_address)
-                                .map_err(|e| err!(ty_loc, "{e}"))?,
-                        )
+                        ty_args.push(self.resolve_type(ty_loc.wrap(t)).await?)
                     }
                 }
 
-                let resolved_address = address.value.clone().into_account_address(&|s| {
-                    self.addresses.get(s).cloned().or_else(|| resolve_address(s))
-                }).map_err(|e| {
-                    let e = err!(address.span, "{e}");
-                    if let ParsedAddress::Named(name) = address.value {
-                        e.with_help(
-                            format!("This is most likely because the named address '{name}' is not in scope. \
-                                     You can either bind a variable to the address that you want to use or use the address in the command."))
-                    } else {
-                        e
+                let resolved_address = match &address.value {
+                    ParsedAddress::Named(name) if name.starts_with('@') => {
+                        self.resolve_mvr_name(name, address.span).await?.into()
                     }
-                })?;
+                    _ => address.value.clone().into_account_address(&|s| {
+                        self.addresses.get(s).cloned().or_else(|| resolve_address(s))
+                    }).map_err(|e| {
+                        let e = err!(address.span, "{e}");
+                        if let ParsedAddress::Named(name) = &address.value {
+                            e.with_help(
+                                format!("This is most likely because the named address '{name}' is not in scope. \
+                                         You can either bind a variable to the address that you want to use or use the address in the command."))
+                        } else {
+                            e
+                        }
+                    })?,
+                };
 
                 let package_id = ObjectID::from_address(resolved_address);
                 let package = self.resolve_to_package(package_id, address.span).await?;
@@ -1078,6 +1392,79 @@ impl<'a> PTBBuilder<'a> {
 // ===========================================================================
 // Helper methods
 // ===========================================================================
+
+/// The Move Registry names used by the commands of `program`, along with the location they are
+/// first used at.
+pub fn mvr_names_in_program(program: &Program) -> BTreeMap<String, Span> {
+    let mut names = BTreeMap::new();
+    for sp!(_, command) in &program.commands {
+        match command {
+            ParsedPTBCommand::MoveCall(sp!(_, access), ty_args, _) => {
+                if let ParsedAddress::Named(name) = &access.address.value {
+                    if name.starts_with('@') {
+                        names.entry(name.clone()).or_insert(access.address.span);
+                    }
+                }
+                if let Some(sp!(loc, ty_args)) = ty_args {
+                    for ty in ty_args {
+                        mvr_names_in_type(ty, *loc, &mut names);
+                    }
+                }
+            }
+            ParsedPTBCommand::MakeMoveVec(sp!(loc, ty), _) => {
+                mvr_names_in_type(ty, *loc, &mut names);
+            }
+            _ => (),
+        }
+    }
+    names
+}
+
+/// Record the Move Registry names that `ty` refers to in `names`, along with the location they are
+/// first used at.
+fn mvr_names_in_type(ty: &ParsedType, loc: Span, names: &mut BTreeMap<String, Span>) {
+    match ty {
+        ParsedType::Vector(ty) => mvr_names_in_type(ty, loc, names),
+        ParsedType::Struct(ParsedStructType { fq_name, type_args }) => {
+            if let ParsedAddress::Named(name) = &fq_name.module.address {
+                if name.starts_with('@') {
+                    names.entry(name.clone()).or_insert(loc);
+                }
+            }
+            for ty in type_args {
+                mvr_names_in_type(ty, loc, names);
+            }
+        }
+        ParsedType::U8
+        | ParsedType::U16
+        | ParsedType::U32
+        | ParsedType::U64
+        | ParsedType::U128
+        | ParsedType::U256
+        | ParsedType::Bool
+        | ParsedType::Address
+        | ParsedType::Signer => (),
+    }
+}
+
+/// Deserialize an `UpgradeCap` from an object read with its BCS contents.
+fn upgrade_cap(object: &SuiObjectData, loc: Span) -> PTBResult<UpgradeCap> {
+    let Some(SuiRawData::MoveObject(cap)) = &object.bcs else {
+        error!(
+            loc,
+            "BCS field in object '{}' is missing or not a Move object.", object.object_id
+        );
+    };
+
+    cap.deserialize().map_err(|e| {
+        err!(
+            loc,
+            "Object '{}' is not an upgrade cap: {e}",
+            object.object_id
+        )
+    })
+}
+
 pub fn to_ordinal_contraction(num: usize) -> String {
     let suffix = match num % 100 {
         // exceptions
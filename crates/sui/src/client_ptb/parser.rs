@@ -1,13 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::iter::Peekable;
+use std::{iter::Peekable, str::FromStr};
 
 use move_core_types::parsing::{
     address::{NumericalAddress, ParsedAddress},
     parser::{parse_u128, parse_u16, parse_u256, parse_u32, parse_u64, parse_u8},
     types::{ParsedFqName, ParsedModuleId, ParsedStructType, ParsedType},
 };
+use sui_graphql_rpc::move_registry::VersionedName;
 use sui_types::{base_types::ObjectID, Identifier};
 
 use crate::{
@@ -39,6 +40,7 @@ struct ProgramParsingState {
     warn_shadows_set: bool,
     serialize_unsigned_set: bool,
     serialize_signed_set: bool,
+    serialize_unsigned_bundle_set: bool,
     json_set: bool,
     dry_run_set: bool,
     dev_inspect_set: bool,
@@ -62,6 +64,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 warn_shadows_set: false,
                 serialize_unsigned_set: false,
                 serialize_signed_set: false,
+                serialize_unsigned_bundle_set: false,
                 json_set: false,
                 dry_run_set: false,
                 dev_inspect_set: false,
@@ -109,6 +112,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
             match lexeme {
                 L(T::Command, A::SERIALIZE_UNSIGNED) => flag!(serialize_unsigned_set),
                 L(T::Command, A::SERIALIZE_SIGNED) => flag!(serialize_signed_set),
+                L(T::Command, A::SERIALIZE_UNSIGNED_BUNDLE) => flag!(serialize_unsigned_bundle_set),
                 L(T::Command, A::SUMMARY) => flag!(summary_set),
                 L(T::Command, A::JSON) => flag!(json_set),
                 L(T::Command, A::DRY_RUN) => flag!(dry_run_set),
@@ -207,6 +211,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     summary_set: self.state.summary_set,
                     serialize_unsigned_set: self.state.serialize_unsigned_set,
                     serialize_signed_set: self.state.serialize_signed_set,
+                    serialize_unsigned_bundle_set: self.state.serialize_unsigned_bundle_set,
                     gas_object_id: self.state.gas_object_id,
                     json_set: self.state.json_set,
                     dry_run_set: self.state.dry_run_set,
@@ -486,7 +491,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 sp.wrap(ParsedType::Vector(Box::new(ty)))
             }
 
-            L(T::Ident | T::Number | T::HexNumber, _) => 'fq: {
+            L(T::Ident | T::Number | T::HexNumber | T::At, _) => 'fq: {
                 let sp!(_, module_access) = self.parse_module_access()?;
                 let sp!(_, address) = module_access.address;
                 let sp!(_, module_name) = module_access.module_name;
@@ -650,6 +655,8 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
 
         let sp!(sp, lexeme) = self.peek();
         let addr = match lexeme {
+            L(T::At, _) => return Ok(self.parse_mvr_name()?.map(ParsedAddress::Named)),
+
             L(T::Ident, name) => {
                 self.bump();
                 ParsedAddress::Named(name.to_owned())
@@ -672,8 +679,8 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
 
             unexpected => error!(
                 sp => help: {
-                    "Value addresses can either be a variable in-scope, or a numerical address, \
-                     e.g., 0xc0ffee"
+                    "Value addresses can either be a variable in-scope, a numerical address, \
+                     e.g., 0xc0ffee, or a Move Registry name, e.g., @org/app"
                 },
                 "Unexpected {unexpected}",
             ),
@@ -682,6 +689,37 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
         Ok(sp.wrap(addr))
     }
 
+    /// Parse a Move Registry name, of the form `@<org>/<app>` or `@<org>/<app>/<version>`. Names
+    /// are kept in their textual form here, and resolved to on-chain packages when the PTB is
+    /// built.
+    fn parse_mvr_name(&mut self) -> PTBResult<Spanned<String>> {
+        use Lexeme as L;
+        use Token as T;
+
+        let sp!(start_sp, _) = self.expect(T::At)?;
+        let sp!(_, L(_, org)) = self.expect(T::Ident)?;
+        self.expect(T::Slash)?;
+        let sp!(mut end_sp, L(_, app)) = self.expect(T::Ident)?;
+        let mut name = format!("@{org}/{app}");
+
+        if let sp!(_, L(T::Slash, _)) = self.peek() {
+            self.bump();
+            let sp!(version_sp, L(_, version)) = self.expect(T::Number)?;
+            name = format!("{name}/{version}");
+            end_sp = version_sp;
+        }
+
+        let sp = start_sp.widen(end_sp);
+        if let Err(e) = VersionedName::from_str(&name) {
+            error!(sp => help: {
+                "Move Registry names are of the form @org/app, or @org/app/<version> to pin a \
+                 specific version of the package"
+            }, "{e}");
+        }
+
+        Ok(sp.wrap(name))
+    }
+
     /// Parse a numeric address literal (must be prefixed by an `@` symbol).
     fn parse_address_literal(&mut self) -> PTBResult<Spanned<NumericalAddress>> {
         let sp!(sp, _) = self.expect(Token::At).map_err(|e| {
@@ -772,6 +810,48 @@ mod tests {
         insta::assert_debug_snapshot!(parsed);
     }
 
+    #[test]
+    fn test_parse_mvr_names() {
+        let inputs = vec![
+            (
+                "--move-call @org/app::module::function",
+                "move-call @org/app::module::function",
+            ),
+            (
+                "--move-call @org/app/3::module::function <@org/app::coin::COIN>",
+                "move-call @org/app/3::module::function<@org/app::coin::COIN>",
+            ),
+            (
+                "--make-move-vec <@org/app/1::coin::COIN> []",
+                "make-move-vec <@org/app/1::coin::COIN> []",
+            ),
+        ];
+
+        for (input, expected) in inputs {
+            let mut x = shlex::split(input).unwrap();
+            x.push("--gas-budget 1".to_owned());
+            let parser = ProgramParser::new(x.iter().map(|x| x.as_str())).unwrap();
+            let (program, _) = parser.parse().unwrap();
+            assert_eq!(program.commands[0].value.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_mvr_names() {
+        let inputs = vec![
+            "--move-call @org::module::function",
+            "--move-call @org/app/v1::module::function",
+            "--move-call @org/app-::module::function",
+        ];
+
+        for input in inputs {
+            let mut x = shlex::split(input).unwrap();
+            x.push("--gas-budget 1".to_owned());
+            let parser = ProgramParser::new(x.iter().map(|x| x.as_str())).unwrap();
+            assert!(parser.parse().is_err(), "{input} should not parse");
+        }
+    }
+
     #[test]
     fn test_parse_args() {
         let inputs = vec![
@@ -5,7 +5,7 @@ use crate::{
     client_commands::{dry_run_or_execute_or_serialize, Opts, OptsWithGas, SuiClientCommandResult},
     client_ptb::{
         ast::{ParsedProgram, Program},
-        builder::PTBBuilder,
+        builder::{mvr_names_in_program, PTBBuilder},
         error::{build_error_reports, PTBError},
         token::{Lexeme, Token},
     },
@@ -18,10 +18,12 @@ use anyhow::{anyhow, ensure, Error};
 use clap::{arg, Args, ValueHint};
 use move_core_types::account_address::AccountAddress;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::{wallet_context::WalletContext, SuiClient};
 use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
     digests::TransactionDigest,
     gas::GasCostSummary,
     transaction::{ProgrammableTransaction, TransactionKind},
@@ -37,6 +39,9 @@ pub struct PTB {
 pub struct PTBPreview<'a> {
     pub program: &'a Program,
     pub program_metadata: &'a ProgramMetadata,
+    /// The Move Registry names used by the program, with the ID and version of the package each
+    /// of them resolved to.
+    pub mvr_names: &'a [(String, ObjectID, SequenceNumber)],
 }
 
 #[derive(Serialize)]
@@ -84,16 +89,48 @@ impl PTB {
         };
 
         ensure!(
-            !program_metadata.serialize_unsigned_set || !program_metadata.serialize_signed_set,
-            "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
+            [
+                program_metadata.serialize_unsigned_set,
+                program_metadata.serialize_signed_set,
+                program_metadata.serialize_unsigned_bundle_set,
+            ]
+            .iter()
+            .filter(|set| **set)
+            .count()
+                <= 1,
+            "Cannot specify more than one of the flags: --serialize-unsigned-transaction, \
+            --serialize-signed-transaction and --serialize-unsigned-bundle."
         );
 
         if program_metadata.preview_set {
+            // Resolving names needs the network, so only connect when the program uses any.
+            let names = mvr_names_in_program(&program);
+            let mvr_names = if !names.is_empty() {
+                let client = context.get_client().await?;
+                match PTBBuilder::new(BTreeMap::new(), client.read_api())
+                    .resolve_mvr_names(names)
+                    .await
+                {
+                    Ok(names) => names,
+                    Err(errors) => {
+                        let suffix = if errors.len() > 1 { "s" } else { "" };
+                        eprintln!("Encountered error{suffix} when resolving names for preview:");
+                        let rendered = build_error_reports(&source_string, errors);
+                        for e in rendered.iter() {
+                            eprintln!("{:?}", e);
+                        }
+                        anyhow::bail!("Could not preview PTB due to previous error{suffix}");
+                    }
+                }
+            } else {
+                vec![]
+            };
             println!(
                 "{}",
                 PTBPreview {
                     program: &program,
-                    program_metadata: &program_metadata
+                    program_metadata: &program_metadata,
+                    mvr_names: &mvr_names,
                 }
             );
             return Ok(());
@@ -154,6 +191,7 @@ impl PTB {
                 gas_budget: program_metadata.gas_budget.map(|x| x.value),
                 serialize_unsigned_transaction: program_metadata.serialize_unsigned_set,
                 serialize_signed_transaction: program_metadata.serialize_signed_set,
+                serialize_unsigned_bundle: program_metadata.serialize_unsigned_bundle_set,
             },
         };
 
@@ -168,7 +206,8 @@ impl PTB {
                 return Ok(());
             }
             SuiClientCommandResult::SerializedUnsignedTransaction(_)
-            | SuiClientCommandResult::SerializedSignedTransaction(_) => {
+            | SuiClientCommandResult::SerializedSignedTransaction(_)
+            | SuiClientCommandResult::SerializedUnsignedBundle(_) => {
                 println!("{}", transaction_response);
                 return Ok(());
             }
@@ -349,7 +388,9 @@ pub fn ptb_description() -> clap::Command {
             \n\nExamples:\
             \n --move-call std::option::is_none <u64> none\
             \n --assign a none\
-            \n --move-call std::option::is_none <u64> a"
+            \n --move-call std::option::is_none <u64> a\
+            \n --move-call @org/app::module::function # resolved through the Move Registry\
+            \n --move-call @org/app/2::module::function # pinned to version 2 of the package"
         )
         .value_names(["PACKAGE::MODULE::FUNCTION", "TYPE_ARGS", "FUNCTION_ARGS"]))
         .arg(arg!(
@@ -407,6 +448,11 @@ pub fn ptb_description() -> clap::Command {
             "Instead of executing the transaction, serialize the bcs bytes of the signed \
             transaction data using base64 encoding."
         ))
+        .arg(arg!(
+            --"serialize-unsigned-bundle"
+            "Instead of executing the transaction, output an unsigned transaction bundle to \
+            review and sign offline with `sui keytool sign-bundle`."
+        ))
         .arg(arg!(
             --"summary"
             "Show only a short summary (digest, execution status, gas cost). \
@@ -24,6 +24,12 @@ impl<'a> Display for PTBPreview<'a> {
                 builder.push_record([command, vals]);
             }
         }
+        for (name, package_id, version) in self.mvr_names {
+            builder.push_record([
+                name.as_str(),
+                format!("{package_id} (version {})", version.value()).as_str(),
+            ]);
+        }
         if let Some(gas_budget) = self.program_metadata.gas_budget {
             builder.push_record([GAS_BUDGET, gas_budget.value.to_string().as_str()]);
         }
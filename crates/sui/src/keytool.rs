@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use crate::key_identity::{get_identity_address_from_keystore, KeyIdentity};
+use crate::multisig_proposal::{MultiSigProposal, ProposalStatus};
+use crate::transaction_bundle::TransactionBundle;
 use crate::zklogin_commands_util::{perform_zk_login_test_tx, read_cli_line};
 use anyhow::anyhow;
 use bip32::DerivationPath;
@@ -38,7 +40,7 @@ use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
-use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, EncryptedKeystore, Keystore};
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{
@@ -46,6 +48,7 @@ use sui_types::crypto::{
     ZkLoginPublicIdentifier,
 };
 use sui_types::crypto::{DefaultHash, PublicKey};
+use sui_types::digests::TransactionDigest;
 use sui_types::error::SuiResult;
 use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
 use sui_types::multisig_legacy::{MultiSigLegacy, MultiSigPublicKeyLegacy};
@@ -73,6 +76,9 @@ pub enum KeyToolCommand {
         /// The alias must start with a letter and can contain only letters, digits, dots, hyphens (-), or underscores (_).
         new_alias: Option<String>,
     },
+    /// Re-encrypt an encrypted keystore under a new passphrase. The current passphrase is read
+    /// from the SUI_KEYSTORE_PASSPHRASE environment variable if set, or prompted for otherwise.
+    ChangePassphrase,
     /// Convert private key in Hex or Base64 to new format (Bech32
     /// encoded 33 byte flag || private key starting with "suiprivkey").
     /// Hex private key format import and export are both deprecated in
@@ -99,6 +105,11 @@ pub enum KeyToolCommand {
         #[clap(long, default_value = "0")]
         cur_epoch: u64,
     },
+    /// Encrypt the private keys of the Sui CLI Keystore at rest, under a passphrase that is
+    /// prompted for. The keystore file is rewritten in place, and the client config is updated
+    /// to use the encrypted keystore. Keys are then decrypted once per command, the first time
+    /// one of them is needed.
+    EncryptKeystore,
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -186,6 +197,47 @@ pub enum KeyToolCommand {
         #[clap(long)]
         threshold: ThresholdUnit,
     },
+    /// Create a MultiSig transaction proposal file for the Base64 encoded transaction bytes, which
+    /// must be sent from the MultiSig address defined by the threshold, the public keys
+    /// `flag || pk` in Base64 and their weights. Members sign into the proposal with
+    /// `multi-sig-sign-proposal`.
+    MultiSigPropose {
+        #[clap(long)]
+        threshold: ThresholdUnit,
+        #[clap(long, num_args(1..))]
+        pks: Vec<PublicKey>,
+        #[clap(long, num_args(1..))]
+        weights: Vec<WeightUnit>,
+        #[clap(long)]
+        tx_bytes: String,
+        /// Path of the proposal file to create.
+        #[clap(long)]
+        proposal: PathBuf,
+    },
+    /// Add a partial signature to a MultiSig transaction proposal, either signing with the key
+    /// for the given address (or its alias) in sui keystore, or adding a signature `flag || sig || pk`
+    /// in Base64 created elsewhere.
+    MultiSigSignProposal {
+        #[clap(long)]
+        proposal: PathBuf,
+        #[clap(long, required_unless_present = "sig", conflicts_with = "sig")]
+        address: Option<KeyIdentity>,
+        #[clap(long)]
+        sig: Option<GenericSignature>,
+    },
+    /// Show which members of the MultiSig have signed a transaction proposal, and whether the
+    /// threshold is met.
+    MultiSigInspectProposal {
+        #[clap(long)]
+        proposal: PathBuf,
+    },
+    /// Combine the partial signatures of a MultiSig transaction proposal that meets its threshold.
+    /// Returns the MultiSig signature and the signed transaction, which can be executed with
+    /// `sui client execute-combined-signed-tx`.
+    MultiSigFinalizeProposal {
+        #[clap(long)]
+        proposal: PathBuf,
+    },
 
     /// Read the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
@@ -203,6 +255,20 @@ pub enum KeyToolCommand {
         #[clap(long)]
         intent: Option<Intent>,
     },
+    /// Sign a transaction bundle created with `--serialize-unsigned-bundle`, using the private
+    /// key for the given address (or its alias). A summary of the transaction is shown for review
+    /// before signing, and the signature is added to the bundle file. This does not need network
+    /// access, so it can be used on an offline machine.
+    SignBundle {
+        #[clap(long)]
+        address: KeyIdentity,
+        /// Path to the transaction bundle, updated in place with the signature.
+        #[clap(long)]
+        bundle: PathBuf,
+        /// Sign without asking for confirmation.
+        #[clap(long)]
+        yes: bool,
+    },
     /// Creates a signature by leveraging AWS KMS. Pass in a key-id to leverage Amazon
     /// KMS to sign a message and the base64 pubkey.
     /// Generate PubKey from pem using MystenLabs/base64pemkey
@@ -339,6 +405,13 @@ pub struct Key {
     peer_id: Option<String>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedKeystoreOutput {
+    keystore_path: PathBuf,
+    num_keys: usize,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportedKey {
@@ -378,6 +451,16 @@ pub struct MultiSigCombinePartialSigLegacyOutput {
     multisig_legacy_serialized: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigFinalizeProposalOutput {
+    multisig_address: SuiAddress,
+    multisig_serialized: String,
+    // Base64 encoded BCS serialized `SenderSignedData`, to pass to
+    // `sui client execute-combined-signed-tx`.
+    signed_tx_bytes: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MultiSigOutput {
@@ -401,6 +484,20 @@ pub struct PrivateKeyBase64 {
     base64: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignBundleOutput {
+    sui_address: SuiAddress,
+    bundle: PathBuf,
+    // Digest of the signed transaction.
+    digest: TransactionDigest,
+    // Base64 encoded `flag || signature || pubkey`, as added to the bundle.
+    sui_signature: String,
+    // Number of signatures in the bundle, and number of signers the transaction needs.
+    num_signatures: usize,
+    num_signers: usize,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SerializedSig {
@@ -455,9 +552,11 @@ pub struct ZkLoginInsecureSignPersonalMessage {
 #[serde(untagged)]
 pub enum CommandOutput {
     Alias(AliasUpdate),
+    ChangePassphrase(EncryptedKeystoreOutput),
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeOrVerifyTx(DecodeOrVerifyTxOutput),
+    EncryptKeystore(EncryptedKeystoreOutput),
     Error(String),
     Generate(Key),
     Import(Key),
@@ -467,9 +566,12 @@ pub enum CommandOutput {
     MultiSigAddress(MultiSigAddress),
     MultiSigCombinePartialSig(MultiSigCombinePartialSig),
     MultiSigCombinePartialSigLegacy(MultiSigCombinePartialSigLegacyOutput),
+    MultiSigFinalizeProposal(MultiSigFinalizeProposalOutput),
+    MultiSigProposal(ProposalStatus),
     PrivateKeyBase64(PrivateKeyBase64),
     Show(Key),
     Sign(SignData),
+    SignBundle(SignBundleOutput),
     SignKMS(SerializedSig),
     ZkLoginSignAndExecuteTx(ZkLoginSignAndExecuteTx),
     ZkLoginInsecureSignPersonalMessage(ZkLoginInsecureSignPersonalMessage),
@@ -489,6 +591,21 @@ impl KeyToolCommand {
                     new_alias,
                 })
             }
+            KeyToolCommand::ChangePassphrase => {
+                let Keystore::Encrypted(encrypted) = keystore else {
+                    return Err(anyhow!(
+                        "The keystore is not encrypted, use `sui keytool encrypt-keystore` instead"
+                    ));
+                };
+                // Unlock with the current passphrase before asking for the new one.
+                encrypted.ensure_unlocked()?;
+                let passphrase = prompt_new_passphrase()?;
+                encrypted.change_passphrase(&passphrase)?;
+                CommandOutput::ChangePassphrase(EncryptedKeystoreOutput {
+                    keystore_path: encrypted.path().to_path_buf(),
+                    num_keys: encrypted.keys().len(),
+                })
+            }
             KeyToolCommand::Convert { value } => {
                 let result = convert_private_key_to_bech32(value)?;
                 CommandOutput::Convert(result)
@@ -584,6 +701,20 @@ impl KeyToolCommand {
                     }
                 }
             }
+            KeyToolCommand::EncryptKeystore => {
+                let Keystore::File(file) = keystore else {
+                    return Err(anyhow!("Only a file based keystore can be encrypted"));
+                };
+                let passphrase = prompt_new_passphrase()?;
+                let encrypted = EncryptedKeystore::from_file_keystore(file, &passphrase)?;
+                let output = EncryptedKeystoreOutput {
+                    keystore_path: encrypted.path().to_path_buf(),
+                    num_keys: encrypted.keys().len(),
+                };
+                *keystore = Keystore::Encrypted(encrypted);
+                CommandOutput::EncryptKeystore(output)
+            }
+
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
@@ -792,6 +923,67 @@ impl KeyToolCommand {
                 )
             }
 
+            KeyToolCommand::MultiSigPropose {
+                threshold,
+                pks,
+                weights,
+                tx_bytes,
+                proposal: proposal_path,
+            } => {
+                let multisig_pk = MultiSigPublicKey::new(pks, weights, threshold)?;
+                let tx_data: TransactionData =
+                    bcs::from_bytes(&Base64::decode(&tx_bytes).map_err(|e| {
+                        anyhow!("Cannot deserialize data as TransactionData {:?}", e)
+                    })?)?;
+                let proposal = MultiSigProposal::new(&multisig_pk, &tx_data)?;
+                if proposal_path.exists() {
+                    return Err(anyhow!(
+                        "MultiSig proposal {} already exists",
+                        proposal_path.display()
+                    ));
+                }
+                proposal.write(&proposal_path)?;
+                CommandOutput::MultiSigProposal(proposal.status()?)
+            }
+
+            KeyToolCommand::MultiSigSignProposal {
+                proposal: proposal_path,
+                address,
+                sig,
+            } => {
+                let mut proposal = MultiSigProposal::read(&proposal_path)?;
+                let signature = match (address, sig) {
+                    (_, Some(sig)) => sig,
+                    (Some(address), None) => {
+                        let address = get_identity_address_from_keystore(address, keystore)?;
+                        let tx_data = proposal.transaction_data()?;
+                        keystore
+                            .sign_secure(&address, &tx_data, Intent::sui_transaction())?
+                            .into()
+                    }
+                    (None, None) => {
+                        return Err(anyhow!("Either an address or a signature must be provided"))
+                    }
+                };
+                proposal.add_signature(signature)?;
+                proposal.write(&proposal_path)?;
+                CommandOutput::MultiSigProposal(proposal.status()?)
+            }
+
+            KeyToolCommand::MultiSigInspectProposal { proposal } => {
+                CommandOutput::MultiSigProposal(MultiSigProposal::read(&proposal)?.status()?)
+            }
+
+            KeyToolCommand::MultiSigFinalizeProposal { proposal } => {
+                let proposal = MultiSigProposal::read(&proposal)?;
+                let (multisig, transaction) = proposal.finalize()?;
+                CommandOutput::MultiSigFinalizeProposal(MultiSigFinalizeProposalOutput {
+                    multisig_address: proposal.multisig_address,
+                    multisig_serialized: multisig.encode_base64(),
+                    signed_tx_bytes: Base64::encode(bcs::to_bytes(transaction.data())?),
+                })
+            }
+
             KeyToolCommand::Show { file } => {
                 let res = read_keypair_from_file(&file);
                 match res {
@@ -849,6 +1041,54 @@ impl KeyToolCommand {
                 })
             }
 
+            KeyToolCommand::SignBundle {
+                address,
+                bundle: bundle_path,
+                yes,
+            } => {
+                let address = get_identity_address_from_keystore(address, keystore)?;
+                let mut bundle = TransactionBundle::read(&bundle_path)?;
+                let tx_data = bundle.transaction_data()?;
+                let signers = tx_data.signers();
+                if !signers.contains(&address) {
+                    return Err(anyhow!(
+                        "Address {address} is not a signer of the transaction in the bundle"
+                    ));
+                }
+
+                eprintln!("{}", bundle.summary(&tx_data)?);
+                if !yes
+                    && !inquire::Confirm::new(&format!("Sign this transaction as {address}?"))
+                        .with_default(false)
+                        .prompt()?
+                {
+                    return Err(anyhow!("Signing aborted"));
+                }
+
+                let sui_signature = keystore
+                    .sign_secure(&address, &tx_data, Intent::sui_transaction())?
+                    .encode_base64();
+                // Replace any earlier signature from the same address.
+                bundle.signatures.retain(|sig| {
+                    Base64::decode(sig)
+                        .ok()
+                        .and_then(|bytes| GenericSignature::from_bytes(&bytes).ok())
+                        .and_then(|sig| SuiAddress::try_from(&sig).ok())
+                        != Some(address)
+                });
+                bundle.signatures.push(sui_signature.clone());
+                bundle.write(&bundle_path)?;
+
+                CommandOutput::SignBundle(SignBundleOutput {
+                    sui_address: address,
+                    bundle: bundle_path,
+                    digest: tx_data.digest(),
+                    sui_signature,
+                    num_signatures: bundle.signatures.len(),
+                    num_signers: signers.len(),
+                })
+            }
+
             KeyToolCommand::SignKMS {
                 data,
                 keyid,
@@ -1444,3 +1684,15 @@ fn anemo_styling(pk: &PublicKey) -> Option<String> {
         None
     }
 }
+
+/// Prompts for a new keystore passphrase, asking for it twice to guard against typos.
+fn prompt_new_passphrase() -> Result<String, anyhow::Error> {
+    let passphrase = inquire::Password::new("Enter a new passphrase for the keystore:")
+        .with_custom_confirmation_message("Confirm the new passphrase:")
+        .with_custom_confirmation_error_message("The passphrases do not match")
+        .prompt()?;
+    if passphrase.is_empty() {
+        return Err(anyhow!("The keystore passphrase cannot be empty"));
+    }
+    Ok(passphrase)
+}
@@ -13,8 +13,10 @@ pub mod genesis_ceremony;
 pub mod genesis_inspector;
 pub mod key_identity;
 pub mod keytool;
+pub mod multisig_proposal;
 pub mod shell;
 pub mod sui_commands;
+pub mod transaction_bundle;
 pub mod upgrade_compatibility;
 pub mod validator_commands;
 mod verifier_meter;
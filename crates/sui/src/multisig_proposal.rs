@@ -0,0 +1,256 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! MultiSig transaction proposals collect the partial signatures of the members of a MultiSig
+//! address for one transaction. A proposal is created with `sui keytool multi-sig-propose`, each
+//! member signs into it with `sui keytool multi-sig-sign-proposal`, and once enough weight has
+//! signed, `sui keytool multi-sig-finalize-proposal` combines the signatures into a transaction
+//! that can be executed with `sui client execute-combined-signed-tx`.
+
+use anyhow::{anyhow, bail, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::fs;
+use std::path::Path;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, SuiSignature};
+use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
+use sui_types::signature::GenericSignature;
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
+
+pub const MULTISIG_PROPOSAL_VERSION: u64 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigProposal {
+    pub version: u64,
+    pub multisig_address: SuiAddress,
+    pub threshold: ThresholdUnit,
+    /// The members of the MultiSig, in the order of its public key.
+    pub members: Vec<ProposalMember>,
+    /// BCS serialized `TransactionData`, Base64 encoded.
+    pub tx_bytes: String,
+    #[serde(default)]
+    pub partial_signatures: Vec<PartialSignature>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalMember {
+    pub address: SuiAddress,
+    /// Base64 encoded `flag || pk`.
+    pub public_key: String,
+    pub weight: WeightUnit,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialSignature {
+    pub address: SuiAddress,
+    pub weight: WeightUnit,
+    /// Base64 encoded `flag || signature || pubkey`.
+    pub signature: String,
+}
+
+/// Which members have signed a proposal, and whether their weight meets the threshold.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalStatus {
+    pub multisig_address: SuiAddress,
+    pub tx_digest: String,
+    pub threshold: ThresholdUnit,
+    pub signed_weight: ThresholdUnit,
+    pub threshold_met: bool,
+    pub members: Vec<ProposalMemberStatus>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalMemberStatus {
+    pub address: SuiAddress,
+    pub weight: WeightUnit,
+    pub signed: bool,
+}
+
+impl MultiSigProposal {
+    /// Creates a proposal for `tx_data`, which must be sent from the MultiSig address of
+    /// `multisig_pk`.
+    pub fn new(
+        multisig_pk: &MultiSigPublicKey,
+        tx_data: &TransactionData,
+    ) -> Result<Self, anyhow::Error> {
+        let multisig_address = SuiAddress::from(multisig_pk);
+        ensure!(
+            tx_data.sender() == multisig_address,
+            "The transaction is sent from {}, not from the MultiSig address {multisig_address}",
+            tx_data.sender()
+        );
+        Ok(Self {
+            version: MULTISIG_PROPOSAL_VERSION,
+            multisig_address,
+            threshold: *multisig_pk.threshold(),
+            members: multisig_pk
+                .pubkeys()
+                .iter()
+                .map(|(pk, weight)| ProposalMember {
+                    address: SuiAddress::from(pk),
+                    public_key: pk.encode_base64(),
+                    weight: *weight,
+                })
+                .collect(),
+            tx_bytes: Base64::encode(bcs::to_bytes(tx_data)?),
+            partial_signatures: vec![],
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read MultiSig proposal {}", path.display()))?;
+        let proposal: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Cannot parse MultiSig proposal {}", path.display()))?;
+        ensure!(
+            proposal.version == MULTISIG_PROPOSAL_VERSION,
+            "Unsupported MultiSig proposal version {}",
+            proposal.version
+        );
+        Ok(proposal)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Cannot write MultiSig proposal {}", path.display()))
+    }
+
+    /// The MultiSig public key of the proposal, checked against its address.
+    pub fn multisig_pk(&self) -> Result<MultiSigPublicKey, anyhow::Error> {
+        let pks = self
+            .members
+            .iter()
+            .map(|m| {
+                PublicKey::decode_base64(&m.public_key)
+                    .map_err(|e| anyhow!("Invalid public key {}: {e}", m.public_key))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let weights = self.members.iter().map(|m| m.weight).collect();
+        let multisig_pk = MultiSigPublicKey::new(pks, weights, self.threshold)?;
+        ensure!(
+            SuiAddress::from(&multisig_pk) == self.multisig_address,
+            "The members of the proposal do not match its MultiSig address {}",
+            self.multisig_address
+        );
+        Ok(multisig_pk)
+    }
+
+    pub fn transaction_data(&self) -> Result<TransactionData, anyhow::Error> {
+        let bytes = Base64::decode(&self.tx_bytes)
+            .map_err(|e| anyhow!("Invalid Base64 encoding of the transaction bytes: {e}"))?;
+        let tx_data: TransactionData = bcs::from_bytes(&bytes)
+            .map_err(|e| anyhow!("Cannot deserialize the transaction bytes: {e}"))?;
+        ensure!(
+            tx_data.sender() == self.multisig_address,
+            "The transaction is sent from {}, not from the MultiSig address {}",
+            tx_data.sender(),
+            self.multisig_address
+        );
+        Ok(tx_data)
+    }
+
+    /// Adds the partial signature of a member, after checking it signs the proposed transaction.
+    /// An earlier signature from the same member is replaced.
+    pub fn add_signature(&mut self, signature: GenericSignature) -> Result<(), anyhow::Error> {
+        let multisig_pk = self.multisig_pk()?;
+        let tx_data = self.transaction_data()?;
+        let GenericSignature::Signature(sig) = &signature else {
+            bail!("Only single key signatures can be added to a MultiSig proposal");
+        };
+        let pk = signature.to_public_key()?;
+        let index = multisig_pk
+            .get_index(&pk)
+            .ok_or_else(|| anyhow!("The signer is not a member of the MultiSig"))?;
+        let member = &self.members[index as usize];
+        sig.verify_secure(
+            &IntentMessage::new(Intent::sui_transaction(), tx_data),
+            member.address,
+            sig.scheme(),
+        )
+        .map_err(|e| anyhow!("The signature of {} is invalid: {e}", member.address))?;
+
+        let partial_signature = PartialSignature {
+            address: member.address,
+            weight: member.weight,
+            signature: signature.encode_base64(),
+        };
+        self.partial_signatures
+            .retain(|s| s.address != partial_signature.address);
+        self.partial_signatures.push(partial_signature);
+        Ok(())
+    }
+
+    pub fn status(&self) -> Result<ProposalStatus, anyhow::Error> {
+        let tx_data = self.transaction_data()?;
+        let members = self
+            .members
+            .iter()
+            .map(|m| ProposalMemberStatus {
+                address: m.address,
+                weight: m.weight,
+                signed: self
+                    .partial_signatures
+                    .iter()
+                    .any(|s| s.address == m.address),
+            })
+            .collect::<Vec<_>>();
+        let signed_weight = members
+            .iter()
+            .filter(|m| m.signed)
+            .map(|m| m.weight as ThresholdUnit)
+            .sum();
+        Ok(ProposalStatus {
+            multisig_address: self.multisig_address,
+            tx_digest: tx_data.digest().to_string(),
+            threshold: self.threshold,
+            signed_weight,
+            threshold_met: signed_weight >= self.threshold,
+            members,
+        })
+    }
+
+    /// Combines the partial signatures into the MultiSig signature of the transaction. Fails if
+    /// the signers' weight does not meet the threshold.
+    pub fn finalize(&self) -> Result<(GenericSignature, Transaction), anyhow::Error> {
+        let status = self.status()?;
+        ensure!(
+            status.threshold_met,
+            "The signatures collected have a weight of {}, below the threshold of {}",
+            status.signed_weight,
+            status.threshold
+        );
+        let multisig_pk = self.multisig_pk()?;
+        let mut signatures = self
+            .partial_signatures
+            .iter()
+            .map(|s| {
+                let bytes = Base64::decode(&s.signature)
+                    .map_err(|e| anyhow!("Invalid signature of {}: {e}", s.address))?;
+                let signature = GenericSignature::from_bytes(&bytes)
+                    .map_err(|e| anyhow!("Invalid signature of {}: {e}", s.address))?;
+                let index = multisig_pk
+                    .get_index(&signature.to_public_key()?)
+                    .ok_or_else(|| anyhow!("{} is not a member of the MultiSig", s.address))?;
+                Ok((index, signature))
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        // MultiSig::combine expects the signatures in the order of the public keys.
+        signatures.sort_by_key(|(index, _)| *index);
+        let multisig = MultiSig::combine(
+            signatures.into_iter().map(|(_, sig)| sig).collect(),
+            multisig_pk,
+        )?;
+        let multisig = GenericSignature::MultiSig(multisig);
+        let transaction =
+            Transaction::from_generic_sig_data(self.transaction_data()?, vec![multisig.clone()]);
+        Ok((multisig, transaction))
+    }
+}
@@ -44,7 +44,7 @@ use sui_graphql_rpc::{
 };
 
 use sui_keys::keypair_file::read_key;
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, EncryptedKeystore, FileBasedKeystore, Keystore};
 use sui_move::{self, execute_move_command};
 use sui_move_build::SuiPackageHooks;
 use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
@@ -265,6 +265,11 @@ pub enum SuiCommand {
     KeyTool {
         #[clap(long)]
         keystore_path: Option<PathBuf>,
+        /// The client config that `encrypt-keystore` switches to the encrypted keystore, if it
+        /// uses the keystore being encrypted. Defaults to the client config in the Sui config
+        /// directory.
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
         ///Return command outputs in json format
         #[clap(long, global = true)]
         json: bool,
@@ -428,13 +433,27 @@ impl SuiCommand {
             SuiCommand::GenesisCeremony(cmd) => run(cmd),
             SuiCommand::KeyTool {
                 keystore_path,
+                config,
                 json,
                 cmd,
             } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
-                let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+                let mut keystore = Keystore::open(&keystore_path)?;
+                // The client config has to be read before the keystore is encrypted, while its
+                // keystore can still be loaded as a file based one.
+                let client_config = if matches!(cmd, KeyToolCommand::EncryptKeystore) {
+                    let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+                    client_config_using_keystore(&config_path, &keystore_path)?
+                } else {
+                    None
+                };
                 cmd.execute(&mut keystore).await?.print(!json);
+                if let Some(mut client_config) = client_config {
+                    client_config.keystore =
+                        Keystore::Encrypted(EncryptedKeystore::new(&keystore_path)?);
+                    client_config.save()?;
+                }
                 Ok(())
             }
             SuiCommand::Console { config } => {
@@ -1316,6 +1335,23 @@ async fn prompt_if_no_config(
     Ok(())
 }
 
+/// Returns the client config at `config_path` if its keystore is the file based keystore at
+/// `keystore_path`.
+fn client_config_using_keystore(
+    config_path: &Path,
+    keystore_path: &Path,
+) -> Result<Option<PersistedConfig<SuiClientConfig>>, anyhow::Error> {
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let config: SuiClientConfig = PersistedConfig::read(config_path)?;
+    let uses_keystore = matches!(
+        &config.keystore,
+        Keystore::File(file) if file.path() == Some(keystore_path)
+    );
+    Ok(uses_keystore.then(|| config.persisted(config_path)))
+}
+
 fn read_line() -> Result<String, anyhow::Error> {
     let mut s = String::new();
     let _ = stdout().flush();
@@ -0,0 +1,238 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transaction bundles carry an unsigned transaction together with everything needed to review
+//! and sign it on an offline machine: the chain it was built for, the transaction decoded, and
+//! snapshots of its input objects. Signatures are collected into the bundle with
+//! `sui keytool sign-bundle`, and the bundle is executed with `sui client submit-bundle`.
+
+use anyhow::{anyhow, bail, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber};
+use sui_types::digests::ObjectDigest;
+use sui_types::object::Owner;
+use sui_types::signature::GenericSignature;
+use sui_types::transaction::{
+    InputObjectKind, Transaction, TransactionData, TransactionDataAPI, TransactionExpiration,
+};
+
+pub const TRANSACTION_BUNDLE_VERSION: u64 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBundle {
+    pub version: u64,
+    /// The chain identifier of the network the transaction was built for.
+    pub chain_id: String,
+    /// BCS serialized `TransactionData`, Base64 encoded. This is what gets signed, the rest of
+    /// the bundle is checked against it.
+    pub tx_bytes: String,
+    /// The transaction, decoded for review.
+    pub transaction: TransactionData,
+    /// The input objects of the transaction, including gas, as of when the bundle was created.
+    pub input_objects: Vec<ObjectSnapshot>,
+    /// Base64 encoded signatures `flag || signature || pubkey` collected so far.
+    #[serde(default)]
+    pub signatures: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectSnapshot {
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub owner: Option<Owner>,
+    pub object_type: Option<String>,
+}
+
+impl TransactionBundle {
+    /// Creates a bundle for `tx_data`, fetching the chain identifier and the current state of
+    /// its input objects from the network.
+    pub async fn new(client: &SuiClient, tx_data: TransactionData) -> Result<Self, anyhow::Error> {
+        let chain_id = client.read_api().get_chain_identifier().await?;
+        let object_ids = input_object_ids(&tx_data)?;
+        let input_objects = client
+            .read_api()
+            .multi_get_object_with_options(
+                object_ids,
+                SuiObjectDataOptions::new().with_owner().with_type(),
+            )
+            .await?
+            .into_iter()
+            .map(|response| {
+                let object = response.into_object()?;
+                Ok(ObjectSnapshot {
+                    object_id: object.object_id,
+                    version: object.version,
+                    digest: object.digest,
+                    owner: object.owner,
+                    object_type: object.type_.map(|t| t.to_string()),
+                })
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+        Ok(Self {
+            version: TRANSACTION_BUNDLE_VERSION,
+            chain_id,
+            tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+            transaction: tx_data,
+            input_objects,
+            signatures: vec![],
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read transaction bundle {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Cannot parse transaction bundle {}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Cannot write transaction bundle {}", path.display()))
+    }
+
+    /// Decodes the transaction to sign from the bundle's bytes, and checks that the decoded
+    /// transaction and the object snapshots shown for review agree with it.
+    pub fn transaction_data(&self) -> Result<TransactionData, anyhow::Error> {
+        ensure!(
+            self.version == TRANSACTION_BUNDLE_VERSION,
+            "Unsupported transaction bundle version {}",
+            self.version
+        );
+        let bytes = Base64::decode(&self.tx_bytes)
+            .map_err(|e| anyhow!("Invalid Base64 encoding of the transaction bytes: {e}"))?;
+        let tx_data: TransactionData = bcs::from_bytes(&bytes)
+            .map_err(|e| anyhow!("Cannot deserialize the transaction bytes: {e}"))?;
+        ensure!(
+            tx_data == self.transaction,
+            "The decoded transaction in the bundle does not match its transaction bytes"
+        );
+        for (id, version, digest) in owned_object_refs(&tx_data)? {
+            let snapshot = self
+                .input_objects
+                .iter()
+                .find(|o| o.object_id == id)
+                .ok_or_else(|| anyhow!("The bundle is missing a snapshot of input object {id}"))?;
+            ensure!(
+                snapshot.version == version && snapshot.digest == digest,
+                "The snapshot of input object {id} does not match the transaction"
+            );
+        }
+        Ok(tx_data)
+    }
+
+    /// A human readable summary of the transaction, for review before signing.
+    pub fn summary(&self, tx_data: &TransactionData) -> Result<String, anyhow::Error> {
+        let mut writer = String::new();
+        writeln!(writer, "Chain ID : {}", self.chain_id)?;
+        writeln!(writer, "Sender : {}", tx_data.sender())?;
+        writeln!(writer, "Gas Owner : {}", tx_data.gas_owner())?;
+        writeln!(writer, "Gas Budget : {} MIST", tx_data.gas_budget())?;
+        writeln!(writer, "Gas Price : {} MIST", tx_data.gas_price())?;
+        for (id, version, _) in tx_data.gas() {
+            writeln!(writer, "Gas Payment : {id} (version {version})")?;
+        }
+        match tx_data.expiration() {
+            TransactionExpiration::None => writeln!(writer, "Expiration : None")?,
+            TransactionExpiration::Epoch(epoch) => writeln!(writer, "Expiration : Epoch {epoch}")?,
+        }
+        writeln!(writer, "Input Objects :")?;
+        for o in &self.input_objects {
+            writeln!(
+                writer,
+                "  {} (version {}) : {}, {}",
+                o.object_id,
+                o.version,
+                o.object_type.as_deref().unwrap_or("unknown type"),
+                o.owner
+                    .as_ref()
+                    .map(|owner| owner.to_string())
+                    .unwrap_or_else(|| "unknown owner".to_string()),
+            )?;
+        }
+        write!(writer, "{}", tx_data.kind())?;
+        Ok(writer)
+    }
+
+    /// Checks that the owned objects the transaction uses are still at the versions it was built
+    /// with, so that it can still be executed.
+    pub async fn check_freshness(
+        &self,
+        client: &SuiClient,
+        tx_data: &TransactionData,
+    ) -> Result<(), anyhow::Error> {
+        let refs = owned_object_refs(tx_data)?;
+        let responses = client
+            .read_api()
+            .multi_get_object_with_options(
+                refs.iter().map(|(id, _, _)| *id).collect(),
+                SuiObjectDataOptions::new(),
+            )
+            .await?;
+        for ((id, version, digest), response) in refs.into_iter().zip(responses) {
+            let Ok(current) = response.into_object() else {
+                bail!("Input object {id} no longer exists, the bundle is stale");
+            };
+            ensure!(
+                current.version == version && current.digest == digest,
+                "Input object {id} changed since the bundle was created (version {version}, now {}), the bundle is stale",
+                current.version
+            );
+        }
+        Ok(())
+    }
+
+    /// The transaction with the signatures collected in the bundle.
+    pub fn signed_transaction(&self) -> Result<Transaction, anyhow::Error> {
+        let tx_data = self.transaction_data()?;
+        ensure!(!self.signatures.is_empty(), "The bundle is not signed");
+        let signatures = self
+            .signatures
+            .iter()
+            .map(|sig| {
+                let bytes = Base64::decode(sig).map_err(|e| anyhow!("Invalid signature: {e}"))?;
+                GenericSignature::from_bytes(&bytes).map_err(|e| anyhow!("Invalid signature: {e}"))
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        Ok(Transaction::from_generic_sig_data(tx_data, signatures))
+    }
+}
+
+/// The IDs of all the objects the transaction reads, including gas and receiving objects.
+fn input_object_ids(tx_data: &TransactionData) -> Result<Vec<ObjectID>, anyhow::Error> {
+    let mut ids = tx_data
+        .input_objects()?
+        .iter()
+        .filter(|o| !matches!(o, InputObjectKind::MovePackage(_)))
+        .map(|o| o.object_id())
+        .collect::<Vec<_>>();
+    ids.extend(tx_data.receiving_objects().iter().map(|(id, _, _)| *id));
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// The references to owned and immutable objects the transaction was built with, including gas
+/// and receiving objects.
+fn owned_object_refs(tx_data: &TransactionData) -> Result<Vec<ObjectRef>, anyhow::Error> {
+    let mut refs = tx_data
+        .input_objects()?
+        .into_iter()
+        .filter_map(|o| match o {
+            InputObjectKind::ImmOrOwnedMoveObject(obj_ref) => Some(obj_ref),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    refs.extend(tx_data.receiving_objects());
+    Ok(refs)
+}
@@ -36,6 +36,7 @@ use sui_types::crypto::Signature;
 use sui_types::crypto::SignatureScheme;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::SuiSignatureInner;
+use sui_types::multisig::MultiSigPublicKey;
 use sui_types::transaction::TransactionData;
 use sui_types::transaction::TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
 use tempfile::TempDir;
@@ -565,3 +566,149 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_multisig_proposal_lifecycle() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(3));
+    let pks = keystore.keys();
+    let addresses = pks.iter().map(SuiAddress::from).collect::<Vec<_>>();
+    let multisig_pk = MultiSigPublicKey::new(pks.clone(), vec![1, 1, 1], 2)?;
+    let multisig_address = SuiAddress::from(&multisig_pk);
+
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let gas_price = 1;
+    let tx_data = TransactionData::new_pay_sui(
+        multisig_address,
+        vec![gas],
+        vec![SuiAddress::random_for_testing_only()],
+        vec![10000],
+        gas,
+        gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_price,
+    )
+    .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let proposal = temp_dir.path().join("proposal.json");
+    KeyToolCommand::MultiSigPropose {
+        threshold: 2,
+        pks,
+        weights: vec![1, 1, 1],
+        tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+        proposal: proposal.clone(),
+    }
+    .execute(&mut keystore)
+    .await?;
+
+    // Sign with the last member first, the signatures are ordered when combined.
+    for address in [addresses[2], addresses[0]] {
+        KeyToolCommand::MultiSigSignProposal {
+            proposal: proposal.clone(),
+            address: Some(KeyIdentity::Address(address)),
+            sig: None,
+        }
+        .execute(&mut keystore)
+        .await?;
+    }
+
+    let CommandOutput::MultiSigProposal(status) = KeyToolCommand::MultiSigInspectProposal {
+        proposal: proposal.clone(),
+    }
+    .execute(&mut keystore)
+    .await?
+    else {
+        panic!("Expected a proposal status");
+    };
+    assert_eq!(status.multisig_address, multisig_address);
+    assert_eq!(status.signed_weight, 2);
+    assert!(status.threshold_met);
+    assert_eq!(
+        status.members.iter().map(|m| m.signed).collect::<Vec<_>>(),
+        vec![true, false, true]
+    );
+
+    let CommandOutput::MultiSigFinalizeProposal(_) =
+        KeyToolCommand::MultiSigFinalizeProposal { proposal }
+            .execute(&mut keystore)
+            .await?
+    else {
+        panic!("Expected a finalized proposal");
+    };
+    Ok(())
+}
+
+#[test]
+async fn test_multisig_proposal_below_threshold() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(2));
+    let pks = keystore.keys();
+    let multisig_pk = MultiSigPublicKey::new(pks.clone(), vec![1, 1], 2)?;
+    let multisig_address = SuiAddress::from(&multisig_pk);
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let tx_data = TransactionData::new_pay_sui(
+        multisig_address,
+        vec![gas],
+        vec![SuiAddress::random_for_testing_only()],
+        vec![10000],
+        gas,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        1,
+    )
+    .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let proposal = temp_dir.path().join("proposal.json");
+    KeyToolCommand::MultiSigPropose {
+        threshold: 2,
+        pks: pks.clone(),
+        weights: vec![1, 1],
+        tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+        proposal: proposal.clone(),
+    }
+    .execute(&mut keystore)
+    .await?;
+
+    // A signature over another transaction is rejected.
+    let other_sig = keystore.sign_secure(
+        &SuiAddress::from(&pks[0]),
+        &TransactionData::new_pay_sui(
+            multisig_address,
+            vec![gas],
+            vec![SuiAddress::random_for_testing_only()],
+            vec![1],
+            gas,
+            TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            1,
+        )
+        .unwrap(),
+        Intent::sui_transaction(),
+    )?;
+    assert!(KeyToolCommand::MultiSigSignProposal {
+        proposal: proposal.clone(),
+        address: None,
+        sig: Some(other_sig.into()),
+    }
+    .execute(&mut keystore)
+    .await
+    .is_err());
+
+    KeyToolCommand::MultiSigSignProposal {
+        proposal: proposal.clone(),
+        address: Some(KeyIdentity::Address(SuiAddress::from(&pks[0]))),
+        sig: None,
+    }
+    .execute(&mut keystore)
+    .await?;
+    assert!(KeyToolCommand::MultiSigFinalizeProposal { proposal }
+        .execute(&mut keystore)
+        .await
+        .is_err());
+    Ok(())
+}
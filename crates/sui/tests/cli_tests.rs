@@ -12,6 +12,7 @@ use std::env;
 use std::str::FromStr;
 
 use expect_test::expect;
+use fastcrypto::encoding::{Base64, Encoding};
 use move_package::{lock_file::schema::ManagedPackage, BuildConfig as MoveBuildConfig};
 use serde_json::json;
 use sui::client_ptb::ptb::PTB;
@@ -20,19 +21,22 @@ use sui::sui_commands::IndexerArgs;
 use sui_sdk::SuiClient;
 use sui_test_transaction_builder::batch_make_transfer_transactions;
 use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::transaction::{
-    TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
-    TEST_ONLY_GAS_UNIT_FOR_PUBLISH, TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
-    TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    Argument, TransactionData, TransactionDataAPI, TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+    TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
 };
 use tokio::time::sleep;
 
 use sui::{
     client_commands::{
         estimate_gas_budget, Opts, OptsWithGas, SuiClientCommandResult, SuiClientCommands,
-        SwitchResponse,
+        SwitchResponse, DEFAULT_MAX_SPONSORED_GAS_BUDGET,
     },
+    keytool::KeyToolCommand,
     sui_commands::{parse_host_port, SuiCommand},
+    transaction_bundle::TransactionBundle,
 };
 use sui_config::{
     PersistedConfig, SUI_CLIENT_CONFIG, SUI_FULLNODE_CONFIG, SUI_GENESIS_FILENAME,
@@ -2907,6 +2911,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
             dev_inspect: false,
             serialize_unsigned_transaction: true,
             serialize_signed_transaction: false,
+            serialize_unsigned_bundle: false,
         },
     }
     .execute(context)
@@ -2922,6 +2927,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
             dev_inspect: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: true,
+            serialize_unsigned_bundle: false,
         },
     }
     .execute(context)
@@ -2938,6 +2944,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
             dev_inspect: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: true,
+            serialize_unsigned_bundle: false,
         },
     }
     .execute(context)
@@ -2967,6 +2974,257 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_sponsored_transaction() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let sender = test_cluster.get_address_0();
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let coin = client
+        .coin_read_api()
+        .get_coins(sender, None, None, None)
+        .await?
+        .data[0]
+        .coin_object_id;
+
+    // The sender builds the transaction, without signing it.
+    context.config.active_address = Some(sender);
+    let SuiClientCommandResult::SerializedUnsignedTransaction(tx_data) =
+        SuiClientCommands::Transfer {
+            to: KeyIdentity::Address(SuiAddress::random_for_testing_only()),
+            object_id: coin,
+            opts: OptsWithGas {
+                gas: None,
+                rest: Opts {
+                    gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+                    dry_run: false,
+                    dev_inspect: false,
+                    serialize_unsigned_transaction: true,
+                    serialize_signed_transaction: false,
+                    serialize_unsigned_bundle: false,
+                },
+            },
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected an unsigned transaction");
+    };
+    let tx_bytes = Base64::encode(bcs::to_bytes(&tx_data)?);
+
+    // The sponsor pays for gas and signs.
+    context.config.active_address = Some(sponsor);
+    let SuiClientCommandResult::SerializedSignedTransaction(sponsored) =
+        SuiClientCommands::SponsorTx {
+            tx_bytes: tx_bytes.clone(),
+            gas: vec![],
+            gas_budget: None,
+            max_gas_budget: DEFAULT_MAX_SPONSORED_GAS_BUDGET,
+            max_gas_price: None,
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a signed transaction");
+    };
+    assert_eq!(sponsored.transaction_data().sender(), sender);
+    assert_eq!(sponsored.transaction_data().gas_owner(), sponsor);
+    let sponsored_bytes = Base64::encode(bcs::to_bytes(&sponsored)?);
+
+    // Submitting without the sender's signature is rejected before reaching the network.
+    assert!(SuiClientCommands::ExecuteCombinedSignedTx {
+        signed_tx_bytes: sponsored_bytes.clone(),
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    // The sender checks the sponsored transaction against its own, and co-signs it.
+    context.config.active_address = Some(sender);
+    let SuiClientCommandResult::SerializedSignedTransaction(signed) =
+        SuiClientCommands::SignSponsoredTx {
+            signed_tx_bytes: sponsored_bytes,
+            original_tx_bytes: Some(tx_bytes),
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a signed transaction");
+    };
+    assert_eq!(signed.tx_signatures().len(), 2);
+
+    let SuiClientCommandResult::TransactionBlock(response) =
+        SuiClientCommands::ExecuteCombinedSignedTx {
+            signed_tx_bytes: Base64::encode(bcs::to_bytes(&signed)?),
+        }
+        .execute(context)
+        .await?
+    else {
+        panic!("Expected a transaction block response");
+    };
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.gas_object().owner, Owner::AddressOwner(sponsor));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sponsor_tx_rejects_unsafe_transactions() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let sender = test_cluster.get_address_0();
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(sender, None, None, None)
+        .await?
+        .data;
+    let gas = coins[0].object_ref();
+    let coin = coins[1].object_ref();
+    let gas_budget = rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
+    let recipient = SuiAddress::random_for_testing_only();
+
+    let sponsor_tx = |tx_data: &TransactionData| SuiClientCommands::SponsorTx {
+        tx_bytes: Base64::encode(bcs::to_bytes(tx_data).unwrap()),
+        gas: vec![],
+        gas_budget: None,
+        max_gas_budget: DEFAULT_MAX_SPONSORED_GAS_BUDGET,
+        max_gas_price: None,
+    };
+    context.config.active_address = Some(sponsor);
+
+    // The gas coin of a sponsored transaction belongs to the sponsor, so a transaction that uses
+    // it could take the sponsor's coin.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_arg(recipient, Argument::GasCoin);
+    let tx_data =
+        TransactionData::new_programmable(sender, vec![gas], builder.finish(), gas_budget, rgp);
+    let err = sponsor_tx(&tx_data).execute(context).await.unwrap_err();
+    assert!(err.to_string().contains("uses the gas coin"), "{err}");
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_object(recipient, coin)?;
+    let pt = builder.finish();
+
+    // A gas budget above the maximum the sponsor accepts.
+    let tx_data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        pt.clone(),
+        DEFAULT_MAX_SPONSORED_GAS_BUDGET + 1,
+        rgp,
+    );
+    let err = sponsor_tx(&tx_data).execute(context).await.unwrap_err();
+    assert!(
+        err.to_string().contains("above the maximum gas budget"),
+        "{err}"
+    );
+
+    // A gas price above the reference gas price.
+    let tx_data =
+        TransactionData::new_programmable(sender, vec![gas], pt.clone(), gas_budget, rgp * 2);
+    let err = sponsor_tx(&tx_data).execute(context).await.unwrap_err();
+    assert!(
+        err.to_string().contains("above the maximum gas price"),
+        "{err}"
+    );
+
+    // The same transaction at the reference gas price is sponsored.
+    let tx_data = TransactionData::new_programmable(sender, vec![gas], pt, gas_budget, rgp);
+    let SuiClientCommandResult::SerializedSignedTransaction(sponsored) =
+        sponsor_tx(&tx_data).execute(context).await?
+    else {
+        panic!("Expected a signed transaction");
+    };
+    assert_eq!(sponsored.transaction_data().gas_owner(), sponsor);
+    Ok(())
+}
+
+#[sim_test]
+async fn test_transaction_bundle() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let coin = client
+        .coin_read_api()
+        .get_coins(address, None, None, None)
+        .await?
+        .data[0]
+        .coin_object_id;
+    let bundle_path = tempfile::tempdir()?.into_path().join("bundle.json");
+
+    let SuiClientCommandResult::SerializedUnsignedBundle(bundle) = SuiClientCommands::Transfer {
+        to: KeyIdentity::Address(SuiAddress::random_for_testing_only()),
+        object_id: coin,
+        opts: OptsWithGas {
+            gas: None,
+            rest: Opts {
+                gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+                dry_run: false,
+                dev_inspect: false,
+                serialize_unsigned_transaction: false,
+                serialize_signed_transaction: false,
+                serialize_unsigned_bundle: true,
+            },
+        },
+    }
+    .execute(context)
+    .await?
+    else {
+        panic!("Expected an unsigned bundle");
+    };
+    let tx_data = bundle.transaction_data()?;
+    assert!(bundle
+        .input_objects
+        .iter()
+        .any(|object| object.object_id == coin));
+    bundle.write(&bundle_path)?;
+
+    // An unsigned bundle cannot be submitted.
+    assert!(SuiClientCommands::SubmitBundle {
+        bundle: bundle_path.clone(),
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    KeyToolCommand::SignBundle {
+        address: KeyIdentity::Address(address),
+        bundle: bundle_path.clone(),
+        yes: true,
+    }
+    .execute(&mut context.config.keystore)
+    .await?;
+    let signed = TransactionBundle::read(&bundle_path)?;
+    assert_eq!(signed.signatures.len(), 1);
+    assert_eq!(signed.transaction_data()?, tx_data);
+
+    let SuiClientCommandResult::TransactionBlock(response) = SuiClientCommands::SubmitBundle {
+        bundle: bundle_path.clone(),
+    }
+    .execute(context)
+    .await?
+    else {
+        panic!("Expected a transaction block response");
+    };
+    assert!(response.effects.unwrap().status().is_ok());
+
+    // The objects the bundle was built with have been used, so it cannot be submitted again.
+    assert!(SuiClientCommands::SubmitBundle {
+        bundle: bundle_path,
+    }
+    .execute(context)
+    .await
+    .is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -3785,6 +4043,7 @@ async fn test_gas_estimation() -> Result<(), anyhow::Error> {
             dev_inspect: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            serialize_unsigned_bundle: false,
         },
     }
     .execute(context)
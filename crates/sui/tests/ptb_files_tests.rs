@@ -53,7 +53,8 @@ async fn test_ptb_files(path: &Path) -> datatest_stable::Result<()> {
         "{}",
         PTBPreview {
             program: &program,
-            program_metadata: &program_meta
+            program_metadata: &program_meta,
+            mvr_names: &[],
         }
     ));
 
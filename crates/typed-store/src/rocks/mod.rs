@@ -904,6 +904,38 @@ impl<K, V> DBMap<K, V> {
             .iterator_cf(&self.cf(), self.opts.readopts(), IteratorMode::Start)
     }
 
+    /// Returns an iterator over the entries whose serialized key starts with the serialized
+    /// `prefix`, e.g. the leading fields of a struct key.
+    pub fn safe_iter_with_prefix<P: Serialize>(
+        &self,
+        prefix: &P,
+    ) -> Result<SafeIter<'_, K, V>, TypedStoreError>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let lower_bound = be_fix_int_ser(prefix)?;
+        let mut readopts = self.opts.readopts();
+        // A prefix made only of 0xFF bytes has no successor, so the scan runs to the end.
+        if !is_max(&lower_bound) {
+            let mut upper_bound = lower_bound.clone();
+            big_endian_saturating_add_one(&mut upper_bound);
+            readopts.set_iterate_upper_bound(upper_bound);
+        }
+        readopts.set_iterate_lower_bound(lower_bound);
+        let db_iter = self.rocksdb.raw_iterator_cf(&self.cf(), readopts);
+        let (_timer, bytes_scanned, keys_scanned, _perf_ctx) = self.create_iter_context();
+        Ok(SafeIter::new(
+            self.cf.clone(),
+            db_iter,
+            _timer,
+            _perf_ctx,
+            bytes_scanned,
+            keys_scanned,
+            Some(self.db_metrics.clone()),
+        ))
+    }
+
     pub fn flush(&self) -> Result<(), TypedStoreError> {
         self.rocksdb
             .flush_cf(&self.cf())
@@ -839,6 +839,45 @@ async fn test_iter_with_bounds(
     assert_eq!(vec![(49, "49".to_string())], db_iter.collect::<Vec<_>>());
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_iter_with_prefix(#[values(true, false)] is_transactional: bool) {
+    let db = open_map(temp_dir(), None, is_transactional);
+
+    for prefix in [0u8, 1, 2, u8::MAX] {
+        for i in 0..10u32 {
+            db.insert(&(prefix, i), &i.to_string()).unwrap();
+        }
+    }
+
+    let entries = |prefix: u8| -> Vec<((u8, u32), String)> {
+        (0..10u32).map(|i| ((prefix, i), i.to_string())).collect()
+    };
+
+    for prefix in [0u8, 1, 2, u8::MAX] {
+        let db_iter = db.safe_iter_with_prefix(&prefix).unwrap();
+        assert_eq!(
+            entries(prefix),
+            db_iter.collect::<Result<Vec<_>, _>>().unwrap()
+        );
+    }
+
+    // A prefix with no entries yields nothing.
+    let db_iter = db.safe_iter_with_prefix(&3u8).unwrap();
+    assert_eq!(0, db_iter.count());
+
+    // Skipping stays within the prefix.
+    let db_iter = db
+        .safe_iter_with_prefix(&1u8)
+        .unwrap()
+        .skip_to(&(1, 5))
+        .unwrap();
+    assert_eq!(
+        entries(1)[5..].to_vec(),
+        db_iter.collect::<Result<Vec<_>, _>>().unwrap()
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_range_iter(
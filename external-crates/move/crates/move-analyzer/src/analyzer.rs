@@ -6,10 +6,11 @@ use anyhow::Result;
 use crossbeam::channel::{bounded, select};
 use lsp_server::{Connection, Message, Notification, Request, Response};
 use lsp_types::{
-    notification::Notification as _, request::Request as _, CompletionOptions, Diagnostic,
-    HoverProviderCapability, InlayHintOptions, InlayHintServerCapabilities, OneOf, SaveOptions,
-    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
-    TypeDefinitionProviderCapability, WorkDoneProgressOptions,
+    notification::Notification as _, request::Request as _, CodeActionKind, CodeActionOptions,
+    CodeActionProviderCapability, CompletionOptions, Diagnostic, HoverProviderCapability,
+    InlayHintOptions, InlayHintServerCapabilities, OneOf, RenameOptions, SaveOptions,
+    SignatureHelpOptions, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TypeDefinitionProviderCapability, WorkDoneProgressOptions,
 };
 use move_compiler::linters::LintLevel;
 use std::{
@@ -19,8 +20,8 @@ use std::{
 };
 
 use crate::{
-    completions::on_completion_request, context::Context, inlay_hints, symbols,
-    vfs::on_text_document_sync_notification,
+    code_actions, completions::on_completion_request, context::Context, formatting, inlay_hints,
+    rename, signature_help, symbols, vfs::on_text_document_sync_notification,
 };
 use url::Url;
 use vfs::{impls::memory::MemoryFS, VfsPath};
@@ -60,13 +61,10 @@ pub fn run() {
         text_document_sync: Some(TextDocumentSyncCapability::Options(
             TextDocumentSyncOptions {
                 open_close: Some(true),
-                // TODO: We request that the language server client send us the entire text of any
-                // files that are modified. We ought to use the "incremental" sync kind, which would
-                // have clients only send us what has changed and where, thereby requiring far less
-                // data be sent "over the wire." However, to do so, our language server would need
-                // to be capable of applying deltas to its view of the client's open files. See the
-                // 'move_analyzer::vfs' module for details.
-                change: Some(TextDocumentSyncKind::FULL),
+                // We request that the language server client send us only what has changed in
+                // the modified files and where, which is then applied to the server's view of the
+                // client's open files. See the 'move_analyzer::vfs' module for details.
+                change: Some(TextDocumentSyncKind::INCREMENTAL),
                 will_save: None,
                 will_save_wait_until: None,
                 save: Some(
@@ -107,6 +105,29 @@ pub fn run() {
                 resolve_provider: None,
             },
         ))),
+        // Renaming is preceded by a prepare request to check if the symbol can be renamed.
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
+        // The server provides signature help when the user starts typing call arguments.
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+            resolve_provider: None,
+        })),
+        document_formatting_provider: Some(OneOf::Left(true)),
         ..Default::default()
     })
     .expect("could not serialize server capabilities");
@@ -319,6 +340,26 @@ fn on_request(
         lsp_types::request::InlayHintRequest::METHOD => {
             inlay_hints::on_inlay_hint_request(context, request);
         }
+        lsp_types::request::PrepareRenameRequest::METHOD => {
+            rename::on_prepare_rename_request(context, request);
+        }
+        lsp_types::request::Rename::METHOD => {
+            rename::on_rename_request(context, request);
+        }
+        lsp_types::request::SignatureHelpRequest::METHOD => {
+            signature_help::on_signature_help_request(
+                context,
+                request,
+                ide_files_root.clone(),
+                pkg_dependencies,
+            );
+        }
+        lsp_types::request::CodeActionRequest::METHOD => {
+            code_actions::on_code_action_request(context, request);
+        }
+        lsp_types::request::Formatting::METHOD => {
+            formatting::on_formatting_request(context, request, ide_files_root.clone());
+        }
         lsp_types::request::Shutdown::METHOD => {
             eprintln!("Shutdown request received");
             let response =
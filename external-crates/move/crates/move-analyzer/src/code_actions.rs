@@ -0,0 +1,423 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements quick-fix code actions for compiler and linter diagnostics. Diagnostics
+//! published by the language server are tagged with their compiler codes, which are used here to
+//! recognize diagnostics that can be fixed automatically:
+//! - unbound modules, types and functions (by adding a `use` statement)
+//! - unused variables (by prefixing them with an underscore)
+//! - unused `mut` modifiers and unused `use` aliases (by removing them)
+//! - lint warnings with an obvious fix (e.g., replacing `while (true)` with `loop`)
+
+use crate::{
+    context::Context,
+    symbols::{DefInfo, ModuleDefs, SymbolicatorRunner, Symbols},
+    utils::{lsp_position_to_offset, lsp_range_text, offset_to_lsp_position_in_source},
+};
+use lsp_server::Request;
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, Diagnostic, NumberOrString,
+    Position, Range, TextEdit, WorkspaceEdit,
+};
+use move_compiler::{
+    diagnostics::codes::{DiagnosticInfo, NameResolution, UnusedItem},
+    expansion::ast::{Address, ModuleIdent_, Visibility},
+    linters::StyleCodes,
+    shared::Identifier,
+};
+use std::{collections::HashMap, path::Path};
+use url::Url;
+
+/// Handles code action request of the language server
+pub fn on_code_action_request(context: &Context, request: &Request) {
+    let parameters = serde_json::from_value::<CodeActionParams>(request.params.clone())
+        .expect("could not deserialize code action request");
+    let fpath = parameters.text_document.uri.to_file_path().unwrap();
+
+    let quick_fix_requested = parameters.context.only.as_ref().map_or(true, |kinds| {
+        kinds
+            .iter()
+            .any(|k| CodeActionKind::QUICKFIX.as_str().starts_with(k.as_str()))
+    });
+    let actions = if quick_fix_requested {
+        let symbols_map = &context.symbols.lock().unwrap();
+        SymbolicatorRunner::root_dir(&fpath)
+            .and_then(|pkg_path| symbols_map.get(&pkg_path))
+            .map(|symbols| code_actions(symbols, &fpath, &parameters.context.diagnostics))
+            .unwrap_or_default()
+    } else {
+        vec![]
+    };
+    eprintln!(
+        "about to send code action response with {} items",
+        actions.len()
+    );
+
+    let result = actions
+        .into_iter()
+        .map(CodeActionOrCommand::CodeAction)
+        .collect::<Vec<_>>();
+    let response = lsp_server::Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send code action response: {:?}", err);
+    }
+}
+
+/// Computes quick-fix code actions for diagnostics reported for a given file.
+pub fn code_actions(symbols: &Symbols, path: &Path, diagnostics: &[Diagnostic]) -> Vec<CodeAction> {
+    let Some(source) = symbols
+        .file_hash(path)
+        .and_then(|fhash| symbols.files.get(&fhash))
+        .map(|(_, source)| source)
+    else {
+        return vec![];
+    };
+    let Ok(uri) = Url::from_file_path(path) else {
+        return vec![];
+    };
+
+    let mut actions = vec![];
+    for diag in diagnostics {
+        let Some(NumberOrString::String(code)) = &diag.code else {
+            continue;
+        };
+        let is = |info: DiagnosticInfo| *code == info.render().0;
+        let fixes = if is(NameResolution::UnboundModule.into())
+            || is(NameResolution::UnboundType.into())
+            || is(NameResolution::UnboundUnscopedName.into())
+            // unbound module alias in an access chain (e.g., `m::foo`)
+            || (is(NameResolution::NamePositionMismatch.into())
+                && diag.message.starts_with("Could not resolve the name"))
+        {
+            add_use_fixes(symbols, path, &source, diag)
+        } else if is(UnusedItem::Variable.into()) {
+            unused_variable_fix(&source, diag).into_iter().collect()
+        } else if is(UnusedItem::MutModifier.into()) {
+            unused_mut_fix(&source, &uri, diag).into_iter().collect()
+        } else if is(UnusedItem::Alias.into()) {
+            unused_alias_fix(&source, diag).into_iter().collect()
+        } else if is(StyleCodes::WhileTrueToLoop.diag_info()) {
+            while_true_fix(&source, diag).into_iter().collect()
+        } else if is(StyleCodes::UnneededReturn.diag_info()) {
+            unneeded_return_fix(&source, diag).into_iter().collect()
+        } else {
+            vec![]
+        };
+        let preferred = fixes.len() == 1;
+        actions.extend(fixes.into_iter().map(|(title, edits)| CodeAction {
+            title,
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(vec![diag.clone()]),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(uri.clone(), edits)])),
+                document_changes: None,
+                change_annotations: None,
+            }),
+            command: None,
+            is_preferred: Some(preferred),
+            disabled: None,
+            data: None,
+        }));
+    }
+    actions
+}
+
+/// A fix described by its title and the edits to the file the diagnostic was reported for.
+type Fix = (String, Vec<TextEdit>);
+
+/// Suggests `use` statements importing modules or module members with the unbound name.
+fn add_use_fixes(symbols: &Symbols, path: &Path, source: &str, diag: &Diagnostic) -> Vec<Fix> {
+    let Some(name) = lsp_range_text(source, &diag.range).filter(|n| is_identifier(n)) else {
+        return vec![];
+    };
+    let all_mods = symbols.file_mods.values().flatten();
+    let mut paths = vec![];
+    if name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        // module alias
+        paths.extend(
+            all_mods
+                .clone()
+                .filter(|mod_defs| mod_defs.ident().module.value().as_str() == name)
+                .map(|mod_defs| mod_ident_path(mod_defs.ident())),
+        );
+    }
+    for mod_defs in all_mods {
+        let is_datatype = mod_defs.structs().contains_key(&name.into())
+            || mod_defs.enums.contains_key(&name.into());
+        let is_visible_fun = mod_defs.functions().get(&name.into()).is_some_and(|f| {
+            !matches!(
+                symbols.def_info(&f.name_loc),
+                Some(DefInfo::Function(_, Visibility::Internal, ..)) | None
+            )
+        });
+        if is_datatype || is_visible_fun {
+            paths.push(format!("{}::{name}", mod_ident_path(mod_defs.ident())));
+        }
+    }
+    paths.sort();
+    paths.dedup();
+
+    let Some((insert_pos, indent)) = use_insert_position(symbols, path, source, &diag.range.start)
+    else {
+        return vec![];
+    };
+    paths
+        .into_iter()
+        .map(|p| {
+            let edit = TextEdit {
+                range: Range::new(insert_pos, insert_pos),
+                new_text: format!("{indent}use {p};\n"),
+            };
+            (format!("Import '{p}'"), vec![edit])
+        })
+        .collect()
+}
+
+/// Returns the path of a module as it would appear in a `use` statement.
+fn mod_ident_path(mod_ident: &ModuleIdent_) -> String {
+    match mod_ident.address {
+        Address::Numerical {
+            name: Some(name), ..
+        } => format!("{name}::{}", mod_ident.module),
+        Address::Numerical { value, .. } => format!("{value}::{}", mod_ident.module),
+        Address::NamedUnassigned(name) => format!("{name}::{}", mod_ident.module),
+    }
+}
+
+/// Finds where a new `use` statement should be inserted for a diagnostic reported at a given
+/// position: after the last `use` statement of the enclosing module or, if there are none, right
+/// after the module declaration. Returns the position and the indentation of the statement.
+fn use_insert_position(
+    symbols: &Symbols,
+    path: &Path,
+    source: &str,
+    diag_pos: &Position,
+) -> Option<(Position, String)> {
+    let mod_line = symbols
+        .file_mods
+        .get(path)?
+        .iter()
+        .filter_map(|mod_defs: &ModuleDefs| {
+            let line = symbols
+                .files
+                .start_position_opt(&mod_defs.name_loc)?
+                .line_offset() as u32;
+            (line <= diag_pos.line).then_some(line)
+        })
+        .max()?;
+    let lines = source.lines().collect::<Vec<_>>();
+    let indent_of = |line: &str| line[..line.len() - line.trim_start().len()].to_string();
+
+    let last_use = (mod_line + 1..diag_pos.line)
+        .filter(|l| {
+            lines
+                .get(*l as usize)
+                .is_some_and(|line| line.trim_start().starts_with("use "))
+        })
+        .last();
+    if let Some(use_line) = last_use {
+        // skip over the rest of a multi-line `use` statement
+        let end_line = (use_line..diag_pos.line)
+            .find(|l| {
+                lines
+                    .get(*l as usize)
+                    .is_some_and(|line| line.trim_end().ends_with(';'))
+            })
+            .unwrap_or(use_line);
+        return Some((
+            Position::new(end_line + 1, 0),
+            indent_of(lines[use_line as usize]),
+        ));
+    }
+    let mod_decl = lines.get(mod_line as usize)?;
+    let indent = if mod_decl.trim_end().ends_with('{') {
+        format!("{}    ", indent_of(mod_decl))
+    } else {
+        indent_of(mod_decl)
+    };
+    Some((Position::new(mod_line + 1, 0), indent))
+}
+
+/// Prefixes an unused variable with an underscore. In a punned field binding (e.g., `S { f }`),
+/// the field name is preserved (e.g., `S { f: _f }`).
+fn unused_variable_fix(source: &str, diag: &Diagnostic) -> Option<Fix> {
+    let name = lsp_range_text(source, &diag.range).filter(|n| is_identifier(n))?;
+    let start = lsp_position_to_offset(source, &diag.range.start)?;
+    let end = lsp_position_to_offset(source, &diag.range.end)?;
+    let prev = source[..start].trim_end().chars().last();
+    let next = source[end..].trim_start().chars().next();
+    let punned = matches!(prev, Some('{' | ',')) && matches!(next, Some('}' | ','));
+    let punned = punned && innermost_open_bracket(&source[..start]) == Some('{');
+    let edit = if punned {
+        TextEdit {
+            range: diag.range,
+            new_text: format!("{name}: _{name}"),
+        }
+    } else {
+        TextEdit {
+            range: Range::new(diag.range.start, diag.range.start),
+            new_text: "_".to_string(),
+        }
+    };
+    Some((format!("Prefix '{name}' with an underscore"), vec![edit]))
+}
+
+/// Removes an unused `mut` modifier, whose location is attached to the diagnostic as related
+/// information.
+fn unused_mut_fix(source: &str, uri: &Url, diag: &Diagnostic) -> Option<Fix> {
+    let mut_range = diag
+        .related_information
+        .iter()
+        .flatten()
+        .map(|info| &info.location)
+        .find(|loc| loc.uri == *uri && lsp_range_text(source, &loc.range) == Some("mut"))?
+        .range;
+    let end = lsp_position_to_offset(source, &mut_range.end)?;
+    let whitespace = source[end..].len() - source[end..].trim_start().len();
+    let edit = TextEdit {
+        range: Range::new(
+            mut_range.start,
+            offset_to_lsp_position_in_source(source, end + whitespace)?,
+        ),
+        new_text: "".to_string(),
+    };
+    Some(("Remove unnecessary 'mut'".to_string(), vec![edit]))
+}
+
+/// Removes an unused alias from a `use` statement, or the whole statement if the alias is the only
+/// one it introduces.
+fn unused_alias_fix(source: &str, diag: &Diagnostic) -> Option<Fix> {
+    let alias = lsp_range_text(source, &diag.range).filter(|n| is_identifier(n))?;
+    let start = lsp_position_to_offset(source, &diag.range.start)?;
+    let end = lsp_position_to_offset(source, &diag.range.end)?;
+    let title = format!("Remove unused alias '{alias}'");
+
+    let stmt_start = source[..start].rfind("use ")?;
+    let stmt_end = start + source[start..].find(';')? + 1;
+    let stmt = &source[stmt_start..stmt_end];
+    if stmt.starts_with("use fun ") {
+        return None;
+    }
+    let remove = |from: usize, to: usize| -> Option<Fix> {
+        let edit = TextEdit {
+            range: Range::new(
+                offset_to_lsp_position_in_source(source, from)?,
+                offset_to_lsp_position_in_source(source, to)?,
+            ),
+            new_text: "".to_string(),
+        };
+        Some((title.clone(), vec![edit]))
+    };
+
+    let Some(list_start) = stmt.find('{').map(|i| stmt_start + i) else {
+        // the statement introduces a single alias - remove the whole line
+        let line_start = source[..stmt_start].rfind('\n').map_or(0, |i| i + 1);
+        if !source[line_start..stmt_start].trim().is_empty() {
+            return None;
+        }
+        let line_end = source[stmt_end..]
+            .find('\n')
+            .map_or(source.len(), |i| stmt_end + i + 1);
+        if !source[stmt_end..line_end].trim().is_empty() {
+            return None;
+        }
+        return remove(line_start, line_end);
+    };
+    if start < list_start || stmt[list_start - stmt_start + 1..].contains('{') {
+        // nested lists are not supported
+        return None;
+    }
+    // the alias is a member of a list (possibly renamed, e.g. `foo as bar`)
+    let item_start = list_start
+        + 1
+        + source[list_start + 1..start]
+            .rfind(',')
+            .map_or(0, |i| i + 1);
+    let item_start =
+        item_start + (source[item_start..].len() - source[item_start..].trim_start().len());
+    let after = source[end..].trim_start();
+    let after_start = end + (source[end..].len() - after.len());
+    if after.starts_with(',') {
+        // remove the item together with the following comma
+        let next = &source[after_start + 1..];
+        let next_start = after_start + 1 + (next.len() - next.trim_start().len());
+        if next.trim_start().starts_with('}') {
+            // trailing comma
+            if source[list_start + 1..item_start].trim().is_empty() {
+                return None;
+            }
+            return remove(item_start, after_start + 1);
+        }
+        remove(item_start, next_start)
+    } else {
+        // last item in the list - remove it together with the preceding comma
+        let before = source[list_start + 1..item_start].trim_end();
+        if before.is_empty() {
+            // the only item in the list
+            return None;
+        }
+        debug_assert!(before.ends_with(','));
+        remove(list_start + 1 + before.len() - 1, end)
+    }
+}
+
+/// Replaces `while (true)` with `loop`.
+fn while_true_fix(source: &str, diag: &Diagnostic) -> Option<Fix> {
+    let text = lsp_range_text(source, &diag.range)?;
+    let rest = text.strip_prefix("while")?.trim_start();
+    let rest = rest.strip_prefix('(')?.trim_start();
+    let rest = rest.strip_prefix("true")?.trim_start();
+    let rest = rest.strip_prefix(')')?;
+    let start = lsp_position_to_offset(source, &diag.range.start)?;
+    let cond_end = start + text.len() - rest.len();
+    let edit = TextEdit {
+        range: Range::new(
+            diag.range.start,
+            offset_to_lsp_position_in_source(source, cond_end)?,
+        ),
+        new_text: "loop".to_string(),
+    };
+    Some(("Replace 'while (true)' with 'loop'".to_string(), vec![edit]))
+}
+
+/// Removes an unneeded `return` keyword.
+fn unneeded_return_fix(source: &str, diag: &Diagnostic) -> Option<Fix> {
+    let text = lsp_range_text(source, &diag.range)?;
+    let rest = text.strip_prefix("return")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let start = lsp_position_to_offset(source, &diag.range.start)?;
+    let keyword_end = start + text.len() - rest.trim_start().len();
+    let edit = TextEdit {
+        range: Range::new(
+            diag.range.start,
+            offset_to_lsp_position_in_source(source, keyword_end)?,
+        ),
+        new_text: "".to_string(),
+    };
+    Some(("Remove unnecessary 'return'".to_string(), vec![edit]))
+}
+
+fn is_identifier(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Returns the innermost bracket that is not closed at the end of the text.
+fn innermost_open_bracket(text: &str) -> Option<char> {
+    let mut depth = 0;
+    for c in text.chars().rev() {
+        match c {
+            ')' | '}' | ']' => depth += 1,
+            '(' | '{' | '[' if depth == 0 => return Some(c),
+            '(' | '{' | '[' => depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
@@ -3,26 +3,28 @@
 
 use crate::utils::{loc_end_to_lsp_position_opt, loc_start_to_lsp_position_opt};
 use codespan_reporting::diagnostic::Severity;
-use lsp_types::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range};
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString, Range,
+};
 use move_command_line_common::files::FileHash;
-use move_compiler::shared::files::MappedFiles;
-use move_ir_types::location::Loc;
+use move_compiler::{diagnostics::Diagnostics as CompilerDiagnostics, shared::files::MappedFiles};
 use std::{collections::BTreeMap, path::PathBuf};
 use url::Url;
 
-/// Converts diagnostics from the codespan format to the format understood by the language server.
+/// Converts compiler diagnostics to the format understood by the language server. Each diagnostic
+/// is tagged with its compiler code (e.g., `W09002`) so that code actions can recognize it.
 pub fn lsp_diagnostics(
-    diagnostics: &Vec<(
-        codespan_reporting::diagnostic::Severity,
-        &'static str,
-        (Loc, String),
-        Vec<(Loc, String)>,
-        Vec<String>,
-    )>,
+    diagnostics: CompilerDiagnostics,
     files: &MappedFiles,
 ) -> BTreeMap<PathBuf, Vec<Diagnostic>> {
+    let diagnostics = diagnostics.into_vec();
+    let codes = diagnostics
+        .iter()
+        .map(|d| d.info().clone().render().0)
+        .collect::<Vec<_>>();
+    let codespan_diagnostics = CompilerDiagnostics::from(diagnostics).into_codespan_format();
     let mut lsp_diagnostics = BTreeMap::new();
-    for (s, _, (loc, msg), labels, notes) in diagnostics {
+    for ((s, _, (loc, msg), labels, notes), code) in codespan_diagnostics.iter().zip(codes) {
         let fpath = files.file_path(&loc.file_hash());
         if let Some(start) = loc_start_to_lsp_position_opt(files, loc) {
             if let Some(end) = loc_end_to_lsp_position_opt(files, loc) {
@@ -65,7 +67,7 @@ pub fn lsp_diagnostics(
                     .push(Diagnostic::new(
                         range,
                         Some(severity(*s)),
-                        None,
+                        Some(NumberOrString::String(code)),
                         None,
                         msg.to_string(),
                         related_info_opt,
@@ -0,0 +1,91 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements document formatting, using the same formatter as `move fmt`. The
+//! formatter's configuration is read from the `move-fmt.toml` file at the root of the package that
+//! the document belongs to, if there is one. Documents with syntax errors are not formatted.
+
+use crate::{
+    context::Context, symbols::SymbolicatorRunner, utils::offset_to_lsp_position_in_source,
+};
+use lsp_server::Request;
+use lsp_types::{DocumentFormattingParams, Position, Range, TextEdit};
+use move_compiler::editions::Edition;
+use move_formatter::{format_string, FormatterConfig};
+use move_package::source_package::manifest_parser::parse_move_manifest_from_file;
+use std::path::Path;
+use vfs::VfsPath;
+
+/// Handles formatting request of the language server
+pub fn on_formatting_request(context: &Context, request: &Request, ide_files_root: VfsPath) {
+    let parameters = serde_json::from_value::<DocumentFormattingParams>(request.params.clone())
+        .expect("could not deserialize formatting request");
+    let fpath = parameters.text_document.uri.to_file_path().unwrap();
+
+    // prefer the content of the file as it is open in the editor
+    let source = ide_files_root
+        .join(fpath.to_string_lossy())
+        .and_then(|vfs_path| vfs_path.read_to_string())
+        .ok()
+        .or_else(|| std::fs::read_to_string(&fpath).ok());
+
+    let edits = source
+        .and_then(|source| format_edit(&fpath, &source))
+        .into_iter()
+        .collect::<Vec<_>>();
+    eprintln!(
+        "about to send formatting response with {} edits",
+        edits.len()
+    );
+
+    let response = lsp_server::Response::new_ok(request.id.clone(), edits);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send formatting response: {:?}", err);
+    }
+}
+
+/// Formats the source of the file at `path`, returning an edit that replaces the whole file with
+/// its formatted source, or `None` if it is already formatted or could not be formatted.
+pub fn format_edit(path: &Path, source: &str) -> Option<TextEdit> {
+    let (edition, config) = match SymbolicatorRunner::root_dir(path) {
+        Some(pkg_path) => {
+            let edition = parse_move_manifest_from_file(&pkg_path)
+                .ok()
+                .and_then(|manifest| manifest.package.edition)
+                .unwrap_or_default();
+            let config = FormatterConfig::for_package(&pkg_path).unwrap_or_else(|e| {
+                eprintln!("could not read formatter config: {:?}", e);
+                FormatterConfig::default()
+            });
+            (edition, config)
+        }
+        None => (Edition::default(), FormatterConfig::default()),
+    };
+
+    let formatted = match format_string(source, edition, &config) {
+        Ok(formatted) => formatted,
+        Err(e) => {
+            eprintln!("could not format {}: {}", path.display(), e);
+            return None;
+        }
+    };
+
+    if formatted == source {
+        return None;
+    }
+
+    Some(TextEdit {
+        range: Range {
+            start: Position {
+                line: 0,
+                character: 0,
+            },
+            end: offset_to_lsp_position_in_source(source, source.len())?,
+        },
+        new_text: formatted,
+    })
+}
@@ -7,11 +7,15 @@ extern crate move_ir_types;
 
 pub mod analysis;
 pub mod analyzer;
+pub mod code_actions;
 pub mod compiler_info;
 pub mod completions;
 pub mod context;
 pub mod diagnostics;
+pub mod formatting;
 pub mod inlay_hints;
+pub mod rename;
+pub mod signature_help;
 pub mod symbols;
 pub mod utils;
 pub mod vfs;
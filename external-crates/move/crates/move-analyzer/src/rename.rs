@@ -0,0 +1,321 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements renaming of identifiers across a package. Renaming is based on the
+//! references information computed during symbolication: all uses of a definition (including the
+//! definition itself) are replaced with the new name, with the exception of uses that go through
+//! an alias (e.g., `use a::m::foo as bar`) whose source text differs from the definition's name
+//! and which remain valid after the rename.
+//!
+//! Field punning needs special care as a single identifier can represent both a field and a local
+//! variable (e.g., `let S { f } = s;`) - in this case the punned form is expanded so that only one
+//! of them is renamed (e.g., `let S { f: new_name } = s;` when renaming the local variable).
+
+use crate::{
+    context::Context,
+    symbols::{DefInfo, SymbolicatorRunner, Symbols, UseLoc},
+    utils::lsp_range_text,
+};
+use lsp_server::{Request, RequestId};
+use lsp_types::{
+    Position, PrepareRenameResponse, Range, RenameParams, TextDocumentPositionParams, TextEdit,
+    WorkspaceEdit,
+};
+use move_compiler::{
+    expansion::name_validation::is_valid_datatype_or_constant_name, parser::keywords::KEYWORDS,
+};
+use move_ir_types::location::Loc;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// Kinds of definitions that can be renamed (the kind determines naming rules for the new name,
+/// and how punned fields are handled).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenameKind {
+    Function,
+    Datatype,
+    Variant,
+    Field,
+    Local,
+    Const,
+    TypeParam,
+}
+
+/// Handles prepare rename request of the language server
+pub fn on_prepare_rename_request(context: &Context, request: &Request) {
+    let parameters = serde_json::from_value::<TextDocumentPositionParams>(request.params.clone())
+        .expect("could not deserialize prepare rename request");
+    let fpath = parameters.text_document.uri.to_file_path().unwrap();
+    let pos = parameters.position;
+
+    let symbols_map = &context.symbols.lock().unwrap();
+    let result = SymbolicatorRunner::root_dir(&fpath)
+        .and_then(|pkg_path| symbols_map.get(&pkg_path))
+        .ok_or_else(|| "No symbols available for this file".to_string())
+        .and_then(|symbols| prepare_rename(symbols, &fpath, pos.line, pos.character));
+    let response = match result {
+        Ok((range, placeholder)) => lsp_server::Response::new_ok(
+            request.id.clone(),
+            PrepareRenameResponse::RangeWithPlaceholder { range, placeholder },
+        ),
+        Err(msg) => rename_error_response(request.id.clone(), msg),
+    };
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send prepare rename response: {:?}", err);
+    }
+}
+
+/// Handles rename request of the language server
+pub fn on_rename_request(context: &Context, request: &Request) {
+    let parameters = serde_json::from_value::<RenameParams>(request.params.clone())
+        .expect("could not deserialize rename request");
+    let fpath = parameters
+        .text_document_position
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let pos = parameters.text_document_position.position;
+
+    let symbols_map = &context.symbols.lock().unwrap();
+    let result = SymbolicatorRunner::root_dir(&fpath)
+        .and_then(|pkg_path| symbols_map.get(&pkg_path))
+        .ok_or_else(|| "No symbols available for this file".to_string())
+        .and_then(|symbols| {
+            rename_edits(
+                symbols,
+                &fpath,
+                pos.line,
+                pos.character,
+                &parameters.new_name,
+            )
+        });
+    let response = match result {
+        Ok(edits) => {
+            let changes = edits
+                .into_iter()
+                .map(|(path, edits)| (Url::from_file_path(path).unwrap(), edits))
+                .collect::<HashMap<_, _>>();
+            lsp_server::Response::new_ok(
+                request.id.clone(),
+                WorkspaceEdit {
+                    changes: Some(changes),
+                    document_changes: None,
+                    change_annotations: None,
+                },
+            )
+        }
+        Err(msg) => rename_error_response(request.id.clone(), msg),
+    };
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send rename response: {:?}", err);
+    }
+}
+
+fn rename_error_response(id: RequestId, msg: String) -> lsp_server::Response {
+    lsp_server::Response::new_err(id, lsp_server::ErrorCode::RequestFailed as i32, msg)
+}
+
+/// Checks if the identifier at a given (0-based) position can be renamed, returning its range and
+/// its current name if so.
+pub fn prepare_rename(
+    symbols: &Symbols,
+    fpath: &Path,
+    line: u32,
+    col: u32,
+) -> Result<(Range, String), String> {
+    let (_, _, old_name, use_range) = rename_target(symbols, fpath, line, col)?;
+    Ok((use_range, old_name))
+}
+
+/// Computes edits (sorted by position in each file) renaming the identifier at a given (0-based)
+/// position, and all other uses of its definition in the package, to `new_name`.
+pub fn rename_edits(
+    symbols: &Symbols,
+    fpath: &Path,
+    line: u32,
+    col: u32,
+    new_name: &str,
+) -> Result<BTreeMap<PathBuf, Vec<TextEdit>>, String> {
+    let (def_loc, kind, old_name, _) = rename_target(symbols, fpath, line, col)?;
+    check_new_name(kind, &old_name, new_name)?;
+
+    let Some(uses) = symbols.references.get(&def_loc) else {
+        return Err(format!("No references found for '{old_name}'"));
+    };
+    let punned = punned_uses(symbols, def_loc, kind, uses);
+
+    let mut file_edits: BTreeMap<_, BTreeMap<_, TextEdit>> = BTreeMap::new();
+    for use_loc in uses {
+        let range = use_loc.range();
+        let Some((_, content)) = symbols.files.get(&use_loc.fhash()) else {
+            continue;
+        };
+        // uses through an alias are not renamed
+        if lsp_range_text(&content, &range) != Some(old_name.as_str()) {
+            continue;
+        }
+        let new_text = if punned.contains(use_loc) {
+            if kind == RenameKind::Field {
+                format!("{new_name}: {old_name}")
+            } else {
+                format!("{old_name}: {new_name}")
+            }
+        } else {
+            new_name.to_string()
+        };
+        let path = symbols.files.file_path(&use_loc.fhash()).clone();
+        file_edits.entry(path).or_default().insert(
+            (range.start.line, range.start.character),
+            TextEdit { range, new_text },
+        );
+    }
+    Ok(file_edits
+        .into_iter()
+        .map(|(path, edits)| (path, edits.into_values().collect()))
+        .collect())
+}
+
+/// Finds the definition to be renamed at a given position, checking that it can be renamed.
+/// Returns definition location, its kind, current name and range of the identifier at the
+/// position.
+fn rename_target(
+    symbols: &Symbols,
+    fpath: &Path,
+    line: u32,
+    col: u32,
+) -> Result<(Loc, RenameKind, String, Range), String> {
+    let Some(use_def) = symbols.use_at(fpath, line, col) else {
+        return Err("No symbol to rename at this position".to_string());
+    };
+    let def_loc = use_def.def_loc();
+    let Some(def_info) = symbols.def_info(&def_loc) else {
+        return Err("No definition found for the symbol at this position".to_string());
+    };
+    let kind = match def_info {
+        DefInfo::Function(..) => RenameKind::Function,
+        DefInfo::Struct(..) | DefInfo::Enum(..) => RenameKind::Datatype,
+        DefInfo::Variant(..) => RenameKind::Variant,
+        DefInfo::Field(..) => RenameKind::Field,
+        DefInfo::Local(..) => RenameKind::Local,
+        DefInfo::Const(..) => RenameKind::Const,
+        DefInfo::Type(_) => RenameKind::TypeParam,
+        DefInfo::Module(..) => return Err("Renaming modules is not supported".to_string()),
+    };
+
+    let def_path = symbols.files.file_path(&def_loc.file_hash());
+    let in_pkg = SymbolicatorRunner::root_dir(fpath).is_some_and(|pkg_path| {
+        // dependencies may be stored in the package's build directory
+        def_path.starts_with(&pkg_path) && !def_path.starts_with(pkg_path.join("build"))
+    });
+    if !in_pkg {
+        return Err("Cannot rename a symbol defined outside of the current package".to_string());
+    }
+
+    let Some(old_name) = symbols
+        .files
+        .source_of_loc_opt(&def_loc)
+        .map(|s| identifier_prefix(s).to_string())
+        .filter(|s| !s.is_empty())
+    else {
+        return Err("No source found for the definition".to_string());
+    };
+
+    let use_range = Range {
+        start: Position::new(line, use_def.col_start()),
+        end: Position::new(line, use_def.col_end()),
+    };
+    let Some((_, use_content)) = symbols
+        .file_hash(fpath)
+        .and_then(|fhash| symbols.files.get(&fhash))
+    else {
+        return Err("No source found for the symbol at this position".to_string());
+    };
+    if lsp_range_text(&use_content, &use_range) != Some(old_name.as_str()) {
+        return Err(format!(
+            "Cannot rename an alias, rename '{old_name}' at its definition instead"
+        ));
+    }
+    Ok((def_loc, kind, old_name, use_range))
+}
+
+/// Returns the leading identifier of a string.
+fn identifier_prefix(s: &str) -> &str {
+    let end = s
+        .char_indices()
+        .find(|(i, c)| !(c.is_alphanumeric() || *c == '_' || (*i == 0 && *c == '$')))
+        .map_or(s.len(), |(i, _)| i);
+    &s[..end]
+}
+
+/// Checks that the new name is a valid identifier for the kind of definition being renamed.
+fn check_new_name(kind: RenameKind, old_name: &str, new_name: &str) -> Result<(), String> {
+    let (is_macro_param, ident) = match new_name.strip_prefix('$') {
+        Some(ident) => (true, ident),
+        None => (false, new_name),
+    };
+    let valid_ident = ident.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && ident.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_ident || KEYWORDS.contains(&ident) {
+        return Err(format!("'{new_name}' is not a valid identifier"));
+    }
+    if is_macro_param != old_name.starts_with('$') {
+        return Err(if is_macro_param {
+            format!("Only macro parameters can be named '{new_name}'")
+        } else {
+            format!("Macro parameter names must start with '$', found '{new_name}'")
+        });
+    }
+    match kind {
+        RenameKind::Datatype | RenameKind::Variant | RenameKind::Const
+            if !is_valid_datatype_or_constant_name(new_name) =>
+        {
+            Err(format!(
+                "'{new_name}' must start with an uppercase letter 'A'..'Z'"
+            ))
+        }
+        RenameKind::Local if ident.starts_with(|c: char| c.is_ascii_uppercase()) => Err(format!(
+            "'{new_name}' must start with a lowercase letter 'a'..'z' or '_'"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Returns uses of a field or local definition that are punned, that is, where the same identifier
+/// also represents, respectively, a local or a field.
+fn punned_uses(
+    symbols: &Symbols,
+    def_loc: Loc,
+    kind: RenameKind,
+    uses: &BTreeSet<UseLoc>,
+) -> BTreeSet<UseLoc> {
+    let other_kind = match kind {
+        RenameKind::Field => RenameKind::Local,
+        RenameKind::Local => RenameKind::Field,
+        _ => return BTreeSet::new(),
+    };
+    symbols
+        .references
+        .iter()
+        .filter(|(loc, _)| **loc != def_loc)
+        .filter(|(loc, _)| {
+            matches!(
+                (other_kind, symbols.def_info(loc)),
+                (RenameKind::Local, Some(DefInfo::Local(..)))
+                    | (RenameKind::Field, Some(DefInfo::Field(..)))
+            )
+        })
+        .flat_map(|(_, other_uses)| other_uses.intersection(uses).copied())
+        .collect()
+}
@@ -0,0 +1,320 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements signature help displayed while the user types arguments of a function
+//! (or macro) call. The call enclosing the cursor is found by lexing the source up to the cursor,
+//! and the called function is resolved using symbolication information for the function name, or
+//! by looking up functions of the same name if the name could not be resolved (which is often the
+//! case as the code being edited may not compile).
+
+use crate::{
+    context::Context,
+    symbols::{
+        self, def_info_doc_string, mod_ident_to_ide_string, ret_type_to_ide_str,
+        type_args_to_ide_string, type_to_ide_string, DefInfo, FunType, PrecomputedPkgInfo,
+        SymbolicatorRunner, Symbols,
+    },
+    utils::{lsp_position_to_offset, offset_to_lsp_position_in_source},
+};
+use lsp_server::Request;
+use lsp_types::{
+    Documentation, ParameterInformation, ParameterLabel, Position, SignatureHelp,
+    SignatureHelpParams, SignatureInformation,
+};
+use move_command_line_common::files::FileHash;
+use move_compiler::{
+    editions::Edition,
+    linters::LintLevel,
+    parser::lexer::{Lexer, Tok},
+    shared::Identifier,
+};
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use vfs::VfsPath;
+
+/// A call enclosing the cursor.
+#[derive(Debug)]
+struct CallSite {
+    /// Module name if the function is called via a module access chain (e.g., `m::foo(...)`)
+    module: Option<Symbol>,
+    /// Function name
+    name: Symbol,
+    /// Position of the function name
+    name_pos: Position,
+    /// Is it a method call (e.g., `x.foo(...)`)?
+    dot_call: bool,
+    /// Index of the argument containing the cursor
+    arg_idx: u32,
+}
+
+/// Handles signature help request of the language server
+pub fn on_signature_help_request(
+    context: &Context,
+    request: &Request,
+    ide_files_root: VfsPath,
+    pkg_dependencies: Arc<Mutex<BTreeMap<PathBuf, PrecomputedPkgInfo>>>,
+) {
+    let parameters = serde_json::from_value::<SignatureHelpParams>(request.params.clone())
+        .expect("could not deserialize signature help request");
+    let path = parameters
+        .text_document_position_params
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let pos = parameters.text_document_position_params.position;
+
+    let help = signature_help(context, ide_files_root, pkg_dependencies, &path, pos);
+    eprintln!(
+        "about to send signature help response (signatures found: {})",
+        help.is_some()
+    );
+    let response = lsp_server::Response::new_ok(request.id.clone(), help);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send signature help response: {:?}", err);
+    }
+}
+
+/// Computes signature help for a given position in a file, attempting to re-compute the symbols
+/// first as the signature help is requested while the file is being edited.
+fn signature_help(
+    context: &Context,
+    ide_files_root: VfsPath,
+    pkg_dependencies: Arc<Mutex<BTreeMap<PathBuf, PrecomputedPkgInfo>>>,
+    path: &Path,
+    pos: Position,
+) -> Option<SignatureHelp> {
+    let pkg_path = SymbolicatorRunner::root_dir(path)?;
+    if let Ok((Some(new_symbols), _)) = symbols::get_symbols(
+        pkg_dependencies,
+        ide_files_root,
+        &pkg_path,
+        Some(vec![path.to_path_buf()]),
+        LintLevel::None,
+        None,
+    ) {
+        return signature_help_with_symbols(&new_symbols, path, pos);
+    }
+    let symbols_map = context.symbols.lock().unwrap();
+    let current_symbols = symbols_map.get(&pkg_path)?;
+    signature_help_with_symbols(current_symbols, path, pos)
+}
+
+/// Computes signature help for a given position in a file using the symbols provided as argument.
+pub fn signature_help_with_symbols(
+    symbols: &Symbols,
+    path: &Path,
+    pos: Position,
+) -> Option<SignatureHelp> {
+    let fhash = symbols.file_hash(path)?;
+    let (_, source) = symbols.files.get(&fhash)?;
+    let call = enclosing_call(&source, &pos)?;
+
+    let mut def_locs = BTreeSet::new();
+    if let Some(u) = symbols.use_at(path, call.name_pos.line, call.name_pos.character) {
+        if matches!(symbols.def_info(&u.def_loc()), Some(DefInfo::Function(..))) {
+            def_locs.insert(u.def_loc());
+        }
+    }
+    if def_locs.is_empty() {
+        def_locs = functions_by_name(symbols, path, &call);
+    }
+
+    let signatures = def_locs
+        .iter()
+        .filter_map(|def_loc| {
+            let def_info = symbols.def_info(def_loc)?;
+            signature_information(def_info, call.dot_call, call.arg_idx)
+        })
+        .collect::<Vec<_>>();
+    if signatures.is_empty() {
+        return None;
+    }
+    let active_parameter = signatures[0].active_parameter;
+    Some(SignatureHelp {
+        signatures,
+        active_signature: Some(0),
+        active_parameter,
+    })
+}
+
+/// Finds the innermost call whose argument list encloses the cursor position by lexing the source
+/// text preceding the cursor.
+fn enclosing_call(source: &str, pos: &Position) -> Option<CallSite> {
+    let cursor_offset = lsp_position_to_offset(source, pos)?;
+    let buffer = &source[..cursor_offset];
+    let mut lexer = Lexer::new(buffer, FileHash::new(buffer), Edition::E2024);
+    let mut tokens = vec![];
+    lexer.advance().ok()?;
+    while lexer.peek() != Tok::EOF {
+        tokens.push((lexer.peek(), lexer.start_loc(), lexer.content()));
+        lexer.advance().ok()?;
+    }
+
+    // scan backwards looking for an unmatched opening parenthesis, counting commas separating
+    // arguments on the way (but not commas separating lambda parameters)
+    let mut depth = 0;
+    let mut commas = 0;
+    let mut lambda_params_commas = None;
+    let mut lparen_idx = None;
+    for (idx, (tok, _, _)) in tokens.iter().enumerate().rev() {
+        match tok {
+            Tok::RParen | Tok::RBrace | Tok::RBracket => depth += 1,
+            Tok::LParen if depth == 0 => {
+                lparen_idx = Some(idx);
+                break;
+            }
+            // the cursor is in a block or vector rather than directly in the argument list
+            Tok::LBrace | Tok::LBracket if depth == 0 => return None,
+            Tok::LParen | Tok::LBrace | Tok::LBracket => depth -= 1,
+            Tok::Semicolon if depth == 0 => return None,
+            Tok::Comma if depth == 0 => {
+                commas += 1;
+                if let Some(c) = lambda_params_commas.as_mut() {
+                    *c += 1;
+                }
+            }
+            Tok::Pipe if depth == 0 => {
+                let opens_lambda = idx > 0 && matches!(tokens[idx - 1].0, Tok::LParen | Tok::Comma);
+                match lambda_params_commas {
+                    Some(c) if opens_lambda => {
+                        commas -= c;
+                        lambda_params_commas = None;
+                    }
+                    None if !opens_lambda => lambda_params_commas = Some(0),
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // find the name of the called function, skipping type arguments and `!` of macro calls
+    let mut idx = lparen_idx?.checked_sub(1)?;
+    if tokens[idx].0 == Tok::Exclaim {
+        idx = idx.checked_sub(1)?;
+    }
+    if matches!(tokens[idx].0, Tok::Greater | Tok::GreaterGreater) {
+        let mut depth = 0;
+        loop {
+            match tokens[idx].0 {
+                Tok::Greater => depth += 1,
+                Tok::GreaterGreater => depth += 2,
+                Tok::Less => depth -= 1,
+                _ => (),
+            }
+            if depth == 0 {
+                break;
+            }
+            idx = idx.checked_sub(1)?;
+        }
+        idx = idx.checked_sub(1)?;
+    }
+    let (Tok::Identifier, name_offset, name) = tokens[idx] else {
+        return None;
+    };
+    let module = match idx.checked_sub(2).map(|i| (tokens[i], tokens[i + 1].0)) {
+        Some(((Tok::Identifier, _, module), Tok::ColonColon)) => Some(Symbol::from(module)),
+        _ => None,
+    };
+    let dot_call = idx > 0 && tokens[idx - 1].0 == Tok::Period;
+
+    Some(CallSite {
+        module,
+        name: Symbol::from(name),
+        name_pos: offset_to_lsp_position_in_source(source, name_offset)?,
+        dot_call,
+        arg_idx: commas,
+    })
+}
+
+/// Finds definitions of functions that may be called at a given call site based on the name of
+/// the function (and of its module, if available). Unqualified calls are resolved to functions in
+/// the current file.
+fn functions_by_name(symbols: &Symbols, path: &Path, call: &CallSite) -> BTreeSet<Loc> {
+    let mods: Box<dyn Iterator<Item = _>> = if call.module.is_none() && !call.dot_call {
+        Box::new(symbols.file_mods.get(path).into_iter().flatten())
+    } else {
+        Box::new(symbols.file_mods.values().flatten())
+    };
+    mods.filter(|mod_defs| {
+        call.module
+            .map_or(true, |module| mod_defs.ident().module.value() == module)
+    })
+    .filter_map(|mod_defs| mod_defs.functions().get(&call.name))
+    .map(|fun_def| fun_def.name_loc)
+    .collect()
+}
+
+/// Builds signature information for a function definition, with the parameter corresponding to
+/// the argument at `arg_idx` being the active one.
+fn signature_information(
+    def_info: &DefInfo,
+    dot_call: bool,
+    arg_idx: u32,
+) -> Option<SignatureInformation> {
+    let DefInfo::Function(
+        mod_ident,
+        _,
+        fun_type,
+        name,
+        type_args,
+        arg_names,
+        arg_types,
+        ret_type,
+        _,
+    ) = def_info
+    else {
+        return None;
+    };
+    let macro_prefix = if matches!(fun_type, FunType::Macro) {
+        "macro "
+    } else {
+        ""
+    };
+    let mut label = format!(
+        "{}fun {}{}{}(",
+        macro_prefix,
+        mod_ident_to_ide_string(mod_ident, None, true),
+        name,
+        type_args_to_ide_string(type_args, /* separate_lines */ false, /* verbose */ false),
+    );
+    let mut parameters = vec![];
+    for (idx, (arg_name, arg_type)) in arg_names.iter().zip(arg_types).enumerate() {
+        if idx > 0 {
+            label.push_str(", ");
+        }
+        // parameter label offsets are expressed in UTF-16 code units
+        let start = label.encode_utf16().count() as u32;
+        label.push_str(&format!(
+            "{}: {}",
+            arg_name.value,
+            type_to_ide_string(arg_type, /* verbose */ false)
+        ));
+        let end = label.encode_utf16().count() as u32;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        });
+    }
+    label.push(')');
+    label.push_str(&ret_type_to_ide_str(ret_type, /* verbose */ false));
+
+    // the receiver of a method call is the first parameter
+    let active_parameter = if dot_call { arg_idx + 1 } else { arg_idx };
+    Some(SignatureInformation {
+        label,
+        documentation: def_info_doc_string(def_info).map(Documentation::String),
+        active_parameter: (!parameters.is_empty()).then_some(active_parameter),
+        parameters: Some(parameters),
+    })
+}
@@ -97,7 +97,9 @@ use move_compiler::{
         name_validation::{IMPLICIT_STD_MEMBERS, IMPLICIT_STD_MODULES},
     },
     linters::LintLevel,
-    naming::ast::{DatatypeTypeParameter, StructFields, Type, TypeName_, Type_, VariantFields},
+    naming::ast::{
+        DatatypeTypeParameter, Neighbor_, StructFields, Type, TypeName_, Type_, VariantFields,
+    },
     parser::ast::{self as P, DocComment},
     shared::{
         files::MappedFiles, unique_map::UniqueMap, Identifier, Name, NamedAddressMap,
@@ -1529,6 +1531,31 @@ impl SymbolicatorRunner {
     }
 }
 
+impl UseLoc {
+    pub fn fhash(&self) -> FileHash {
+        self.fhash
+    }
+
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    pub fn col_end(&self) -> u32 {
+        self.col_end
+    }
+
+    /// Range of the use identifier
+    pub fn range(&self) -> Range {
+        Range {
+            start: self.start,
+            end: Position {
+                line: self.start.line,
+                character: self.col_end,
+            },
+        }
+    }
+}
+
 impl UseDef {
     pub fn new(
         references: &mut References,
@@ -1752,6 +1779,14 @@ impl Symbols {
         file_symbols.get(use_line).unwrap_or_else(BTreeSet::new)
     }
 
+    /// Returns the use-def for the identifier containing a given (0-based) position in a file
+    pub fn use_at(&self, use_fpath: &Path, use_line: u32, use_col: u32) -> Option<UseDef> {
+        self.line_uses(use_fpath, use_line)
+            .into_iter()
+            .filter(|u| use_col >= u.col_start && use_col <= u.col_end)
+            .last()
+    }
+
     pub fn def_info(&self, def_loc: &Loc) -> Option<&DefInfo> {
         self.def_info.get(def_loc)
     }
@@ -1810,6 +1845,67 @@ fn is_typed_mod_modified(
     })
 }
 
+/// For each module in the (cached) typed program, computes the file the module is defined in
+/// and the modules it depends on. A module declaring another module as its friend is treated
+/// as a dependency of the friend, as the friend can call its `public(friend)` functions.
+fn module_files_and_deps(
+    typed_modules: &UniqueMap<ModuleIdent, ModuleDefinition>,
+    file_hashes: &BTreeMap<PathBuf, FileHash>,
+) -> BTreeMap<ModuleIdent, (PathBuf, BTreeSet<ModuleIdent>)> {
+    let file_paths = file_hashes
+        .iter()
+        .map(|(fpath, fhash)| (*fhash, fpath))
+        .collect::<BTreeMap<_, _>>();
+    let mut modules: BTreeMap<ModuleIdent, (PathBuf, BTreeSet<ModuleIdent>)> = BTreeMap::new();
+    for (mident, mdef) in typed_modules.key_cloned_iter() {
+        let Some(fpath) = file_paths.get(&mdef.loc.file_hash()) else {
+            continue;
+        };
+        modules.entry(mident).or_default().0 = (*fpath).clone();
+        for (neighbor, kind) in mdef.immediate_neighbors.key_cloned_iter() {
+            match kind.value {
+                Neighbor_::Dependency => {
+                    modules.entry(mident).or_default().1.insert(neighbor);
+                }
+                Neighbor_::Friend => {
+                    modules.entry(neighbor).or_default().1.insert(mident);
+                }
+            }
+        }
+    }
+    // drop modules whose files are not known (e.g. friends declared but not defined)
+    modules.retain(|_, (fpath, _)| !fpath.as_os_str().is_empty());
+    modules
+}
+
+/// Extends the set of files to compile with files containing modules that (transitively)
+/// depend on modules from the files that are already in the set.
+fn add_dependent_files<K: Ord + Clone>(
+    mut files_to_compile: BTreeSet<PathBuf>,
+    modules: &BTreeMap<K, (PathBuf, BTreeSet<K>)>,
+) -> BTreeSet<PathBuf> {
+    let mut to_compile = modules
+        .iter()
+        .filter(|(_, (fpath, _))| files_to_compile.contains(fpath))
+        .map(|(m, _)| m.clone())
+        .collect::<BTreeSet<_>>();
+    loop {
+        let dependents = modules
+            .iter()
+            .filter(|(m, (_, deps))| {
+                !to_compile.contains(*m) && deps.iter().any(|d| to_compile.contains(d))
+            })
+            .map(|(m, _)| m.clone())
+            .collect::<Vec<_>>();
+        if dependents.is_empty() {
+            break;
+        }
+        to_compile.extend(dependents);
+    }
+    files_to_compile.extend(to_compile.iter().map(|m| modules[m].0.clone()));
+    files_to_compile
+}
+
 /// Merges a cached compiled program with newly computed compiled program
 /// In the newly computed program, only modified files are fully compiled
 /// and these files are mereged with the cached compiled program.
@@ -1998,6 +2094,20 @@ pub fn get_compiled_pkg(
         (true, BTreeSet::new())
     };
 
+    // modules that depend on the modified ones may no longer type check (or their types may
+    // have changed) so they have to be re-compiled as well
+    let files_to_compile = match &cached_info_opt {
+        Some(AnalyzedPkgInfo {
+            program: Some(program),
+            file_hashes,
+            ..
+        }) if !full_compilation => add_dependent_files(
+            files_to_compile,
+            &module_files_and_deps(&program.typed_modules, file_hashes),
+        ),
+        _ => files_to_compile,
+    };
+
     let mut edition = None;
     let compiled_libs = cached_info_opt
         .clone()
@@ -2066,8 +2176,7 @@ pub fn get_compiled_pkg(
 
     let mut ide_diagnostics = lsp_empty_diagnostics(mapped_files.file_name_mapping());
     if let Some((compiler_diagnostics, failure)) = diagnostics {
-        let lsp_diagnostics =
-            lsp_diagnostics(&compiler_diagnostics.into_codespan_format(), &mapped_files);
+        let lsp_diagnostics = lsp_diagnostics(compiler_diagnostics, &mapped_files);
         // start with empty diagnostics for all files and replace them with actual diagnostics
         // only for files that have failures/warnings so that diagnostics for all other files
         // (that no longer have failures/warnings) are reset
@@ -3343,14 +3452,8 @@ pub fn on_use_request(
     if let Some(symbols) =
         SymbolicatorRunner::root_dir(use_fpath).and_then(|pkg_path| symbols_map.get(&pkg_path))
     {
-        if let Some(mod_symbols) = symbols.file_use_defs.get(use_fpath) {
-            if let Some(uses) = mod_symbols.get(use_line) {
-                for u in uses {
-                    if use_col >= u.col_start && use_col <= u.col_end {
-                        result = use_def_action(&u, symbols);
-                    }
-                }
-            }
+        if let Some(u) = symbols.use_at(use_fpath, use_line, use_col) {
+            result = use_def_action(&u, symbols);
         }
     }
     eprintln!(
@@ -3558,3 +3661,44 @@ fn enum_variant_symbols(enum_def: &MemberDef, symbols: &Symbols) -> Vec<Document
     }
     variants
 }
+
+#[cfg(test)]
+mod tests {
+    use super::add_dependent_files;
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn dependent_files_are_recompiled() {
+        // c depends on b, b depends on a; d depends on nothing
+        let module = |file: &str, deps: &[&'static str]| {
+            (
+                PathBuf::from(file),
+                deps.iter().copied().collect::<BTreeSet<_>>(),
+            )
+        };
+        let modules = BTreeMap::from([
+            ("a", module("a.move", &[])),
+            ("b", module("b.move", &["a"])),
+            ("c", module("c.move", &["b"])),
+            ("d", module("a.move", &[])),
+            ("e", module("e.move", &["d"])),
+        ]);
+        let files = |names: &[&str]| names.iter().map(PathBuf::from).collect::<BTreeSet<_>>();
+
+        assert_eq!(
+            add_dependent_files(files(&["b.move"]), &modules),
+            files(&["b.move", "c.move"])
+        );
+        assert_eq!(
+            add_dependent_files(files(&["a.move"]), &modules),
+            files(&["a.move", "b.move", "c.move", "e.move"])
+        );
+        assert_eq!(
+            add_dependent_files(files(&["c.move", "x.move"]), &modules),
+            files(&["c.move", "x.move"])
+        );
+    }
+}
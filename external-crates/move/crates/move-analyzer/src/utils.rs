@@ -1,7 +1,7 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use lsp_types::Position;
+use lsp_types::{Position, Range};
 use move_command_line_common::files::FileHash;
 use move_compiler::{
     shared::files::MappedFiles, unit_test::filter_test_members::UNIT_TEST_POISON_FUN_NAME,
@@ -65,3 +65,108 @@ pub fn ignored_function(name: Symbol) -> bool {
     // of this function's info whe hovering close to `module` keyword.
     name == UNIT_TEST_POISON_FUN_NAME
 }
+
+//**************************************************************************************************
+// Source Text
+//**************************************************************************************************
+
+/// Converts a line/character (Position) to a byte offset in the source text. Following the LSP
+/// specification, the character offset in a position is expressed in UTF-16 code units, and a
+/// character offset beyond the end of a line is interpreted as the end of the line.
+pub fn lsp_position_to_offset(source: &str, pos: &Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..pos.line {
+        line_start += source[line_start..].find('\n')? + 1;
+    }
+    let line = source[line_start..].split('\n').next().unwrap_or_default();
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    let mut utf16_offset = 0;
+    for (byte_offset, c) in line.char_indices() {
+        if utf16_offset >= pos.character as usize {
+            return Some(line_start + byte_offset);
+        }
+        utf16_offset += c.len_utf16();
+    }
+    Some(line_start + line.len())
+}
+
+/// Converts a byte offset in the source text to line/character (Position) format, where character
+/// is expressed in UTF-16 code units.
+pub fn offset_to_lsp_position_in_source(source: &str, offset: usize) -> Option<Position> {
+    let prefix = source.get(..offset)?;
+    let line = prefix.matches('\n').count() as u32;
+    let line_start = prefix.rfind('\n').map_or(0, |i| i + 1);
+    let character = prefix[line_start..].encode_utf16().count() as u32;
+    Some(Position { line, character })
+}
+
+/// Returns the source text within a given range.
+pub fn lsp_range_text<'a>(source: &'a str, range: &Range) -> Option<&'a str> {
+    let start = lsp_position_to_offset(source, &range.start)?;
+    let end = lsp_position_to_offset(source, &range.end)?;
+    source.get(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lsp_positions_in_source_utf16() {
+        // 'é' is one UTF-16 code unit (two bytes in UTF-8), '𝄞' is two UTF-16 code units (four
+        // bytes in UTF-8)
+        let source = "fun f() {}\r\n// é𝄞x\nlet y = 0;";
+        let x_offset = source.find('x').unwrap();
+        let x_pos = Position {
+            line: 1,
+            character: 6,
+        };
+
+        assert_eq!(lsp_position_to_offset(source, &x_pos), Some(x_offset));
+        assert_eq!(
+            offset_to_lsp_position_in_source(source, x_offset),
+            Some(x_pos)
+        );
+
+        // The end of the source text, on a line after a non-ASCII one
+        let end_pos = Position {
+            line: 2,
+            character: 10,
+        };
+        assert_eq!(lsp_position_to_offset(source, &end_pos), Some(source.len()));
+        assert_eq!(
+            offset_to_lsp_position_in_source(source, source.len()),
+            Some(end_pos)
+        );
+
+        // Offsets beyond the end of a line are clamped to the end of the line, before its
+        // line terminator
+        let past_end = Position {
+            line: 1,
+            character: 100,
+        };
+        assert_eq!(
+            lsp_position_to_offset(source, &past_end),
+            Some(x_offset + 1)
+        );
+        assert_eq!(
+            lsp_position_to_offset(
+                source,
+                &Position {
+                    line: 0,
+                    character: 100
+                }
+            ),
+            Some("fun f() {}".len())
+        );
+
+        // Lines beyond the end of the source text, and offsets that are not on a character
+        // boundary, are invalid
+        let past_last_line = Position {
+            line: 3,
+            character: 0,
+        };
+        assert_eq!(lsp_position_to_offset(source, &past_last_line), None);
+        assert_eq!(offset_to_lsp_position_in_source(source, x_offset - 1), None);
+    }
+}
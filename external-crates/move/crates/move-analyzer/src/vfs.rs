@@ -10,11 +10,11 @@
 //! basically just a mapping from file identifier (this could be the file's path were it to be
 //! saved) to its textual contents.
 
-use crate::symbols;
+use crate::{symbols, utils::lsp_position_to_offset};
 use lsp_server::Notification;
 use lsp_types::{
     notification::Notification as _, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
-    DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, TextDocumentContentChangeEvent,
 };
 use std::{io::Write, path::PathBuf};
 use vfs::VfsPath;
@@ -48,6 +48,29 @@ impl VirtualFileSystem {
     }
 }
 
+/// Applies changes sent by the client (in the order in which they were sent) to the content of a
+/// file. Each change either replaces a range of the content or, if the range is missing, the whole
+/// content. Returns `None` if any of the ranges is not valid for the content it applies to.
+pub fn apply_content_changes(
+    content: &str,
+    changes: &[TextDocumentContentChangeEvent],
+) -> Option<String> {
+    let mut content = content.to_string();
+    for change in changes {
+        let Some(range) = change.range else {
+            content.clone_from(&change.text);
+            continue;
+        };
+        let start = lsp_position_to_offset(&content, &range.start)?;
+        let end = lsp_position_to_offset(&content, &range.end)?;
+        if start > end {
+            return None;
+        }
+        content.replace_range(start..end, &change.text);
+    }
+    Some(content)
+}
+
 /// Updates the given virtual file system based on the text document sync notification that was sent.
 pub fn on_text_document_sync_notification(
     ide_files_root: VfsPath,
@@ -129,6 +152,21 @@ pub fn on_text_document_sync_notification(
                 );
                 return;
             };
+            // the client sends only the changed parts of the file, which are applied to the
+            // content of the file as stored in the virtual file system
+            let content = ide_files_root
+                .join(file_path.to_string_lossy())
+                .and_then(|vfs_path| vfs_path.read_to_string())
+                .unwrap_or_default();
+            let Some(new_content) = apply_content_changes(&content, &parameters.content_changes)
+            else {
+                eprintln!("Could not apply changes to file at {:?}", file_path);
+                return;
+            };
+            if new_content == content {
+                // nothing to re-compile
+                return;
+            }
             let Some(mut vfs_file) = vfs_file_create(
                 &ide_files_root,
                 file_path.clone(),
@@ -136,11 +174,9 @@ pub fn on_text_document_sync_notification(
             ) else {
                 return;
             };
-            let Some(changes) = parameters.content_changes.last() else {
-                eprintln!("Could not read last opened file change");
-                return;
-            };
-            if vfs_file.write_all(changes.text.as_bytes()).is_ok() {
+            if vfs_file.write_all(new_content.as_bytes()).is_ok() {
+                // only modules from the changed file and modules that depend on them are
+                // re-typed, cached typing information is re-used for the remaining ones
                 symbolicator_runner.run(file_path);
             }
         }
@@ -190,3 +226,76 @@ pub fn on_text_document_sync_notification(
     }
     eprintln!("text document notification handled");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::apply_content_changes;
+    use lsp_types::{Position, Range, TextDocumentContentChangeEvent};
+
+    fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range::new(
+                Position::new(start.0, start.1),
+                Position::new(end.0, end.1),
+            )),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn apply_changes_in_order() {
+        let content = "module a::m {\n    fun foo() {}\n}\n";
+        let changes = vec![
+            change((1, 8), (1, 11), "bar"),
+            change((1, 15), (1, 15), " 42 "),
+            change((2, 1), (2, 1), "\n// end"),
+        ];
+        assert_eq!(
+            apply_content_changes(content, &changes).unwrap(),
+            "module a::m {\n    fun bar() { 42 }\n}\n// end\n"
+        );
+    }
+
+    #[test]
+    fn apply_multi_line_changes() {
+        let content = "line 0\r\nline 1\r\nline 2\r\n";
+        let changes = vec![change((0, 4), (2, 4), ""), change((0, 100), (1, 0), "!")];
+        assert_eq!(apply_content_changes(content, &changes).unwrap(), "line 2!");
+    }
+
+    #[test]
+    fn apply_full_change() {
+        let changes = vec![
+            change((0, 0), (0, 0), "ignored"),
+            TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "new".to_string(),
+            },
+            change((0, 3), (0, 3), " content"),
+        ];
+        assert_eq!(
+            apply_content_changes("old", &changes).unwrap(),
+            "new content"
+        );
+    }
+
+    #[test]
+    fn apply_changes_utf16() {
+        // 'é' is one UTF-16 code unit (two bytes in UTF-8), '𝄞' is two UTF-16 code units (four
+        // bytes in UTF-8)
+        let content = "// é𝄞x\n";
+        let changes = vec![change((0, 6), (0, 7), "y")];
+        assert_eq!(
+            apply_content_changes(content, &changes).unwrap(),
+            "// é𝄞y\n"
+        );
+    }
+
+    #[test]
+    fn apply_invalid_changes() {
+        assert!(apply_content_changes("a\nb", &[change((2, 0), (2, 0), "c")]).is_none());
+        assert!(apply_content_changes("abc", &[change((0, 2), (0, 1), "")]).is_none());
+    }
+}
@@ -0,0 +1,15 @@
+module CodeActionsUnbound::code_actions_unbound {
+    use std::option::some;
+
+    public fun unbound(): u64 {
+        let v = singleton(42);
+        let s: String = ascii::string(b"x");
+        bcs::to_bytes(&some(v)).length() + s.length()
+    }
+}
+
+module CodeActionsUnbound::code_actions_no_uses {
+    public fun unbound(): vector<u8> {
+        bcs::to_bytes(&42)
+    }
+}
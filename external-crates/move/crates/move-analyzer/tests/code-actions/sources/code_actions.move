@@ -0,0 +1,25 @@
+module CodeActions::code_actions {
+    use std::string::utf8;
+    use std::vector::{length, is_empty};
+
+    public struct SomeStruct has drop {
+        some_field: u64,
+    }
+
+    public fun unused(s: SomeStruct) {
+        let x = 42;
+        let SomeStruct { some_field } = s;
+    }
+
+    public fun unused_mut(): u64 {
+        let mut y = 42;
+        y
+    }
+
+    public fun loops(v: &vector<u64>): u64 {
+        while (true) {
+            if (length(v) > 0) break
+        };
+        return length(v)
+    }
+}
@@ -10,10 +10,16 @@ use std::{
 };
 
 use json_comments::StripComments;
-use lsp_types::{InlayHintKind, InlayHintLabel, InlayHintTooltip, Position};
+use lsp_types::{
+    Diagnostic, InlayHintKind, InlayHintLabel, InlayHintTooltip, NumberOrString, ParameterLabel,
+    Position, TextEdit,
+};
 use move_analyzer::{
+    code_actions::code_actions,
     completions::compute_completions_with_symbols,
     inlay_hints::inlay_hints_internal,
+    rename::{prepare_rename, rename_edits},
+    signature_help::signature_help_with_symbols,
     symbols::{
         compute_symbols, compute_symbols_parsed_program, compute_symbols_pre_process,
         def_info_doc_string, get_compiled_pkg, maybe_convert_for_guard, CompiledPkgInfo, Symbols,
@@ -49,6 +55,18 @@ enum TestSuite {
         project: String,
         file_tests: BTreeMap<String, Vec<HintTest>>,
     },
+    Rename {
+        project: String,
+        file_tests: BTreeMap<String, Vec<RenameTest>>,
+    },
+    SignatureHelp {
+        project: String,
+        file_tests: BTreeMap<String, Vec<SignatureHelpTest>>,
+    },
+    CodeAction {
+        project: String,
+        file_tests: BTreeMap<String, Vec<CodeActionTest>>,
+    },
 }
 
 #[derive(Serialize, Deserialize)]
@@ -76,6 +94,25 @@ struct HintTest {
     use_col: u32,
 }
 
+#[derive(Serialize, Deserialize)]
+struct RenameTest {
+    use_line: u32,
+    use_col: u32,
+    new_name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignatureHelpTest {
+    use_line: u32,
+    use_col: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CodeActionTest {
+    use_line: u32,
+    use_col: u32,
+}
+
 //**************************************************************************************************
 // Test Impls
 //**************************************************************************************************
@@ -338,6 +375,171 @@ impl HintTest {
     }
 }
 
+impl RenameTest {
+    fn test(
+        &self,
+        test_idx: usize,
+        symbols: &Symbols,
+        output: &mut dyn std::io::Write,
+        project_path: &Path,
+        use_file_path: &Path,
+    ) -> anyhow::Result<()> {
+        let lsp_line = self.use_line - 1; // 0th-based
+        let lsp_col = self.use_col - 1; // 0th-based
+
+        writeln!(output, "-- test {test_idx} -------------------")?;
+        writeln!(
+            output,
+            "use line: {}, use_col: {}, new name: {}",
+            self.use_line, self.use_col, self.new_name
+        )?;
+        match prepare_rename(symbols, use_file_path, lsp_line, lsp_col) {
+            Ok((range, placeholder)) => writeln!(
+                output,
+                "PREPARE: '{placeholder}' at {}",
+                range_to_string(&range)
+            )?,
+            Err(msg) => {
+                writeln!(output, "PREPARE ERROR: {msg}")?;
+                return Ok(());
+            }
+        }
+        match rename_edits(symbols, use_file_path, lsp_line, lsp_col, &self.new_name) {
+            Ok(file_edits) => {
+                for (path, edits) in file_edits {
+                    let canonical_project_path = dunce::canonicalize(project_path)?;
+                    let rel_path = path.strip_prefix(&canonical_project_path).unwrap_or(&path);
+                    writeln!(output, "FILE: {}", rel_path.display())?;
+                    write_edits(output, &edits)?;
+                }
+            }
+            Err(msg) => writeln!(output, "RENAME ERROR: {msg}")?,
+        }
+        Ok(())
+    }
+}
+
+impl SignatureHelpTest {
+    fn test(
+        &self,
+        test_idx: usize,
+        symbols: &Symbols,
+        output: &mut dyn std::io::Write,
+        use_file_path: &Path,
+    ) -> anyhow::Result<()> {
+        let pos = Position::new(self.use_line - 1, self.use_col - 1); // 0th-based
+
+        writeln!(output, "-- test {test_idx} -------------------")?;
+        writeln!(
+            output,
+            "use line: {}, use_col: {}",
+            self.use_line, self.use_col
+        )?;
+        let Some(help) = signature_help_with_symbols(symbols, use_file_path, pos) else {
+            writeln!(output, "NO SIGNATURE HELP")?;
+            return Ok(());
+        };
+        for sig in help.signatures {
+            writeln!(output, "SIGNATURE: {}", sig.label)?;
+            let active = sig
+                .active_parameter
+                .and_then(|idx| sig.parameters.as_ref()?.get(idx as usize));
+            if let Some(param) = active {
+                let label = match &param.label {
+                    ParameterLabel::Simple(s) => s.clone(),
+                    ParameterLabel::LabelOffsets([start, end]) => {
+                        let utf16 = sig.label.encode_utf16().collect::<Vec<_>>();
+                        String::from_utf16_lossy(&utf16[*start as usize..*end as usize])
+                    }
+                };
+                writeln!(output, "ACTIVE PARAM: {label}")?;
+            } else {
+                writeln!(output, "NO ACTIVE PARAM")?;
+            }
+            if let Some(lsp_types::Documentation::String(doc)) = sig.documentation {
+                writeln!(output, "DOC: {}", doc.trim())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CodeActionTest {
+    fn test(
+        &self,
+        test_idx: usize,
+        symbols: &Symbols,
+        diagnostics: &[Diagnostic],
+        output: &mut dyn std::io::Write,
+        use_file_path: &Path,
+    ) -> anyhow::Result<()> {
+        let pos = Position::new(self.use_line - 1, self.use_col - 1); // 0th-based
+
+        writeln!(output, "-- test {test_idx} -------------------")?;
+        writeln!(
+            output,
+            "use line: {}, use_col: {}",
+            self.use_line, self.use_col
+        )?;
+        let diags = diagnostics
+            .iter()
+            .filter(|d| d.range.start <= pos && pos < d.range.end)
+            .cloned()
+            .collect::<Vec<_>>();
+        if diags.is_empty() {
+            writeln!(output, "NO DIAGNOSTICS")?;
+            return Ok(());
+        }
+        for d in &diags {
+            let code = match &d.code {
+                Some(NumberOrString::String(code)) => code.clone(),
+                Some(NumberOrString::Number(code)) => code.to_string(),
+                None => "NO CODE".to_string(),
+            };
+            writeln!(output, "DIAGNOSTIC {code}: {}", d.message)?;
+        }
+        let actions = code_actions(symbols, use_file_path, &diags);
+        if actions.is_empty() {
+            writeln!(output, "NO CODE ACTIONS")?;
+        }
+        for action in actions {
+            writeln!(output, "ACTION: {}", action.title)?;
+            for edits in action
+                .edit
+                .and_then(|e| e.changes)
+                .into_iter()
+                .flat_map(|c| c.into_values())
+            {
+                write_edits(output, &edits)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn range_to_string(range: &lsp_types::Range) -> String {
+    // 1-based positions, to match test specifications
+    format!(
+        "{}:{}-{}:{}",
+        range.start.line + 1,
+        range.start.character + 1,
+        range.end.line + 1,
+        range.end.character + 1
+    )
+}
+
+fn write_edits(output: &mut dyn std::io::Write, edits: &[TextEdit]) -> anyhow::Result<()> {
+    for edit in edits {
+        writeln!(
+            output,
+            "    {} => {:?}",
+            range_to_string(&edit.range),
+            edit.new_text
+        )?;
+    }
+    Ok(())
+}
+
 //**************************************************************************************************
 // Test Suite Runner Code
 //**************************************************************************************************
@@ -371,6 +573,21 @@ fn initial_symbols(
     project: String,
     files: &BTreeSet<&String>,
 ) -> datatest_stable::Result<(PathBuf, CompiledPkgInfo, Symbols)> {
+    let (project_path, compiled_pkg_info, symbols, _) =
+        initial_symbols_with_diagnostics(project, files, LintLevel::None)?;
+    Ok((project_path, compiled_pkg_info, symbols))
+}
+
+fn initial_symbols_with_diagnostics(
+    project: String,
+    files: &BTreeSet<&String>,
+    lint: LintLevel,
+) -> datatest_stable::Result<(
+    PathBuf,
+    CompiledPkgInfo,
+    Symbols,
+    BTreeMap<PathBuf, Vec<Diagnostic>>,
+)> {
     let base_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let mut project_path = base_path.clone();
     project_path.push(project);
@@ -378,29 +595,29 @@ fn initial_symbols(
     let ide_files_root: VfsPath = MemoryFS::new().into();
     let pkg_deps = Arc::new(Mutex::new(BTreeMap::new()));
 
-    let (mut compiled_pkg_info_opt, _) = get_compiled_pkg(
+    let (mut compiled_pkg_info_opt, mut diagnostics) = get_compiled_pkg(
         pkg_deps.clone(),
         ide_files_root.clone(),
         project_path.as_path(),
         None,
-        LintLevel::None,
+        lint,
     )?;
 
     if let Some(f) = files.first() {
         let mod_file = project_path.join("sources").join(f);
-        (compiled_pkg_info_opt, _) = get_compiled_pkg(
+        (compiled_pkg_info_opt, diagnostics) = get_compiled_pkg(
             pkg_deps.clone(),
             ide_files_root.clone(),
             project_path.as_path(),
             Some(vec![mod_file]),
-            LintLevel::None,
+            lint,
         )?;
     }
 
     let compiled_pkg_info = compiled_pkg_info_opt.ok_or("PACKAGE COMPILATION FAILED")?;
     let symbols = compute_symbols(pkg_deps.clone(), compiled_pkg_info.clone(), None);
 
-    Ok((project_path, compiled_pkg_info, symbols))
+    Ok((project_path, compiled_pkg_info, symbols, diagnostics))
 }
 
 fn use_def_test_suite(
@@ -525,6 +742,99 @@ fn hint_test_suite(
     Ok(result)
 }
 
+fn rename_test_suite(
+    project: String,
+    file_tests: BTreeMap<String, Vec<RenameTest>>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols) = initial_symbols(project, &file_tests.keys().collect())?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (file, tests) in file_tests {
+        writeln!(
+            writer,
+            "== {file} ========================================================"
+        )?;
+
+        let mut fpath = project_path.clone();
+
+        fpath.push(format!("sources/{file}"));
+        let cpath = dunce::canonicalize(&fpath).unwrap();
+
+        for (idx, test) in tests.iter().enumerate() {
+            test.test(idx, &symbols, writer, &project_path, &cpath)?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
+fn signature_help_test_suite(
+    project: String,
+    file_tests: BTreeMap<String, Vec<SignatureHelpTest>>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols) = initial_symbols(project, &file_tests.keys().collect())?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (file, tests) in file_tests {
+        writeln!(
+            writer,
+            "== {file} ========================================================"
+        )?;
+
+        let mut fpath = project_path.clone();
+
+        fpath.push(format!("sources/{file}"));
+        let cpath = dunce::canonicalize(&fpath).unwrap();
+
+        for (idx, test) in tests.iter().enumerate() {
+            test.test(idx, &symbols, writer, &cpath)?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
+fn code_action_test_suite(
+    project: String,
+    file_tests: BTreeMap<String, Vec<CodeActionTest>>,
+) -> datatest_stable::Result<String> {
+    let (project_path, _, symbols, diagnostics) =
+        initial_symbols_with_diagnostics(project, &file_tests.keys().collect(), LintLevel::All)?;
+
+    let mut output: BufWriter<_> = BufWriter::new(Vec::new());
+    let writer: &mut dyn io::Write = output.get_mut();
+
+    for (file, tests) in file_tests {
+        writeln!(
+            writer,
+            "== {file} ========================================================"
+        )?;
+
+        let mut fpath = project_path.clone();
+
+        fpath.push(format!("sources/{file}"));
+        let cpath = dunce::canonicalize(&fpath).unwrap();
+        let file_diagnostics = diagnostics
+            .iter()
+            .find(|(path, _)| dunce::canonicalize(path).is_ok_and(|p| p == cpath))
+            .map(|(_, diags)| diags.as_slice())
+            .unwrap_or_default();
+
+        for (idx, test) in tests.iter().enumerate() {
+            test.test(idx, &symbols, file_diagnostics, writer, &cpath)?;
+        }
+    }
+
+    let result: String = String::from_utf8(output.into_inner().unwrap()).unwrap();
+    Ok(result)
+}
+
 fn move_ide_testsuite(test_path: &Path) -> datatest_stable::Result<()> {
     let suite_file = io::BufReader::new(File::open(test_path)?);
     let stripped = StripComments::new(suite_file);
@@ -547,6 +857,18 @@ fn move_ide_testsuite(test_path: &Path) -> datatest_stable::Result<()> {
             project,
             file_tests,
         } => hint_test_suite(project, file_tests),
+        TestSuite::Rename {
+            project,
+            file_tests,
+        } => rename_test_suite(project, file_tests),
+        TestSuite::SignatureHelp {
+            project,
+            file_tests,
+        } => signature_help_test_suite(project, file_tests),
+        TestSuite::CodeAction {
+            project,
+            file_tests,
+        } => code_action_test_suite(project, file_tests),
     }?;
 
     let exp_string = test_path
@@ -0,0 +1,25 @@
+module Rename::rename {
+
+    public struct SomeStruct has drop {
+        some_field: u64,
+    }
+
+    public fun make(some_field: u64): SomeStruct {
+        SomeStruct { some_field }
+    }
+
+    public fun field(s: &SomeStruct): u64 {
+        let SomeStruct { some_field } = s;
+        *some_field
+    }
+
+    public fun sum(s: &SomeStruct, x: u64): u64 {
+        s.some_field + x
+    }
+
+    const LIMIT: u64 = 42;
+
+    public fun limited(s: &SomeStruct): bool {
+        field(s) < LIMIT
+    }
+}
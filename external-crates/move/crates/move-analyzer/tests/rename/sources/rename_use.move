@@ -0,0 +1,16 @@
+module Rename::rename_use {
+    use Rename::rename::{Self, SomeStruct, make as create};
+
+    public fun use_make(): SomeStruct {
+        create(7)
+    }
+
+    public fun use_sum(s: &SomeStruct): u64 {
+        rename::sum(s, rename::field(s))
+    }
+
+    public fun use_limited(): bool {
+        let s = rename::make(0);
+        s.limited() && vector::is_empty(&vector[1])
+    }
+}
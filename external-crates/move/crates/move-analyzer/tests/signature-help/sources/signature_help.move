@@ -0,0 +1,28 @@
+module SignatureHelp::signature_help {
+
+    public struct SomeStruct has drop {
+        some_field: u64,
+    }
+
+    /// Adds two numbers.
+    public fun add(first: u64, second: u64): u64 {
+        first + second
+    }
+
+    public fun get<T: copy>(s: &SomeStruct, default: T): u64 {
+        s.some_field
+    }
+
+    public macro fun apply($x: u64, $f: |u64| -> u64): u64 {
+        $f($x)
+    }
+
+    public fun calls(s: &SomeStruct): u64 {
+        let a = add(1, add(2, 3));
+        let b = s.get(true);
+        let c = get<u8>(s, 0);
+        let d = apply!(a, |x| add(x, b));
+        let e = vector[add(c, 1)];
+        std::vector::length(&e) + d
+    }
+}
@@ -72,6 +72,12 @@ interface ILaunchRequestArguments extends DebugProtocol.LaunchRequestArguments {
     source: string;
     /** Trace selected for viewing. */
     traceInfo: string;
+    /**
+     * An absolute path to a trace file to view instead of a unit test trace
+     * (e.g., a trace of a replayed on-chain transaction). If specified,
+     * `source` and `traceInfo` are ignored.
+     */
+    traceFile?: string;
     /** Automatically stop target after launch. If not specified, target does not stop. */
     stopOnEntry?: boolean;
     /** enable logging the Debug Adapter Protocol */
@@ -138,7 +144,7 @@ export class MoveDebugSession extends LoggingDebugSession {
         response.body.supportsEvaluateForHovers = false;
 
         // make VS Code show a 'step back' button
-        response.body.supportsStepBack = false;
+        response.body.supportsStepBack = true;
 
         // make VS Code support data breakpoints
         response.body.supportsDataBreakpoints = false;
@@ -192,10 +198,14 @@ export class MoveDebugSession extends LoggingDebugSession {
         args: ILaunchRequestArguments
     ): Promise<void> {
         logger.setup(convertLoggerLogLevel(args.logLevel ?? LogLevel.None), false);
-        logger.log(`Launching trace viewer for file: ${args.source} and trace: ${args.traceInfo}`);
-
         try {
-            await this.runtime.start(args.source, args.traceInfo, args.stopOnEntry || false);
+            if (args.traceFile) {
+                logger.log(`Launching trace viewer for trace file: ${args.traceFile}`);
+                this.runtime.startFromTraceFile(args.traceFile);
+            } else {
+                logger.log(`Launching trace viewer for file: ${args.source} and trace: ${args.traceInfo}`);
+                await this.runtime.start(args.source, args.traceInfo, args.stopOnEntry || false);
+            }
         } catch (err) {
             response.success = false;
             response.message = err instanceof Error ? err.message : String(err);
@@ -546,6 +556,32 @@ export class MoveDebugSession extends LoggingDebugSession {
         this.sendResponse(response);
     }
 
+    protected stepBackRequest(
+        response: DebugProtocol.StepBackResponse,
+        _args: DebugProtocol.StepBackArguments
+    ): void {
+        try {
+            this.runtime.stepBack();
+        } catch (err) {
+            response.success = false;
+            response.message = err instanceof Error ? err.message : String(err);
+        }
+        this.sendResponse(response);
+    }
+
+    protected reverseContinueRequest(
+        response: DebugProtocol.ReverseContinueResponse,
+        _args: DebugProtocol.ReverseContinueArguments
+    ): void {
+        try {
+            this.runtime.reverseContinue();
+        } catch (err) {
+            response.success = false;
+            response.message = err instanceof Error ? err.message : String(err);
+        }
+        this.sendResponse(response);
+    }
+
     protected setBreakPointsRequest(response: DebugProtocol.SetBreakpointsResponse, args: DebugProtocol.SetBreakpointsArguments): void {
         try {
             const finalBreakpoints = [];
@@ -146,6 +146,15 @@ export interface IRuntimeStack {
     globals: Map<number, RuntimeValueType>;
 }
 
+/**
+ * State of the runtime at a given point of the trace viewing session,
+ * used to step back to that point.
+ */
+interface IRuntimeSnapshot {
+    eventIndex: number;
+    frameStack: IRuntimeStack;
+}
+
 /**
  * Events emitted by the runtime during trace viewing session.
  */
@@ -206,6 +215,12 @@ export class Runtime extends EventEmitter {
         globals: new Map<number, RuntimeValueType>()
     };
 
+    /**
+     * Snapshots of the runtime state taken before each step/next/step out/continue
+     * action (most recent at the end), to support stepping back.
+     */
+    private history: IRuntimeSnapshot[] = [];
+
     /**
      * Map of file hashes to file info.
      */
@@ -250,6 +265,35 @@ export class Runtime extends EventEmitter {
         // reconstruct trace file path from trace info
         const traceFilePath = path.join(pkgRoot, 'traces', traceInfo.replace(/:/g, '_') + '.json');
 
+        this.startTrace(traceFilePath, sourceMapsModMap);
+    }
+
+    /**
+     * Start a trace viewing session for a trace that was not generated from a package's
+     * unit tests (e.g., a trace of a replayed on-chain transaction). The directory containing
+     * the trace file must also contain:
+     * - `sources` directory with Move source files (`.move`) or disassembled bytecode
+     *   (`.mvb`) of modules executed in the trace
+     * - `source_maps` directory with the source maps for these files
+     *
+     * @param traceFilePath path to the trace file.
+     * @throws Error with a descriptive error message if starting runtime has failed.
+     */
+    public startFromTraceFile(traceFilePath: string): void {
+        const traceDir = path.dirname(traceFilePath);
+        this.hashToFileMap(path.join(traceDir, 'sources'));
+        const sourceMapsModMap = readAllSourceMaps(path.join(traceDir, 'source_maps'), this.filesMap);
+        this.startTrace(traceFilePath, sourceMapsModMap);
+    }
+
+    /**
+     * Reads the trace and sets up the initial state of the runtime.
+     *
+     * @param traceFilePath path to the trace file.
+     * @param sourceMapsModMap a map from stringified module info to a source map.
+     * @throws Error with a descriptive error message if starting runtime has failed.
+     */
+    private startTrace(traceFilePath: string, sourceMapsModMap: Map<string, ISourceMap>): void {
         // create a mapping from file hash to its corresponding source map
         const sourceMapsHashMap = new Map<string, ISourceMap>;
         for (const [_, sourceMap] of sourceMapsModMap) {
@@ -260,6 +304,7 @@ export class Runtime extends EventEmitter {
 
         // start trace viewing session with the first trace event
         this.eventIndex = 0;
+        this.history = [];
 
         // setup frame stack with the first frame
         const currentEvent = this.trace.events[this.eventIndex];
@@ -279,7 +324,7 @@ export class Runtime extends EventEmitter {
             frames: [newFrame],
             globals: new Map<number, RuntimeValueType>()
         };
-        this.step(/* next */ false, /* stopAtCloseFrame */ false);
+        this.advance(/* next */ false, /* stopAtCloseFrame */ false);
     }
 
     /**
@@ -302,6 +347,74 @@ export class Runtime extends EventEmitter {
      * @throws Error with a descriptive error message if the step event cannot be handled.
      */
     public step(next: boolean, stopAtCloseFrame: boolean): ExecutionResult {
+        this.saveSnapshot();
+        return this.advance(next, stopAtCloseFrame);
+    }
+
+    /**
+     * Handles "step out" adapter action.
+     *
+     * @param next determines if it's  part of `next` (or otherwise `step`) action.
+     * @returns ExecutionResult.Ok if the step action was successful, ExecutionResult.TraceEnd if we
+     * reached the end of the trace, and ExecutionResult.Exception if an exception was encountered.
+     * @throws Error with a descriptive error message if the step out event cannot be handled.
+     */
+    public stepOut(next: boolean): ExecutionResult {
+        this.saveSnapshot();
+        return this.advanceOut(next);
+    }
+
+    /**
+     * Handles "step back" adapter action by restoring the state of the runtime
+     * from before the most recent step/next/step out/continue action.
+     *
+     * @returns `true` if the runtime stepped back, `false` if it was already
+     * at the beginning of the trace.
+     */
+    public stepBack(): boolean {
+        const snapshot = this.history.pop();
+        if (snapshot) {
+            this.restoreSnapshot(snapshot);
+        }
+        this.sendEvent(RuntimeEvents.stopOnStep);
+        return snapshot !== undefined;
+    }
+
+    /**
+     * Handles "reverse continue" adapter action by stepping back until reaching
+     * a line with a breakpoint or the beginning of the trace.
+     *
+     * @returns `true` if the runtime stopped on a line with a breakpoint, `false` if
+     * it stopped at the beginning of the trace.
+     */
+    public reverseContinue(): boolean {
+        while (true) {
+            const snapshot = this.history.pop();
+            if (!snapshot) {
+                this.sendEvent(RuntimeEvents.stopOnStep);
+                return false;
+            }
+            this.restoreSnapshot(snapshot);
+            const frames = this.frameStack.frames;
+            const currentFrame = frames[frames.length - 1];
+            if (currentFrame && this.lineBreakpoints.get(currentFrame.file)?.has(currentFrame.line)) {
+                this.sendEvent(RuntimeEvents.stopOnLineBreakpoint);
+                return true;
+            }
+        }
+    }
+
+    /**
+     * Processes trace events until the next place where a step/next action should stop.
+     *
+     * @param next determines if it's `next` (or otherwise `step`) action.
+     * @param stopAtCloseFrame determines if the action should stop at `CloseFrame` event
+     * (rather then proceed to the following instruction).
+     * @returns ExecutionResult.Ok if the step action was successful, ExecutionResult.TraceEnd if we
+     * reached the end of the trace, and ExecutionResult.Exception if an exception was encountered.
+     * @throws Error with a descriptive error message if the step event cannot be handled.
+     */
+    private advance(next: boolean, stopAtCloseFrame: boolean): ExecutionResult {
         this.eventIndex++;
         if (this.eventIndex >= this.trace.events.length) {
             this.sendEvent(RuntimeEvents.stopOnStep);
@@ -396,7 +509,7 @@ export class Runtime extends EventEmitter {
                     this.sendEvent(RuntimeEvents.stopOnStep);
                     return ExecutionResult.Ok;
                 } else {
-                    return this.step(next, stopAtCloseFrame);
+                    return this.advance(next, stopAtCloseFrame);
                 }
             }
             this.sendEvent(RuntimeEvents.stopOnStep);
@@ -416,7 +529,7 @@ export class Runtime extends EventEmitter {
             }
             currentFrame.file = currentFile.path;
             this.frameStack.frames.push(currentFrame);
-            return this.step(next, stopAtCloseFrame);
+            return this.advance(next, stopAtCloseFrame);
         } else if (currentEvent.type === TraceEventKind.OpenFrame) {
             // if function is native then the next event will be CloseFrame
             if (currentEvent.isNative) {
@@ -431,7 +544,7 @@ export class Runtime extends EventEmitter {
                 }
                 // process optional effects until reaching CloseFrame for the native function
                 while (true) {
-                    const executionResult = this.step(/* next */ false, /* stopAtCloseFrame */ true);
+                    const executionResult = this.advance(/* next */ false, /* stopAtCloseFrame */ true);
                     if (executionResult === ExecutionResult.Exception) {
                         return executionResult;
                     }
@@ -445,7 +558,7 @@ export class Runtime extends EventEmitter {
                 }
                 // skip over CloseFrame as there is no frame to pop
                 this.eventIndex++;
-                return this.step(next, stopAtCloseFrame);
+                return this.advance(next, stopAtCloseFrame);
             }
 
             // create a new frame and push it onto the stack
@@ -466,9 +579,9 @@ export class Runtime extends EventEmitter {
 
             if (next) {
                 // step out of the frame right away
-                return this.stepOut(next);
+                return this.advanceOut(next);
             } else {
-                return this.step(next, stopAtCloseFrame);
+                return this.advance(next, stopAtCloseFrame);
             }
         } else if (currentEvent.type === TraceEventKind.CloseFrame) {
             if (stopAtCloseFrame) {
@@ -491,7 +604,7 @@ export class Runtime extends EventEmitter {
                         + ')');
                 }
                 this.frameStack.frames.pop();
-                return this.step(next, stopAtCloseFrame);
+                return this.advance(next, stopAtCloseFrame);
             }
         } else if (currentEvent.type === TraceEventKind.Effect) {
             const effect = currentEvent.effect;
@@ -518,22 +631,22 @@ export class Runtime extends EventEmitter {
                     localWrite(frame, traceLocation.localIndex, traceValue);
                 }
             }
-            return this.step(next, stopAtCloseFrame);
+            return this.advance(next, stopAtCloseFrame);
         } else {
             // ignore other events
-            return this.step(next, stopAtCloseFrame);
+            return this.advance(next, stopAtCloseFrame);
         }
     }
 
     /**
-     * Handles "step out" adapter action.
+     * Processes trace events until the current function call returns to its caller.
      *
      * @param next determines if it's  part of `next` (or otherwise `step`) action.
      * @returns ExecutionResult.Ok if the step action was successful, ExecutionResult.TraceEnd if we
      * reached the end of the trace, and ExecutionResult.Exception if an exception was encountered.
      * @throws Error with a descriptive error message if the step out event cannot be handled.
      */
-    public stepOut(next: boolean): ExecutionResult {
+    private advanceOut(next: boolean): ExecutionResult {
         const stackHeight = this.frameStack.frames.length;
         if (stackHeight <= 1) {
             // do nothing as there is no frame to step out to
@@ -550,7 +663,7 @@ export class Runtime extends EventEmitter {
             // skipping over calls next-style otherwise we can miss seeing
             // the actual close frame event that we are looking for
             // and have the loop execute too far
-            const executionResult = this.step(/* next */ false, /* stopAtCloseFrame */ true);
+            const executionResult = this.advance(/* next */ false, /* stopAtCloseFrame */ true);
             if (executionResult === ExecutionResult.Exception) {
                 return executionResult;
             }
@@ -569,7 +682,7 @@ export class Runtime extends EventEmitter {
                 }
             }
         }
-        return this.step(next, /* stopAtCloseFrame */ false);
+        return this.advance(next, /* stopAtCloseFrame */ false);
     }
 
     /**
@@ -579,8 +692,9 @@ export class Runtime extends EventEmitter {
      * @throws Error with a descriptive error message if the continue event cannot be handled.
      */
     public continue(): ExecutionResult {
+        this.saveSnapshot();
         while (true) {
-            const executionResult = this.step(/* next */ false, /* stopAtCloseFrame */ false);
+            const executionResult = this.advance(/* next */ false, /* stopAtCloseFrame */ false);
             if (executionResult === ExecutionResult.TraceEnd ||
                 executionResult === ExecutionResult.Exception) {
                 return executionResult;
@@ -736,6 +850,27 @@ export class Runtime extends EventEmitter {
         return stackFrame;
     }
 
+    /**
+     * Saves the current state of the runtime so that it can be restored
+     * when stepping back.
+     */
+    private saveSnapshot(): void {
+        this.history.push({
+            eventIndex: this.eventIndex,
+            frameStack: structuredClone(this.frameStack)
+        });
+    }
+
+    /**
+     * Restores the state of the runtime from a snapshot.
+     *
+     * @param snapshot snapshot to restore.
+     */
+    private restoreSnapshot(snapshot: IRuntimeSnapshot): void {
+        this.eventIndex = snapshot.eventIndex;
+        this.frameStack = snapshot.frameStack;
+    }
+
     /**
      * Emits an event to the adapter.
      *
@@ -762,7 +897,7 @@ export class Runtime extends EventEmitter {
                 const stats = fs.statSync(filePath);
                 if (stats.isDirectory()) {
                     processDirectory(filePath);
-                } else if (path.extname(f) === '.move') {
+                } else if (path.extname(f) === '.move' || path.extname(f) === '.mvb') {
                     const content = fs.readFileSync(filePath, 'utf8');
                     const numFileHash = computeFileHash(content);
                     const lines = content.split('\n');
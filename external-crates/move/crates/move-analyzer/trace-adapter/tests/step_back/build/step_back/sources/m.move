@@ -0,0 +1,16 @@
+// Test stepping back functionality:
+// - step back over a step out
+// - step back over a step into a function
+// - step back past the beginning of the trace
+module step_back::m;
+
+fun foo(p: u64): u64 {
+    p + p
+}
+
+#[test]
+fun test() {
+    let mut _res = foo(42);
+    _res = _res + foo(_res);
+    _res = _res + foo(_res); // to force another unoptimized read to keep `res` visible
+}
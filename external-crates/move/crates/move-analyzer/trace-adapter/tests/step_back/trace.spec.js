@@ -0,0 +1,20 @@
+let action = (runtime) => {
+    let res = '';
+    // step into a function
+    runtime.step(false);
+    res += runtime.toString();
+    // step out of a function
+    runtime.stepOut(false);
+    res += runtime.toString();
+    // step back to where the step out started
+    runtime.stepBack();
+    res += runtime.toString();
+    // step back to where the step into the function started
+    runtime.stepBack();
+    res += runtime.toString();
+    // step back at the beginning of the trace (should not change anything)
+    runtime.stepBack();
+    res += runtime.toString();
+    return res;
+};
+run_spec(__dirname, action);
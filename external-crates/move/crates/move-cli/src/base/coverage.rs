@@ -5,12 +5,18 @@ use super::reroot_path;
 use clap::*;
 use move_compiler::compiled_unit::NamedCompiledModule;
 use move_coverage::{
-    coverage_map::CoverageMap, format_csv_summary, format_human_summary,
+    cobertura::write_cobertura, coverage_map::CoverageMap, format_csv_summary,
+    format_human_summary, lcov::write_lcov, line_coverage::ModuleLineCoverage,
     source_coverage::SourceCoverageBuilder, summary::summarize_inst_cov,
 };
 use move_disassembler::disassembler::Disassembler;
-use move_package::BuildConfig;
-use std::path::Path;
+use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Component, Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 #[derive(Parser)]
 pub enum CoverageSummaryOptions {
@@ -36,6 +42,20 @@ pub enum CoverageSummaryOptions {
         #[clap(long = "module")]
         module_name: String,
     },
+    /// Write an LCOV report of the coverage of all modules in this package
+    #[clap(name = "lcov")]
+    Lcov {
+        /// The file to write the report to, instead of standard output
+        #[clap(long = "output-file", short = 'o', value_name = "PATH")]
+        output_file: Option<PathBuf>,
+    },
+    /// Write a Cobertura XML report of the coverage of all modules in this package
+    #[clap(name = "cobertura")]
+    Cobertura {
+        /// The file to write the report to, instead of standard output
+        #[clap(long = "output-file", short = 'o', value_name = "PATH")]
+        output_file: Option<PathBuf>,
+    },
 }
 
 /// Inspect test coverage for this package. A previous test run with the `--coverage` flag must
@@ -49,6 +69,8 @@ pub struct Coverage {
 
 impl Coverage {
     pub fn execute(self, path: Option<&Path>, config: BuildConfig) -> anyhow::Result<()> {
+        // Output files are relative to the working directory before re-rooting to the package.
+        let cwd = std::env::current_dir()?;
         let path = reroot_path(path)?;
         let coverage_map = CoverageMap::from_binary_file(path.join(".coverage_map.mvcov"))?;
         let package = config.compile_package(&path, &mut Vec::new())?;
@@ -95,7 +117,59 @@ impl Coverage {
                 disassembler.add_coverage_map(coverage_map.to_unified_exec_map());
                 println!("{}", disassembler.disassemble()?);
             }
+            CoverageSummaryOptions::Lcov { output_file } => {
+                let modules = line_coverage(&package, &coverage_map)?;
+                let mut out = output_writer(&cwd, output_file)?;
+                write_lcov(&modules, &mut out)?;
+            }
+            CoverageSummaryOptions::Cobertura { output_file } => {
+                let modules = line_coverage(&package, &coverage_map)?;
+                let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                let mut out = output_writer(&cwd, output_file)?;
+                write_cobertura(
+                    package.compiled_package_info.package_name.as_str(),
+                    &modules,
+                    &std::env::current_dir()?,
+                    timestamp,
+                    &mut out,
+                )?;
+            }
         }
         Ok(())
     }
 }
+
+/// Maps the coverage of the package's modules back to their source files.
+fn line_coverage(
+    package: &CompiledPackage,
+    coverage_map: &CoverageMap,
+) -> anyhow::Result<Vec<ModuleLineCoverage>> {
+    let coverage_map = coverage_map.to_unified_exec_map();
+    let mut modules = vec![];
+    for unit in package.root_modules() {
+        let source = fs::read_to_string(&unit.source_path)?;
+        // Paths in reports are relative to the package root, which is the working directory.
+        let source_path: PathBuf = unit
+            .source_path
+            .strip_prefix(std::env::current_dir()?)
+            .unwrap_or(&unit.source_path)
+            .components()
+            .filter(|c| !matches!(c, Component::CurDir))
+            .collect();
+        modules.push(ModuleLineCoverage::new(
+            &unit.unit.module,
+            &coverage_map,
+            &unit.unit.source_map,
+            &source_path,
+            &source,
+        ));
+    }
+    Ok(modules)
+}
+
+fn output_writer(cwd: &Path, output_file: Option<PathBuf>) -> anyhow::Result<Box<dyn Write>> {
+    Ok(match output_file {
+        Some(path) => Box::new(BufWriter::new(File::create(cwd.join(path))?)),
+        None => Box::new(std::io::stdout()),
+    })
+}
@@ -0,0 +1,156 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_command_line_common::files::{FileHash, MOVE_EXTENSION};
+use move_compiler::{
+    diagnostics::report_diagnostics_to_buffer_with_env_color, shared::files::MappedFiles,
+};
+use move_formatter::{format_string, FormatError, FormatterConfig};
+use move_package::{
+    source_package::{layout::SourcePackageLayout, manifest_parser::parse_move_manifest_from_file},
+    BuildConfig,
+};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Format the Move source files of the package at `path`. If no path is provided defaults to
+/// current directory.
+#[derive(Parser)]
+#[clap(name = "fmt")]
+pub struct Fmt {
+    /// Check that the files are formatted, without changing them. Lists the files that are not
+    /// formatted, and fails if there are any.
+    #[clap(long = "check")]
+    pub check: bool,
+
+    /// Read the formatter's configuration from this file, instead of the `move-fmt.toml` file at
+    /// the root of the package.
+    #[clap(long = "config", value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Only format these files, instead of all the Move source files in the package.
+    #[clap(value_name = "FILES")]
+    pub files: Vec<PathBuf>,
+}
+
+impl Fmt {
+    pub fn execute(self, path: Option<&Path>, config: BuildConfig) -> anyhow::Result<()> {
+        // Resolve paths relative to the working directory before re-rooting to the package.
+        let files = self
+            .files
+            .iter()
+            .map(|f| f.canonicalize())
+            .collect::<Result<Vec<_>, _>>()?;
+        let fmt_config = self.config.as_deref().map(Path::canonicalize).transpose()?;
+
+        let rerooted_path = reroot_path(path)?;
+        let fmt_config = match fmt_config {
+            Some(path) => FormatterConfig::from_file(&path)?,
+            None => FormatterConfig::for_package(&rerooted_path)?,
+        };
+
+        let edition = parse_move_manifest_from_file(&rerooted_path)?
+            .package
+            .edition
+            .or(config.default_edition)
+            .unwrap_or_default();
+
+        let files = if files.is_empty() {
+            package_files(&rerooted_path)
+        } else {
+            files
+        };
+
+        let mut unformatted = vec![];
+        let mut failed = false;
+        for file in &files {
+            let source = fs::read_to_string(file)?;
+            let formatted = match format_string(&source, edition, &fmt_config) {
+                Ok(formatted) => formatted,
+                Err(e) => {
+                    report_error(file, &source, e);
+                    failed = true;
+                    continue;
+                }
+            };
+
+            if formatted == source {
+                continue;
+            }
+
+            if self.check {
+                unformatted.push(file);
+            } else {
+                fs::write(file, formatted)?;
+            }
+        }
+
+        if !unformatted.is_empty() {
+            println!("The following files are not formatted:");
+            for file in &unformatted {
+                println!("  {}", file.display());
+            }
+        }
+
+        if failed {
+            anyhow::bail!("Unable to format all files");
+        }
+
+        if !unformatted.is_empty() {
+            anyhow::bail!("{} file(s) are not formatted", unformatted.len());
+        }
+
+        Ok(())
+    }
+}
+
+/// The Move source files in the package rooted at `root`, in its sources, tests, examples and
+/// scripts directories.
+fn package_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for dir in [
+        SourcePackageLayout::Sources,
+        SourcePackageLayout::Tests,
+        SourcePackageLayout::Examples,
+        SourcePackageLayout::Scripts,
+    ] {
+        let dir = root.join(dir.path());
+        if !dir.is_dir() {
+            continue;
+        }
+
+        for entry in walkdir::WalkDir::new(dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            let path = entry.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == MOVE_EXTENSION) {
+                files.push(path.to_path_buf());
+            }
+        }
+    }
+
+    files
+}
+
+fn report_error(file: &Path, source: &str, error: FormatError) {
+    match error {
+        FormatError::Parse(diags) => {
+            let mut files = MappedFiles::empty();
+            files.add(
+                FileHash::new(source),
+                file.to_string_lossy().to_string().into(),
+                source.into(),
+            );
+            let buffer = report_diagnostics_to_buffer_with_env_color(&files, diags);
+            let _ = std::io::stderr().write_all(&buffer);
+        }
+        FormatError::Internal(_) => eprintln!("{}: {error}", file.display()),
+    }
+}
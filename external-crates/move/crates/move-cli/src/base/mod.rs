@@ -5,6 +5,7 @@ pub mod build;
 pub mod coverage;
 pub mod disassemble;
 pub mod docgen;
+pub mod fmt;
 pub mod info;
 pub mod migrate;
 pub mod new;
@@ -9,15 +9,18 @@ use move_binary_format::CompiledModule;
 use move_command_line_common::files::MOVE_COVERAGE_MAP_EXTENSION;
 use move_compiler::{
     diagnostics::{self, Diagnostics},
+    shared::files::MappedFiles,
     shared::{NumberFormat, NumericalAddress},
     unit_test::{plan_builder::construct_test_plan, TestPlan},
-    PASS_CFGIR,
+    Compiler, FullyCompiledProgram, PASS_CFGIR,
 };
 use move_coverage::coverage_map::{output_map_to_file, CoverageMap};
-use move_package::{compilation::build_plan::BuildPlan, BuildConfig};
+use move_package::{
+    compilation::build_plan::BuildPlan, resolution::resolution_graph::ResolvedGraph, BuildConfig,
+};
 use move_unit_test::UnitTestingConfig;
 use move_vm_test_utils::gas_schedule::CostTable;
-use std::{io::Write, path::Path, process::ExitStatus};
+use std::{io::Write, path::Path, process::ExitStatus, sync::Arc};
 // if windows
 #[cfg(target_family = "windows")]
 use std::os::windows::process::ExitStatusExt;
@@ -53,6 +56,10 @@ pub struct Test {
     /// Report test statistics at the end of testing. CSV report generated if 'csv' passed
     #[clap(name = "report-statistics", short = 's', long = "statistics")]
     pub report_statistics: Option<Option<String>>,
+    /// Write a report of each test's status, failure, gas used and time to PATH, as FORMAT
+    /// (`junit` or `json`), e.g. for CI systems to track test results over time.
+    #[clap(long = "report", num_args = 2, value_names = ["FORMAT", "PATH"])]
+    pub report: Option<Vec<String>>,
 
     /// Verbose mode
     #[clap(long = "verbose")]
@@ -111,6 +118,7 @@ impl Test {
             list,
             num_threads,
             report_statistics,
+            report,
             verbose_mode,
             compute_coverage: _,
             seed,
@@ -123,6 +131,7 @@ impl Test {
             list,
             num_threads,
             report_statistics,
+            report,
             verbose: verbose_mode,
             seed,
             rand_num_iters,
@@ -142,14 +151,13 @@ pub enum UnitTestResult {
 pub fn run_move_unit_tests<W: Write + Send>(
     pkg_path: &Path,
     mut build_config: move_package::BuildConfig,
-    mut unit_test_config: UnitTestingConfig,
+    unit_test_config: UnitTestingConfig,
     natives: Vec<NativeFunctionRecord>,
     cost_table: Option<CostTable>,
     compute_coverage: bool,
     save_disassembly: bool,
     writer: &mut W,
 ) -> Result<(UnitTestResult, Option<Diagnostics>)> {
-    let mut test_plan = None;
     build_config.test_mode = true;
     build_config.dev_mode = true;
     build_config.save_disassembly = save_disassembly;
@@ -159,6 +167,60 @@ pub fn run_move_unit_tests<W: Write + Send>(
     let resolution_graph =
         build_config.resolution_graph_for_package(pkg_path, None, &mut Vec::new())?;
 
+    run_move_unit_tests_impl(
+        pkg_path,
+        resolution_graph,
+        None,
+        unit_test_config,
+        natives,
+        cost_table,
+        compute_coverage,
+        /* exit_on_compilation_errors */ true,
+        writer,
+    )
+}
+
+/// Runs the unit tests of the package at `pkg_path`, whose dependencies have already been
+/// resolved into `resolution_graph` with test and dev mode enabled, and, if `pre_compiled_deps`
+/// is provided, compiled with `BuildPlan::compile_source_deps`. This allows callers that run the
+/// tests repeatedly to only resolve and compile the dependencies again when they change.
+/// Compilation errors are reported to stderr and result in a [UnitTestResult::Failure], rather
+/// than terminating the process.
+pub fn run_move_unit_tests_with_resolution_graph<W: Write + Send>(
+    pkg_path: &Path,
+    resolution_graph: ResolvedGraph,
+    pre_compiled_deps: Option<Arc<FullyCompiledProgram>>,
+    unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    compute_coverage: bool,
+    writer: &mut W,
+) -> Result<(UnitTestResult, Option<Diagnostics>)> {
+    run_move_unit_tests_impl(
+        pkg_path,
+        resolution_graph,
+        pre_compiled_deps,
+        unit_test_config,
+        natives,
+        cost_table,
+        compute_coverage,
+        /* exit_on_compilation_errors */ false,
+        writer,
+    )
+}
+
+fn run_move_unit_tests_impl<W: Write + Send>(
+    pkg_path: &Path,
+    resolution_graph: ResolvedGraph,
+    pre_compiled_deps: Option<Arc<FullyCompiledProgram>>,
+    mut unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    compute_coverage: bool,
+    exit_on_compilation_errors: bool,
+    writer: &mut W,
+) -> Result<(UnitTestResult, Option<Diagnostics>)> {
+    let mut test_plan = None;
     // Note: unit_test_config.named_address_values is always set to vec![] (the default value) before
     // being passed in.
     unit_test_config.named_address_values = resolution_graph
@@ -197,18 +259,32 @@ pub fn run_move_unit_tests<W: Write + Send>(
     // then save it, before resuming the rest of the compilation and returning the results and
     // control back to the Move package system.
     let mut warning_diags = None;
-    build_plan.compile_with_driver(writer, |compiler| {
+    let mut compilation_failed = false;
+    let driver = |compiler: Compiler| {
         let (files, comments_and_compiler_res) = compiler.run::<PASS_CFGIR>().unwrap();
-        let compiler =
-            diagnostics::unwrap_or_report_pass_diagnostics(&files, comments_and_compiler_res);
+        let compiler = match comments_and_compiler_res {
+            Ok(compiler) => compiler,
+            Err((_pass, diags)) if !exit_on_compilation_errors => {
+                compilation_failed = true;
+                report_errors(&files, diags)?;
+                anyhow::bail!("Compilation error");
+            }
+            Err(err) => diagnostics::unwrap_or_report_pass_diagnostics(&files, Err(err)),
+        };
         let (compiler, cfgir) = compiler.into_ast();
         let compilation_env = compiler.compilation_env();
         let built_test_plan = construct_test_plan(compilation_env, Some(root_package), &cfgir);
         let mapped_files = compilation_env.mapped_files().clone();
 
-        let compilation_result = compiler.at_cfgir(cfgir).build();
-        let (units, warnings) =
-            diagnostics::unwrap_or_report_pass_diagnostics(&files, compilation_result);
+        let (units, warnings) = match compiler.at_cfgir(cfgir).build() {
+            Ok(res) => res,
+            Err((_pass, diags)) if !exit_on_compilation_errors => {
+                compilation_failed = true;
+                report_errors(&files, diags)?;
+                anyhow::bail!("Compilation error");
+            }
+            Err(err) => diagnostics::unwrap_or_report_pass_diagnostics(&files, Err(err)),
+        };
         diagnostics::report_warnings(&files, warnings.clone());
         let named_units: Vec<_> = units
             .clone()
@@ -218,9 +294,24 @@ pub fn run_move_unit_tests<W: Write + Send>(
         test_plan = Some((built_test_plan, mapped_files, named_units));
         warning_diags = Some(warnings);
         Ok((files, units))
-    })?;
+    };
+    let compiled = match &pre_compiled_deps {
+        Some(deps) => {
+            build_plan.compile_with_driver_and_pre_compiled_deps(deps.clone(), writer, driver)
+        }
+        None => build_plan.compile_with_driver(writer, driver),
+    };
+    if compilation_failed {
+        return Ok((UnitTestResult::Failure, None));
+    }
+    compiled?;
 
-    let (test_plan, mapped_files, units) = test_plan.unwrap();
+    let (test_plan, mut mapped_files, mut units) = test_plan.unwrap();
+    if let Some(deps) = pre_compiled_deps {
+        // the dependencies were not compiled with the package, but the VM needs their modules
+        mapped_files.extend_with_duplicates(deps.files.clone());
+        units.extend(deps.compiled.iter().map(|unit| unit.named_module.clone()));
+    }
     let test_plan = test_plan.unwrap();
     let no_tests = test_plan.is_empty();
     let test_plan = TestPlan::new(test_plan, mapped_files, units, bytecode_deps_modules);
@@ -261,6 +352,13 @@ pub fn run_move_unit_tests<W: Write + Send>(
     Ok((UnitTestResult::Success, warning_diags))
 }
 
+fn report_errors(files: &MappedFiles, diags: Diagnostics) -> Result<()> {
+    assert!(!diags.is_empty());
+    let buffer = diagnostics::report_diagnostics_to_buffer_with_env_color(files, diags);
+    std::io::stderr().write_all(&buffer)?;
+    Ok(())
+}
+
 impl From<UnitTestResult> for ExitStatus {
     fn from(result: UnitTestResult) -> Self {
         match result {
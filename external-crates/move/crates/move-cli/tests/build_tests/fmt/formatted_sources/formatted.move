@@ -0,0 +1,3 @@
+module A::formatted {
+    public fun id(x: u64): u64 { x }
+}
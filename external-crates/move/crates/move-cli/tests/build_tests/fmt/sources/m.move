@@ -0,0 +1,4 @@
+module A::m{
+    public struct S has copy,drop{x:u64}
+  public fun get(s:&S):u64{s.x}
+}
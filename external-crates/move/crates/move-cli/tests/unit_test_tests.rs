@@ -0,0 +1,97 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_cli::base::test::{run_move_unit_tests_with_resolution_graph, UnitTestResult};
+use move_compiler::FullyCompiledProgram;
+use move_core_types::account_address::AccountAddress;
+use move_package::{
+    compilation::build_plan::BuildPlan, resolution::resolution_graph::ResolvedGraph, BuildConfig,
+};
+use move_stdlib_natives::{all_natives, GasParameters};
+use move_unit_test::UnitTestingConfig;
+use std::{path::Path, sync::Arc};
+use tempfile::{tempdir, TempDir};
+
+fn resolve(pkg_path: &Path) -> (ResolvedGraph, TempDir) {
+    let install_dir = tempdir().unwrap();
+    let build_config = BuildConfig {
+        test_mode: true,
+        dev_mode: true,
+        install_dir: Some(install_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let graph = build_config
+        .resolution_graph_for_package(pkg_path, None, &mut Vec::new())
+        .unwrap();
+    (graph, install_dir)
+}
+
+fn run_tests(
+    pkg_path: &Path,
+    graph: ResolvedGraph,
+    pre_compiled_deps: Option<Arc<FullyCompiledProgram>>,
+) -> (UnitTestResult, String) {
+    let mut output = vec![];
+    let (result, _) = run_move_unit_tests_with_resolution_graph(
+        pkg_path,
+        graph,
+        pre_compiled_deps,
+        UnitTestingConfig::default_with_bound(None),
+        all_natives(
+            AccountAddress::ONE,
+            GasParameters::zeros(),
+            /* silent */ true,
+        ),
+        None,
+        /* compute_coverage */ false,
+        &mut output,
+    )
+    .unwrap();
+    (result, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn unit_tests_with_pre_compiled_deps() {
+    let pkg_path = Path::new("tests/move_unit_tests/assign_dev_addr_for_dep");
+    let (graph, _install_dir) = resolve(pkg_path);
+    let pre_compiled_deps = BuildPlan::create(graph.clone())
+        .unwrap()
+        .compile_source_deps()
+        .unwrap()
+        .unwrap_or_else(|_| panic!("dependencies failed to compile"));
+    let pre_compiled_deps = Arc::new(pre_compiled_deps);
+
+    // apart from the dependencies not being built again, the output is the same as when they are
+    // compiled with the package, and the same compiled dependencies can be used for several runs
+    let (result, expected) = run_tests(pkg_path, graph.clone(), None);
+    assert_eq!(result, UnitTestResult::Success, "{expected}");
+    assert!(expected.contains("INCLUDING DEPENDENCY Bar"), "{expected}");
+    let expected = &expected[expected.find("BUILDING Foo").unwrap()..];
+    for _ in 0..2 {
+        let (result, output) = run_tests(pkg_path, graph.clone(), Some(pre_compiled_deps.clone()));
+        assert_eq!(result, UnitTestResult::Success, "{output}");
+        assert_eq!(output, expected);
+    }
+}
+
+#[test]
+fn unit_tests_compilation_failure() {
+    let pkg_dir = tempdir().unwrap();
+    let pkg_path = pkg_dir.path();
+    std::fs::write(
+        pkg_path.join("Move.toml"),
+        "[package]\nname = \"Broken\"\nedition = \"2024.beta\"\n\n[addresses]\nA = \"0x2\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir(pkg_path.join("sources")).unwrap();
+    std::fs::write(
+        pkg_path.join("sources").join("M.move"),
+        "module A::M {\n    #[test]\n    fun nop() { missing() }\n}\n",
+    )
+    .unwrap();
+
+    // compilation errors are reported as a failure instead of terminating the process
+    let (graph, _install_dir) = resolve(pkg_path);
+    let (result, _) = run_tests(pkg_path, graph, None);
+    assert_eq!(result, UnitTestResult::Failure);
+}
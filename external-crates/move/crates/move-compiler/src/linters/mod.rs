@@ -83,7 +83,7 @@ macro_rules! lints {
                 }
             }
 
-            const fn diag_info(&self) -> DiagnosticInfo {
+            pub const fn diag_info(&self) -> DiagnosticInfo {
                 let (category, code, msg) = self.category_code_and_message();
                 custom(
                     LINT_WARNING_PREFIX,
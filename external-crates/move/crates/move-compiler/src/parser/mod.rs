@@ -12,10 +12,16 @@ mod token_set;
 pub(crate) mod verification_attribute_filter;
 
 use crate::{
-    parser::{self, ast::PackageDefinition, syntax::parse_file_string},
+    diagnostics::{codes::Severity, Diagnostics},
+    editions::Edition,
+    parser::{
+        self,
+        ast::{Definition, PackageDefinition},
+        syntax::parse_file_string,
+    },
     shared::{
-        files::MappedFiles, CompilationEnv, IndexedVfsPackagePath, NamedAddressMapIndex,
-        NamedAddressMaps,
+        files::MappedFiles, CompilationEnv, Flags, IndexedVfsPackagePath, NamedAddressMapIndex,
+        NamedAddressMaps, PackageConfig,
     },
 };
 use anyhow::anyhow;
@@ -24,7 +30,11 @@ use comments::*;
 use move_command_line_common::files::FileHash;
 use move_symbol_pool::Symbol;
 use rayon::iter::*;
-use std::{collections::BTreeSet, path::PathBuf, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    sync::Arc,
+};
 use vfs::VfsPath;
 
 struct ParsedFile {
@@ -205,3 +215,29 @@ fn parse_file(
         text: source_str,
     })
 }
+
+/// Parses the contents of a single source file on its own, outside of any package, for tools that
+/// work on source text directly (e.g., the formatter). Returns the file's definitions, or the
+/// diagnostics for its syntax errors.
+pub fn parse_source(
+    file_hash: FileHash,
+    source: &str,
+    edition: Edition,
+) -> Result<Vec<Definition>, Diagnostics> {
+    verify_string(file_hash, source)?;
+    let compilation_env = CompilationEnv::new(
+        Flags::empty(),
+        vec![],
+        vec![],
+        None,
+        BTreeMap::new(),
+        Some(PackageConfig {
+            edition,
+            ..PackageConfig::default()
+        }),
+        None,
+    );
+    let defs = parse_file_string(&compilation_env, file_hash, source, None)?;
+    compilation_env.check_diags_at_or_above_severity(Severity::NonblockingError)?;
+    Ok(defs)
+}
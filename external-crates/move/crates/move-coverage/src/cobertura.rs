@@ -0,0 +1,199 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Writes coverage reports in the Cobertura XML format, as read by most CI systems' coverage
+//! views. Each module is reported as a class, and each function as a method.
+
+use crate::line_coverage::{BranchCoverage, ModuleLineCoverage};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Writes a Cobertura report for the modules of the package `package_name`. Source file paths in
+/// the report are relative to `source_root`, and `timestamp` is the time that the report was
+/// generated, in seconds since the Unix epoch.
+pub fn write_cobertura<'a, W: Write>(
+    package_name: &str,
+    modules: impl IntoIterator<Item = &'a ModuleLineCoverage>,
+    source_root: &Path,
+    timestamp: u64,
+    writer: &mut W,
+) -> io::Result<()> {
+    let modules: Vec<_> = modules.into_iter().collect();
+
+    let mut total = Counts::default();
+    for module in &modules {
+        total.add(&Counts::new(
+            &module.lines(),
+            module.functions.iter().flat_map(|f| &f.branches),
+        ));
+    }
+
+    writeln!(writer, r#"<?xml version="1.0" ?>"#)?;
+    writeln!(
+        writer,
+        r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<coverage {} version="1.9" timestamp="{timestamp}">"#,
+        total.attributes(),
+    )?;
+    writeln!(writer, "  <sources>")?;
+    writeln!(
+        writer,
+        "    <source>{}</source>",
+        escape(&source_root.to_string_lossy())
+    )?;
+    writeln!(writer, "  </sources>")?;
+    writeln!(writer, "  <packages>")?;
+    writeln!(
+        writer,
+        r#"    <package name="{}" line-rate="{:.4}" branch-rate="{:.4}" complexity="0">"#,
+        escape(package_name),
+        total.line_rate(),
+        total.branch_rate(),
+    )?;
+    writeln!(writer, "      <classes>")?;
+
+    for module in modules {
+        let lines = module.lines();
+        let branches = module.branches();
+        let counts = Counts::new(&lines, module.functions.iter().flat_map(|f| &f.branches));
+        writeln!(
+            writer,
+            r#"        <class name="{}" filename="{}" line-rate="{:.4}" branch-rate="{:.4}" complexity="0">"#,
+            escape(&module.module_name),
+            escape(&module.source_path.to_string_lossy()),
+            counts.line_rate(),
+            counts.branch_rate(),
+        )?;
+
+        writeln!(writer, "          <methods>")?;
+        for function in &module.functions {
+            let mut fn_branches: BTreeMap<_, Vec<_>> = BTreeMap::new();
+            for branch in &function.branches {
+                fn_branches.entry(branch.line).or_default().push(branch);
+            }
+            let counts = Counts::new(&function.lines, function.branches.iter());
+            writeln!(
+                writer,
+                r#"            <method name="{}" signature="" line-rate="{:.4}" branch-rate="{:.4}" complexity="0">"#,
+                escape(&function.name),
+                counts.line_rate(),
+                counts.branch_rate(),
+            )?;
+            write_lines(writer, "              ", &function.lines, &fn_branches)?;
+            writeln!(writer, "            </method>")?;
+        }
+        writeln!(writer, "          </methods>")?;
+
+        write_lines(writer, "          ", &lines, &branches)?;
+        writeln!(writer, "        </class>")?;
+    }
+
+    writeln!(writer, "      </classes>")?;
+    writeln!(writer, "    </package>")?;
+    writeln!(writer, "  </packages>")?;
+    writeln!(writer, "</coverage>")?;
+    Ok(())
+}
+
+fn write_lines<W: Write>(
+    writer: &mut W,
+    indent: &str,
+    lines: &BTreeMap<u32, u64>,
+    branches: &BTreeMap<u32, Vec<&BranchCoverage>>,
+) -> io::Result<()> {
+    writeln!(writer, "{indent}<lines>")?;
+    for (line, hits) in lines {
+        match branches.get(line) {
+            None => writeln!(
+                writer,
+                r#"{indent}  <line number="{line}" hits="{hits}" branch="false"/>"#
+            )?,
+            Some(branches) => {
+                let total: usize = branches.iter().map(|b| b.taken.len()).sum();
+                let covered: usize = branches.iter().map(|b| b.covered()).sum();
+                writeln!(
+                    writer,
+                    r#"{indent}  <line number="{line}" hits="{hits}" branch="true" condition-coverage="{}% ({covered}/{total})"/>"#,
+                    covered * 100 / total.max(1),
+                )?
+            }
+        }
+    }
+    writeln!(writer, "{indent}</lines>")
+}
+
+#[derive(Default)]
+struct Counts {
+    lines_valid: usize,
+    lines_covered: usize,
+    branches_valid: usize,
+    branches_covered: usize,
+}
+
+impl Counts {
+    fn new<'a>(
+        lines: &BTreeMap<u32, u64>,
+        branches: impl Iterator<Item = &'a BranchCoverage>,
+    ) -> Self {
+        let mut counts = Counts {
+            lines_valid: lines.len(),
+            lines_covered: lines.values().filter(|h| **h > 0).count(),
+            ..Counts::default()
+        };
+        for branch in branches {
+            counts.branches_valid += branch.taken.len();
+            counts.branches_covered += branch.covered();
+        }
+        counts
+    }
+
+    fn add(&mut self, other: &Counts) {
+        self.lines_valid += other.lines_valid;
+        self.lines_covered += other.lines_covered;
+        self.branches_valid += other.branches_valid;
+        self.branches_covered += other.branches_covered;
+    }
+
+    fn line_rate(&self) -> f64 {
+        rate(self.lines_covered, self.lines_valid)
+    }
+
+    fn branch_rate(&self) -> f64 {
+        rate(self.branches_covered, self.branches_valid)
+    }
+
+    fn attributes(&self) -> String {
+        format!(
+            r#"line-rate="{:.4}" branch-rate="{:.4}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0""#,
+            self.line_rate(),
+            self.branch_rate(),
+            self.lines_covered,
+            self.lines_valid,
+            self.branches_covered,
+            self.branches_valid,
+        )
+    }
+}
+
+fn rate(covered: usize, valid: usize) -> f64 {
+    if valid == 0 {
+        1.0
+    } else {
+        covered as f64 / valid as f64
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,79 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Writes coverage reports in the LCOV tracefile format, as read by `genhtml` and most code
+//! coverage services.
+
+use crate::line_coverage::{merge_lines, ModuleLineCoverage};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Writes an LCOV tracefile for `modules`, with one record per source file.
+pub fn write_lcov<'a, W: Write>(
+    modules: impl IntoIterator<Item = &'a ModuleLineCoverage>,
+    writer: &mut W,
+) -> io::Result<()> {
+    let mut files: BTreeMap<&Path, Vec<&ModuleLineCoverage>> = BTreeMap::new();
+    for module in modules {
+        files.entry(&module.source_path).or_default().push(module);
+    }
+
+    for (path, modules) in files {
+        writeln!(writer, "TN:")?;
+        writeln!(writer, "SF:{}", path.display())?;
+
+        let functions = modules
+            .iter()
+            .flat_map(|m| m.functions.iter().map(move |f| (m, f)));
+        let (mut found, mut hit) = (0, 0);
+        for (module, function) in functions.clone() {
+            writeln!(
+                writer,
+                "FN:{},{}::{}",
+                function.line, module.module_name, function.name
+            )?;
+        }
+        for (module, function) in functions.clone() {
+            let name = format!("{}::{}", module.module_name, function.name);
+            writeln!(writer, "FNDA:{},{}", function.hits, name)?;
+            found += 1;
+            hit += (function.hits > 0) as usize;
+        }
+        writeln!(writer, "FNF:{found}")?;
+        writeln!(writer, "FNH:{hit}")?;
+
+        // Each branch is its own block, so that branches on the same line are kept apart.
+        let (mut found, mut hit) = (0, 0);
+        let branches = functions.flat_map(|(_, f)| &f.branches);
+        for (block, branch) in branches.enumerate() {
+            for (i, taken) in branch.taken.iter().enumerate() {
+                match taken {
+                    Some(n) => writeln!(writer, "BRDA:{},{block},{i},{n}", branch.line)?,
+                    None => writeln!(writer, "BRDA:{},{block},{i},-", branch.line)?,
+                }
+            }
+            found += branch.taken.len();
+            hit += branch.covered();
+        }
+        writeln!(writer, "BRF:{found}")?;
+        writeln!(writer, "BRH:{hit}")?;
+
+        let mut lines = BTreeMap::new();
+        for module in &modules {
+            merge_lines(&mut lines, &module.lines());
+        }
+        for (line, hits) in &lines {
+            writeln!(writer, "DA:{line},{hits}")?;
+        }
+        writeln!(writer, "LF:{}", lines.len())?;
+        writeln!(writer, "LH:{}", lines.values().filter(|h| **h > 0).count())?;
+        writeln!(writer, "end_of_record")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,266 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Maps the per-instruction execution counts in a coverage map back to the lines and branches of
+//! a module's source file, for the standard coverage report formats in the `lcov` and `cobertura`
+//! modules.
+
+use crate::coverage_map::ExecCoverageMap;
+use move_binary_format::{
+    file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex},
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::files::FileHash;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The coverage of a module's source, by line. Lines are numbered from 1.
+#[derive(Debug)]
+pub struct ModuleLineCoverage {
+    /// The name of the module, as `address::name`.
+    pub module_name: String,
+    /// The path of the module's source file, as it should appear in reports.
+    pub source_path: PathBuf,
+    /// The module's functions, in definition order. Native functions are not included.
+    pub functions: Vec<FunctionLineCoverage>,
+}
+
+#[derive(Debug)]
+pub struct FunctionLineCoverage {
+    pub name: String,
+    /// The line that the function is declared on.
+    pub line: u32,
+    /// The number of times the function was called.
+    pub hits: u64,
+    /// The number of times each line containing the function's code was executed.
+    pub lines: BTreeMap<u32, u64>,
+    /// The function's conditional branches, in code order.
+    pub branches: Vec<BranchCoverage>,
+}
+
+/// A conditional branch (`if`, `while`, etc.) or a `match` on an enum.
+#[derive(Debug)]
+pub struct BranchCoverage {
+    pub line: u32,
+    /// The number of times each possible outcome of the branch was taken, or `None` if the branch
+    /// was never reached or the number of times is unknown.
+    ///
+    /// Coverage maps only record how often each instruction was executed, so an outcome's count
+    /// is the number of times its target was executed, when the branch is the only way to reach
+    /// that target. Otherwise (e.g., the target is where the arms of an `if` join), it is the
+    /// number of times the branch was executed less the counts of its other outcomes, when all of
+    /// those are known.
+    pub taken: Vec<Option<u64>>,
+}
+
+impl ModuleLineCoverage {
+    /// Computes the line coverage of `module`, whose source is `source`, from its `source_map`.
+    /// Code that the source map locates outside of `source` (e.g., code inlined from macros
+    /// defined in other files) is ignored.
+    pub fn new(
+        module: &CompiledModule,
+        coverage_map: &ExecCoverageMap,
+        source_map: &SourceMap,
+        source_path: &Path,
+        source: &str,
+    ) -> Self {
+        let module_id = module.self_id();
+        let module_map = coverage_map
+            .module_maps
+            .get(&(*module_id.address(), module_id.name().to_owned()));
+        let file_hash = FileHash::new(source);
+        let lines = LineIndex::new(source);
+
+        let mut functions = vec![];
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            let Some(code_unit) = &function_def.code else {
+                continue;
+            };
+            let Ok(function_map) =
+                source_map.get_function_source_map(FunctionDefinitionIndex(idx as u16))
+            else {
+                continue;
+            };
+
+            let fn_handle = module.function_handle_at(function_def.function);
+            let fn_name = module.identifier_at(fn_handle.name);
+            let fn_coverage = module_map.and_then(|m| m.get_function_coverage(fn_name));
+            let hits = |pc: CodeOffset| {
+                fn_coverage
+                    .and_then(|c| c.get(&(pc as u64)))
+                    .copied()
+                    .unwrap_or(0)
+            };
+
+            let line_of = |pc: CodeOffset| {
+                function_map
+                    .get_code_location(pc)
+                    .filter(|loc| loc.file_hash() == file_hash)
+                    .map(|loc| lines.line(loc.start() as usize))
+            };
+
+            // The number of ways to reach each instruction, including entering the function.
+            let mut predecessors = vec![0usize; code_unit.code.len()];
+            predecessors[0] += 1;
+            for pc in 0..code_unit.code.len() {
+                let successors = Bytecode::get_successors(
+                    pc as CodeOffset,
+                    &code_unit.code,
+                    &code_unit.jump_tables,
+                );
+                for successor in successors {
+                    predecessors[successor as usize] += 1;
+                }
+            }
+
+            let mut fn_lines = BTreeMap::new();
+            let mut branches = vec![];
+            for (pc, instr) in code_unit.code.iter().enumerate() {
+                let pc = pc as CodeOffset;
+                let Some(line) = line_of(pc) else {
+                    continue;
+                };
+
+                let count = fn_lines.entry(line).or_insert(0);
+                *count = (*count).max(hits(pc));
+
+                if matches!(
+                    instr,
+                    Bytecode::BrTrue(_) | Bytecode::BrFalse(_) | Bytecode::VariantSwitch(_)
+                ) {
+                    let executed = hits(pc);
+                    let targets =
+                        Bytecode::get_successors(pc, &code_unit.code, &code_unit.jump_tables);
+                    // Only a target that can't be reached any other way counts the times that
+                    // this outcome was taken.
+                    let exclusive: Vec<_> = targets
+                        .iter()
+                        .map(|target| {
+                            (predecessors[*target as usize] == 1)
+                                .then(|| hits(*target).min(executed))
+                        })
+                        .collect();
+                    let taken = (0..targets.len())
+                        .map(|i| {
+                            if executed == 0 {
+                                return None;
+                            }
+                            exclusive[i].or_else(|| {
+                                let others = exclusive
+                                    .iter()
+                                    .enumerate()
+                                    .filter(|(j, _)| *j != i)
+                                    .map(|(_, n)| *n)
+                                    .sum::<Option<u64>>()?;
+                                Some(executed.saturating_sub(others))
+                            })
+                        })
+                        .collect();
+                    branches.push(BranchCoverage { line, taken });
+                }
+            }
+
+            let definition_loc = function_map.definition_location;
+            functions.push(FunctionLineCoverage {
+                name: fn_name.to_string(),
+                line: if definition_loc.file_hash() == file_hash {
+                    lines.line(definition_loc.start() as usize)
+                } else {
+                    fn_lines.keys().next().copied().unwrap_or(1)
+                },
+                hits: hits(0),
+                lines: fn_lines,
+                branches,
+            });
+        }
+
+        Self {
+            module_name: format!(
+                "{}::{}",
+                module_id.address().to_hex_literal(),
+                module_id.name()
+            ),
+            source_path: source_path.to_path_buf(),
+            functions,
+        }
+    }
+
+    /// The number of times each line of the module was executed.
+    pub fn lines(&self) -> BTreeMap<u32, u64> {
+        let mut lines = BTreeMap::new();
+        for function in &self.functions {
+            merge_lines(&mut lines, &function.lines);
+        }
+        lines
+    }
+
+    /// The module's branches, grouped by line.
+    pub fn branches(&self) -> BTreeMap<u32, Vec<&BranchCoverage>> {
+        let mut branches: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for branch in self.functions.iter().flat_map(|f| &f.branches) {
+            branches.entry(branch.line).or_default().push(branch);
+        }
+        branches
+    }
+}
+
+impl BranchCoverage {
+    /// The number of outcomes of the branch that were taken at least once.
+    pub fn covered(&self) -> usize {
+        self.taken
+            .iter()
+            .filter(|t| t.is_some_and(|n| n > 0))
+            .count()
+    }
+}
+
+/// Merges the line counts in `other` into `lines`. A line is counted as executed as many times as
+/// the most executed code on it.
+pub(crate) fn merge_lines(lines: &mut BTreeMap<u32, u64>, other: &BTreeMap<u32, u64>) {
+    for (line, hits) in other {
+        let count = lines.entry(*line).or_insert(0);
+        *count = (*count).max(*hits);
+    }
+}
+
+/// Maps byte offsets in a source file to line numbers.
+struct LineIndex {
+    /// The byte offset that each line starts at.
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(source: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { line_starts }
+    }
+
+    /// The (1-based) line containing byte `offset`.
+    fn line(&self, offset: usize) -> u32 {
+        self.line_starts.partition_point(|start| *start <= offset) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineIndex;
+
+    #[test]
+    fn line_index() {
+        let lines = LineIndex::new("ab\n\ncd\nef");
+        // A newline belongs to the line that it ends.
+        let expected = [1, 1, 1, 2, 3, 3, 3, 4, 4];
+        for (offset, line) in expected.into_iter().enumerate() {
+            assert_eq!(line, lines.line(offset), "offset {offset}");
+        }
+        // Offsets past the end are on the last line.
+        assert_eq!(4, lines.line(100));
+    }
+}
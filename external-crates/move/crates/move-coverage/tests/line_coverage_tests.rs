@@ -0,0 +1,208 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_compiler::{compiled_unit::AnnotatedCompiledUnit, shared::NumericalAddress, Compiler};
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use move_coverage::{
+    cobertura::write_cobertura, coverage_map::ExecCoverageMap, lcov::write_lcov,
+    line_coverage::ModuleLineCoverage,
+};
+use std::{collections::BTreeMap, path::Path};
+
+const SOURCE: &str = "module 0x42::a {
+    public fun max(x: u64, y: u64): u64 {
+        if (x > y) x
+        else y
+    }
+
+    public fun unused(b: bool): u64 {
+        if (b) 1 else 2
+    }
+}
+
+module 0x42::b {
+    public fun call(x: u64): u64 {
+        0x42::a::max(x, 10)
+    }
+
+    public fun inc(mut x: u64, c: bool): u64 {
+        if (c) x = x + 1;
+        x
+    }
+}
+";
+
+fn compile(source: &str) -> Vec<AnnotatedCompiledUnit> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("m.move");
+    std::fs::write(&path, source).unwrap();
+
+    let (_, units) = Compiler::from_files(
+        None,
+        vec![path.to_str().unwrap().to_string()],
+        vec![],
+        BTreeMap::<String, NumericalAddress>::new(),
+    )
+    .build_and_report()
+    .unwrap();
+    units
+}
+
+/// The coverage of `SOURCE` after calling `b::call` three times, with `a::max` taking its `if`
+/// branch twice and its `else` branch once, and calling `b::inc` twice with `c` set.
+fn line_coverage() -> Vec<ModuleLineCoverage> {
+    let mut coverage_map = ExecCoverageMap::new("test".to_string());
+    let mut insert = |module: &str, function: &str, pcs: &[(u64, u64)]| {
+        for (pc, count) in pcs {
+            coverage_map.insert_multi(
+                AccountAddress::from_hex_literal("0x42").unwrap(),
+                Identifier::new(module).unwrap(),
+                Identifier::new(function).unwrap(),
+                *pc,
+                *count,
+            );
+        }
+    };
+
+    // 0: CopyLoc(0), 1: CopyLoc(1), 2: Gt, 3: BrFalse(7), 4: MoveLoc(0), 5: StLoc(2),
+    // 6: Branch(9), 7: MoveLoc(1), 8: StLoc(2), 9: MoveLoc(2), 10: Ret
+    insert(
+        "a",
+        "max",
+        &[
+            (0, 3),
+            (1, 3),
+            (2, 3),
+            (3, 3),
+            (4, 2),
+            (5, 2),
+            (6, 2),
+            (7, 1),
+            (8, 1),
+            (9, 3),
+            (10, 3),
+        ],
+    );
+    // 0: MoveLoc(0), 1: LdU64(10), 2: Call(a::max), 3: Ret
+    insert("b", "call", &[(0, 3), (1, 3), (2, 3), (3, 3)]);
+    // 0: MoveLoc(1), 1: BrFalse(6), 2: MoveLoc(0), 3: LdU64(1), 4: Add, 5: StLoc(0),
+    // 6: MoveLoc(0), 7: Ret
+    insert("b", "inc", &(0..8).map(|pc| (pc, 2)).collect::<Vec<_>>());
+
+    compile(SOURCE)
+        .iter()
+        .map(|unit| {
+            ModuleLineCoverage::new(
+                &unit.named_module.module,
+                &coverage_map,
+                &unit.named_module.source_map,
+                Path::new("sources/m.move"),
+                SOURCE,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn lcov_report() {
+    let mut out = vec![];
+    write_lcov(&line_coverage(), &mut out).unwrap();
+
+    // Both modules share a source file, so they are reported in a single record.
+    // `b::inc`'s `if` has no `else`, so its false edge jumps to where both paths join, and is
+    // only taken as often as the `if` is reached without running its body: never.
+    let expected = "\
+TN:
+SF:sources/m.move
+FN:2,0x42::a::max
+FN:7,0x42::a::unused
+FN:13,0x42::b::call
+FN:17,0x42::b::inc
+FNDA:3,0x42::a::max
+FNDA:0,0x42::a::unused
+FNDA:3,0x42::b::call
+FNDA:2,0x42::b::inc
+FNF:4
+FNH:3
+BRDA:3,0,0,2
+BRDA:3,0,1,1
+BRDA:8,1,0,-
+BRDA:8,1,1,-
+BRDA:18,2,0,2
+BRDA:18,2,1,0
+BRF:6
+BRH:3
+DA:3,3
+DA:4,1
+DA:8,0
+DA:14,3
+DA:18,2
+DA:19,2
+LF:6
+LH:5
+end_of_record
+";
+    assert_eq!(expected, String::from_utf8(out).unwrap());
+}
+
+#[test]
+fn cobertura_report() {
+    let mut out = vec![];
+    write_cobertura("test", &line_coverage(), Path::new("/pkg"), 0, &mut out).unwrap();
+
+    let expected = r#"<?xml version="1.0" ?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage line-rate="0.8333" branch-rate="0.5000" lines-covered="5" lines-valid="6" branches-covered="3" branches-valid="6" complexity="0" version="1.9" timestamp="0">
+  <sources>
+    <source>/pkg</source>
+  </sources>
+  <packages>
+    <package name="test" line-rate="0.8333" branch-rate="0.5000" complexity="0">
+      <classes>
+        <class name="0x42::a" filename="sources/m.move" line-rate="0.6667" branch-rate="0.5000" complexity="0">
+          <methods>
+            <method name="max" signature="" line-rate="1.0000" branch-rate="1.0000" complexity="0">
+              <lines>
+                <line number="3" hits="3" branch="true" condition-coverage="100% (2/2)"/>
+                <line number="4" hits="1" branch="false"/>
+              </lines>
+            </method>
+            <method name="unused" signature="" line-rate="0.0000" branch-rate="0.0000" complexity="0">
+              <lines>
+                <line number="8" hits="0" branch="true" condition-coverage="0% (0/2)"/>
+              </lines>
+            </method>
+          </methods>
+          <lines>
+            <line number="3" hits="3" branch="true" condition-coverage="100% (2/2)"/>
+            <line number="4" hits="1" branch="false"/>
+            <line number="8" hits="0" branch="true" condition-coverage="0% (0/2)"/>
+          </lines>
+        </class>
+        <class name="0x42::b" filename="sources/m.move" line-rate="1.0000" branch-rate="0.5000" complexity="0">
+          <methods>
+            <method name="call" signature="" line-rate="1.0000" branch-rate="1.0000" complexity="0">
+              <lines>
+                <line number="14" hits="3" branch="false"/>
+              </lines>
+            </method>
+            <method name="inc" signature="" line-rate="1.0000" branch-rate="0.5000" complexity="0">
+              <lines>
+                <line number="18" hits="2" branch="true" condition-coverage="50% (1/2)"/>
+                <line number="19" hits="2" branch="false"/>
+              </lines>
+            </method>
+          </methods>
+          <lines>
+            <line number="14" hits="3" branch="false"/>
+            <line number="18" hits="2" branch="true" condition-coverage="50% (1/2)"/>
+            <line number="19" hits="2" branch="false"/>
+          </lines>
+        </class>
+      </classes>
+    </package>
+  </packages>
+</coverage>
+"#;
+    assert_eq!(expected, String::from_utf8(out).unwrap());
+}
@@ -0,0 +1,51 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Name of the file, at the root of a package, that the formatter reads its configuration from.
+pub const CONFIG_FILE_NAME: &str = "move-fmt.toml";
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FormatterConfig {
+    /// The width (in characters) that the formatter tries to keep lines within. Lines can still
+    /// exceed it if there is nowhere to break them.
+    pub max_width: usize,
+
+    /// The number of spaces to indent by, per level of nesting.
+    pub indent_size: usize,
+}
+
+impl FormatterConfig {
+    /// Read the configuration from the TOML file at `path`. Fields that are missing from the file
+    /// are given their default values.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Unable to read formatter config at {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Invalid formatter config at {}", path.display()))
+    }
+
+    /// Read the configuration for the package rooted at `package_root`, from its
+    /// [`CONFIG_FILE_NAME`] file, if it has one, and use the default configuration otherwise.
+    pub fn for_package(package_root: &Path) -> Result<Self> {
+        let path = package_root.join(CONFIG_FILE_NAME);
+        if path.is_file() {
+            Self::from_file(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 100,
+            indent_size: 4,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A source formatter for Move.
+//!
+//! The formatter normalizes the whitespace of a source file -- indentation, spacing between
+//! tokens, blank lines, and where long lines are broken -- without changing its tokens, apart from
+//! adding or removing trailing commas in lists that are broken over multiple lines. Comments are
+//! preserved. Formatting is idempotent: Formatting already formatted source leaves it unchanged.
+
+pub mod config;
+mod printer;
+mod tokens;
+
+pub use config::{FormatterConfig, CONFIG_FILE_NAME};
+
+use move_command_line_common::files::FileHash;
+use move_compiler::{
+    diagnostics::Diagnostics,
+    editions::Edition,
+    parser::{self, lexer::Tok},
+};
+use std::fmt;
+use tokens::Lexeme;
+
+#[derive(Debug)]
+pub enum FormatError {
+    /// The source does not parse, so it is left as it is.
+    Parse(Diagnostics),
+
+    /// The formatter failed to produce output that is equivalent to its input. This is a bug in
+    /// the formatter.
+    Internal(anyhow::Error),
+}
+
+/// Format the contents of a Move source file, written for `edition`.
+pub fn format_string(
+    source: &str,
+    edition: Edition,
+    config: &FormatterConfig,
+) -> Result<String, FormatError> {
+    parser::parse_source(FileHash::new(source), source, edition).map_err(FormatError::Parse)?;
+
+    let lexemes = tokens::lex(source, edition).map_err(FormatError::Internal)?;
+    let tree = tokens::parse_tree(lexemes).map_err(FormatError::Internal)?;
+    let formatted = printer::print(&tree, config);
+
+    verify(source, &formatted, edition).map_err(FormatError::Internal)?;
+    Ok(formatted)
+}
+
+/// Check that `formatted` is `source` with only its whitespace (and trailing commas) changed, and
+/// that it still parses.
+fn verify(source: &str, formatted: &str, edition: Edition) -> anyhow::Result<()> {
+    let (source_tokens, source_comments) = significant(source, edition)?;
+    let (formatted_tokens, formatted_comments) = significant(formatted, edition)?;
+
+    if source_tokens != formatted_tokens {
+        let position = source_tokens
+            .iter()
+            .zip(&formatted_tokens)
+            .position(|(s, f)| s != f)
+            .unwrap_or(source_tokens.len().min(formatted_tokens.len()));
+        anyhow::bail!(
+            "Formatting changed the tokens of the source, starting from token {position}: {:?}",
+            formatted_tokens.get(position),
+        );
+    }
+
+    if source_comments != formatted_comments {
+        anyhow::bail!("Formatting changed the comments in the source");
+    }
+
+    if parser::parse_source(FileHash::new(formatted), formatted, edition).is_err() {
+        anyhow::bail!("Formatted source does not parse");
+    }
+
+    Ok(())
+}
+
+/// The tokens of `source`, without commas that come directly before a closing delimiter, and the
+/// lines of its comments, without leading and trailing whitespace.
+fn significant(source: &str, edition: Edition) -> anyhow::Result<(Vec<&str>, Vec<&str>)> {
+    let mut tokens = vec![];
+    let mut comments = vec![];
+    let mut trailing_comma = false;
+
+    for lexeme in tokens::lex(source, edition)? {
+        match lexeme {
+            Lexeme::Comment(c) => comments.extend(c.text.lines().map(str::trim)),
+            Lexeme::Token(t) => {
+                let is_close = matches!(t.tok, Tok::RParen | Tok::RBracket | Tok::RBrace);
+                if trailing_comma && !is_close {
+                    tokens.push(",");
+                }
+
+                trailing_comma = t.tok == Tok::Comma;
+                if !trailing_comma {
+                    tokens.push(t.text);
+                }
+            }
+        }
+    }
+
+    if trailing_comma {
+        tokens.push(",");
+    }
+
+    Ok((tokens, comments))
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Parse(_) => write!(f, "Source has syntax errors"),
+            FormatError::Internal(e) => write!(f, "Failed to format source: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
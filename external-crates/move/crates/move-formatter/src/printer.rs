@@ -0,0 +1,849 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lays out the tokens and comments of a source file.
+//!
+//! The layout follows the line structure of the source: Line breaks between statements and
+//! declarations are kept (collapsing runs of blank lines into one), while the spacing between
+//! tokens on a line, and the indentation of lines, are normalized. Delimited groups that do not
+//! fit within the configured width, or that already span multiple lines, are broken up:
+//!
+//! - Blocks (`{ ... }` containing statements or declarations) are laid out with one statement per
+//!   line, indented one level deeper than their delimiters.
+//! - Lists (delimited groups containing comma-separated elements) are laid out with one element per
+//!   line, each followed by a comma. If the only thing stopping a list from fitting on one line is
+//!   a block or a list at its end (e.g. a lambda's body passed as the last argument to a call),
+//!   that is broken up instead, and the rest of the list stays on one line.
+//! - Other groups (e.g. parenthesized expressions) keep their line breaks, like statements.
+
+use crate::{
+    config::FormatterConfig,
+    tokens::{Comment, Group, Node},
+};
+use move_compiler::parser::lexer::Tok;
+
+/// Lay out a whole source file.
+pub(crate) fn print(items: &[Node], config: &FormatterConfig) -> String {
+    let mut printer = Printer::new(config);
+    let roles = roles(items);
+    printer.sequence(items, &roles, 0, Sequence::BLOCK);
+    printer.finish()
+}
+
+/// How a token is laid out relative to its neighbours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    /// Names, literals, keywords, and most punctuation.
+    Word,
+    /// An operator between two operands, e.g. `+` or `=`.
+    BinaryOp,
+    /// An operator applied to the operand after it, e.g. `!` or `&`.
+    UnaryOp,
+    /// The `<` and `>` around type arguments or type parameters.
+    TypeOpen,
+    TypeClose,
+    /// The `!` of a macro call.
+    MacroBang,
+    /// The `|`s around the parameters of a lambda or a function type.
+    LambdaOpen,
+    LambdaClose,
+    /// A comma between the elements of a list.
+    Separator,
+    Group,
+    Comment,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GroupKind {
+    /// Comma-separated elements, e.g. arguments, parameters, fields or match arms.
+    List,
+    /// Statements or declarations.
+    Block,
+    /// Anything else, e.g. a parenthesized expression, laid out like the sequence around it.
+    Inline,
+}
+
+/// How to lay out a sequence of items.
+#[derive(Clone, Copy)]
+struct Sequence {
+    /// Whether statements that follow a `;` always start on a new line.
+    break_after_semicolon: bool,
+    /// Whether lines that continue the previous line's statement are indented an extra level.
+    indent_continuations: bool,
+    /// The width of the text that will follow the sequence on its last line.
+    tail: usize,
+}
+
+impl Sequence {
+    const BLOCK: Self = Self {
+        break_after_semicolon: true,
+        indent_continuations: true,
+        tail: 0,
+    };
+}
+
+/// One element of a list: `body` are the indices of its items (up to the last item that is not a
+/// comment), and `trailing` are the indices of the comments that follow it on the same line (or
+/// before its separator).
+struct Element {
+    body: std::ops::Range<usize>,
+    trailing: Vec<usize>,
+}
+
+struct Printer<'c> {
+    config: &'c FormatterConfig,
+    out: String,
+    /// The column (in characters) that the next character written to `out` will be at.
+    column: usize,
+    /// The indentation level of the current line (or of the next line, if there are pending line
+    /// breaks).
+    line_indent: usize,
+    /// Line breaks that will be written before the next text. Line breaks are written lazily so
+    /// that there are no trailing spaces, and no leading line breaks in the output.
+    pending_newlines: usize,
+}
+
+impl<'c> Printer<'c> {
+    fn new(config: &'c FormatterConfig) -> Self {
+        Self {
+            config,
+            out: String::new(),
+            column: 0,
+            line_indent: 0,
+            pending_newlines: 1,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.out.truncate(self.out.trim_end().len());
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.out
+    }
+
+    /// Start a new line at indentation level `indent`, after a blank line if `blank` is set.
+    fn newline(&mut self, blank: bool, indent: usize) {
+        self.pending_newlines = self.pending_newlines.max(if blank { 2 } else { 1 });
+        self.line_indent = indent;
+    }
+
+    fn space(&mut self) {
+        if self.pending_newlines == 0 {
+            self.out.push(' ');
+            self.column += 1;
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        if self.pending_newlines > 0 {
+            if !self.out.is_empty() {
+                self.out.truncate(self.out.trim_end_matches(' ').len());
+                for _ in 0..self.pending_newlines {
+                    self.out.push('\n');
+                }
+            }
+
+            self.pending_newlines = 0;
+            self.column = self.line_indent * self.config.indent_size;
+            self.out.extend(std::iter::repeat(' ').take(self.column));
+        }
+
+        self.out.push_str(text);
+        match text.rfind('\n') {
+            Some(i) => self.column = text[i + 1..].chars().count(),
+            None => self.column += text.chars().count(),
+        }
+    }
+
+    /// The column that the next text will be written at.
+    fn column(&self) -> usize {
+        if self.pending_newlines > 0 {
+            self.line_indent * self.config.indent_size
+        } else {
+            self.column
+        }
+    }
+
+    /// Whether `width` characters, followed by `tail` more, fit on the current line.
+    fn fits(&self, width: usize, tail: usize) -> bool {
+        self.column() + width + tail <= self.config.max_width
+    }
+
+    /// Lay out `items` starting on the current line. The caller is responsible for any line break
+    /// before the first item. Lines started within the sequence are indented at level `indent`.
+    fn sequence(&mut self, items: &[Node], roles: &[Role], indent: usize, seq: Sequence) {
+        // The index of the last item that was not a comment.
+        let mut prev = None;
+
+        for (i, item) in items.iter().enumerate() {
+            let mut newlines = if i == 0 { 0 } else { item.newlines_before() };
+
+            if i > 0 {
+                match &items[i - 1] {
+                    // `else` goes on the same line as the block before it.
+                    Node::Group(g) if item.is_tok(Tok::Else) && g.open.tok == Tok::LBrace => {
+                        newlines = 0;
+                    }
+
+                    Node::Comment(c) if c.is_line => newlines = newlines.max(1),
+
+                    // Start a new line after a statement, unless it is followed by a comment.
+                    Node::Token(t)
+                        if seq.break_after_semicolon
+                            && t.tok == Tok::Semicolon
+                            && !matches!(item, Node::Comment(c) if c.newlines_before == 0) =>
+                    {
+                        newlines = newlines.max(1)
+                    }
+
+                    _ => (),
+                }
+            }
+
+            if newlines > 0 {
+                let continuation =
+                    seq.indent_continuations && prev.is_some_and(|j| !ends_statement(items, j));
+                self.newline(newlines > 1, indent + continuation as usize);
+            } else if i > 0 && space_between(&items[i - 1], roles[i - 1], item, roles[i]) {
+                self.space();
+            }
+
+            match item {
+                Node::Token(t) => self.write(t.text),
+                Node::Comment(c) => self.comment(c),
+                Node::Group(g) => {
+                    let tail = tail_width(items, roles, i, seq.tail);
+                    self.group(g, pad_braces(items, i), tail)
+                }
+            }
+
+            if !matches!(item, Node::Comment(_)) {
+                prev = Some(i);
+            }
+        }
+    }
+
+    fn comment(&mut self, comment: &Comment) {
+        if !comment.text.contains('\n') {
+            self.write(comment.text);
+            return;
+        }
+
+        // Shift the lines of a multi-line comment by as much as its first line moved.
+        let column = self.column();
+        let mut lines = comment.text.lines().map(str::trim_end);
+        let mut text = lines.next().unwrap_or_default().to_string();
+        for line in lines {
+            text.push('\n');
+            if column >= comment.column {
+                if !line.is_empty() {
+                    text.extend(std::iter::repeat(' ').take(column - comment.column));
+                }
+                text.push_str(line);
+            } else {
+                let indent = line.len() - line.trim_start_matches(' ').len();
+                text.push_str(&line[indent.min(comment.column - column)..]);
+            }
+        }
+
+        self.write(&text);
+    }
+
+    /// Lay out a group. `pad` controls whether braces are padded with spaces on the inside when
+    /// the group fits on one line, and `tail` is the width of the text that must follow the group
+    /// on its last line.
+    fn group(&mut self, group: &Group, pad: bool, tail: usize) {
+        if group.items.is_empty() {
+            self.write(group.open.text);
+            self.write(group.close.text);
+            return;
+        }
+
+        let roles = roles(&group.items);
+        let kind = group_kind(group, &roles);
+        if kind == GroupKind::List || (kind == GroupKind::Block && !has_statements(group)) {
+            if let Some(flat) = flat_group(group, pad) {
+                if self.fits(flat.chars().count(), tail) {
+                    self.write(&flat);
+                    return;
+                }
+            }
+        }
+
+        match kind {
+            GroupKind::Block => self.block(group, &roles),
+            GroupKind::List => {
+                if !self.hug(group, &roles, tail) {
+                    self.list(group, &roles)
+                }
+            }
+            GroupKind::Inline => self.inline(group, &roles, tail),
+        }
+    }
+
+    fn block(&mut self, group: &Group, roles: &[Role]) {
+        self.write(group.open.text);
+        let indent = self.line_indent;
+        let start = self.comments_after_open(group);
+
+        self.newline(false, indent + 1);
+        self.sequence(
+            &group.items[start..],
+            &roles[start..],
+            indent + 1,
+            Sequence::BLOCK,
+        );
+        self.newline(false, indent);
+        self.write(group.close.text);
+    }
+
+    fn list(&mut self, group: &Group, roles: &[Role]) {
+        self.write(group.open.text);
+        let indent = self.line_indent;
+        let start = self.comments_after_open(group);
+        let items = &group.items[start..];
+        let roles = &roles[start..];
+
+        let element_seq = Sequence {
+            break_after_semicolon: false,
+            indent_continuations: true,
+            // For the comma
+            tail: 1,
+        };
+
+        for (i, Element { body, trailing }) in elements(items, roles).into_iter().enumerate() {
+            if !body.is_empty() {
+                let blank = i > 0 && items[body.start].newlines_before() > 1;
+                self.newline(blank, indent + 1);
+                self.sequence(&items[body.clone()], &roles[body], indent + 1, element_seq);
+                self.write(",");
+            }
+
+            for j in trailing {
+                let Node::Comment(c) = &items[j] else {
+                    continue;
+                };
+
+                if c.newlines_before > 0 || self.pending_newlines > 0 {
+                    self.newline(c.newlines_before > 1, indent + 1);
+                } else {
+                    self.space();
+                }
+
+                self.comment(c);
+                if c.is_line {
+                    self.newline(false, indent + 1);
+                }
+            }
+        }
+
+        self.newline(false, indent);
+        self.write(group.close.text);
+    }
+
+    /// Try to lay out a list on one line, apart from the block or list that it ends with, which is
+    /// broken up. Returns whether the list was laid out.
+    fn hug(&mut self, group: &Group, roles: &[Role], tail: usize) -> bool {
+        if group.open.tok == Tok::LBrace
+            || group.close.newlines_before > 0
+            || group.items.iter().any(|item| {
+                item.newlines_before() > 0 || matches!(item, Node::Comment(c) if c.is_line)
+            })
+        {
+            return false;
+        }
+
+        // Find the last item, ignoring a trailing separator, which must be a group.
+        let mut last = group.items.len() - 1;
+        if roles[last] == Role::Separator && last > 0 {
+            last -= 1;
+        }
+
+        let Node::Group(inner) = &group.items[last] else {
+            return false;
+        };
+
+        let inner_roles = self::roles(&inner.items);
+        let huggable = match inner.open.tok {
+            Tok::LBrace => true,
+            Tok::LBracket => group_kind(inner, &inner_roles) == GroupKind::List,
+            _ => false,
+        };
+
+        if !huggable {
+            return false;
+        }
+
+        let mut prefix = group.open.text.to_string();
+        if flat_sequence(&group.items[..last], &roles[..last], false, &mut prefix).is_none() {
+            return false;
+        }
+
+        if last > 0
+            && space_between(
+                &group.items[last - 1],
+                roles[last - 1],
+                &group.items[last],
+                Role::Group,
+            )
+        {
+            prefix.push(' ');
+        }
+
+        if !self.fits(prefix.chars().count() + inner.open.text.len(), 0) {
+            return false;
+        }
+
+        self.write(&prefix);
+        self.group(
+            inner,
+            pad_braces(&group.items, last),
+            group.close.text.len() + tail,
+        );
+        self.write(group.close.text);
+        true
+    }
+
+    fn inline(&mut self, group: &Group, roles: &[Role], tail: usize) {
+        self.write(group.open.text);
+        let indent = self.line_indent;
+
+        if group.items[0].newlines_before() > 0 {
+            self.newline(false, indent + 1);
+        }
+
+        let seq = Sequence {
+            break_after_semicolon: false,
+            indent_continuations: false,
+            tail: group.close.text.len() + tail,
+        };
+
+        self.sequence(&group.items, roles, indent + 1, seq);
+        if group.close.newlines_before > 0 {
+            self.newline(false, indent);
+        }
+
+        self.write(group.close.text);
+    }
+
+    /// Write the line comments that follow a group's opening delimiter on the same line, and
+    /// return the index of the first item after them.
+    fn comments_after_open(&mut self, group: &Group) -> usize {
+        let mut start = 0;
+        while let Some(Node::Comment(c)) = group.items.get(start) {
+            if !c.is_line || c.newlines_before > 0 {
+                break;
+            }
+
+            self.space();
+            self.write(c.text);
+            start += 1;
+        }
+
+        start
+    }
+}
+
+/// Assign roles to the items of a sequence.
+fn roles(items: &[Node]) -> Vec<Role> {
+    let mut roles = Vec::with_capacity(items.len());
+    let mut prev: Option<(&Node, Role)> = None;
+    let mut type_depth = 0usize;
+    let mut in_lambda = false;
+
+    for item in items {
+        let role = match item {
+            Node::Comment(_) => {
+                roles.push(Role::Comment);
+                continue;
+            }
+
+            Node::Group(_) => Role::Group,
+
+            Node::Token(t) => {
+                let after_name = prev.is_some_and(|(p, _)| is_name(p));
+                let after_operand = prev.is_some_and(|(p, r)| is_operand(p, r));
+                let after_macro = prev.is_some_and(|(_, r)| r == Role::MacroBang);
+                let glued = prev
+                    .and_then(|(p, _)| p.last_token())
+                    .is_some_and(|p| p.end == t.start);
+
+                match t.tok {
+                    // Whitespace is significant here: A `<` directly after a name starts type
+                    // arguments, while a `<` after whitespace is a comparison.
+                    Tok::Less if (after_name && glued) || after_macro => {
+                        type_depth += 1;
+                        Role::TypeOpen
+                    }
+
+                    Tok::Greater if type_depth > 0 => {
+                        type_depth -= 1;
+                        Role::TypeClose
+                    }
+
+                    Tok::GreaterGreater if type_depth > 0 => {
+                        type_depth = type_depth.saturating_sub(2);
+                        Role::TypeClose
+                    }
+
+                    Tok::Exclaim if after_name => Role::MacroBang,
+
+                    Tok::Comma if type_depth == 0 && !in_lambda => Role::Separator,
+
+                    Tok::Pipe if in_lambda => {
+                        in_lambda = false;
+                        Role::LambdaClose
+                    }
+
+                    Tok::Pipe if !after_operand => {
+                        in_lambda = true;
+                        Role::LambdaOpen
+                    }
+
+                    // A lambda without parameters.
+                    Tok::PipePipe if !after_operand => Role::Word,
+
+                    Tok::Amp | Tok::Star | Tok::AtSign if !after_operand => Role::UnaryOp,
+                    Tok::Exclaim | Tok::AmpMut | Tok::NumSign => Role::UnaryOp,
+
+                    Tok::Plus
+                    | Tok::Minus
+                    | Tok::Star
+                    | Tok::Slash
+                    | Tok::Percent
+                    | Tok::Amp
+                    | Tok::AmpAmp
+                    | Tok::Pipe
+                    | Tok::PipePipe
+                    | Tok::Caret
+                    | Tok::Less
+                    | Tok::LessEqual
+                    | Tok::LessLess
+                    | Tok::Greater
+                    | Tok::GreaterEqual
+                    | Tok::GreaterGreater
+                    | Tok::Equal
+                    | Tok::EqualEqual
+                    | Tok::ExclaimEqual
+                    | Tok::EqualGreater
+                    | Tok::EqualEqualGreater
+                    | Tok::LessEqualEqualGreater
+                    | Tok::MinusGreater
+                    | Tok::AtSign => Role::BinaryOp,
+
+                    _ => Role::Word,
+                }
+            }
+        };
+
+        roles.push(role);
+        prev = Some((item, role));
+    }
+
+    roles
+}
+
+fn is_name(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Token(t) if matches!(
+            t.tok,
+            Tok::Identifier | Tok::RestrictedIdentifier | Tok::SyntaxIdentifier
+        )
+    )
+}
+
+/// Whether an operator following `node` would be a binary operator.
+fn is_operand(node: &Node, role: Role) -> bool {
+    match (node, role) {
+        (_, Role::TypeClose) => true,
+        (Node::Token(t), Role::Word) => matches!(
+            t.tok,
+            Tok::Identifier
+                | Tok::RestrictedIdentifier
+                | Tok::SyntaxIdentifier
+                | Tok::NumValue
+                | Tok::NumTypedValue
+                | Tok::ByteStringValue
+                | Tok::True
+                | Tok::False
+        ),
+        (Node::Group(g), _) => {
+            g.open.tok != Tok::LBrace || group_kind(g, &roles(&g.items)) == GroupKind::List
+        }
+        _ => false,
+    }
+}
+
+fn group_kind(group: &Group, roles: &[Role]) -> GroupKind {
+    if !roles.contains(&Role::Separator) {
+        return if group.open.tok == Tok::LBrace {
+            GroupKind::Block
+        } else {
+            GroupKind::Inline
+        };
+    }
+
+    if group.open.tok != Tok::LBrace {
+        return GroupKind::List;
+    }
+
+    // Braces with commas in them could still contain declarations (e.g. `has key, store` in a
+    // module), or a lambda (e.g. `|x, y| x + y` in a block).
+    let is_match = group
+        .items
+        .iter()
+        .any(|item| item.is_tok(Tok::EqualGreater));
+    let is_block = group.items.iter().zip(roles).any(|(item, role)| {
+        (*role == Role::LambdaOpen && !is_match)
+            || matches!(
+                item,
+                Node::Token(t) if matches!(
+                    t.tok,
+                    Tok::Semicolon
+                        | Tok::Fun
+                        | Tok::Struct
+                        | Tok::Enum
+                        | Tok::Module
+                        | Tok::Use
+                        | Tok::Const
+                        | Tok::Friend
+                        | Tok::Let
+                        | Tok::Public
+                        | Tok::Spec
+                        | Tok::NumSign
+                )
+            )
+    });
+
+    if is_block {
+        GroupKind::Block
+    } else {
+        GroupKind::List
+    }
+}
+
+/// Whether a block contains statements or declarations, rather than a single expression. Such
+/// blocks are always broken up, with one statement per line.
+fn has_statements(group: &Group) -> bool {
+    group.items.iter().any(|item| {
+        matches!(
+            item,
+            Node::Token(t) if matches!(
+                t.tok,
+                Tok::Semicolon
+                    | Tok::Fun
+                    | Tok::Struct
+                    | Tok::Enum
+                    | Tok::Use
+                    | Tok::Const
+                    | Tok::Friend
+                    | Tok::Spec
+                    | Tok::NumSign
+            )
+        )
+    })
+}
+
+/// Whether the item at `i` ends a statement or declaration, so that the line after it is not a
+/// continuation.
+fn ends_statement(items: &[Node], i: usize) -> bool {
+    match &items[i] {
+        Node::Token(t) => matches!(t.tok, Tok::Semicolon | Tok::Comma),
+        Node::Group(g) => {
+            g.open.tok == Tok::LBrace
+                || (g.open.tok == Tok::LBracket && i > 0 && items[i - 1].is_tok(Tok::NumSign))
+        }
+        Node::Comment(_) => false,
+    }
+}
+
+/// Whether there is a space between two adjacent items on the same line.
+fn space_between(prev: &Node, prev_role: Role, next: &Node, next_role: Role) -> bool {
+    use Role as R;
+
+    match (prev_role, next_role) {
+        (R::Comment, _) | (_, R::Comment) => return true,
+        (R::UnaryOp, _) => return prev.is_tok(Tok::AmpMut),
+        (R::TypeOpen | R::MacroBang | R::LambdaOpen, _) => return false,
+        (_, R::TypeOpen | R::TypeClose | R::MacroBang | R::LambdaClose | R::Separator) => {
+            return false
+        }
+        _ => (),
+    }
+
+    let (Some(p), Some(n)) = (prev.last_token(), next.first_token()) else {
+        return true;
+    };
+
+    match (p.tok, n.tok) {
+        (_, Tok::Comma | Tok::Semicolon | Tok::Colon | Tok::Period | Tok::ColonColon) => false,
+        (Tok::Period | Tok::ColonColon, _) => false,
+
+        // Calls, declarations and `public(package)`.
+        (
+            Tok::Identifier | Tok::RestrictedIdentifier | Tok::SyntaxIdentifier | Tok::Public,
+            Tok::LParen,
+        ) => false,
+
+        // Vector literals, e.g. `vector[...]`, and index expressions, e.g. `v[i][j]`.
+        (Tok::Identifier | Tok::RParen | Tok::RBracket, Tok::LBracket) => false,
+        (_, Tok::LParen | Tok::LBracket) => prev_role != R::TypeClose,
+
+        _ => true,
+    }
+}
+
+/// Whether the braces of the group at `i` should be padded with spaces on the inside. They are,
+/// except when they follow a `::`, as in `use a::{b, c}`.
+fn pad_braces(items: &[Node], i: usize) -> bool {
+    i == 0 || !items[i - 1].is_tok(Tok::ColonColon)
+}
+
+/// The width of the text that must follow the item at `i` on the same line: the tokens that follow
+/// it without a space, and the sequence's own tail if they run until the end of the sequence.
+fn tail_width(items: &[Node], roles: &[Role], i: usize, seq_tail: usize) -> usize {
+    let mut width = 0;
+    for j in i + 1..items.len() {
+        match &items[j] {
+            Node::Token(t)
+                if t.newlines_before == 0
+                    && !space_between(&items[j - 1], roles[j - 1], &items[j], roles[j]) =>
+            {
+                width += t.text.chars().count()
+            }
+            _ => return width,
+        }
+    }
+
+    width + seq_tail
+}
+
+/// Split the items of a list into its elements.
+fn elements(items: &[Node], roles: &[Role]) -> Vec<Element> {
+    let mut elements = vec![];
+    let mut start = 0;
+    let mut i = 0;
+
+    let body_end = |start: usize, end: usize| {
+        let mut end = end;
+        while end > start && matches!(items[end - 1], Node::Comment(_)) {
+            end -= 1;
+        }
+        end
+    };
+
+    while i < items.len() {
+        if roles[i] != Role::Separator {
+            i += 1;
+            continue;
+        }
+
+        let end = body_end(start, i);
+        let mut trailing: Vec<_> = (end..i).collect();
+
+        i += 1;
+        while let Some(Node::Comment(c)) = items.get(i) {
+            if c.newlines_before > 0 {
+                break;
+            }
+            trailing.push(i);
+            i += 1;
+        }
+
+        elements.push(Element {
+            body: start..end,
+            trailing,
+        });
+        start = i;
+    }
+
+    if start < items.len() {
+        let end = body_end(start, items.len());
+        elements.push(Element {
+            body: start..end,
+            trailing: (end..items.len()).collect(),
+        });
+    }
+
+    elements
+}
+
+/// Lay out a group on one line, if it can be.
+fn flat_group(group: &Group, pad: bool) -> Option<String> {
+    let mut out = String::new();
+    write_flat_group(group, pad, &mut out)?;
+    Some(out)
+}
+
+fn write_flat_group(group: &Group, pad: bool, out: &mut String) -> Option<()> {
+    out.push_str(group.open.text);
+    if group.items.is_empty() {
+        out.push_str(group.close.text);
+        return Some(());
+    }
+
+    if group.items[0].newlines_before() > 0 || group.close.newlines_before > 0 {
+        return None;
+    }
+
+    let roles = roles(&group.items);
+    let pad = pad && group.open.tok == Tok::LBrace;
+    let is_list = group_kind(group, &roles) == GroupKind::List;
+
+    if pad {
+        out.push(' ');
+    }
+
+    flat_sequence(&group.items, &roles, is_list, out)?;
+
+    if pad {
+        out.push(' ');
+    }
+
+    out.push_str(group.close.text);
+    Some(())
+}
+
+/// Lay out a sequence of items on one line, if it can be, optionally dropping the trailing
+/// separator of a list.
+fn flat_sequence(
+    items: &[Node],
+    roles: &[Role],
+    drop_trailing_separator: bool,
+    out: &mut String,
+) -> Option<()> {
+    let trailing_separator = roles
+        .iter()
+        .rposition(|r| *r != Role::Comment)
+        .filter(|i| drop_trailing_separator && roles[*i] == Role::Separator);
+
+    let mut prev = None;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 && item.newlines_before() > 0 {
+            return None;
+        }
+
+        if Some(i) == trailing_separator {
+            continue;
+        }
+
+        if let Some(j) = prev {
+            if space_between(&items[j], roles[j], item, roles[i]) {
+                out.push(' ');
+            }
+        }
+
+        match item {
+            Node::Token(t) => out.push_str(t.text),
+            Node::Comment(c) if c.is_line || c.text.contains('\n') => return None,
+            Node::Comment(c) => out.push_str(c.text),
+            Node::Group(g) => write_flat_group(g, pad_braces(items, i), out)?,
+        }
+
+        prev = Some(i);
+    }
+
+    Some(())
+}
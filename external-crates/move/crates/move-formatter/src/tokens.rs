@@ -0,0 +1,233 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Splits source text into the tokens and comments that the formatter lays out, and nests them
+//! into groups by their delimiters: `(...)`, `[...]` and `{...}`.
+
+use anyhow::{bail, Result};
+use move_command_line_common::files::FileHash;
+use move_compiler::{
+    editions::Edition,
+    parser::lexer::{Lexer, Tok},
+};
+
+pub(crate) struct Token<'a> {
+    pub tok: Tok,
+    pub text: &'a str,
+    pub start: usize,
+    pub end: usize,
+    /// The number of line breaks between this token and the token or comment before it.
+    pub newlines_before: usize,
+}
+
+pub(crate) struct Comment<'a> {
+    /// The text of the comment, including its delimiters, without trailing whitespace.
+    pub text: &'a str,
+    /// Whether this is a `//` comment (which runs until the end of the line), or a `/* */` comment.
+    pub is_line: bool,
+    /// The column (in characters) that the comment started at in the source.
+    pub column: usize,
+    /// The number of line breaks between this comment and the token or comment before it.
+    pub newlines_before: usize,
+}
+
+pub(crate) struct Group<'a> {
+    pub open: Token<'a>,
+    pub items: Vec<Node<'a>>,
+    pub close: Token<'a>,
+}
+
+pub(crate) enum Node<'a> {
+    Token(Token<'a>),
+    Comment(Comment<'a>),
+    Group(Group<'a>),
+}
+
+impl<'a> Node<'a> {
+    /// The token at the start of this node (the opening delimiter for groups), if it is not a
+    /// comment.
+    pub fn first_token(&self) -> Option<&Token<'a>> {
+        match self {
+            Node::Token(t) => Some(t),
+            Node::Group(g) => Some(&g.open),
+            Node::Comment(_) => None,
+        }
+    }
+
+    /// The token at the end of this node (the closing delimiter for groups), if it is not a
+    /// comment.
+    pub fn last_token(&self) -> Option<&Token<'a>> {
+        match self {
+            Node::Token(t) => Some(t),
+            Node::Group(g) => Some(&g.close),
+            Node::Comment(_) => None,
+        }
+    }
+
+    pub fn newlines_before(&self) -> usize {
+        match self {
+            Node::Token(t) => t.newlines_before,
+            Node::Comment(c) => c.newlines_before,
+            Node::Group(g) => g.open.newlines_before,
+        }
+    }
+
+    pub fn is_tok(&self, tok: Tok) -> bool {
+        matches!(self, Node::Token(t) if t.tok == tok)
+    }
+}
+
+/// A comment or a token, in source order.
+pub(crate) enum Lexeme<'a> {
+    Token(Token<'a>),
+    Comment(Comment<'a>),
+}
+
+/// Lex `source` into its tokens and comments (not including the EOF token).
+pub(crate) fn lex(source: &str, edition: Edition) -> Result<Vec<Lexeme<'_>>> {
+    let mut lexer = Lexer::new(source, FileHash::new(source), edition);
+    let mut lexemes = vec![];
+    loop {
+        if lexer.advance().is_err() {
+            bail!("Failed to lex source");
+        }
+
+        let start = lexer.start_loc();
+        let newlines_before = scan_gap(source, lexer.previous_end_loc(), start, &mut lexemes);
+        if lexer.peek() == Tok::EOF {
+            break;
+        }
+
+        // The content of `&mut` includes the whitespace after it.
+        let text = lexer.content().trim_end();
+        lexemes.push(Lexeme::Token(Token {
+            tok: lexer.peek(),
+            text,
+            start,
+            end: start + text.len(),
+            newlines_before,
+        }));
+    }
+
+    Ok(lexemes)
+}
+
+/// Nest the lexemes of a whole file into groups.
+pub(crate) fn parse_tree(lexemes: Vec<Lexeme<'_>>) -> Result<Vec<Node<'_>>> {
+    let mut stack: Vec<(Token, Vec<Node>)> = vec![];
+    let mut items = vec![];
+
+    for lexeme in lexemes {
+        match lexeme {
+            Lexeme::Comment(c) => items.push(Node::Comment(c)),
+            Lexeme::Token(t) => match t.tok {
+                Tok::LParen | Tok::LBracket | Tok::LBrace => {
+                    stack.push((t, std::mem::take(&mut items)));
+                }
+
+                Tok::RParen | Tok::RBracket | Tok::RBrace => {
+                    let Some((open, outer)) = stack.pop() else {
+                        bail!("Unmatched '{}'", t.text);
+                    };
+
+                    if closing_delimiter(open.tok) != t.tok {
+                        bail!("Mismatched '{}' and '{}'", open.text, t.text);
+                    }
+
+                    let inner = std::mem::replace(&mut items, outer);
+                    items.push(Node::Group(Group {
+                        open,
+                        items: inner,
+                        close: t,
+                    }));
+                }
+
+                _ => items.push(Node::Token(t)),
+            },
+        }
+    }
+
+    if let Some((open, _)) = stack.pop() {
+        bail!("Unclosed '{}'", open.text);
+    }
+
+    Ok(items)
+}
+
+pub(crate) fn closing_delimiter(tok: Tok) -> Tok {
+    match tok {
+        Tok::LParen => Tok::RParen,
+        Tok::LBracket => Tok::RBracket,
+        Tok::LBrace => Tok::RBrace,
+        _ => Tok::EOF,
+    }
+}
+
+/// Scan the text between two tokens, `source[start..end]`, which only contains whitespace and
+/// comments. Comments are added to `lexemes`, and the number of line breaks after the last comment
+/// (or since `start` if there are no comments) is returned.
+fn scan_gap<'a>(source: &'a str, start: usize, end: usize, lexemes: &mut Vec<Lexeme<'a>>) -> usize {
+    let mut newlines = 0;
+    let mut pos = start;
+
+    while pos < end {
+        let rest = &source[pos..end];
+        if rest.starts_with("//") {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            lexemes.push(Lexeme::Comment(Comment {
+                text: rest[..len].trim_end(),
+                is_line: true,
+                column: column(source, pos),
+                newlines_before: newlines,
+            }));
+            newlines = 0;
+            pos += len;
+        } else if rest.starts_with("/*") {
+            let len = block_comment_len(rest);
+            lexemes.push(Lexeme::Comment(Comment {
+                text: &rest[..len],
+                is_line: false,
+                column: column(source, pos),
+                newlines_before: newlines,
+            }));
+            newlines = 0;
+            pos += len;
+        } else {
+            let c = rest.chars().next().unwrap();
+            if c == '\n' {
+                newlines += 1;
+            }
+            pos += c.len_utf8();
+        }
+    }
+
+    newlines
+}
+
+/// The length of the (possibly nested) block comment at the start of `text`.
+fn block_comment_len(text: &str) -> usize {
+    let mut depth = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        let rest = &text[pos..];
+        if rest.starts_with("/*") {
+            depth += 1;
+            pos += 2;
+        } else if rest.starts_with("*/") {
+            depth -= 1;
+            pos += 2;
+            if depth == 0 {
+                break;
+            }
+        } else {
+            pos += rest.chars().next().unwrap().len_utf8();
+        }
+    }
+    pos
+}
+
+/// The column (in characters) of byte offset `pos` in `source`.
+fn column(source: &str, pos: usize) -> usize {
+    let line_start = source[..pos].rfind('\n').map_or(0, |i| i + 1);
+    source[line_start..pos].chars().count()
+}
@@ -0,0 +1,56 @@
+// Copyright header comment
+module   0x42::basic{
+use std::vector;
+use sui::{object::{Self,UID},transfer,tx_context::TxContext};
+
+    /// A coin-like object.
+public struct Coin has key,store{id:UID,value:u64}
+
+public enum Shape has copy,drop{
+    Circle{radius:u64},Square(u64),
+    Empty
+}
+
+const E_NOT_FOUND:u64=0;
+
+    #[test_only]
+fun helper<T:copy+drop>(x:&T,v:&mut vector<T>):vector<T>{
+let mut i=0;
+    while(i < 10){ vector::push_back(v,*x); i=i+1; };
+        // trailing comment inside
+ let r=vector<T>[];
+if (i > 5) { r } else {
+    vector[]
+}
+}
+
+public fun area(s:&Shape):u64{
+    match(s){
+        Shape::Circle{radius}=>*radius**radius*3,
+        Shape::Square(side)=>*side * *side,
+        Shape::Empty => 0,
+    }
+}
+
+public fun long_call(a: u64, b: u64, c: u64): u64 { compute_something_long(a + b + c, a * b * c, a - b, b - c, c - a, 1000000) }
+
+fun compute_something_long(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64): u64 { a + b + c + d + e + f }
+
+macro fun do_twice($f: |u64| -> u64, $x: u64): u64 {
+    let f = $f;
+    f(f($x))
+}
+
+fun use_macro(v: vector<u64>): u64 {
+    let x = do_twice!(|y| y + 1, 3);
+    let mut sum = 0;
+    v.do!(|e| { sum = sum + e; });
+    'outer: loop { if (sum > x) break 'outer; sum = sum + 1; };
+    let _ = x < sum && sum > x;
+    (sum as u64)
+}
+
+/* block
+   comment */
+fun is_some(v: &vector<u64>): bool { !v.is_empty() }
+}
@@ -0,0 +1,27 @@
+/// Doc comment on the module.
+module 0x42::comments {   // after module
+    // Leading comment.
+
+
+
+    public struct S {  // after open
+        a: u64, // after a
+        /* before b */ b: bool,
+        // dangling before c
+        c: vector<u8>
+    }
+
+    /**
+       * Multi-line doc comment.
+     */
+    fun f(/* no args */): u64 {
+        let x = 1 /* inline */ + 2;
+        // last comment
+        x
+    }
+
+    fun g(
+        a: u64, // first
+        b: u64,
+    ): u64 { a + b }
+}
@@ -0,0 +1,33 @@
+module 0x42::edition_2024;
+
+use std::string::String;
+
+public enum Action<T: store> has store {
+    Stop,
+    Move { x: u64, y: u64 },
+    Write(String),
+    Store(T)
+}
+
+public fun describe<T: store>(a: &Action<T>): u64 {
+    match (a) {
+        Action::Stop => 0,
+        Action::Move { x, y } => {
+            let sum = *x + *y;
+            sum
+        },
+        Action::Write(_) | Action::Store(_) => 2,
+    }
+}
+
+public(package) fun nested(v: &vector<vector<u64>>): u64 {
+    let mut total = 0u64;
+    'a: {
+        if (v.length() == 0) return 'a 0;
+        v.do_ref!(|inner| inner.do_ref!(|x| total = total + *x));
+        total
+    }
+}
+
+#[test, expected_failure(abort_code = 0)]
+fun test_abort() { abort 0 }
@@ -0,0 +1,17 @@
+module 0x42::long_lines {
+    public struct Config has copy, drop, store { first_field: u64, second_field: u64, third_field: vector<u8>, fourth_field: bool }
+
+    public fun very_long_function_name_for_testing(first_argument: u64, second_argument: u64, third_argument: u64): u64 {
+        first_argument
+    }
+
+    public fun make(): Config {
+        let v = vector[1000000000, 2000000000, 3000000000, 4000000000, 5000000000, 6000000000, 7000000000];
+        very_long_function_name_for_testing(v[0], very_long_function_name_for_testing(1, 2, 3), 300000000000);
+        v.do_ref!(|x| { let y = *x + 1; assert!(y > 1, 0); });
+        let total = v[0] + v[1] + v[2] + v[3] + v[4] + v[5] + v[6] + v[0] + v[1] + v[2] + v[3] + v[4];
+        let other = total
+            + 1;
+        Config { first_field: total, second_field: other, third_field: vector[], fourth_field: true }
+    }
+}
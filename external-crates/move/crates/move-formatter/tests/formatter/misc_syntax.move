@@ -0,0 +1,26 @@
+module 0x42::misc_syntax {
+    use fun vector_sum as vector.sum;
+    friend 0x42::other;
+
+    public struct Wrapper<phantom T, U: copy + drop> has copy, drop { inner: vector<vector<U>> }
+
+    fun vector_sum(v: &vector<u64>): u64 { let mut s = 0; v.do_ref!(|x| s = s + *x); s }
+
+    fun apply<T>(f: |&mut T|, x: &mut T) { f(x) }
+
+    fun unpack(w: Wrapper<u8, u64>): u64 {
+        let Wrapper { inner } = w;
+        let nested: vector<vector<u64>> = inner;
+        if (nested.length() > 0)
+            return (nested[0][0] as u64);
+        let x = &mut 0;
+        *x = 1 << 3 >> 1;
+        let b = 0x42 ^ 0xff | 1 & 2;
+        let c = @0x1;
+        let d = b"bytes";
+        let e = x"00ff";
+        abort 0
+    }
+
+    spec module { pragma verify = false; }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_command_line_common::files::FileHash;
+use move_command_line_common::testing::{
+    add_update_baseline_fix, format_diff, read_env_update_baseline, EXP_EXT,
+};
+use move_compiler::{
+    diagnostics::report_diagnostics_to_buffer, editions::Edition, shared::files::MappedFiles,
+};
+use move_formatter::{format_string, FormatError, FormatterConfig};
+use std::{fs, path::Path};
+
+/// Formats a `.move` file and compares the result against its `.exp` file. Formatting the result
+/// again must not change it.
+fn run_test(path: &Path) -> datatest_stable::Result<()> {
+    let source = fs::read_to_string(path)?;
+    let config = FormatterConfig::for_package(path.parent().unwrap())?;
+
+    let output = match format_string(&source, Edition::default(), &config) {
+        Ok(formatted) => {
+            let reformatted = format_string(&formatted, Edition::default(), &config)
+                .map_err(|e| format!("Failed to re-format output: {e}"))?;
+            if formatted != reformatted {
+                return Err(format!(
+                    "Formatting is not idempotent for {}:\n{}",
+                    path.display(),
+                    format_diff(&formatted, &reformatted),
+                )
+                .into());
+            }
+            formatted
+        }
+        Err(FormatError::Parse(diags)) => {
+            let mut files = MappedFiles::empty();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            files.add(FileHash::new(&source), name.into(), source.clone().into());
+            String::from_utf8(report_diagnostics_to_buffer(&files, diags, false))?
+        }
+        Err(e) => format!("{e}\n"),
+    };
+
+    let exp_path = path.with_extension(EXP_EXT);
+    if read_env_update_baseline() {
+        fs::write(&exp_path, &output)?;
+        return Ok(());
+    }
+
+    let expected = fs::read_to_string(&exp_path).unwrap_or_default();
+    if expected != output {
+        let msg = format!(
+            "Expected output differs for {}:\n{}",
+            path.display(),
+            format_diff(&expected, &output),
+        );
+        return Err(add_update_baseline_fix(msg).into());
+    }
+
+    Ok(())
+}
+
+datatest_stable::harness!(run_test, "tests/formatter", r".*\.move$");
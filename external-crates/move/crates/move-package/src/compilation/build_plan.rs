@@ -14,16 +14,18 @@ use crate::{
 use anyhow::Result;
 use move_compiler::{
     compiled_unit::AnnotatedCompiledUnit,
-    diagnostics::{report_diagnostics_to_buffer_with_env_color, Migration},
+    construct_pre_compiled_lib,
+    diagnostics::{report_diagnostics_to_buffer_with_env_color, Diagnostics, Migration},
     editions::Edition,
     shared::{files::MappedFiles, PackagePaths},
-    Compiler,
+    Compiler, FullyCompiledProgram,
 };
 use move_symbol_pool::Symbol;
 use std::{
     collections::BTreeSet,
     io::Write,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use toml_edit::{value, Document};
 use vfs::VfsPath;
@@ -267,6 +269,60 @@ impl BuildPlan {
         Ok(compiled)
     }
 
+    /// Compiles the dependencies of the root package that are available as source, on their own,
+    /// so that the root package can be compiled against them repeatedly (see
+    /// `compile_with_driver_and_pre_compiled_deps`) without compiling them again. Returns the
+    /// diagnostics instead if the dependencies fail to compile.
+    pub fn compile_source_deps(
+        &self,
+    ) -> Result<Result<FullyCompiledProgram, (MappedFiles, Diagnostics)>> {
+        let src_deps = self
+            .compute_dependencies()
+            .make_deps_for_compiler()?
+            .into_iter()
+            .filter_map(|(paths, format)| match format {
+                ModuleFormat::Source => Some(paths),
+                ModuleFormat::Bytecode => None,
+            })
+            .collect();
+        construct_pre_compiled_lib(
+            src_deps,
+            None,
+            self.resolution_graph.build_options.compiler_flags(),
+            self.compiler_vfs_root.clone(),
+        )
+    }
+
+    /// Like `compile_with_driver`, but the dependencies of the root package that are available as
+    /// source are not compiled again: they are taken from `pre_compiled_deps`, as returned by
+    /// `compile_source_deps`. The compiled units of these dependencies are added to the ones
+    /// returned by `compiler_driver`.
+    pub fn compile_with_driver_and_pre_compiled_deps<W: Write>(
+        &self,
+        pre_compiled_deps: Arc<FullyCompiledProgram>,
+        writer: &mut W,
+        compiler_driver: impl FnOnce(
+            Compiler,
+        )
+            -> anyhow::Result<(MappedFiles, Vec<AnnotatedCompiledUnit>)>,
+    ) -> Result<CompiledPackage> {
+        let mut dependencies = self.compute_dependencies();
+        let src_deps = dependencies
+            .transitive_dependencies
+            .iter()
+            .filter(|dep| matches!(dep.module_format, ModuleFormat::Source))
+            .map(|dep| dep.name)
+            .collect();
+        dependencies.remove_deps(src_deps);
+        self.compile_with_driver_and_deps(dependencies, writer, |compiler| {
+            let compiler = compiler.set_pre_compiled_lib(pre_compiled_deps.clone());
+            let (mut files, mut units) = compiler_driver(compiler)?;
+            files.extend_with_duplicates(pre_compiled_deps.files.clone());
+            units.extend(pre_compiled_deps.compiled.iter().cloned());
+            Ok((files, units))
+        })
+    }
+
     // Clean out old packages that are no longer used, or no longer used under the current
     // compilation flags
     fn clean(build_root: &Path, keep_paths: BTreeSet<PackageName>) -> Result<()> {
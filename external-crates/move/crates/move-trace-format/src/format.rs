@@ -306,6 +306,14 @@ impl MoveTraceBuilder {
         self.push_event(TraceEvent::Effect(Box::new(effect)));
     }
 
+    /// Push an `External` event to the trace. This is meant for events recorded by the embedder of
+    /// the VM outside of the execution of a function (e.g., before or after it).
+    pub fn external<T: Serialize>(&mut self, e: T) {
+        self.push_event(TraceEvent::External(Box::new(
+            serde_json::to_value(e).unwrap(),
+        )));
+    }
+
     // All events pushed to the trace are first pushed, and then the tracer is notified of the
     // event.
     fn push_event(&mut self, event: TraceEvent) {
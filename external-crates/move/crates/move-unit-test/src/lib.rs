@@ -7,7 +7,7 @@ pub mod extensions;
 pub mod test_reporter;
 pub mod test_runner;
 
-use crate::test_runner::TestRunner;
+use crate::{test_reporter::TestReportFormat, test_runner::TestRunner};
 use anyhow::{bail, Result};
 use clap::*;
 use move_binary_format::CompiledModule;
@@ -23,7 +23,7 @@ use move_compiler::{
 use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_test_utils::gas_schedule::CostTable;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex};
+use std::{collections::BTreeMap, io::Write, marker::Send, path::PathBuf, sync::Mutex};
 
 /// The default value bounding the amount of gas consumed in a test.
 const DEFAULT_EXECUTION_BOUND: u64 = 1_000_000;
@@ -34,6 +34,7 @@ const DEFAULT_RAND_ITERS: u64 = 10;
 const RAND_NUM_ITERS_FLAG: &str = "rand-num-iters";
 const SEED_FLAG: &str = "seed";
 const TRACE_FLAG: &str = "trace-execution";
+const REPORT_FLAG: &str = "report";
 
 #[derive(Debug, Parser, Clone)]
 #[clap(author, version, about)]
@@ -82,6 +83,11 @@ pub struct UnitTestingConfig {
     #[clap(name = "report-statistics", short = 's', long = "statistics")]
     pub report_statistics: Option<Option<String>>,
 
+    /// Write a report of each test's status, failure, gas used and time to PATH, as FORMAT
+    /// (`junit` or `json`)
+    #[clap(long = REPORT_FLAG, num_args = 2, value_names = ["FORMAT", "PATH"])]
+    pub report: Option<Vec<String>>,
+
     #[clap(
         name = "report_stacktrace_on_abort",
         short = 'r',
@@ -147,6 +153,7 @@ impl UnitTestingConfig {
             filter: None,
             num_threads: 8,
             report_statistics: None,
+            report: None,
             report_stacktrace_on_abort: false,
             source_files: vec![],
             dep_files: vec![],
@@ -254,6 +261,14 @@ impl UnitTestingConfig {
             None => DEFAULT_RAND_ITERS,
         };
 
+        let report = match self.report.as_deref() {
+            None => None,
+            Some([format, path]) => {
+                Some((format.parse::<TestReportFormat>()?, PathBuf::from(path)))
+            }
+            Some(_) => bail!("Invalid argument -- '{REPORT_FLAG}' takes a format and a path"),
+        };
+
         if self.list {
             for (module_id, module_test_plan) in &test_plan.module_tests {
                 for test_name in module_test_plan.tests.keys() {
@@ -296,6 +311,9 @@ impl UnitTestingConfig {
         if let Some(report_type) = &self.report_statistics {
             test_results.report_statistics(&shared_writer, report_type)?;
         }
+        if let Some((format, path)) = report {
+            test_results.write_report(format, &path)?;
+        }
 
         let ok = test_results.summarize(&shared_writer)?;
 
@@ -16,10 +16,12 @@ use move_core_types::{
 };
 use move_ir_types::location::Loc;
 use move_trace_format::format::MoveTrace;
+use serde::Serialize;
 use std::{
     collections::{BTreeMap, BTreeSet},
     io::{Result, Write},
     path::Path,
+    str::FromStr,
     sync::Mutex,
     time::Duration,
 };
@@ -65,6 +67,62 @@ pub struct TestStatistics {
     failed: BTreeMap<ModuleId, TestRuns<TestFailure>>,
 }
 
+/// Formats of the machine readable reports written by [TestResults::write_report].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestReportFormat {
+    Junit,
+    Json,
+}
+
+/// The module function and source location at which a test aborted or errored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AbortLocation {
+    pub module: String,
+    pub function: String,
+    pub code_offset: u16,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct JsonReport {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    duration_secs: f64,
+    tests: Vec<TestCaseReport>,
+}
+
+/// The runs of one test function. Tests using generated inputs run several times, and fail if
+/// any of their runs fails.
+#[derive(Serialize)]
+struct TestCaseReport {
+    module: String,
+    name: String,
+    status: TestStatus,
+    runs: usize,
+    duration_secs: f64,
+    gas_used: u64,
+    failures: Vec<TestFailureReport>,
+}
+
+#[derive(Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum TestStatus {
+    Passed,
+    Failed,
+}
+
+#[derive(Serialize)]
+struct TestFailureReport {
+    reason: &'static str,
+    message: String,
+    abort_location: Option<AbortLocation>,
+    seed: Option<u64>,
+    duration_secs: f64,
+    gas_used: u64,
+}
+
 // #[derive(Debug, Clone)]
 pub struct TestResults {
     final_statistics: TestStatistics,
@@ -178,28 +236,41 @@ impl TestFailure {
     }
 
     pub fn render_error(&self, test_plan: &TestPlan) -> String {
+        let base_message = self.failure_message(test_plan);
+        match &self.failure_reason {
+            FailureReason::NoError(_) | FailureReason::Timeout(_) | FailureReason::Property(_) => {
+                base_message
+            }
+            FailureReason::WrongError(..)
+            | FailureReason::WrongAbortDEPRECATED(..)
+            | FailureReason::UnexpectedError(..) => {
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+            }
+        }
+    }
+
+    /// The reason for the failure, without the source location or stack trace.
+    pub fn failure_message(&self, test_plan: &TestPlan) -> String {
         match &self.failure_reason {
             FailureReason::NoError(message) => message.to_string(),
             FailureReason::Timeout(message) => message.to_string(),
             FailureReason::WrongError(message, expected, actual) => {
-                let base_message = format!(
+                format!(
                     "{message}. Expected test {} but instead it {} rooted here",
                     expected
                         .with_context(&test_plan.module_info)
                         .present_tense(),
                     actual.with_context(&test_plan.module_info).past_tense(),
-                );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                )
             }
             FailureReason::WrongAbortDEPRECATED(message, expected_code, actual) => {
-                let base_message = format!(
+                format!(
                     "{}. \
                     Expected test to abort with code {}, but instead it {} rooted here",
                     message,
                     expected_code,
                     actual.with_context(&test_plan.module_info).past_tense(),
-                );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                )
             }
             FailureReason::UnexpectedError(message, error) => {
                 let prefix = match error.0.status_type() {
@@ -217,18 +288,51 @@ impl TestFailure {
                     // execution errors are expected, so no message
                     StatusType::Execution => "",
                 };
-                let base_message = format!(
+                format!(
                     "{}{}, but it {} rooted here",
                     prefix,
                     message,
                     error.with_context(&test_plan.module_info).past_tense(),
-                );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                )
             }
             FailureReason::Property(message) => message.clone(),
         }
     }
 
+    /// Where the test aborted or errored, if it did so in a module of the test plan.
+    pub fn abort_location(&self, test_plan: &TestPlan) -> Option<AbortLocation> {
+        let vm_error = self.vm_error.as_ref()?;
+        let Location::Module(module_id) = vm_error.location() else {
+            return None;
+        };
+        let (fdef_idx, code_offset) = *vm_error.offsets().first()?;
+        let named_module = test_plan.module_info.get(module_id)?;
+        let fn_handle_idx = named_module.module.function_def_at(fdef_idx).function;
+        let fn_id_idx = named_module.module.function_handle_at(fn_handle_idx).name;
+        let source = named_module
+            .source_map
+            .get_function_source_map(fdef_idx)
+            .ok()
+            .and_then(|function_source_map| function_source_map.get_code_location(code_offset))
+            .and_then(|loc| {
+                let position = test_plan.mapped_files.position_opt(&loc)?;
+                Some((
+                    test_plan
+                        .mapped_files
+                        .filename(&loc.file_hash())
+                        .to_string(),
+                    position.start.user_line(),
+                ))
+            });
+        Some(AbortLocation {
+            module: format_module_id(&test_plan.module_info, module_id),
+            function: named_module.module.identifier_at(fn_id_idx).to_string(),
+            code_offset,
+            file: source.as_ref().map(|(file, _)| file.clone()),
+            line: source.map(|(_, line)| line),
+        })
+    }
+
     fn report_exec_state(test_plan: &TestPlan, exec_state: &ExecutionState) -> String {
         let stack_trace = exec_state.stack_trace();
         let mut buf = String::new();
@@ -277,6 +381,17 @@ impl TestFailure {
         buf
     }
 
+    fn failure_kind(&self) -> &'static str {
+        match &self.failure_reason {
+            FailureReason::NoError(_) => "no_error",
+            FailureReason::WrongError(..) => "wrong_error",
+            FailureReason::WrongAbortDEPRECATED(..) => "wrong_abort",
+            FailureReason::UnexpectedError(..) => "unexpected_error",
+            FailureReason::Timeout(_) => "timeout",
+            FailureReason::Property(_) => "property",
+        }
+    }
+
     fn report_error_with_location(
         test_plan: &TestPlan,
         base_message: String,
@@ -567,6 +682,91 @@ impl TestResults {
         writeln!(writer.lock().unwrap())
     }
 
+    /// Writes a report of every test's status, failure reason and abort location, gas used and
+    /// wall time to `path`, in `format`.
+    pub fn write_report(&self, format: TestReportFormat, path: &Path) -> anyhow::Result<()> {
+        let tests = self.test_case_reports();
+        let contents = match format {
+            TestReportFormat::Json => {
+                let passed = tests
+                    .iter()
+                    .filter(|t| t.status == TestStatus::Passed)
+                    .count();
+                serde_json::to_string_pretty(&JsonReport {
+                    total: tests.len(),
+                    passed,
+                    failed: tests.len() - passed,
+                    duration_secs: tests.iter().map(|t| t.duration_secs).sum(),
+                    tests,
+                })?
+            }
+            TestReportFormat::Junit => junit_report(&tests),
+        };
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid report path {}", path.display()))?;
+        write_string_to_file(path_str, &contents)?;
+        Ok(())
+    }
+
+    fn test_case_reports(&self) -> Vec<TestCaseReport> {
+        let module_ids = self
+            .final_statistics
+            .passed
+            .keys()
+            .chain(self.final_statistics.failed.keys())
+            .collect::<BTreeSet<_>>();
+        let mut reports = vec![];
+        for module_id in module_ids {
+            let passed = self.final_statistics.passed.get(module_id);
+            let failed = self.final_statistics.failed.get(module_id);
+            let test_names = passed
+                .into_iter()
+                .flat_map(|runs| runs.keys())
+                .chain(failed.into_iter().flat_map(|runs| runs.keys()))
+                .collect::<BTreeSet<_>>();
+            for test_name in test_names {
+                let passed_runs = passed
+                    .and_then(|runs| runs.get(test_name))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let failed_runs = failed
+                    .and_then(|runs| runs.get(test_name))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let (duration, gas_used) = calculate_run_statistics(
+                    passed_runs
+                        .iter()
+                        .chain(failed_runs.iter().map(|f| &f.test_run_info)),
+                );
+                reports.push(TestCaseReport {
+                    module: format_module_id(&self.test_plan.module_info, module_id),
+                    name: test_name.clone(),
+                    status: if failed_runs.is_empty() {
+                        TestStatus::Passed
+                    } else {
+                        TestStatus::Failed
+                    },
+                    runs: passed_runs.len() + failed_runs.len(),
+                    duration_secs: duration.as_secs_f64(),
+                    gas_used,
+                    failures: failed_runs
+                        .iter()
+                        .map(|failure| TestFailureReport {
+                            reason: failure.failure_kind(),
+                            message: failure.failure_message(&self.test_plan),
+                            abort_location: failure.abort_location(&self.test_plan),
+                            seed: failure.prng_seed,
+                            duration_secs: failure.test_run_info.elapsed_time.as_secs_f64(),
+                            gas_used: failure.test_run_info.instructions_executed,
+                        })
+                        .collect(),
+                });
+            }
+        }
+        reports
+    }
+
     /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
     pub fn summarize<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
         let num_failed_tests = self
@@ -639,3 +839,104 @@ impl TestResults {
         Ok(num_failed_tests == 0)
     }
 }
+
+impl FromStr for TestReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "junit" => Ok(TestReportFormat::Junit),
+            "json" => Ok(TestReportFormat::Json),
+            _ => anyhow::bail!("Unknown test report format '{s}', expected 'junit' or 'json'"),
+        }
+    }
+}
+
+/// Renders the tests as a JUnit XML document, with a test suite per module. The gas used by each
+/// test is recorded as a property of its test case.
+fn junit_report(tests: &[TestCaseReport]) -> String {
+    let mut suites: BTreeMap<&str, Vec<&TestCaseReport>> = BTreeMap::new();
+    for test in tests {
+        suites.entry(&test.module).or_default().push(test);
+    }
+    let failures = |tests: &[&TestCaseReport]| {
+        tests
+            .iter()
+            .filter(|t| t.status == TestStatus::Failed)
+            .count()
+    };
+    let duration = |tests: &[&TestCaseReport]| tests.iter().map(|t| t.duration_secs).sum::<f64>();
+
+    let all = tests.iter().collect::<Vec<_>>();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"Move unit tests\" tests=\"{}\" failures=\"{}\" time=\"{:.6}\">\n",
+        all.len(),
+        failures(&all),
+        duration(&all),
+    ));
+    for (module, tests) in suites {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.6}\">\n",
+            xml_escape(module),
+            tests.len(),
+            failures(&tests),
+            duration(&tests),
+        ));
+        for test in tests {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.6}\">\n",
+                xml_escape(&test.name),
+                xml_escape(module),
+                test.duration_secs,
+            ));
+            xml.push_str(&format!(
+                "      <properties>\n        \
+                <property name=\"gas_used\" value=\"{}\"/>\n        \
+                <property name=\"runs\" value=\"{}\"/>\n      \
+                </properties>\n",
+                test.gas_used, test.runs,
+            ));
+            for failure in &test.failures {
+                let mut details = failure.message.clone();
+                if let Some(location) = &failure.abort_location {
+                    details.push_str(&format!(
+                        "\nat {}::{} (code offset {})",
+                        location.module, location.function, location.code_offset
+                    ));
+                    if let (Some(file), Some(line)) = (&location.file, location.line) {
+                        details.push_str(&format!(", {file}:{line}"));
+                    }
+                }
+                if let Some(seed) = failure.seed {
+                    details.push_str(&format!("\nseed: {seed}"));
+                }
+                xml.push_str(&format!(
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                    failure.reason,
+                    xml_escape(&failure.message),
+                    xml_escape(&details),
+                ));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
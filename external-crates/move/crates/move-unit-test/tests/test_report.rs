@@ -0,0 +1,85 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_unit_test::{self, UnitTestingConfig};
+use std::path::{Path, PathBuf};
+
+fn run_with_report(format: &str, report_path: &Path) -> bool {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let source = path.join("tests/test_sources/cross_module_aborts.move");
+    let testing_config = UnitTestingConfig {
+        num_threads: 1,
+        source_files: vec![source.to_string_lossy().to_string()],
+        dep_files: move_stdlib::move_stdlib_files(),
+        report: Some(vec![
+            format.to_string(),
+            report_path.to_string_lossy().to_string(),
+        ]),
+        ..UnitTestingConfig::default_with_bound(None)
+            .with_named_addresses(move_stdlib::move_stdlib_named_addresses())
+    };
+    let test_plan = testing_config.build_test_plan().unwrap();
+    let (_, ok) = testing_config
+        .run_and_report_unit_tests(test_plan, None, None, Vec::new())
+        .unwrap();
+    ok
+}
+
+#[test]
+fn test_json_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let report_path = dir.path().join("report.json");
+    assert!(!run_with_report("json", &report_path));
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(report_path).unwrap()).unwrap();
+    assert_eq!(report["total"], 2);
+    assert_eq!(report["passed"], 1);
+    assert_eq!(report["failed"], 1);
+
+    let tests = report["tests"].as_array().unwrap();
+    let passed = tests.iter().find(|t| t["name"] == "dummy_test").unwrap();
+    assert_eq!(passed["status"], "passed");
+    assert_eq!(passed["failures"].as_array().unwrap().len(), 0);
+
+    let failed = tests.iter().find(|t| t["name"] == "failing_test").unwrap();
+    assert_eq!(failed["module"], "0x7::B");
+    assert_eq!(failed["status"], "failed");
+    let failure = &failed["failures"][0];
+    assert_eq!(failure["reason"], "unexpected_error");
+    assert_eq!(failure["abort_location"]["module"], "0x6::M");
+    assert_eq!(failure["abort_location"]["function"], "this_aborts");
+    assert!(failure["gas_used"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn test_junit_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let report_path = dir.path().join("report.xml");
+    assert!(!run_with_report("junit", &report_path));
+
+    let report = std::fs::read_to_string(report_path).unwrap();
+    assert!(report.starts_with("<?xml"));
+    assert!(report.contains("<testsuites name=\"Move unit tests\" tests=\"2\" failures=\"1\""));
+    assert!(report.contains("<testcase name=\"dummy_test\" classname=\"0x6::M\""));
+    assert!(report.contains("<testcase name=\"failing_test\" classname=\"0x7::B\""));
+    assert!(report.contains("<failure type=\"unexpected_error\""));
+    assert!(report.contains("<property name=\"gas_used\""));
+}
+
+#[test]
+fn test_unknown_report_format() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let source = path.join("tests/test_sources/cross_module_aborts.move");
+    let testing_config = UnitTestingConfig {
+        source_files: vec![source.to_string_lossy().to_string()],
+        dep_files: move_stdlib::move_stdlib_files(),
+        report: Some(vec!["xml".to_string(), "report.xml".to_string()]),
+        ..UnitTestingConfig::default_with_bound(None)
+            .with_named_addresses(move_stdlib::move_stdlib_named_addresses())
+    };
+    let test_plan = testing_config.build_test_plan().unwrap();
+    assert!(testing_config
+        .run_and_report_unit_tests(test_plan, None, None, Vec::new())
+        .is_err());
+}
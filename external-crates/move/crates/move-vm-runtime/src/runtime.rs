@@ -496,6 +496,7 @@ impl VMRuntime {
         data_store: &mut impl DataStore,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: Option<&mut MoveTraceBuilder>,
     ) -> VMResult<SerializedReturnValues> {
         move_vm_profiler::tracing_feature_enabled! {
             use move_vm_profiler::GasProfiler;
@@ -507,6 +508,12 @@ impl VMRuntime {
             }
         }
 
+        let tracer = if cfg!(feature = "tracing") {
+            tracer
+        } else {
+            None
+        };
+
         let bypass_declared_entry_check = true;
         self.execute_function(
             module,
@@ -517,7 +524,7 @@ impl VMRuntime {
             gas_meter,
             extensions,
             bypass_declared_entry_check,
-            None,
+            tracer,
         )
     }
 
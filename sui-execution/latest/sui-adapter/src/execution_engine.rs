@@ -54,6 +54,7 @@ mod checked {
     use sui_types::execution::is_certificate_denied;
     use sui_types::execution_config_utils::to_binary_config;
     use sui_types::execution_status::{CongestedObjects, ExecutionStatus};
+    use sui_types::execution_trace::TransactionTrace;
     use sui_types::gas::GasCostSummary;
     use sui_types::gas::SuiGasStatus;
     use sui_types::id::UID;
@@ -92,6 +93,7 @@ mod checked {
         metrics: Arc<LimitsMetrics>,
         enable_expensive_checks: bool,
         certificate_deny_set: &HashSet<TransactionDigest>,
+        trace: &mut Option<TransactionTrace>,
     ) -> (
         InnerTemporaryStore,
         SuiGasStatus,
@@ -144,6 +146,7 @@ mod checked {
             deny_cert,
             contains_deleted_input,
             cancelled_objects,
+            trace,
         );
 
         let status = if let Err(error) = &execution_result {
@@ -278,6 +281,7 @@ mod checked {
         deny_cert: bool,
         contains_deleted_input: bool,
         cancelled_objects: Option<(Vec<ObjectID>, SequenceNumber)>,
+        trace: &mut Option<TransactionTrace>,
     ) -> (
         GasCostSummary,
         Result<Mode::ExecutionResults, ExecutionError>,
@@ -330,6 +334,7 @@ mod checked {
                     gas_charger,
                     protocol_config,
                     metrics.clone(),
+                    trace,
                 )
             };
 
@@ -550,6 +555,7 @@ mod checked {
         gas_charger: &mut GasCharger,
         protocol_config: &ProtocolConfig,
         metrics: Arc<LimitsMetrics>,
+        trace: &mut Option<TransactionTrace>,
     ) -> Result<Mode::ExecutionResults, ExecutionError> {
         let result = match transaction_kind {
             TransactionKind::ChangeEpoch(change_epoch) => {
@@ -626,7 +632,7 @@ mod checked {
                 Ok(Mode::empty_results())
             }
             TransactionKind::ProgrammableTransaction(pt) => {
-                programmable_transactions::execution::execute::<Mode>(
+                programmable_transactions::execution::execute_with_trace::<Mode>(
                     protocol_config,
                     metrics,
                     move_vm,
@@ -634,6 +640,7 @@ mod checked {
                     tx_ctx,
                     gas_charger,
                     pt,
+                    trace,
                 )
             }
             TransactionKind::EndOfEpochTransaction(txns) => {
@@ -28,6 +28,7 @@ mod checked {
         file_format::{CodeOffset, FunctionDefinitionIndex, TypeParameterIndex},
         CompiledModule,
     };
+    use move_core_types::annotated_value::MoveValue;
     use move_core_types::resolver::ModuleResolver;
     use move_core_types::vm_status::StatusCode;
     use move_core_types::{
@@ -35,6 +36,7 @@ mod checked {
         identifier::IdentStr,
         language_storage::{ModuleId, StructTag, TypeTag},
     };
+    use move_trace_format::format::{MoveTrace, MoveTraceBuilder};
     use move_vm_runtime::native_extensions::NativeContextExtensions;
     use move_vm_runtime::{
         move_vm::MoveVM,
@@ -47,6 +49,7 @@ mod checked {
     };
     use sui_protocol_config::ProtocolConfig;
     use sui_types::execution::ExecutionResults;
+    use sui_types::execution_trace::{FramePackageTracer, ObjectLoadKind, SuiTraceEvent};
     use sui_types::storage::{DenyListResult, PackageObject};
     use sui_types::{
         balance::Balance,
@@ -100,6 +103,20 @@ mod checked {
         /// Map of arguments that are currently borrowed in this command, true if the borrow is mutable
         /// This gets cleared out when new results are pushed, i.e. the end of a command
         borrowed: HashMap<Argument, /* mut */ bool>,
+        /// The trace of the command being executed, if the transaction is traced
+        command_trace: Option<CommandTrace>,
+    }
+
+    /// The state of the trace of a command, while the command is executed
+    struct CommandTrace {
+        builder: MoveTraceBuilder,
+        /// The input objects passed to the command, with their contents before the command.
+        /// None if the object was already taken/moved
+        input_objects: Vec<(Argument, Option<Vec<u8>>)>,
+        /// The objects loaded at runtime before the command
+        runtime_objects: BTreeSet<ObjectID>,
+        /// The child objects that existed before the command, with their contents
+        child_objects: BTreeMap<ObjectID, Vec<u8>>,
     }
 
     /// A write for an object that was generated outside of the Move ObjectRuntime
@@ -227,6 +244,7 @@ mod checked {
                 new_packages: vec![],
                 user_events: vec![],
                 borrowed: HashMap::new(),
+                command_trace: None,
             })
         }
 
@@ -234,6 +252,142 @@ mod checked {
             self.native_extensions.get()
         }
 
+        /// Start tracing a command that is passed `arguments`. The Move functions called by the
+        /// command are traced until `finish_command_trace` is called.
+        pub fn start_command_trace(&mut self, arguments: &[Argument]) {
+            let mut input_objects: Vec<(Argument, Option<Vec<u8>>)> = vec![];
+            for arg in arguments {
+                if !matches!(arg, Argument::GasCoin | Argument::Input(_))
+                    || input_objects.iter().any(|(a, _)| a == arg)
+                {
+                    continue;
+                }
+                let Ok((Some(InputObjectMetadata::InputObject { .. }), value)) =
+                    self.borrow_mut_impl(*arg, None)
+                else {
+                    continue;
+                };
+                let bytes = value.as_ref().map(|value| {
+                    let mut bytes = vec![];
+                    value.write_bcs_bytes(&mut bytes);
+                    bytes
+                });
+                input_objects.push((*arg, bytes));
+            }
+            let runtime_objects = self
+                .object_runtime()
+                .loaded_runtime_objects()
+                .into_keys()
+                .collect();
+            let child_objects = self
+                .child_object_contents()
+                .into_iter()
+                .map(|(id, _, bytes)| (id, bytes))
+                .collect();
+            self.command_trace = Some(CommandTrace {
+                builder: MoveTraceBuilder::new(),
+                input_objects,
+                runtime_objects,
+                child_objects,
+            });
+        }
+
+        /// Finish tracing the current command, recording the objects that it loaded, and the
+        /// input objects and child objects that it modified or added. Child objects that the
+        /// command removed have no value left to record. Returns None if the command was not
+        /// traced.
+        pub fn finish_command_trace(&mut self) -> Option<MoveTrace> {
+            let CommandTrace {
+                mut builder,
+                input_objects,
+                runtime_objects,
+                child_objects,
+            } = self.command_trace.take()?;
+
+            for (arg, _) in &input_objects {
+                if let Ok((
+                    Some(InputObjectMetadata::InputObject {
+                        id, owner, version, ..
+                    }),
+                    _,
+                )) = self.borrow_mut_impl(*arg, None)
+                {
+                    builder.external(SuiTraceEvent::ObjectLoad {
+                        object_id: *id,
+                        version: *version,
+                        owner: owner.clone(),
+                        kind: ObjectLoadKind::Input,
+                    });
+                }
+            }
+
+            for (id, metadata) in self.object_runtime().loaded_runtime_objects() {
+                if !runtime_objects.contains(&id) {
+                    builder.external(SuiTraceEvent::ObjectLoad {
+                        object_id: id,
+                        version: metadata.version,
+                        owner: metadata.owner,
+                        kind: ObjectLoadKind::Runtime,
+                    });
+                }
+            }
+
+            for (arg, before) in input_objects {
+                let Ok((
+                    Some(InputObjectMetadata::InputObject { id, .. }),
+                    Some(Value::Object(object)),
+                )) = self.borrow_mut_impl(arg, None)
+                else {
+                    continue;
+                };
+                let mut after = vec![];
+                object.write_bcs_bytes(&mut after);
+                if before.as_ref() == Some(&after) {
+                    continue;
+                }
+                let (object_id, type_) = (*id, object.type_.clone());
+                if let Some(value) = self.annotated_value(&type_, &after) {
+                    builder.external(SuiTraceEvent::ObjectWrite { object_id, value });
+                }
+            }
+
+            for (object_id, type_, after) in self.child_object_contents() {
+                if child_objects.get(&object_id) == Some(&after) {
+                    continue;
+                }
+                if let Some(value) = self.annotated_value(&type_, &after) {
+                    builder.external(SuiTraceEvent::ObjectWrite { object_id, value });
+                }
+            }
+
+            Some(builder.into_trace())
+        }
+
+        /// The child objects loaded or added so far that still exist, with their types and
+        /// contents. Objects whose layout cannot be resolved are left out.
+        fn child_object_contents(&mut self) -> Vec<(ObjectID, Type, Vec<u8>)> {
+            let values = self.object_runtime().child_object_values();
+            values
+                .into_iter()
+                .filter_map(|(id, type_, value)| {
+                    let layout = self.vm.get_runtime().type_to_type_layout(&type_).ok()?;
+                    let bytes = value.simple_serialize(&layout)?;
+                    Some((id, type_, bytes))
+                })
+                .collect()
+        }
+
+        /// The contents of an object of type `type_`, annotated for the trace. None if the layout
+        /// of the type cannot be resolved, in which case the object is left out of the trace.
+        fn annotated_value(&self, type_: &Type, bytes: &[u8]) -> Option<MoveValue> {
+            let layout = self
+                .vm
+                .get_runtime()
+                .type_to_fully_annotated_layout(type_)
+                .ok()?;
+            MoveValue::simple_deserialize(bytes, &layout).ok()
+        }
+
         /// Create a new ID and update the state
         pub fn fresh_id(&mut self) -> Result<ObjectID, ExecutionError> {
             let object_id = self.tx_context.fresh_id();
@@ -966,6 +1120,11 @@ mod checked {
         ) -> VMResult<SerializedReturnValues> {
             let gas_status = self.gas_charger.move_gas_status_mut();
             let mut data_store = SuiDataStore::new(&self.linkage_view, &self.new_packages);
+            let tracer = self.command_trace.as_mut().map(|trace| {
+                trace.builder.tracer =
+                    Box::new(FramePackageTracer::new(self.linkage_view.linked_packages()));
+                &mut trace.builder
+            });
             self.vm.get_runtime().execute_function_bypass_visibility(
                 module,
                 function_name,
@@ -974,6 +1133,7 @@ mod checked {
                 &mut data_store,
                 gas_status,
                 &mut self.native_extensions,
+                tracer,
             )
         }
 
@@ -38,6 +38,7 @@ mod checked {
     use sui_protocol_config::ProtocolConfig;
     use sui_types::execution_config_utils::to_binary_config;
     use sui_types::execution_status::{CommandArgumentError, PackageUpgradeError};
+    use sui_types::execution_trace::TransactionTrace;
     use sui_types::storage::{get_package_objects, PackageObject};
     use sui_types::type_input::TypeInput;
     use sui_types::{
@@ -74,6 +75,29 @@ mod checked {
         tx_context: &mut TxContext,
         gas_charger: &mut GasCharger,
         pt: ProgrammableTransaction,
+    ) -> Result<Mode::ExecutionResults, ExecutionError> {
+        execute_with_trace::<Mode>(
+            protocol_config,
+            metrics,
+            vm,
+            state_view,
+            tx_context,
+            gas_charger,
+            pt,
+            &mut None,
+        )
+    }
+
+    /// Execute `pt`, recording the trace of each of its commands in `trace` if it is set
+    pub fn execute_with_trace<Mode: ExecutionMode>(
+        protocol_config: &ProtocolConfig,
+        metrics: Arc<LimitsMetrics>,
+        vm: &MoveVM,
+        state_view: &mut dyn ExecutionState,
+        tx_context: &mut TxContext,
+        gas_charger: &mut GasCharger,
+        pt: ProgrammableTransaction,
+        trace: &mut Option<TransactionTrace>,
     ) -> Result<Mode::ExecutionResults, ExecutionError> {
         let ProgrammableTransaction { inputs, commands } = pt;
         let mut context = ExecutionContext::new(
@@ -88,7 +112,14 @@ mod checked {
         // execute commands
         let mut mode_results = Mode::empty_results();
         for (idx, command) in commands.into_iter().enumerate() {
-            if let Err(err) = execute_command::<Mode>(&mut context, &mut mode_results, command) {
+            if trace.is_some() {
+                context.start_command_trace(&command_arguments(&command));
+            }
+            let result = execute_command::<Mode>(&mut context, &mut mode_results, command);
+            if let Some(trace) = trace {
+                trace.commands.extend(context.finish_command_trace());
+            }
+            if let Err(err) = result {
                 let object_runtime: &ObjectRuntime = context.object_runtime();
                 // We still need to record the loaded child objects for replay
                 let loaded_runtime_objects = object_runtime.loaded_runtime_objects();
@@ -118,6 +149,29 @@ mod checked {
         Ok(mode_results)
     }
 
+    /// The arguments passed to `command`
+    fn command_arguments(command: &Command) -> Vec<Argument> {
+        match command {
+            Command::MoveCall(move_call) => move_call.arguments.clone(),
+            Command::TransferObjects(objs, addr_arg) => objs
+                .iter()
+                .chain(std::iter::once(addr_arg))
+                .copied()
+                .collect(),
+            Command::SplitCoins(coin_arg, amount_args) => std::iter::once(coin_arg)
+                .chain(amount_args)
+                .copied()
+                .collect(),
+            Command::MergeCoins(target_arg, coin_args) => std::iter::once(target_arg)
+                .chain(coin_args)
+                .copied()
+                .collect(),
+            Command::MakeMoveVec(_, args) => args.clone(),
+            Command::Publish(_, _) => vec![],
+            Command::Upgrade(_, _, _, upgrade_ticket) => vec![*upgrade_ticket],
+        }
+    }
+
     /// Execute a single command
     #[instrument(level = "trace", skip_all)]
     fn execute_command<Mode: ExecutionMode>(
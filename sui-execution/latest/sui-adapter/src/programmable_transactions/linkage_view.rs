@@ -16,7 +16,7 @@ use move_core_types::{
 };
 use sui_types::storage::{get_module, PackageObject};
 use sui_types::{
-    base_types::ObjectID,
+    base_types::{ObjectID, SequenceNumber},
     error::{ExecutionError, SuiError, SuiResult},
     move_package::{MovePackage, TypeOrigin, UpgradeInfo},
     storage::BackingPackageStore,
@@ -45,6 +45,7 @@ pub struct LinkageView<'state> {
 #[derive(Debug)]
 pub struct LinkageInfo {
     storage_id: AccountAddress,
+    storage_version: SequenceNumber,
     runtime_id: AccountAddress,
     link_table: BTreeMap<ObjectID, UpgradeInfo>,
 }
@@ -194,6 +195,30 @@ impl<'state> LinkageView<'state> {
             .map_or(AccountAddress::ZERO, |l| l.storage_id)
     }
 
+    /// The packages that modules are loaded from in the current link context, keyed by their
+    /// runtime ID, with the storage ID and version of each. Empty if there is no link context.
+    pub(crate) fn linked_packages(&self) -> BTreeMap<AccountAddress, (ObjectID, SequenceNumber)> {
+        let Some(linkage) = &self.linkage_info else {
+            return BTreeMap::new();
+        };
+
+        let mut packages: BTreeMap<_, _> = linkage
+            .link_table
+            .iter()
+            .map(|(runtime_id, upgrade)| {
+                (
+                    AccountAddress::from(*runtime_id),
+                    (upgrade.upgraded_id, upgrade.upgraded_version),
+                )
+            })
+            .collect();
+        packages.insert(
+            linkage.runtime_id,
+            (linkage.storage_id.into(), linkage.storage_version),
+        );
+        packages
+    }
+
     pub(crate) fn relocate(&self, module_id: &ModuleId) -> Result<ModuleId, SuiError> {
         let Some(linkage) = &self.linkage_info else {
             invariant_violation!("No linkage context set while relocating {module_id}.")
@@ -266,6 +291,7 @@ impl From<&MovePackage> for LinkageInfo {
     fn from(package: &MovePackage) -> Self {
         Self {
             storage_id: package.id().into(),
+            storage_version: package.version(),
             runtime_id: package.original_package_id().into(),
             link_table: package.linkage_table().clone(),
         }
@@ -462,6 +462,14 @@ impl<'a> ObjectRuntime<'a> {
         self.child_object_store.all_active_objects()
     }
 
+    /// The child objects loaded or added so far that still exist, with their types and a copy of
+    /// their current values.
+    pub fn child_object_values(&self) -> Vec<(ObjectID, Type, Value)> {
+        self.all_active_child_objects()
+            .filter_map(|child| Some((*child.id, child.ty.clone(), child.copied_value?)))
+            .collect()
+    }
+
     pub fn loaded_runtime_objects(&self) -> BTreeMap<ObjectID, DynamicallyLoadedObjectMetadata> {
         // The loaded child objects, and the received objects, should be disjoint. If they are not,
         // this is an error since it could lead to incorrect transaction dependency computations.
@@ -11,6 +11,7 @@ use sui_types::{
     effects::TransactionEffects,
     error::ExecutionError,
     execution::{ExecutionResult, TypeLayoutStore},
+    execution_trace::TransactionTrace,
     gas::SuiGasStatus,
     inner_temporary_store::InnerTemporaryStore,
     layout_resolver::LayoutResolver,
@@ -47,6 +48,54 @@ pub trait Executor {
         Result<(), ExecutionError>,
     );
 
+    /// Like `execute_transaction_to_effects`, but also records the trace of each command of a
+    /// programmable transaction in `trace`, if it is set. Execution layers that do not support
+    /// tracing clear `trace`.
+    fn execute_transaction_to_effects_with_trace(
+        &self,
+        store: &dyn BackingStore,
+        // Configuration
+        protocol_config: &ProtocolConfig,
+        metrics: Arc<LimitsMetrics>,
+        enable_expensive_checks: bool,
+        certificate_deny_set: &HashSet<TransactionDigest>,
+        // Epoch
+        epoch_id: &EpochId,
+        epoch_timestamp_ms: u64,
+        // Transaction Inputs
+        input_objects: CheckedInputObjects,
+        // Gas related
+        gas_coins: Vec<ObjectRef>,
+        gas_status: SuiGasStatus,
+        // Transaction
+        transaction_kind: TransactionKind,
+        transaction_signer: SuiAddress,
+        transaction_digest: TransactionDigest,
+        trace: &mut Option<TransactionTrace>,
+    ) -> (
+        InnerTemporaryStore,
+        SuiGasStatus,
+        TransactionEffects,
+        Result<(), ExecutionError>,
+    ) {
+        *trace = None;
+        self.execute_transaction_to_effects(
+            store,
+            protocol_config,
+            metrics,
+            enable_expensive_checks,
+            certificate_deny_set,
+            epoch_id,
+            epoch_timestamp_ms,
+            input_objects,
+            gas_coins,
+            gas_status,
+            transaction_kind,
+            transaction_signer,
+            transaction_digest,
+        )
+    }
+
     fn dev_inspect_transaction(
         &self,
         store: &dyn BackingStore,